@@ -1,7 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 extern crate alloc;
 
-use alloc::{string::String, vec::Vec};
+use alloc::{string::String, vec, vec::Vec};
 use core::{fmt::Debug, str::FromStr};
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
@@ -108,6 +108,123 @@ impl FromStr for WorkerRole {
 	}
 }
 
+#[repr(u8)]
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
+pub enum EndpointScheme {
+	Http,
+	Https,
+}
+
+impl EndpointScheme {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			EndpointScheme::Http => "http",
+			EndpointScheme::Https => "https",
+		}
+	}
+
+	pub fn default_port(&self) -> u16 {
+		match self {
+			EndpointScheme::Http => 80,
+			EndpointScheme::Https => 443,
+		}
+	}
+}
+
+/// The services a worker endpoint serves
+#[repr(u8)]
+#[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
+pub enum WorkerService {
+	Tagging,
+	Verify,
+	KeyDistribution,
+}
+
+impl WorkerService {
+	/// The services a worker of `role` serves unless it announces otherwise
+	pub fn defaults_for(role: &WorkerRole) -> Vec<WorkerService> {
+		match role {
+			WorkerRole::Full => vec![WorkerService::Tagging, WorkerService::Verify, WorkerService::KeyDistribution],
+			WorkerRole::Verifier => vec![WorkerService::Verify, WorkerService::KeyDistribution],
+			WorkerRole::Marker => vec![WorkerService::Tagging, WorkerService::KeyDistribution],
+		}
+	}
+
+	pub fn allowed_for(&self, role: &WorkerRole) -> bool {
+		match self {
+			WorkerService::Tagging => *role == WorkerRole::Full || *role == WorkerRole::Marker,
+			WorkerService::Verify => *role == WorkerRole::Full || *role == WorkerRole::Verifier,
+			WorkerService::KeyDistribution => true,
+		}
+	}
+}
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo)]
+#[cfg_attr(feature = "enable_serde", derive(Serialize, Deserialize))]
+pub struct WorkerEndpoint {
+	pub scheme: EndpointScheme,
+	pub host: String,
+	pub port: u16,
+	pub services: Vec<WorkerService>,
+}
+
+impl WorkerEndpoint {
+	/// Parse a `scheme://host[:port][/]` url, the form ceseal registers its endpoint in
+	pub fn parse(url: &str, services: Vec<WorkerService>) -> Result<Self, String> {
+		let (scheme, rest) = url.split_once("://").ok_or_else(|| String::from("missing endpoint scheme"))?;
+		let scheme = match scheme.to_lowercase().as_str() {
+			"http" => EndpointScheme::Http,
+			"https" => EndpointScheme::Https,
+			_ => return Err(String::from("unsupported endpoint scheme")),
+		};
+		let authority = rest.trim_end_matches('/');
+		if authority.contains('/') {
+			return Err(String::from("endpoint must not carry a path"));
+		}
+		let parse_port = |port: &str| port.parse::<u16>().map_err(|_| String::from("invalid endpoint port"));
+		let (host, port) = if authority.starts_with('[') {
+			// An IPv6 address, which holds colons of its own, so the port can only follow the bracket.
+			let end = authority.find(']').ok_or_else(|| String::from("unterminated endpoint address"))?;
+			let (host, rest) = authority.split_at(end + 1);
+			if host.len() == 2 {
+				return Err(String::from("missing endpoint host"));
+			}
+			match rest {
+				"" => (host, scheme.default_port()),
+				_ => (host, parse_port(rest.strip_prefix(':').ok_or_else(|| String::from("invalid endpoint port"))?)?),
+			}
+		} else {
+			match authority.rsplit_once(':') {
+				Some((host, port)) => (host, parse_port(port)?),
+				None => (authority, scheme.default_port()),
+			}
+		};
+		if host.is_empty() {
+			return Err(String::from("missing endpoint host"));
+		}
+		Ok(WorkerEndpoint { scheme, host: String::from(host), port, services })
+	}
+
+	pub fn url(&self) -> String {
+		alloc::format!("{}://{}:{}", self.scheme.as_str(), self.host, self.port)
+	}
+}
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct EndpointUpdatePayload {
+	pub pubkey: WorkerPublicKey,
+	pub endpoint: WorkerEndpoint,
+	pub signing_time: u64,
+}
+
+#[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo)]
+pub struct HeartbeatPayload {
+	pub pubkey: WorkerPublicKey,
+	pub signing_time: u64,
+}
+
 #[derive(Encode, Decode, Debug, Clone, PartialEq, Eq, TypeInfo)]
 pub struct MasterKeyApplyPayload {
 	pub pubkey: WorkerPublicKey,
@@ -179,3 +296,50 @@ impl FromStr for ChainNetwork {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn worker_endpoint_parse_works() {
+		let endpoint = WorkerEndpoint::parse("https://tee.cess.network/", vec![WorkerService::Verify]).unwrap();
+		assert_eq!(endpoint.scheme, EndpointScheme::Https);
+		assert_eq!(endpoint.host, "tee.cess.network");
+		assert_eq!(endpoint.port, 443);
+		assert_eq!(endpoint.url(), "https://tee.cess.network:443");
+
+		let endpoint = WorkerEndpoint::parse("http://10.0.0.1:8000", vec![]).unwrap();
+		assert_eq!(endpoint.scheme, EndpointScheme::Http);
+		assert_eq!(endpoint.host, "10.0.0.1");
+		assert_eq!(endpoint.port, 8000);
+
+		assert!(WorkerEndpoint::parse("tee.cess.network", vec![]).is_err());
+		assert!(WorkerEndpoint::parse("ftp://tee.cess.network", vec![]).is_err());
+		assert!(WorkerEndpoint::parse("https://tee.cess.network:port", vec![]).is_err());
+		assert!(WorkerEndpoint::parse("https://tee.cess.network/api", vec![]).is_err());
+		assert!(WorkerEndpoint::parse("https://:443", vec![]).is_err());
+
+		let endpoint = WorkerEndpoint::parse("https://[::1]/", vec![]).unwrap();
+		assert_eq!(endpoint.host, "[::1]");
+		assert_eq!(endpoint.port, 443);
+		assert_eq!(endpoint.url(), "https://[::1]:443");
+		let endpoint = WorkerEndpoint::parse("http://[fe80::1]:8080", vec![]).unwrap();
+		assert_eq!(endpoint.host, "[fe80::1]");
+		assert_eq!(endpoint.port, 8080);
+		assert!(WorkerEndpoint::parse("http://[::1", vec![]).is_err());
+		assert!(WorkerEndpoint::parse("http://[]:8080", vec![]).is_err());
+		assert!(WorkerEndpoint::parse("http://[::1]8080", vec![]).is_err());
+	}
+
+	#[test]
+	fn worker_service_role_rules() {
+		assert!(WorkerService::Tagging.allowed_for(&WorkerRole::Marker));
+		assert!(!WorkerService::Tagging.allowed_for(&WorkerRole::Verifier));
+		assert!(WorkerService::Verify.allowed_for(&WorkerRole::Verifier));
+		assert!(!WorkerService::Verify.allowed_for(&WorkerRole::Marker));
+		for role in [WorkerRole::Full, WorkerRole::Verifier, WorkerRole::Marker] {
+			assert!(WorkerService::defaults_for(&role).iter().all(|service| service.allowed_for(&role)));
+		}
+	}
+}
@@ -1,12 +1,17 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use pallet_babe::{Config, EpochIndex, ParentBlockRandomness};
-use codec::{alloc::string::ToString, Decode};
+use codec::{alloc::string::ToString, Decode, Encode, MaxEncodedLen};
 use frame_election_provider_support::{Assignment, NposSolver, WeightInfo as NposWeightInfo};
-use frame_support::{traits::Randomness, weights::Weight};
-use sp_npos_elections::{ElectionResult, ExtendedBalance, IdentifierT, PerThing128, VoteWeight};
+use frame_support::{pallet_prelude::RuntimeDebug, traits::Randomness, weights::Weight};
+use scale_info::TypeInfo;
+use sp_npos_elections::{ElectionResult, IdentifierT, PerThing128};
+pub use sp_npos_elections::{ExtendedBalance, VoteWeight};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
+#[cfg(test)]
+mod tests;
+
 /// Trait used to retrieve credits of validators.
 pub trait ValidatorCredits<ValidatorId> {
 	/// Returns the full score.
@@ -31,18 +36,120 @@ pub trait VrfSloverConfig {
 	fn min_electable_weight() -> VoteWeight;
 }
 
+/// How a candidate was scored in an election.
+///
+/// Candidates backed by less than the minimum electable weight are ineligible: they get no
+/// random draw and are never elected.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CandidateScore<AccountId> {
+	pub who: AccountId,
+	pub backed_stake: ExtendedBalance,
+	/// Out of the full credit.
+	pub credit_score: u32,
+	/// Backed stake relative to the best backed candidate, out of 100.
+	pub stake_score: u32,
+	/// The random draw, before it is reduced to the full credit.
+	pub random_number: u32,
+	pub random_score: u32,
+	/// 50% of the credit score, 30% of the stake score and 20% of the random score.
+	pub final_score: u32,
+	pub eligible: bool,
+	pub elected: bool,
+}
+
+/// Trait used to report how the candidates of an election were scored.
+pub trait ElectionObserver<AccountId> {
+	/// Called with the scores of the candidates, best scored first.
+	fn on_scored(epoch_index: u64, scores: Vec<CandidateScore<AccountId>>);
+}
+
+impl<AccountId> ElectionObserver<AccountId> for () {
+	fn on_scored(_epoch_index: u64, _scores: Vec<CandidateScore<AccountId>>) {}
+}
+
+/// Trait used to retrieve the epoch and the randomness an election runs with.
+pub trait ElectionRandomness {
+	/// Returns the epoch the credits are fetched for.
+	fn epoch_index() -> u64;
+
+	/// Returns a random number for `subject`.
+	fn random_number(subject: &[u8]) -> u32;
+}
+
+/// The randomness of the BABE parent block, used on chain.
+pub struct BabeRandomness<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> ElectionRandomness for BabeRandomness<T> {
+	fn epoch_index() -> u64 {
+		EpochIndex::<T>::get()
+	}
+
+	fn random_number(subject: &[u8]) -> u32 {
+		let (hash, _) = ParentBlockRandomness::<T>::random(subject);
+		let hash = match hash {
+			Some(h) => h,
+			None => T::Hash::default(),
+		};
+		log::debug!(target: "rrsc::vrf_solver", "{:?} Hash: {:?}", subject, hash);
+		u32::decode(&mut hash.as_ref()).expect("secure hashes should always be bigger than u32; qed")
+	}
+}
+
+/// Randomness derived from a fixed seed, to replay an election off chain.
+pub fn seeded_random_number(seed: &[u8; 32], subject: &[u8]) -> u32 {
+	let hash = sp_crypto_hashing::blake2_256(&[&seed[..], subject].concat());
+	u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// The inputs of an election, as exported from a chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElectionInput<AccountId> {
+	pub to_elect: usize,
+	pub targets: Vec<AccountId>,
+	pub voters: Vec<(AccountId, VoteWeight, Vec<AccountId>)>,
+	pub credits: BTreeMap<AccountId, u32>,
+	pub full_credit: u32,
+	pub min_electable_weight: VoteWeight,
+}
+
+/// Run the election of [`VrfSolver`] on `input`, with randomness derived from `seed`.
+///
+/// Gives the same result for the same inputs, wherever it runs.
+pub fn simulate<AccountId: IdentifierT, Accuracy: PerThing128>(
+	input: ElectionInput<AccountId>,
+	seed: [u8; 32],
+) -> ElectionResult<AccountId, Accuracy> {
+	simulate_with_scores(input, seed).0
+}
+
+/// Same as [`simulate`], also returning how the candidates were scored, best scored first.
+pub fn simulate_with_scores<AccountId: IdentifierT, Accuracy: PerThing128>(
+	input: ElectionInput<AccountId>,
+	seed: [u8; 32],
+) -> (ElectionResult<AccountId, Accuracy>, Vec<CandidateScore<AccountId>>) {
+	elect(
+		input.to_elect,
+		input.targets,
+		input.voters,
+		&input.credits,
+		input.full_credit,
+		input.min_electable_weight,
+		|subject| seeded_random_number(&seed, subject),
+	)
+}
+
 /// A wrapper for elect by vrf that implements [`NposSolver`].
-pub struct VrfSolver<AccountId, Accuracy, T, Credits, SloverConfig>(
-	sp_std::marker::PhantomData<(AccountId, Accuracy, T, Credits, SloverConfig)>,
+pub struct VrfSolver<AccountId, Accuracy, Random, Credits, SloverConfig>(
+	sp_std::marker::PhantomData<(AccountId, Accuracy, Random, Credits, SloverConfig)>,
 );
 
 impl<
 		AccountId: IdentifierT,
 		Accuracy: PerThing128,
-		T: Config,
+		Random: ElectionRandomness,
 		Credits: ValidatorCredits<AccountId>,
 		SloverConfig: VrfSloverConfig,
-	> NposSolver for VrfSolver<AccountId, Accuracy, T, Credits, SloverConfig>
+	> NposSolver for VrfSolver<AccountId, Accuracy, Random, Credits, SloverConfig>
 {
 	type AccountId = AccountId;
 	type Accuracy = Accuracy;
@@ -52,84 +159,17 @@ impl<
 		targets: Vec<Self::AccountId>,
 		voters: Vec<(Self::AccountId, VoteWeight, impl IntoIterator<Item = Self::AccountId>)>,
 	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
-		let to_elect = winners;
-
-		let ElectionResult { winners, assignments } = Self::setup_inputs(targets, voters);
-		// max_stake is used as a benchmark value of 100 stake_score
-		let mut max_stake: ExtendedBalance = SloverConfig::min_electable_weight().into();
-		winners.clone().into_iter().for_each(|(_, backed_stake)| {
-			if backed_stake > max_stake {
-				max_stake = backed_stake;
-			}
-		});
-
-		let credits = Credits::credits(EpochIndex::<T>::get());
-		let full_credit = Credits::full_credit();
-
-		let mut account_scores = winners
-			.into_iter()
-			.enumerate()
-			.map(|(account_index, (account_id, backed_stake))| {
-				// credit_score
-				let credit_score = match credits.get(&account_id) {
-					Some(c) => *c,
-					None => 0,
-				};
-				// stake_score
-				let stake_score = Accuracy::from_rational(backed_stake, max_stake).mul_floor(100) as u32;
-				// random_score
-				let random_number = Self::random_number("authorities", &account_index);
-				let random_score = random_number % full_credit;
-				// final_score = `credit_score` * 50% + `stake_score` * 30% + `random_score` * 20%
-				let final_score = credit_score
-					.saturating_mul(5)
-					.saturating_add(stake_score.saturating_mul(3))
-					.saturating_add(random_score.saturating_mul(2))
-					.saturating_div(10);
-
-				log::debug!(
-					target: "rrsc::vrf_solver",
-					"account: {:?}, credit_score: {:?}, stake_score: {:?}, random_score: {:?}, final_score: {:?}",
-					account_id,
-					credit_score,
-					stake_score,
-					random_score,
-					final_score,
-				);
-				(account_id, backed_stake, final_score)
-			})
-			.collect::<Vec<(AccountId, ExtendedBalance, u32)>>();
-
-		account_scores.sort_by_key(|e| e.2);
-		account_scores.reverse();
-
-		let winners = account_scores
-			.into_iter()
-			.take(to_elect)
-			.map(|e| (e.0, e.1))
-			.collect::<Vec<_>>();
-		let winner_accounts = winners.clone().into_iter().map(|w| w.0).collect::<Vec<AccountId>>();
-
-		let assignments = assignments
-			.into_iter()
-			.filter_map(|assignment| {
-				let mut distribution: Vec<(AccountId, Accuracy)> = Vec::new();
-				for d in assignment.distribution {
-					if winner_accounts.contains(&d.0) {
-						distribution.push(d);
-					} // else {} would be wrong votes. We don't really care about it.
-				}
-				if distribution.is_empty() {
-					None
-				} else {
-					Some(Assignment { who: assignment.who, distribution })
-				}
-			})
-			.collect::<Vec<_>>();
-
-		log::debug!(target: "rrsc::vrf_solver", "[solve] winners: {:#?}", winners);
-		log::debug!(target: "rrsc::vrf_solver", "[solve] assignments: {:#?}", assignments);
-		Ok(ElectionResult { winners, assignments })
+		let credits = Credits::credits(Random::epoch_index());
+		let (result, _) = elect(
+			winners,
+			targets,
+			voters,
+			&credits,
+			Credits::full_credit(),
+			SloverConfig::min_electable_weight(),
+			Random::random_number,
+		);
+		Ok(result)
 	}
 
 	fn weight<W: NposWeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
@@ -140,74 +180,230 @@ impl<
 impl<
 		AccountId: IdentifierT,
 		Accuracy: PerThing128,
-		T: Config,
+		Random: ElectionRandomness,
 		Credits: ValidatorCredits<AccountId>,
 		SloverConfig: VrfSloverConfig,
-	> VrfSolver<AccountId, Accuracy, T, Credits, SloverConfig>
+	> VrfSolver<AccountId, Accuracy, Random, Credits, SloverConfig>
 {
 	pub fn random_number(context: &str, authority_index: &usize) -> u32 {
-		let mut b_context = context.to_string();
-		b_context.push_str(authority_index.to_string().as_str());
-		let (hash, _) = ParentBlockRandomness::<T>::random(&b_context.as_bytes());
-		let hash = match hash {
-			Some(h) => h,
-			None => T::Hash::default(),
-		};
-		log::debug!(target: "rrsc::vrf_solver", "{:?} Hash: {:?}", b_context, hash);
-		let random_number =
-			u32::decode(&mut hash.as_ref()).expect("secure hashes should always be bigger than u32; qed");
-		random_number
+		Random::random_number(&random_subject(context, authority_index))
 	}
 
-	/// Converts raw inputs to types used in this crate.
+	/// Score the candidates of an election as [`NposSolver::solve`] would in this block, best
+	/// scored first, along with the epoch the credits were fetched for.
 	///
-	/// This will perform some cleanup that are most often important:
-	/// - It drops any votes that are pointing to non-candidates.
-	/// - It drops duplicate targets within a voter.
-	fn setup_inputs(
-		initial_candidates: Vec<AccountId>,
-		initial_voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
-	) -> ElectionResult<AccountId, Accuracy> {
-		let mut candidates = BTreeMap::<AccountId, ExtendedBalance>::new();
-
-		initial_candidates.into_iter().for_each(|who| {
-			candidates.insert(who, 0u128);
-		});
-
-		let assignments = initial_voters
-			.into_iter()
-			.filter_map(|(who, vote_weight, votes)| {
-				let mut distribution: Vec<(AccountId, Accuracy)> = Vec::new();
-				let votes_vec = votes.into_iter().collect::<Vec<_>>();
-				log::debug!(target: "rrsc::vrf_solver", "[setup_inputs] vote: {:#?}, {:#?}, {:#?}", who, vote_weight, votes_vec);
-				let votes_count = votes_vec.len() as u128;
-				for v in votes_vec {
-					if distribution.iter().any(|e| e.0 == v) {
-						// duplicate vote.
-						continue
-					}
-					if let Some(backed_stake) = candidates.get_mut(&v) {
-						// This candidate is valid.
-						*backed_stake = backed_stake.saturating_add((vote_weight as u128).saturating_div(votes_count));
-						distribution.push((v.clone(), Accuracy::from_rational(1, votes_count)));
-					} // else {} would be wrong votes. We don't really care about it.
-				}
-				if distribution.is_empty() {
-					None
-				} else {
-					Some(Assignment { who, distribution })
-				}
-			})
-			.collect::<Vec<_>>();
-
-		log::debug!(target: "rrsc::vrf_solver", "[setup_inputs] candidates: {:#?}", candidates);
-		let winners = candidates
-			.into_iter()
-			.filter(|(_, bakced_stake)| *bakced_stake >= SloverConfig::min_electable_weight().into())
-			.collect::<Vec<_>>();
-		log::debug!(target: "rrsc::vrf_solver", "[setup_inputs] winners: {:#?}", winners);
-		log::debug!(target: "rrsc::vrf_solver", "[setup_inputs] assignments: {:#?}", assignments);
-
-		ElectionResult { winners, assignments }
+	/// Meant to be called in the block an election is enacted: the solver itself may run off
+	/// chain, where nothing it records is kept. `elected` is left unset.
+	pub fn score(
+		targets: Vec<AccountId>,
+		voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+	) -> (u64, Vec<CandidateScore<AccountId>>) {
+		let epoch_index = Random::epoch_index();
+		let credits = Credits::credits(epoch_index);
+		let (scores, _) = score_candidates::<AccountId, Accuracy>(
+			targets,
+			voters,
+			&credits,
+			Credits::full_credit(),
+			SloverConfig::min_electable_weight(),
+			Random::random_number,
+		);
+		(epoch_index, scores)
 	}
 }
+
+fn random_subject(context: &str, authority_index: &usize) -> Vec<u8> {
+	let mut b_context = context.to_string();
+	b_context.push_str(authority_index.to_string().as_str());
+	b_context.into_bytes()
+}
+
+/// Elect `to_elect` targets, scored by credit, backed stake and randomness.
+fn elect<AccountId: IdentifierT, Accuracy: PerThing128>(
+	to_elect: usize,
+	targets: Vec<AccountId>,
+	voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+	credits: &BTreeMap<AccountId, u32>,
+	full_credit: u32,
+	min_electable_weight: VoteWeight,
+	random_number: impl Fn(&[u8]) -> u32,
+) -> (ElectionResult<AccountId, Accuracy>, Vec<CandidateScore<AccountId>>) {
+	let (mut account_scores, assignments) = score_candidates::<AccountId, Accuracy>(
+		targets,
+		voters,
+		credits,
+		full_credit,
+		min_electable_weight,
+		random_number,
+	);
+	account_scores.iter_mut().filter(|e| e.eligible).take(to_elect).for_each(|e| e.elected = true);
+
+	let winners = account_scores
+		.iter()
+		.filter(|e| e.elected)
+		.map(|e| (e.who.clone(), e.backed_stake))
+		.collect::<Vec<_>>();
+	let winner_accounts = winners.clone().into_iter().map(|w| w.0).collect::<Vec<AccountId>>();
+
+	let assignments = assignments
+		.into_iter()
+		.filter_map(|assignment| {
+			let mut distribution: Vec<(AccountId, Accuracy)> = Vec::new();
+			for d in assignment.distribution {
+				if winner_accounts.contains(&d.0) {
+					distribution.push(d);
+				} // else {} would be wrong votes. We don't really care about it.
+			}
+			if distribution.is_empty() {
+				None
+			} else {
+				Some(Assignment { who: assignment.who, distribution })
+			}
+		})
+		.collect::<Vec<_>>();
+
+	log::debug!(target: "rrsc::vrf_solver", "[solve] winners: {:#?}", winners);
+	log::debug!(target: "rrsc::vrf_solver", "[solve] assignments: {:#?}", assignments);
+	(ElectionResult { winners, assignments }, account_scores)
+}
+
+/// Score `targets` by credit, backed stake and randomness, best scored first, along with the
+/// votes backing them.
+///
+/// Targets backed by less than `min_electable_weight` are listed after the eligible ones, scored
+/// without a random draw.
+fn score_candidates<AccountId: IdentifierT, Accuracy: PerThing128>(
+	targets: Vec<AccountId>,
+	voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+	credits: &BTreeMap<AccountId, u32>,
+	full_credit: u32,
+	min_electable_weight: VoteWeight,
+	random_number: impl Fn(&[u8]) -> u32,
+) -> (Vec<CandidateScore<AccountId>>, Vec<Assignment<AccountId, Accuracy>>) {
+	let ElectionResult { winners: candidates, assignments } = setup_inputs::<AccountId, Accuracy>(targets, voters, 0);
+	let (eligible, ineligible): (Vec<_>, Vec<_>) = candidates
+		.into_iter()
+		.partition(|(_, backed_stake)| *backed_stake >= min_electable_weight.into());
+	// max_stake is used as a benchmark value of 100 stake_score
+	let mut max_stake: ExtendedBalance = min_electable_weight.into();
+	eligible.iter().for_each(|(_, backed_stake)| {
+		if *backed_stake > max_stake {
+			max_stake = *backed_stake;
+		}
+	});
+
+	let score = |account_id: AccountId, backed_stake: ExtendedBalance, random_number: u32, eligible: bool| {
+		// credit_score
+		let credit_score = match credits.get(&account_id) {
+			Some(c) => *c,
+			None => 0,
+		};
+		// stake_score
+		let stake_score = Accuracy::from_rational(backed_stake, max_stake).mul_floor(100) as u32;
+		// random_score
+		let random_score = random_number.checked_rem(full_credit).unwrap_or(0);
+		// final_score = `credit_score` * 50% + `stake_score` * 30% + `random_score` * 20%
+		let final_score = credit_score
+			.saturating_mul(5)
+			.saturating_add(stake_score.saturating_mul(3))
+			.saturating_add(random_score.saturating_mul(2))
+			.saturating_div(10);
+
+		log::debug!(
+			target: "rrsc::vrf_solver",
+			"account: {:?}, credit_score: {:?}, stake_score: {:?}, random_score: {:?}, final_score: {:?}",
+			account_id,
+			credit_score,
+			stake_score,
+			random_score,
+			final_score,
+		);
+		CandidateScore {
+			who: account_id,
+			backed_stake,
+			credit_score,
+			stake_score,
+			random_number,
+			random_score,
+			final_score,
+			eligible,
+			elected: false,
+		}
+	};
+
+	// The random draws are indexed among the eligible candidates only, so that ineligible ones
+	// don't change the outcome.
+	let mut account_scores = eligible
+		.into_iter()
+		.enumerate()
+		.map(|(account_index, (account_id, backed_stake))| {
+			let random_number = random_number(&random_subject("authorities", &account_index));
+			score(account_id, backed_stake, random_number, true)
+		})
+		.collect::<Vec<CandidateScore<AccountId>>>();
+	account_scores.sort_by_key(|e| e.final_score);
+	account_scores.reverse();
+
+	let mut ineligible_scores = ineligible
+		.into_iter()
+		.map(|(account_id, backed_stake)| score(account_id, backed_stake, 0, false))
+		.collect::<Vec<CandidateScore<AccountId>>>();
+	ineligible_scores.sort_by_key(|e| e.final_score);
+	ineligible_scores.reverse();
+	account_scores.append(&mut ineligible_scores);
+
+	(account_scores, assignments)
+}
+
+/// Converts raw inputs to types used in this crate.
+///
+/// This will perform some cleanup that are most often important:
+/// - It drops any votes that are pointing to non-candidates.
+/// - It drops duplicate targets within a voter.
+fn setup_inputs<AccountId: IdentifierT, Accuracy: PerThing128>(
+	initial_candidates: Vec<AccountId>,
+	initial_voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+	min_electable_weight: VoteWeight,
+) -> ElectionResult<AccountId, Accuracy> {
+	let mut candidates = BTreeMap::<AccountId, ExtendedBalance>::new();
+
+	initial_candidates.into_iter().for_each(|who| {
+		candidates.insert(who, 0u128);
+	});
+
+	let assignments = initial_voters
+		.into_iter()
+		.filter_map(|(who, vote_weight, votes)| {
+			let mut distribution: Vec<(AccountId, Accuracy)> = Vec::new();
+			let votes_vec = votes.into_iter().collect::<Vec<_>>();
+			log::debug!(target: "rrsc::vrf_solver", "[setup_inputs] vote: {:#?}, {:#?}, {:#?}", who, vote_weight, votes_vec);
+			let votes_count = votes_vec.len() as u128;
+			for v in votes_vec {
+				if distribution.iter().any(|e| e.0 == v) {
+					// duplicate vote.
+					continue
+				}
+				if let Some(backed_stake) = candidates.get_mut(&v) {
+					// This candidate is valid.
+					*backed_stake = backed_stake.saturating_add((vote_weight as u128).saturating_div(votes_count));
+					distribution.push((v.clone(), Accuracy::from_rational(1, votes_count)));
+				} // else {} would be wrong votes. We don't really care about it.
+			}
+			if distribution.is_empty() {
+				None
+			} else {
+				Some(Assignment { who, distribution })
+			}
+		})
+		.collect::<Vec<_>>();
+
+	log::debug!(target: "rrsc::vrf_solver", "[setup_inputs] candidates: {:#?}", candidates);
+	let winners = candidates
+		.into_iter()
+		.filter(|(_, bakced_stake)| *bakced_stake >= min_electable_weight.into())
+		.collect::<Vec<_>>();
+	log::debug!(target: "rrsc::vrf_solver", "[setup_inputs] winners: {:#?}", winners);
+	log::debug!(target: "rrsc::vrf_solver", "[setup_inputs] assignments: {:#?}", assignments);
+
+	ElectionResult { winners, assignments }
+}
@@ -0,0 +1,160 @@
+use super::*;
+use sp_runtime::Perbill;
+
+type AccountId = u32;
+
+/// Builds a random election out of `case`, every case being reproducible.
+fn random_input(case: u32) -> ElectionInput<AccountId> {
+	let seed = sp_crypto_hashing::blake2_256(&case.to_le_bytes());
+	let mut counter = 0u32;
+	let mut next = |modulo: u32| {
+		counter += 1;
+		seeded_random_number(&seed, &counter.to_le_bytes()) % modulo
+	};
+
+	let targets = (0..next(30)).collect::<Vec<AccountId>>();
+	let voters = (100..100 + next(60))
+		.map(|who| {
+			let weight = next(10_000) as VoteWeight;
+			// votes may point to non-candidates and may be duplicated.
+			let votes = (0..next(8)).map(|_| next(40)).collect::<Vec<_>>();
+			(who, weight, votes)
+		})
+		.collect::<Vec<_>>();
+	let credits = targets.iter().map(|t| (*t, next(1_000))).collect::<BTreeMap<_, _>>();
+
+	ElectionInput {
+		to_elect: next(20) as usize,
+		targets,
+		voters,
+		credits,
+		full_credit: 1000,
+		min_electable_weight: next(2_000) as VoteWeight,
+	}
+}
+
+#[test]
+fn simulate_is_deterministic() {
+	for case in 0..100 {
+		let input = random_input(case);
+		let seed = [case as u8; 32];
+		assert_eq!(
+			simulate::<AccountId, Perbill>(input.clone(), seed),
+			simulate::<AccountId, Perbill>(input, seed),
+		);
+	}
+}
+
+#[test]
+fn winners_are_bounded_electable_candidates() {
+	for case in 0..100 {
+		let input = random_input(case);
+		let result = simulate::<AccountId, Perbill>(input.clone(), [7; 32]);
+
+		assert!(result.winners.len() <= input.to_elect);
+		let mut accounts = result.winners.iter().map(|w| w.0).collect::<Vec<_>>();
+		accounts.sort();
+		accounts.dedup();
+		assert_eq!(accounts.len(), result.winners.len());
+		for (who, backed_stake) in result.winners.iter() {
+			assert!(input.targets.contains(who));
+			assert!(*backed_stake >= input.min_electable_weight as ExtendedBalance);
+		}
+	}
+}
+
+#[test]
+fn assignments_only_back_winners() {
+	for case in 0..100 {
+		let input = random_input(case);
+		let result = simulate::<AccountId, Perbill>(input.clone(), [3; 32]);
+
+		for assignment in result.assignments.iter() {
+			assert!(input.voters.iter().any(|v| v.0 == assignment.who));
+			assert!(!assignment.distribution.is_empty());
+			for (target, _) in assignment.distribution.iter() {
+				assert!(result.winners.iter().any(|w| w.0 == *target));
+			}
+		}
+	}
+}
+
+#[test]
+fn every_electable_candidate_wins_when_seats_suffice() {
+	for case in 0..100 {
+		let mut input = random_input(case);
+		input.to_elect = input.targets.len();
+		let electable = setup_inputs::<AccountId, Perbill>(
+			input.targets.clone(),
+			input.voters.clone(),
+			input.min_electable_weight,
+		)
+		.winners;
+
+		let result = simulate::<AccountId, Perbill>(input, [11; 32]);
+		let mut winners = result.winners;
+		winners.sort();
+		assert_eq!(winners, electable);
+	}
+}
+
+#[test]
+fn full_credit_without_stake_or_randomness_wins() {
+	let input = ElectionInput {
+		to_elect: 1,
+		targets: vec![1, 2],
+		voters: vec![(10, 100, vec![1]), (11, 100, vec![2])],
+		credits: BTreeMap::from([(1, 0), (2, 1000)]),
+		full_credit: 1,
+		min_electable_weight: 0,
+	};
+	let result = simulate::<AccountId, Perbill>(input, [0; 32]);
+	assert_eq!(result.winners, vec![(2, 100)]);
+	assert_eq!(result.assignments.len(), 1);
+	assert_eq!(result.assignments[0].who, 11);
+}
+
+#[test]
+fn scores_explain_the_winners() {
+	for case in 0..100 {
+		let input = random_input(case);
+		let (result, scores) = simulate_with_scores::<AccountId, Perbill>(input.clone(), [5; 32]);
+
+		let eligible = scores.iter().take_while(|s| s.eligible).collect::<Vec<_>>();
+		let ineligible = &scores[eligible.len()..];
+		assert!(eligible.windows(2).all(|w| w[0].final_score >= w[1].final_score));
+		assert!(ineligible.windows(2).all(|w| w[0].final_score >= w[1].final_score));
+		assert!(ineligible.iter().all(|s| !s.eligible && !s.elected && s.random_number == 0));
+		let elected = scores.iter().filter(|s| s.elected).map(|s| (s.who, s.backed_stake)).collect::<Vec<_>>();
+		assert_eq!(elected, result.winners);
+		for score in scores.iter() {
+			assert!(score.random_score < input.full_credit);
+			assert!(score.stake_score <= 100);
+		}
+	}
+}
+
+#[test]
+fn ineligible_candidates_are_scored_without_changing_the_outcome() {
+	for case in 0..100 {
+		let input = random_input(case);
+		let (result, scores) = simulate_with_scores::<AccountId, Perbill>(input.clone(), [9; 32]);
+
+		let mut scored = scores.iter().map(|s| s.who).collect::<Vec<_>>();
+		scored.sort();
+		assert_eq!(scored, input.targets);
+		for score in scores.iter() {
+			assert_eq!(score.eligible, score.backed_stake >= input.min_electable_weight as ExtendedBalance);
+		}
+
+		// Dropping the ineligible candidates elects the same winners.
+		let mut eligible_only = input.clone();
+		eligible_only.targets = scores.iter().filter(|s| s.eligible).map(|s| s.who).collect();
+		eligible_only.targets.sort();
+		let mut winners = result.winners;
+		let mut eligible_winners = simulate::<AccountId, Perbill>(eligible_only, [9; 32]).winners;
+		winners.sort();
+		eligible_winners.sort();
+		assert_eq!(winners, eligible_winners);
+	}
+}
@@ -21,6 +21,9 @@ pub trait SchedulerCreditCounter<SchedulerCtrlAccountId> {
   /// Record the number of times the scheduler has been punished
 	fn record_punishment(scheduler_id: &SchedulerCtrlAccountId) -> DispatchResult;
 
+  /// The credit score the scheduler earned over the recent periods
+	fn credit_score(scheduler_id: &SchedulerCtrlAccountId) -> u32;
+
 }
 
 /// Stash account finder, used to find the corresponding Stash account according to the Controller account
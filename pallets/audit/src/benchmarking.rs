@@ -387,4 +387,41 @@ benchmarks! {
             None => assert!(false),
         };
     }
+
+    submit_cross_check_result {
+        log::info!("start submit_cross_check_result");
+        pallet_tee_worker::benchmarking::generate_workers::<T>()?;
+        let caller: AccountOf<T> = account("relayer", 100, SEED);
+        let miner: AccountOf<T> = account("miner1", 100, SEED);
+        let tee_puk = pallet_tee_worker::benchmarking::get_pubkey::<T>();
+        let first = sp_core::sr25519::Public::from_raw([1u8; 32]);
+        let checker = sp_core::sr25519::Public::from_raw([2u8; 32]);
+
+        // The arbitration of a disagreement records the outcome for both disputing workers.
+        let mission = CrossCheckMission::Idle(VerifyIdleResultInfo::<T> {
+            miner: miner.clone(),
+            miner_prove: [8u8; 64].to_vec().try_into().unwrap(),
+            front: 0,
+            rear: 1,
+            accumulator: [0u8; 256],
+            space_challenge_param: [0u64; 8],
+            result: true,
+            tee_puk: first,
+        });
+        let hashing = mission.checker_hash(true, tee_puk);
+        let deadline = frame_system::Pallet::<T>::block_number() + T::CrossCheckPeriod::get();
+        <PendingCrossCheck<T>>::insert(
+            &miner,
+            ProveKind::Idle,
+            CrossCheckInfo::<T> { checker: tee_puk, deadline, mission, disputed: Some((checker, false)) },
+        );
+        <CrossCheckSlip<T>>::insert(&deadline, (&miner, ProveKind::Idle), true);
+
+        let sig = pallet_tee_worker::benchmarking::sign_message::<T>(&hashing);
+        let sig: BoundedVec<u8, ConstU32<64>> = sig.0.to_vec().try_into().map_err(|_| "bounded convert error")?;
+    }: _(RawOrigin::Signed(caller), miner.clone(), ProveKind::Idle, true, sig, tee_puk)
+    verify {
+        assert!(!<PendingCrossCheck<T>>::contains_key(&miner, ProveKind::Idle));
+        assert_eq!(<TeeCrossCheckRecord<T>>::get(&checker).disagreed, 1);
+    }
 }
@@ -10,3 +10,17 @@ pub(super) const IDLE_VERIFY_RATE: u128 = 2_147_483_648;
 pub(super) const SERVICE_FAULT_TOLERANT: u8 = 2;
 
 pub(super) type SpaceChallengeParam = [u64; 8];
+
+// Domain tags of the random seeds, so that no two draws reuse the same randomness.
+pub(super) const VERIFY_TEE_SEED_TAG: &[u8] = b"audit/verify-tee";
+
+pub(super) const CROSS_CHECK_SAMPLE_SEED_TAG: &[u8] = b"audit/cross-check/sample";
+
+pub(super) const CROSS_CHECK_CHECKER_SEED_TAG: &[u8] = b"audit/cross-check/checker";
+
+pub(super) const CROSS_CHECK_ARBITER_SEED_TAG: &[u8] = b"audit/cross-check/arbiter";
+
+// Selection weight a tee worker gets on top of its credit score, so new workers are still picked.
+pub(super) const TEE_SELECT_BASE_WEIGHT: u64 = 1_000;
+
+pub(super) const TEE_SELECT_WEIGHT_SCALE: u64 = 1_000;
@@ -25,6 +25,7 @@
 //!
 //! * `submit_challange_prove`   Miner submits challenge certificate.
 //! * `verify_proof`             Consensus submission verification challenge proof results.
+//! * `submit_cross_check_result` Submits the result of the tee sampled to re-verify a result.
 //!
 //! ### Scenarios
 //!
@@ -34,6 +35,19 @@
 //!   or the miner fails to complete the challenge on time, the miner
 //!   will be punished in both cases. Decide whether to reduce power
 //!   or space according to the file type of punishment
+//!
+//! #### Cross-check
+//!
+//!   A sampled fraction of verify results is re-verified by a second tee worker.
+//!   When the two disagree, a third worker re-verifies the result and the disagreement is
+//!   recorded against the worker it contradicts. A worker whose disagreement rate exceeds
+//!   the configured threshold is slashed and loses scheduler credit.
+//!
+//! #### Tee assignment
+//!
+//!   Proofs are assigned to tee workers with the `Verifier` or `Full` role, weighted by
+//!   scheduler credit score and by the number of verify missions each worker has pending.
+//!   Workers bonded to the challenged miner's own stash are never assigned.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -79,7 +93,7 @@ use pallet_storage_handler::StorageHandle;
 use pallet_tee_worker::TeeWorkerHandler;
 use scale_info::TypeInfo;
 use sp_core::{crypto::KeyTypeId, offchain::OpaqueNetworkState, H256};
-use sp_runtime::{app_crypto::RuntimeAppPublic, SaturatedConversion, Saturating};
+use sp_runtime::{app_crypto::RuntimeAppPublic, PerThing, Perbill, SaturatedConversion, Saturating};
 use sp_std::{
 	convert::{TryFrom, TryInto},
 	prelude::*,
@@ -209,6 +223,18 @@ pub mod pallet {
 		type ReassignCeiling: Get<u8> + Clone + Eq + PartialEq;
 
 		type CreditCounter: SchedulerCreditCounter<Self::AccountId>;
+		/// The fraction of verify results that are re-verified by a second tee worker.
+		#[pallet::constant]
+		type CrossCheckRate: Get<Perbill>;
+		/// How many blocks the second tee worker has to submit its cross-check result.
+		#[pallet::constant]
+		type CrossCheckPeriod: Get<BlockNumberFor<Self>>;
+		/// The number of cross-checks a tee worker needs before its disagreement rate is judged.
+		#[pallet::constant]
+		type MinCrossCheckSample: Get<u32>;
+		/// Tee workers whose disagreement rate exceeds this threshold are punished.
+		#[pallet::constant]
+		type DisagreementThreshold: Get<Perbill>;
 	}
 
 	#[pallet::event]
@@ -225,6 +251,27 @@ pub mod pallet {
 		SubmitServiceVerifyResult { tee: WorkerPublicKey, miner: AccountOf<T>, result: bool },
 
 		VerifyProof { tee_worker: WorkerPublicKey, miner: AccountOf<T> },
+
+		CrossCheckAssigned { miner: AccountOf<T>, kind: ProveKind, tee: WorkerPublicKey, checker: WorkerPublicKey },
+
+		CrossCheckSubmitted {
+			miner: AccountOf<T>,
+			kind: ProveKind,
+			tee: WorkerPublicKey,
+			checker: WorkerPublicKey,
+			agree: bool,
+		},
+
+		CrossCheckArbitrated {
+			miner: AccountOf<T>,
+			kind: ProveKind,
+			arbiter: WorkerPublicKey,
+			contradicted: WorkerPublicKey,
+		},
+
+		CrossCheckExpired { miner: AccountOf<T>, kind: ProveKind, checker: WorkerPublicKey },
+
+		TeeDisagreementPunished { tee: WorkerPublicKey, checked: u32, disagreed: u32 },
 	}
 
 	/// Error for the audit pallet.
@@ -274,6 +321,8 @@ pub mod pallet {
 		TeeNoPermission,
 		/// Signature format conversion failed
 		MalformedSignature,
+		/// No tee worker is eligible to verify the miner's proof
+		NoEligibleTee,
 	}
 
 	#[pallet::storage]
@@ -313,6 +362,34 @@ pub mod pallet {
 		bool,
 	>;
 
+	/// Verify results waiting to be re-verified by a second tee worker.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_cross_check)]
+	pub(super) type PendingCrossCheck<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, AccountOf<T>, Twox64Concat, ProveKind, CrossCheckInfo<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn cross_check_slip)]
+	pub(super) type CrossCheckSlip<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		BlockNumberFor<T>,
+		Blake2_128Concat,
+		(AccountOf<T>, ProveKind),
+		bool,
+	>;
+
+	/// The number of verify missions assigned to each tee worker and not yet submitted.
+	#[pallet::storage]
+	#[pallet::getter(fn tee_verify_load)]
+	pub(super) type TeeVerifyLoad<T: Config> = StorageMap<_, Twox64Concat, WorkerPublicKey, u32, ValueQuery>;
+
+	/// The cross-check history of each tee worker since it was last judged.
+	#[pallet::storage]
+	#[pallet::getter(fn cross_check_record)]
+	pub(super) type TeeCrossCheckRecord<T: Config> =
+		StorageMap<_, Twox64Concat, WorkerPublicKey, CrossCheckRecord, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
@@ -325,6 +402,7 @@ pub mod pallet {
 				.saturating_add(Self::generate_challenge(now))
 				.saturating_add(Self::clear_challenge(now))
 				.saturating_add(Self::clear_verify_mission(now))
+				.saturating_add(Self::clear_cross_check(now))
 		}
 	}
 
@@ -357,7 +435,8 @@ pub mod pallet {
 				}
 
 				if challenge_info.prove_info.idle_prove.is_none() {
-					let tee_puk = Self::random_select_tee_acc(0)?;
+					let tee_puk = Self::select_verify_tee(&sender, &[], VERIFY_TEE_SEED_TAG, ProveKind::Idle)?;
+					Self::increase_tee_load(&tee_puk);
 
 					let idle_prove_info = IdleProveInfo::<T> {
 						tee_puk: tee_puk.clone(),
@@ -409,7 +488,8 @@ pub mod pallet {
 				}
 
 				if challenge_info.prove_info.service_prove.is_none() {
-					let tee_puk = Self::random_select_tee_acc(0)?;
+					let tee_puk = Self::select_verify_tee(&sender, &[], VERIFY_TEE_SEED_TAG, ProveKind::Service)?;
+					Self::increase_tee_load(&tee_puk);
 
 					let service_prove_info = ServiceProveInfo::<T> {
 						tee_puk: tee_puk.clone(),
@@ -514,6 +594,7 @@ pub mod pallet {
 
 				let now = <frame_system::Pallet<T>>::block_number();
 				T::TeeWorkerHandler::update_work_block(now, &tee_puk)?;
+				Self::assign_cross_check(&sender, ProveKind::Idle, CrossCheckMission::Idle(verify_idle_info), now);
 
 				let idle_result = Self::check_idle_verify_param(
 					idle_result,
@@ -525,6 +606,7 @@ pub mod pallet {
 					&idle_prove.idle_prove,
 				);
 
+				Self::decrease_tee_load(&idle_prove.tee_puk);
 				idle_prove.verify_result = Some(idle_result);
 
 				if let Some(service_prove) = &challenge_info.prove_info.service_prove {
@@ -651,7 +733,14 @@ pub mod pallet {
 
 				let now = <frame_system::Pallet<T>>::block_number();
 				T::TeeWorkerHandler::update_work_block(now, &tee_puk)?;
+				Self::assign_cross_check(
+					&sender,
+					ProveKind::Service,
+					CrossCheckMission::Service(verify_service_info),
+					now,
+				);
 
+				Self::decrease_tee_load(&service_prove.tee_puk);
 				service_prove.verify_result = Some(service_result);
 
 				if let Some(idle_prove) = &challenge_info.prove_info.idle_prove {
@@ -690,6 +779,93 @@ pub mod pallet {
 				Ok(())
 			})
 		}
+
+		/// Submit the result of a tee worker assigned to re-verify an earlier verify result.
+		///
+		/// A sampled fraction of idle and service verify results is handed to a second tee
+		/// worker. The result is signed by the worker key of the assigned checker, so it is the
+		/// checker, not the challenged miner, that decides which result reaches the chain. If
+		/// the checker disagrees with the first worker, a third worker is assigned to re-verify
+		/// the result, and its result is submitted the same way. The disagreement is recorded
+		/// against the worker the third result contradicts.
+		///
+		/// # Parameters
+		///
+		/// - `origin`: The origin of the transaction, relaying the checker's result.
+		/// - `miner`: The challenged miner whose verify result is being cross-checked.
+		/// - `kind`: Whether the idle or the service verify result is being cross-checked.
+		/// - `result`: The verification result of the checker.
+		/// - `signature`: The signature of the checker's worker key over its verify result.
+		/// - `tee_puk`: The public key of the checker assigned to this cross-check.
+		#[pallet::call_index(12)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::submit_cross_check_result())]
+		pub fn submit_cross_check_result(
+			origin: OriginFor<T>,
+			miner: AccountOf<T>,
+			kind: ProveKind,
+			result: bool,
+			signature: BoundedVec<u8, ConstU32<64>>,
+			tee_puk: WorkerPublicKey,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+
+			let cross_check = <PendingCrossCheck<T>>::try_get(&miner, kind)
+				.map_err(|_| Error::<T>::NonExistentMission)?;
+			ensure!(cross_check.checker == tee_puk, Error::<T>::TeeNoPermission);
+			ensure!(T::TeeWorkerHandler::can_verify(&tee_puk), Error::<T>::TeeNoPermission);
+
+			let hashing = cross_check.mission.checker_hash(result, tee_puk);
+			let sig =
+				sp_core::sr25519::Signature::try_from(signature.as_slice()).or(Err(Error::<T>::MalformedSignature))?;
+			ensure!(sp_io::crypto::sr25519_verify(&sig, &hashing, &tee_puk), Error::<T>::VerifyTeeSigFailed);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			T::TeeWorkerHandler::update_work_block(now, &tee_puk)?;
+
+			<PendingCrossCheck<T>>::remove(&miner, kind);
+			<CrossCheckSlip<T>>::remove(&cross_check.deadline, (&miner, kind));
+			Self::decrease_tee_load(&tee_puk);
+
+			let tee = cross_check.mission.tee_puk();
+			let first_result = cross_check.mission.result();
+			match cross_check.disputed {
+				None => {
+					let agree = first_result == result;
+					if agree {
+						Self::record_cross_check(&tee, true);
+						Self::record_cross_check(&tee_puk, true);
+					} else {
+						Self::assign_arbiter(&miner, kind, cross_check.mission, (tee_puk, result), now);
+					}
+
+					Self::deposit_event(Event::<T>::CrossCheckSubmitted {
+						miner,
+						kind,
+						tee,
+						checker: tee_puk,
+						agree,
+					});
+				},
+				Some((checker, _)) => {
+					// The checker disagreed with the first worker, so the arbiter sides with
+					// exactly one of them.
+					let (upheld, contradicted) = if first_result == result { (tee, checker) } else { (checker, tee) };
+					Self::record_cross_check(&upheld, true);
+					Self::record_cross_check(&contradicted, false);
+
+					Self::deposit_event(Event::<T>::CrossCheckArbitrated {
+						miner,
+						kind,
+						arbiter: tee_puk,
+						contradicted,
+					});
+				},
+			}
+
+			Ok(())
+		}
+
 		// FOR TEST
 		/// Update and reset the counted clear value for a specific miner.
 		///
@@ -949,25 +1125,35 @@ pub mod pallet {
 				}
 
 				if flag {
+					if let Some(challenge_info) = <ChallengeSnapShot<T>>::take(&miner) {
+						Self::release_tee_load(&challenge_info.prove_info);
+					}
 					<VerifySlip<T>>::remove(&now, &miner);
-					<ChallengeSnapShot<T>>::remove(&miner);
 					weight = weight.saturating_add(T::DbWeight::get().writes(2));
 				} else {
 					if let Ok(mut challenge_info) = <ChallengeSnapShot<T>>::try_get(&miner) {
 						challenge_info.prove_info.assign += 1;
-						if challenge_info.prove_info.idle_prove.is_some() {
-							if let Ok(tee_puk) = Self::random_select_tee_acc(0) {
-								let idle_prove =
-									challenge_info.prove_info.idle_prove.as_mut().unwrap();
-								idle_prove.tee_puk = tee_puk;
+						if let Some(idle_prove) = challenge_info.prove_info.idle_prove.as_mut() {
+							if idle_prove.verify_result.is_none() {
+								if let Ok(tee_puk) =
+									Self::select_verify_tee(&miner, &[], VERIFY_TEE_SEED_TAG, ProveKind::Idle)
+								{
+									Self::decrease_tee_load(&idle_prove.tee_puk);
+									Self::increase_tee_load(&tee_puk);
+									idle_prove.tee_puk = tee_puk;
+								}
 							}
 						}
 
-						if challenge_info.prove_info.service_prove.is_some() {
-							if let Ok(tee_puk) = Self::random_select_tee_acc(0) {
-								let service_prove =
-									challenge_info.prove_info.service_prove.as_mut().unwrap();
-								service_prove.tee_puk = tee_puk;
+						if let Some(service_prove) = challenge_info.prove_info.service_prove.as_mut() {
+							if service_prove.verify_result.is_none() {
+								if let Ok(tee_puk) =
+									Self::select_verify_tee(&miner, &[], VERIFY_TEE_SEED_TAG, ProveKind::Service)
+								{
+									Self::decrease_tee_load(&service_prove.tee_puk);
+									Self::increase_tee_load(&tee_puk);
+									service_prove.tee_puk = tee_puk;
+								}
 							}
 						}
 
@@ -1144,30 +1330,227 @@ pub mod pallet {
 			weight
 		}
 
-		/// Randomly Select Trusted Execution Environment (TEE) Account
+		/// Select the tee worker that verifies a miner's proof.
 		///
-		/// This function selects a Trusted Execution Environment (TEE) account from a list
-		/// of available TEE accounts. The selection is made based on a random index generated
-		/// from a seed derived from the current block number. An optional bit mask can be
-		/// applied to the seed to influence the randomness.
+		/// Only workers allowed to verify (`Verifier` or `Full` role) are eligible, and workers
+		/// bonded to the challenged miner's own account or staking account are skipped, as is
+		/// `exclude` if given. Each remaining worker is drawn with probability proportional to
+		/// its selection weight, which grows with its scheduler credit score and shrinks with the
+		/// number of verify missions it still has pending.
 		///
 		/// Parameters:
-		/// - `mask`: An optional bit mask to apply to the randomization process. It can be used to
-		///   influence the selection by XOR-ing with the block-based seed.
+		/// - `miner`: The challenged miner.
+		/// - `exclude`: Workers that must not be selected, e.g. the ones being cross-checked.
+		/// - `tag`: The domain tag of the random seed, distinct for each purpose of the draw.
+		/// - `subject`: Distinguishes the draws made for the same purpose and miner in a block.
 		///
 		/// Returns:
-		/// - A `Result` containing the selected TEE account if successful, or a `DispatchError` if
-		///   there are no available TEE accounts or if random number generation fails.
-		fn random_select_tee_acc(mask: u32) -> Result<WorkerPublicKey, DispatchError> {
-			let tee_list = T::TeeWorkerHandler::get_pubkey_list();
-			ensure!(tee_list.len() > 0, Error::<T>::SystemError);
-
-			let seed: u32 = <frame_system::Pallet<T>>::block_number().saturated_into();
-			let index = Self::random_number(seed + mask).map_err(|_| Error::<T>::RandomErr)? as u32;
-			let index: u32 = index % (tee_list.len() as u32);
-			let tee_puk = &tee_list[index as usize];
-
-			Ok(tee_puk.clone())
+		/// - A `Result` containing the selected TEE public key, or a `DispatchError` if no worker
+		///   is eligible or if random number generation fails.
+		fn select_verify_tee(
+			miner: &AccountOf<T>,
+			exclude: &[WorkerPublicKey],
+			tag: &'static [u8],
+			subject: impl Encode,
+		) -> Result<WorkerPublicKey, DispatchError> {
+			let miner_stash = T::MinerControl::get_staking_account(miner).ok();
+
+			let mut candidates: Vec<(WorkerPublicKey, u64)> = Vec::new();
+			let mut total_weight: u64 = 0;
+			for tee_puk in T::TeeWorkerHandler::get_pubkey_list() {
+				if exclude.contains(&tee_puk) || !T::TeeWorkerHandler::can_verify(&tee_puk) {
+					continue
+				}
+
+				let credit = match T::TeeWorkerHandler::get_stash(&tee_puk) {
+					Ok(stash) => {
+						if &stash == miner || Some(&stash) == miner_stash.as_ref() {
+							continue
+						}
+						T::CreditCounter::credit_score(&stash)
+					},
+					Err(_) => 0,
+				};
+
+				let weight = Self::tee_selection_weight(credit, <TeeVerifyLoad<T>>::get(&tee_puk));
+				total_weight = total_weight.saturating_add(weight);
+				candidates.push((tee_puk, weight));
+			}
+			ensure!(candidates.len() > 0, Error::<T>::NoEligibleTee);
+
+			let mut draw =
+				Self::tagged_random_number(tag, (miner, subject)).map_err(|_| Error::<T>::RandomErr)? % total_weight;
+			for (tee_puk, weight) in candidates {
+				if draw < weight {
+					return Ok(tee_puk)
+				}
+				draw -= weight;
+			}
+
+			Err(Error::<T>::SystemError.into())
+		}
+
+		// A worker with full credit weighs twice as much as one without credit, and the weight
+		// is divided by one plus the number of verify missions the worker has pending.
+		fn tee_selection_weight(credit: u32, load: u32) -> u64 {
+			TEE_SELECT_BASE_WEIGHT
+				.saturating_add(credit as u64)
+				.saturating_mul(TEE_SELECT_WEIGHT_SCALE)
+				.saturating_div((load as u64).saturating_add(1))
+				.max(1)
+		}
+
+		fn increase_tee_load(tee_puk: &WorkerPublicKey) {
+			<TeeVerifyLoad<T>>::mutate(tee_puk, |load| *load = load.saturating_add(1));
+		}
+
+		fn decrease_tee_load(tee_puk: &WorkerPublicKey) {
+			<TeeVerifyLoad<T>>::mutate_exists(tee_puk, |load| {
+				*load = load.map(|load| load.saturating_sub(1)).filter(|load| *load > 0);
+			});
+		}
+
+		// Release the load of the workers that never submitted their verify result.
+		fn release_tee_load(prove_info: &ProveInfo<T>) {
+			if let Some(idle_prove) = &prove_info.idle_prove {
+				if idle_prove.verify_result.is_none() {
+					Self::decrease_tee_load(&idle_prove.tee_puk);
+				}
+			}
+			if let Some(service_prove) = &prove_info.service_prove {
+				if service_prove.verify_result.is_none() {
+					Self::decrease_tee_load(&service_prove.tee_puk);
+				}
+			}
+		}
+
+		/// Sample a verify result for re-verification by a second tee worker.
+		///
+		/// With probability `CrossCheckRate`, a tee worker other than the one that produced the
+		/// result is selected as checker and the result is kept in `PendingCrossCheck` until the
+		/// checker's result arrives or `CrossCheckPeriod` elapses. A miner has at most one pending
+		/// cross-check per proof kind.
+		fn assign_cross_check(
+			miner: &AccountOf<T>,
+			kind: ProveKind,
+			mission: CrossCheckMission<T>,
+			now: BlockNumberFor<T>,
+		) {
+			if <PendingCrossCheck<T>>::contains_key(miner, kind) {
+				return
+			}
+
+			let random = match Self::tagged_random_number(CROSS_CHECK_SAMPLE_SEED_TAG, (miner, kind)) {
+				Ok(random) => random,
+				Err(e) => {
+					log::info!("audit: {:?}", e);
+					return
+				},
+			};
+			let draw = Perbill::from_parts((random % Perbill::ACCURACY as u64) as u32);
+			if draw >= T::CrossCheckRate::get() {
+				return
+			}
+
+			let tee = mission.tee_puk();
+			let checker = match Self::select_verify_tee(miner, &[tee], CROSS_CHECK_CHECKER_SEED_TAG, kind) {
+				Ok(checker) => checker,
+				Err(_) => return,
+			};
+			let deadline = now.saturating_add(T::CrossCheckPeriod::get());
+			Self::insert_cross_check(miner, kind, CrossCheckInfo::<T> { checker, deadline, mission, disputed: None });
+
+			Self::deposit_event(Event::<T>::CrossCheckAssigned { miner: miner.clone(), kind, tee, checker });
+		}
+
+		/// Hand a verify result the checker disagreed with to a third tee worker.
+		///
+		/// Without a worker other than the two that disagree, the disagreement cannot be
+		/// settled and is dropped without being recorded against either of them.
+		fn assign_arbiter(
+			miner: &AccountOf<T>,
+			kind: ProveKind,
+			mission: CrossCheckMission<T>,
+			disputed: (WorkerPublicKey, bool),
+			now: BlockNumberFor<T>,
+		) {
+			let tee = mission.tee_puk();
+			let arbiter =
+				match Self::select_verify_tee(miner, &[tee, disputed.0], CROSS_CHECK_ARBITER_SEED_TAG, kind) {
+					Ok(arbiter) => arbiter,
+					Err(_) => return,
+				};
+			let deadline = now.saturating_add(T::CrossCheckPeriod::get());
+			Self::insert_cross_check(
+				miner,
+				kind,
+				CrossCheckInfo::<T> { checker: arbiter, deadline, mission, disputed: Some(disputed) },
+			);
+
+			Self::deposit_event(Event::<T>::CrossCheckAssigned { miner: miner.clone(), kind, tee, checker: arbiter });
+		}
+
+		// Keep a cross-check until its checker answers or its deadline passes.
+		fn insert_cross_check(miner: &AccountOf<T>, kind: ProveKind, info: CrossCheckInfo<T>) {
+			Self::increase_tee_load(&info.checker);
+			<CrossCheckSlip<T>>::insert(&info.deadline, (miner, kind), true);
+			<PendingCrossCheck<T>>::insert(miner, kind, info);
+		}
+
+		/// Drop cross-checks whose checker did not answer before the deadline, recording the
+		/// miss against the checker.
+		fn clear_cross_check(now: BlockNumberFor<T>) -> Weight {
+			let mut weight: Weight = Weight::zero();
+
+			for ((miner, kind), _) in <CrossCheckSlip<T>>::drain_prefix(&now) {
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+				if let Some(cross_check) = <PendingCrossCheck<T>>::take(&miner, kind) {
+					Self::decrease_tee_load(&cross_check.checker);
+					Self::record_cross_check(&cross_check.checker, false);
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(3, 3));
+					Self::deposit_event(Event::<T>::CrossCheckExpired {
+						miner,
+						kind,
+						checker: cross_check.checker,
+					});
+				}
+			}
+
+			weight
+		}
+
+		/// Record the outcome of a cross-check for a tee worker.
+		///
+		/// `agree` is false when a third worker contradicted this one, or when the worker missed
+		/// the deadline of a cross-check it was assigned. Once the worker has
+		/// at least `MinCrossCheckSample` cross-checks and its disagreement rate exceeds
+		/// `DisagreementThreshold`, it is punished through the tee worker handler,
+		/// which slashes its stash and reports the punishment to scheduler credit. Its record
+		/// is then reset.
+		fn record_cross_check(tee: &WorkerPublicKey, agree: bool) {
+			let record = <TeeCrossCheckRecord<T>>::mutate(tee, |record| {
+				record.checked = record.checked.saturating_add(1);
+				if !agree {
+					record.disagreed = record.disagreed.saturating_add(1);
+				}
+				record.clone()
+			});
+
+			if record.checked < T::MinCrossCheckSample::get() {
+				return
+			}
+
+			if Perbill::from_rational(record.disagreed, record.checked) > T::DisagreementThreshold::get() {
+				<TeeCrossCheckRecord<T>>::remove(tee);
+				if let Err(e) = T::TeeWorkerHandler::punish_scheduler(*tee) {
+					log::info!("audit: punish tee {:?} failed: {:?}", tee, e);
+				}
+				Self::deposit_event(Event::<T>::TeeDisagreementPunished {
+					tee: *tee,
+					checked: record.checked,
+					disagreed: record.disagreed,
+				});
+			}
 		}
 
 		/// Generate Miner QElement
@@ -1274,7 +1657,14 @@ pub mod pallet {
 			Ok(space_challenge_param)
 		}
 
-		// Generate a random number from a given seed.
+		// A random number drawn from a seed of its own, for each domain `tag` and `subject`.
+		fn tagged_random_number(tag: &'static [u8], subject: impl Encode) -> Result<u64, AuditErr> {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let (random_seed, _) = T::MyRandomness::random(&(T::MyPalletId::get(), tag, now, subject).encode());
+			let random_seed = random_seed.ok_or(AuditErr::RandomErr)?;
+			<u64>::decode(&mut random_seed.as_ref()).map_err(|_| AuditErr::RandomErr)
+		}
+
 		fn random_number(seed: u32) -> Result<u64, AuditErr> {
 			let (random_seed, _) = T::MyRandomness::random(&(T::MyPalletId::get(), seed).encode());
 			let random_seed = match random_seed {
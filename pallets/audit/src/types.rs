@@ -108,3 +108,69 @@ pub struct QElement {
 	pub(super) random_index_list: BoundedVec<u32, ConstU32<1024>>,
 	pub(super) random_list: BoundedVec<[u8; 20], ConstU32<1024>>,
 }
+
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Copy, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum ProveKind {
+	Idle,
+	Service,
+}
+
+// The verify result of the first tee, kept until a second tee re-verifies it.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub enum CrossCheckMission<T: pallet::Config> {
+	Idle(VerifyIdleResultInfo<T>),
+	Service(VerifyServiceResultInfo<T>),
+}
+
+impl<T: pallet::Config> CrossCheckMission<T> {
+	pub(super) fn tee_puk(&self) -> WorkerPublicKey {
+		match self {
+			CrossCheckMission::Idle(info) => info.tee_puk,
+			CrossCheckMission::Service(info) => info.tee_puk,
+		}
+	}
+
+	pub(super) fn result(&self) -> bool {
+		match self {
+			CrossCheckMission::Idle(info) => info.result,
+			CrossCheckMission::Service(info) => info.result,
+		}
+	}
+
+	// The hash the checker's worker key signature must cover.
+	pub(super) fn checker_hash(&self, result: bool, checker: WorkerPublicKey) -> [u8; 32] {
+		let encoding = match self.clone() {
+			CrossCheckMission::Idle(mut info) => {
+				info.result = result;
+				info.tee_puk = checker;
+				info.encode()
+			},
+			CrossCheckMission::Service(mut info) => {
+				info.result = result;
+				info.tee_puk = checker;
+				info.encode()
+			},
+		};
+		sp_io::hashing::sha2_256(&encoding)
+	}
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct CrossCheckInfo<T: pallet::Config> {
+	pub(super) checker: WorkerPublicKey,
+	pub(super) deadline: BlockNumberFor<T>,
+	pub(super) mission: CrossCheckMission<T>,
+	// The checker that disagreed with the first worker and its result, once the cross-check
+	// has been handed to `checker` as arbiter.
+	pub(super) disputed: Option<(WorkerPublicKey, bool)>,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct CrossCheckRecord {
+	pub checked: u32,
+	pub disagreed: u32,
+}
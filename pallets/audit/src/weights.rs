@@ -42,6 +42,7 @@ pub trait WeightInfo {
 	fn submit_verify_service_result() -> Weight;
 	fn submit_verify_idle_result_reward() -> Weight;
 	fn submit_verify_service_result_reward() -> Weight;
+	fn submit_cross_check_result() -> Weight;
 }
 
 /// Weights for `pallet_audit` using the Substrate node and recommended hardware.
@@ -165,6 +166,26 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(7_u64))
 			.saturating_add(T::DbWeight::get().writes(6_u64))
 	}
+	/// Storage: `Audit::PendingCrossCheck` (r:1 w:1)
+	/// Proof: `Audit::PendingCrossCheck` (`max_values`: None, `max_size`: Some(2773), added: 5248, mode: `MaxEncodedLen`)
+	/// Storage: `TeeWorker::Workers` (r:1 w:0)
+	/// Proof: `TeeWorker::Workers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TeeWorker::LastWork` (r:1 w:1)
+	/// Proof: `TeeWorker::LastWork` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Audit::TeeVerifyLoad` (r:1 w:1)
+	/// Proof: `Audit::TeeVerifyLoad` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `Audit::TeeCrossCheckRecord` (r:2 w:2)
+	/// Proof: `Audit::TeeCrossCheckRecord` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Audit::CrossCheckSlip` (r:0 w:1)
+	/// Proof: `Audit::CrossCheckSlip` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	///
+	/// Not benchmarked yet: estimated from `submit_verify_idle_result` for the arbitration path
+	/// measured by the `submit_cross_check_result` benchmark. Regenerate with that benchmark.
+	fn submit_cross_check_result() -> Weight {
+		Weight::from_parts(121_530_000, 6204)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -287,4 +308,24 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(7_u64))
 			.saturating_add(RocksDbWeight::get().writes(6_u64))
 	}
+	/// Storage: `Audit::PendingCrossCheck` (r:1 w:1)
+	/// Proof: `Audit::PendingCrossCheck` (`max_values`: None, `max_size`: Some(2773), added: 5248, mode: `MaxEncodedLen`)
+	/// Storage: `TeeWorker::Workers` (r:1 w:0)
+	/// Proof: `TeeWorker::Workers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `TeeWorker::LastWork` (r:1 w:1)
+	/// Proof: `TeeWorker::LastWork` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Audit::TeeVerifyLoad` (r:1 w:1)
+	/// Proof: `Audit::TeeVerifyLoad` (`max_values`: None, `max_size`: Some(44), added: 2519, mode: `MaxEncodedLen`)
+	/// Storage: `Audit::TeeCrossCheckRecord` (r:2 w:2)
+	/// Proof: `Audit::TeeCrossCheckRecord` (`max_values`: None, `max_size`: Some(48), added: 2523, mode: `MaxEncodedLen`)
+	/// Storage: `Audit::CrossCheckSlip` (r:0 w:1)
+	/// Proof: `Audit::CrossCheckSlip` (`max_values`: None, `max_size`: Some(86), added: 2561, mode: `MaxEncodedLen`)
+	///
+	/// Not benchmarked yet: estimated from `submit_verify_idle_result` for the arbitration path
+	/// measured by the `submit_cross_check_result` benchmark. Regenerate with that benchmark.
+	fn submit_cross_check_result() -> Weight {
+		Weight::from_parts(121_530_000, 6204)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
 }
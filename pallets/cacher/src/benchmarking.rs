@@ -2,20 +2,75 @@
 
 use super::*;
 
-use frame_benchmarking::{account, benchmarks};
+use frame_benchmarking::{account, benchmarks, BenchmarkError};
 use frame_system::RawOrigin;
-use scale_info::prelude::{format, string::String};
-use sp_runtime::traits::{Bounded, Hash};
+use sp_runtime::traits::Bounded;
 
 #[allow(unused)]
 use crate::Pallet as Cacher;
 
 const SEED: u32 = 0;
 
+/// Registers a cacher charging one unit per byte.
+fn register_cacher<T: Config>() -> AccountOf<T> {
+	let cacher = T::BenchmarkHelper::cacher();
+	let info = CacherInfo::<AccountOf<T>, BalanceOf<T>> {
+		payee: cacher.clone(),
+		ip: IpAddress::IPV4([127,0,0,1], 8080),
+		byte_price: 1u32.into(),
+	};
+	T::Currency::make_free_balance_be(&cacher, BalanceOf::<T>::max_value() / 2u32.into());
+	T::Currency::reserve(&cacher, T::CacherDeposit::get()).unwrap();
+	CacherDeposits::<T>::insert(&cacher, T::CacherDeposit::get());
+	Cachers::<T>::insert(&cacher, info);
+	cacher
+}
+
+/// Returns `count` bills of `cacher` to `payer`, with their signatures.
+fn signed_bills<T: Config>(
+	payer: &AccountOf<T>,
+	cacher: &AccountOf<T>,
+	count: u32,
+) -> BoundedVec<(BillOf<T>, T::OffchainSignature), T::BillsLimit> {
+	let mut bills = Vec::new();
+	for i in 0 .. count {
+		let file_hash = Hash([i as u8; 64]);
+		let slice_hash = Hash([i as u8 + 1; 64]);
+		T::FragmentLookup::insert_fragment(file_hash, slice_hash);
+		let bill = BillOf::<T> {
+			id: [i as u8; 16],
+			to: cacher.clone(),
+			amount: FRAGMENT_SIZE.saturated_into(),
+			file_hash,
+			slice_hash,
+			expiration_time: u64::MAX,
+		};
+		let signature = T::BenchmarkHelper::sign(cacher, &(payer, &bill).encode());
+		bills.push((bill, signature));
+	}
+	bills.try_into().unwrap()
+}
+
+/// Funds the escrow of `payer` with `amount`.
+fn fund_escrow<T: Config>(payer: &AccountOf<T>, amount: BalanceOf<T>) {
+	T::Currency::make_free_balance_be(payer, BalanceOf::<T>::max_value());
+	T::Currency::reserve(payer, amount).unwrap();
+	Escrows::<T>::insert(payer, Escrow { balance: amount, pending: Zero::zero() });
+}
+
+/// Pays `count` bills of `cacher` from the escrow of `payer`.
+fn pay_bills<T: Config>(payer: &AccountOf<T>, cacher: &AccountOf<T>, count: u32) -> Vec<BillId> {
+	let bills = signed_bills::<T>(payer, cacher, count);
+	let ids = bills.iter().map(|(bill, _)| bill.id).collect();
+	Cacher::<T>::pay(RawOrigin::Signed(payer.clone()).into(), bills).unwrap();
+	ids
+}
+
 benchmarks! {
 
 	register {
 		let alice: AccountOf<T> = account("alice", 0, SEED);
+		T::Currency::make_free_balance_be(&alice, BalanceOf::<T>::max_value());
 		let info = CacherInfo::<AccountOf<T>, BalanceOf<T>> {
 			payee: alice.clone(),
 			ip: IpAddress::IPV4([127,0,0,1], 8080),
@@ -26,6 +81,7 @@ benchmarks! {
 		assert!(Cachers::<T>::contains_key(&alice));
 		let cacher_info = Cachers::<T>::get(&alice).unwrap();
 		assert_eq!(info, cacher_info);
+		assert_eq!(CacherDeposits::<T>::get(&alice), T::CacherDeposit::get());
 	}
 
 	update {
@@ -50,42 +106,90 @@ benchmarks! {
 	}
 
 	logout {
-		let alice: AccountOf<T> = account("alice", 0, SEED);
-		let info = CacherInfo::<AccountOf<T>, BalanceOf<T>> {
-			payee: alice.clone(),
-			ip: IpAddress::IPV4([127,0,0,1], 8080),
-			byte_price: 100u32.into(),
-		};
-		Cachers::<T>::insert(&alice, info);
-	}: _(RawOrigin::Signed(alice.clone()))
+		let cacher = register_cacher::<T>();
+	}: _(RawOrigin::Signed(cacher.clone()))
 	verify {
-		assert!(!Cachers::<T>::contains_key(&alice));
+		assert!(!Cachers::<T>::contains_key(&cacher));
+		assert!(!CacherDeposits::<T>::contains_key(&cacher));
 	}
 
 	pay {
-		let v in 0 .. 10;
+		let v in 0 .. T::BillsLimit::get();
+		let alice: AccountOf<T> = account("alice", 0, SEED);
+		fund_escrow::<T>(&alice, BalanceOf::<T>::max_value() / 2u32.into());
+		let cacher = register_cacher::<T>();
+		let bills = signed_bills::<T>(&alice, &cacher, v);
+	}: _(RawOrigin::Signed(alice.clone()), bills)
+	verify {
+		let amount: BalanceOf<T> = FRAGMENT_SIZE.saturated_into();
+		assert_eq!(Escrows::<T>::get(&alice).pending, amount * v.into());
+	}
+
+	deposit_escrow {
 		let alice: AccountOf<T> = account("alice", 0, SEED);
 		T::Currency::make_free_balance_be(&alice, BalanceOf::<T>::max_value());
-		let bob: AccountOf<T> = account("bob", 1, SEED);
-		T::Currency::make_free_balance_be(&bob, T::Currency::minimum_balance());
-		let s_file = String::from("file");
-		let s_slice = String::from("slice");
-		let mut bill_vec = Vec::new();
-		for i in 0 .. v {
-			let bill = Bill::<AccountOf<T>, BalanceOf<T>, T::Hash> {
-				id: [i as u8; 16],
-				to: bob.clone(),
-				amount: 10000u32.into(),
-				file_hash: T::Hashing::hash_of(&format!("{}{}", s_file, i)),
-				slice_hash: T::Hashing::hash_of(&format!("{}{}", s_slice, i)),
-				expiration_time: 1675900800u64,
-			};
-			bill_vec.push(bill);
+		let amount: BalanceOf<T> = 10000u32.into();
+	}: _(RawOrigin::Signed(alice.clone()), amount)
+	verify {
+		assert_eq!(Escrows::<T>::get(&alice).balance, amount);
+	}
+
+	withdraw_escrow {
+		let alice: AccountOf<T> = account("alice", 0, SEED);
+		let amount: BalanceOf<T> = 10000u32.into();
+		fund_escrow::<T>(&alice, amount);
+	}: _(RawOrigin::Signed(alice.clone()), amount)
+	verify {
+		assert!(!Escrows::<T>::contains_key(&alice));
+	}
+
+	dispute {
+		let alice: AccountOf<T> = account("alice", 0, SEED);
+		fund_escrow::<T>(&alice, BalanceOf::<T>::max_value() / 2u32.into());
+		let cacher = register_cacher::<T>();
+		let id = pay_bills::<T>(&alice, &cacher, 1)[0];
+	}: _(RawOrigin::Signed(alice.clone()), id)
+	verify {
+		assert!(!PendingBills::<T>::contains_key(&id));
+		assert!(DisputedBills::<T>::contains_key(&id));
+	}
+
+	settle {
+		let v in 0 .. T::BillsLimit::get();
+		let alice: AccountOf<T> = account("alice", 0, SEED);
+		fund_escrow::<T>(&alice, BalanceOf::<T>::max_value() / 2u32.into());
+		let cacher = register_cacher::<T>();
+		let ids = pay_bills::<T>(&alice, &cacher, v);
+		let now = frame_system::Pallet::<T>::block_number();
+		frame_system::Pallet::<T>::set_block_number(now + T::DisputePeriod::get());
+		let ids: BoundedVec<BillId, T::BillsLimit> = ids.try_into().unwrap();
+	}: _(RawOrigin::Signed(alice.clone()), ids.clone())
+	verify {
+		for id in ids {
+			assert!(ClosedBills::<T>::contains_key(&id));
 		}
-		let bills: BoundedVec<_, T::BillsLimit> = bill_vec.try_into().unwrap();
-	}: _(RawOrigin::Signed(alice), bills)
+	}
+
+	increase_deposit {
+		let cacher = register_cacher::<T>();
+		let amount: BalanceOf<T> = 10000u32.into();
+	}: _(RawOrigin::Signed(cacher.clone()), amount)
 	verify {
+		assert_eq!(CacherDeposits::<T>::get(&cacher), T::CacherDeposit::get() + amount);
+	}
 
+	resolve_dispute {
+		let alice: AccountOf<T> = account("alice", 0, SEED);
+		fund_escrow::<T>(&alice, BalanceOf::<T>::max_value() / 2u32.into());
+		let cacher = register_cacher::<T>();
+		let id = pay_bills::<T>(&alice, &cacher, 1)[0];
+		Cacher::<T>::dispute(RawOrigin::Signed(alice.clone()).into(), id).unwrap();
+		let origin = T::GovernanceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	}: _<T::RuntimeOrigin>(origin, id, false)
+	verify {
+		assert!(ClosedBills::<T>::contains_key(&id));
+		assert_eq!(Reputations::<T>::get(&cacher).settled, 1);
+		assert_eq!(OpenDisputes::<T>::get(&alice), 0);
 	}
 
 	impl_benchmark_test_suite!(Cacher, crate::mock::new_test_ext(), crate::mock::Test)
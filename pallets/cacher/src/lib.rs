@@ -8,21 +8,38 @@ pub mod mock;
 mod tests;
 pub mod weights;
 
+pub mod migrations;
+
 mod types;
-use types::*;
+pub use types::*;
 
-use cp_cess_common::IpAddress;
+use cp_cess_common::{Hash, IpAddress, FRAGMENT_SIZE};
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, ExistenceRequirement::KeepAlive, LockableCurrency},
+	storage::with_storage_layer,
+	traits::{BalanceStatus, Currency, Imbalance, OnUnbalanced, ReservableCurrency, UnixTime},
 	transactional,
 };
 use frame_system::pallet_prelude::*;
+use pallet_file_bank::FragmentLookup;
+use sp_runtime::{
+	traits::{IdentifyAccount, Saturating, Verify, Zero},
+	Perbill, SaturatedConversion,
+};
 
 pub use pallet::*;
 use sp_std::prelude::*;
 pub use weights::WeightInfo;
 
+type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<AccountOf<T>>>::NegativeImbalance;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// The maximum number of cachers returned by one discovery query.
+pub const MAX_CACHERS_PAGE: u32 = 100;
+/// The maximum number of entries of each map visited for pruning in one block.
+pub const MAX_PRUNE_PER_BLOCK: u32 = 100;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use crate::*;
@@ -33,13 +50,63 @@ pub mod pallet {
 		/// The overarching event type.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// The currency trait.
-		type Currency: LockableCurrency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
 
 		/// The maximum length of bill list when calling the pay function.
 		#[pallet::constant]
 		type BillsLimit: Get<u32>;
 
+		/// Used to check bills have not expired.
+		type UnixTime: UnixTime;
+
+		/// Used to check bills are for fragments stored in file-bank.
+		type FragmentLookup: FragmentLookup;
+
+		/// The signature cachers sign bills with.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter + MaxEncodedLen;
+
+		/// The key cachers sign bills with.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+
+		/// How long a payer can dispute a paid bill before it is settled.
+		#[pallet::constant]
+		type DisputePeriod: Get<BlockNumberFor<Self>>;
+
+		/// The bond reserved from a payer disputing a bill, forfeited to the cacher when the
+		/// dispute is rejected.
+		#[pallet::constant]
+		type DisputeBond: Get<BalanceOf<Self>>;
+
+		/// How long governance has to resolve a dispute before it lapses.
+		#[pallet::constant]
+		type DisputeResolutionPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of disputes of a payer waiting for governance.
+		#[pallet::constant]
+		type MaxOpenDisputes: Get<u32>;
+
+		/// How long a cacher paid for serving a fragment is listed first for it.
+		#[pallet::constant]
+		type ServedFragmentRetention: Get<BlockNumberFor<Self>>;
+
+		/// The deposit reserved from a cacher on registration.
+		#[pallet::constant]
+		type CacherDeposit: Get<BalanceOf<Self>>;
+
+		/// The share of `CacherDeposit` slashed for each upheld dispute.
+		#[pallet::constant]
+		type DisputeSlash: Get<Perbill>;
+
+		/// Handler for the deposit slashed from cachers.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Origin that resolves disputed bills.
+		type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
 		type WeightInfo: WeightInfo;
+
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AccountId, Self::OffchainSignature>;
 	}
 
 	#[pallet::event]
@@ -59,10 +126,51 @@ pub mod pallet {
 		Logout {
 			acc: AccountOf<T>,
 		},
-		//Pay to cacher success event
+		//Pay to cacher success event, the bills are settled after the dispute period
 		Pay {
 			acc: AccountOf<T>,
-			bills: BoundedVec<Bill<AccountOf<T>, BalanceOf<T>, T::Hash>, T::BillsLimit>,
+			bills: BoundedVec<BillOf<T>, T::BillsLimit>,
+		},
+		//Escrow deposit success event
+		EscrowDeposited {
+			acc: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+		//Escrow withdrawal success event
+		EscrowWithdrawn {
+			acc: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+		//Bill dispute success event, the bill waits for governance to resolve it
+		BillDisputed {
+			acc: AccountOf<T>,
+			id: BillId,
+			bond: BalanceOf<T>,
+		},
+		//Bill settlement success event
+		BillSettled {
+			id: BillId,
+			payee: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+		//Bills left out of a settlement, as they are not pending or still in their dispute window
+		BillsSkipped {
+			ids: BoundedVec<BillId, T::BillsLimit>,
+		},
+		//Cacher deposit increase success event
+		DepositIncreased {
+			acc: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+		//Dispute resolution event, an upheld dispute slashes the cacher and the bill is not paid
+		DisputeResolved {
+			id: BillId,
+			upheld: bool,
+			slashed: BalanceOf<T>,
+		},
+		//Dispute lapse event, governance didn't resolve it in time and the bill is paid
+		DisputeLapsed {
+			id: BillId,
 		},
 	}
 
@@ -74,6 +182,30 @@ pub mod pallet {
 		UnRegistered,
 		/// Option parse Error
 		OptionParseError,
+		/// A bill with the same id was already paid
+		BillExisted,
+		/// The bill has expired
+		BillExpired,
+		/// The bill amount is not the cacher's price of a fragment
+		AmountMismatch,
+		/// The bill is not for a fragment stored in file-bank
+		FragmentNonExistent,
+		/// The bill is not signed by the cacher
+		InvalidSignature,
+		/// The escrow does not cover the bills still in their dispute window
+		InsufficientEscrow,
+		/// The bill is not waiting to be settled
+		BillNonExistent,
+		/// The bill was paid by another account
+		NotPayer,
+		/// The dispute period of the bill is over
+		DisputePeriodOver,
+		/// The payer has `MaxOpenDisputes` disputes waiting for governance already
+		TooManyDisputes,
+		/// The cacher's deposit is below `CacherDeposit`
+		InsufficientDeposit,
+		/// The cacher still has bills to settle or resolve
+		BillsPending,
 	}
 
 	/// Store all cacher info
@@ -82,16 +214,86 @@ pub mod pallet {
 	pub(super) type Cachers<T: Config> =
 		StorageMap<_, Blake2_128Concat, AccountOf<T>, CacherInfo<AccountOf<T>, BalanceOf<T>>>;
 
+	/// The prepaid escrow of each payer.
+	#[pallet::storage]
+	#[pallet::getter(fn escrow)]
+	pub(super) type Escrows<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountOf<T>, Escrow<BalanceOf<T>>, ValueQuery>;
+
+	/// The paid bills waiting out their dispute window.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_bill)]
+	pub(super) type PendingBills<T: Config> =
+		StorageMap<_, Blake2_128Concat, BillId, PendingBill<AccountOf<T>, BalanceOf<T>, BlockNumberFor<T>>>;
+
+	/// The expiration time of each settled or resolved bill. Bills are kept until they expire,
+	/// so that none is paid twice.
+	#[pallet::storage]
+	#[pallet::getter(fn closed_bill)]
+	pub(super) type ClosedBills<T: Config> = StorageMap<_, Blake2_128Concat, BillId, u64>;
+
+	/// The closed bill the last pruning stopped at.
+	#[pallet::storage]
+	pub(super) type ClosedBillsCursor<T: Config> = StorageValue<_, BillId>;
+
+	/// The deposit reserved from each cacher.
+	#[pallet::storage]
+	#[pallet::getter(fn cacher_deposit)]
+	pub(super) type CacherDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, AccountOf<T>, BalanceOf<T>, ValueQuery>;
+
+	/// The track record of each cacher's bills.
+	#[pallet::storage]
+	#[pallet::getter(fn reputation)]
+	pub(super) type Reputations<T: Config> = StorageMap<_, Blake2_128Concat, AccountOf<T>, Reputation, ValueQuery>;
+
+	/// The disputed bills waiting for governance to resolve them.
+	#[pallet::storage]
+	#[pallet::getter(fn disputed_bill)]
+	pub(super) type DisputedBills<T: Config> =
+		StorageMap<_, Blake2_128Concat, BillId, DisputedBill<AccountOf<T>, BalanceOf<T>, BlockNumberFor<T>>>;
+
+	/// The number of disputes of each payer waiting for governance.
+	#[pallet::storage]
+	#[pallet::getter(fn open_disputes)]
+	pub(super) type OpenDisputes<T: Config> = StorageMap<_, Blake2_128Concat, AccountOf<T>, u32, ValueQuery>;
+
+	/// The disputed bill the last lapse check stopped at.
+	#[pallet::storage]
+	pub(super) type DisputedBillsCursor<T: Config> = StorageValue<_, BillId>;
+
+	/// The block each cacher was last paid for serving a fragment, keyed by fragment. Entries
+	/// are pruned after `ServedFragmentRetention`, or once their cacher logs out.
+	#[pallet::storage]
+	pub(super) type ServedFragments<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, Hash, Blake2_128Concat, AccountOf<T>, BlockNumberFor<T>>;
+
+	/// The served fragment the last pruning stopped at.
+	#[pallet::storage]
+	pub(super) type ServedFragmentsCursor<T: Config> = StorageValue<_, (Hash, AccountOf<T>)>;
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut used = Self::lapse_disputes(now, remaining_weight);
+			used.saturating_accrue(Self::prune_closed_bills(remaining_weight.saturating_sub(used)));
+			used.saturating_accrue(Self::prune_served_fragments(now, remaining_weight.saturating_sub(used)));
+			used
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// Register for cacher.
+		/// Register for cacher, reserving `CacherDeposit` from the signer.
 		///
 		/// Parameters:
 		/// - `info`: The cacher info related to signer account.
 		#[pallet::call_index(0)]
+		#[transactional]
 		#[pallet::weight(T::WeightInfo::register())]
 		pub fn register(
 			origin: OriginFor<T>,
@@ -99,6 +301,9 @@ pub mod pallet {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			ensure!(!<Cachers<T>>::contains_key(&sender), Error::<T>::AlreadyRegistered);
+			let deposit = T::CacherDeposit::get();
+			T::Currency::reserve(&sender, deposit)?;
+			<CacherDeposits<T>>::insert(&sender, deposit);
 			<Cachers<T>>::insert(&sender, info.clone());
 
 			Self::deposit_event(Event::<T>::Register { acc: sender, info });
@@ -131,13 +336,18 @@ pub mod pallet {
 		}
 
 		/// Cacher exit method, Irreversible process.
+		///
+		/// The deposit is returned once no bill of the cacher is left to settle or resolve.
 		#[pallet::call_index(2)]
+		#[transactional]
 		#[pallet::weight(T::WeightInfo::logout())]
 		pub fn logout(origin: OriginFor<T>) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			ensure!(<Cachers<T>>::contains_key(&sender), Error::<T>::UnRegistered);
+			ensure!(<Reputations<T>>::get(&sender).pending == 0, Error::<T>::BillsPending);
 
 			<Cachers<T>>::remove(&sender);
+			T::Currency::unreserve(&sender, <CacherDeposits<T>>::take(&sender));
 
 			Self::deposit_event(Event::<T>::Logout { acc: sender });
 
@@ -146,24 +356,501 @@ pub mod pallet {
 
 		/// Pay to cachers for downloading files.
 		///
+		/// The bills are paid from the escrow of the signer, once their dispute period is over.
+		/// Each bill must be signed by its cacher, be unique by id, not have expired, be for a
+		/// fragment stored in file-bank and charge the cacher's price for the fragment. The
+		/// cacher's deposit must cover `CacherDeposit`.
+		///
 		/// Parameters:
-		/// - `bills`: list of bill.
+		/// - `bills`: list of bill, with the signature of its cacher.
 		#[pallet::call_index(3)]
 		#[transactional]
 		#[pallet::weight(T::WeightInfo::pay(bills.len() as u32))]
 		pub fn pay(
 			origin: OriginFor<T>,
-			bills: BoundedVec<Bill<AccountOf<T>, BalanceOf<T>, T::Hash>, T::BillsLimit>,
+			bills: BoundedVec<(BillOf<T>, T::OffchainSignature), T::BillsLimit>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
+			let settles_at = <frame_system::Pallet<T>>::block_number().saturating_add(T::DisputePeriod::get());
+
+			let paid = BoundedVec::truncate_from(bills.iter().map(|(bill, _)| bill.clone()).collect());
+			for (bill, signature) in bills {
+				let payee = Self::check_bill(&sender, &bill, &signature)?;
+				<Reputations<T>>::mutate(&bill.to, |reputation| reputation.pending.saturating_inc());
+
+				<Escrows<T>>::try_mutate(&sender, |escrow| -> DispatchResult {
+					let pending = escrow.pending.saturating_add(bill.amount);
+					ensure!(pending <= escrow.balance, Error::<T>::InsufficientEscrow);
+					escrow.pending = pending;
+					Ok(())
+				})?;
+				<PendingBills<T>>::insert(
+					&bill.id,
+					PendingBill {
+						payer: sender.clone(),
+						cacher: bill.to.clone(),
+						payee,
+						fragment_hash: bill.slice_hash,
+						amount: bill.amount,
+						settles_at,
+						expiration_time: bill.expiration_time,
+					},
+				);
+			}
+
+			Self::deposit_event(Event::<T>::Pay { acc: sender, bills: paid });
+
+			Ok(())
+		}
+
+		/// Prepay into the escrow bills are paid from.
+		///
+		/// Parameters:
+		/// - `amount`: The amount reserved from the signer into their escrow.
+		#[pallet::call_index(4)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::deposit_escrow())]
+		pub fn deposit_escrow(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			T::Currency::reserve(&sender, amount)?;
+			<Escrows<T>>::mutate(&sender, |escrow| escrow.balance = escrow.balance.saturating_add(amount));
+
+			Self::deposit_event(Event::<T>::EscrowDeposited { acc: sender, amount });
+
+			Ok(())
+		}
+
+		/// Withdraw from the escrow the part not owed for bills in their dispute window.
+		///
+		/// Parameters:
+		/// - `amount`: The amount released from the signer's escrow.
+		#[pallet::call_index(5)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::withdraw_escrow())]
+		pub fn withdraw_escrow(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			<Escrows<T>>::try_mutate_exists(&sender, |escrow_opt| -> DispatchResult {
+				let escrow = escrow_opt.as_mut().ok_or(Error::<T>::InsufficientEscrow)?;
+				ensure!(
+					escrow.pending.saturating_add(amount) <= escrow.balance,
+					Error::<T>::InsufficientEscrow
+				);
+				escrow.balance = escrow.balance.saturating_sub(amount);
+				if escrow.balance.is_zero() {
+					*escrow_opt = None;
+				}
+				Ok(())
+			})?;
+			T::Currency::unreserve(&sender, amount);
+
+			Self::deposit_event(Event::<T>::EscrowWithdrawn { acc: sender, amount });
+
+			Ok(())
+		}
+
+		/// Contest a bill whose download was not delivered, during its dispute period.
+		///
+		/// The bill stays owed from the escrow until `GovernanceOrigin` resolves the dispute, and
+		/// `DisputeBond` is reserved from the signer until then. A dispute not resolved within
+		/// `DisputeResolutionPeriod` lapses: the bill is paid and the bond is returned.
+		///
+		/// Parameters:
+		/// - `id`: The id of the bill the signer paid.
+		#[pallet::call_index(6)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::dispute())]
+		pub fn dispute(origin: OriginFor<T>, id: BillId) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			let bill = <PendingBills<T>>::get(&id).ok_or(Error::<T>::BillNonExistent)?;
+			ensure!(bill.payer == sender, Error::<T>::NotPayer);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now < bill.settles_at, Error::<T>::DisputePeriodOver);
+			<OpenDisputes<T>>::try_mutate(&sender, |open| -> DispatchResult {
+				ensure!(*open < T::MaxOpenDisputes::get(), Error::<T>::TooManyDisputes);
+				open.saturating_inc();
+				Ok(())
+			})?;
+			let bond = T::DisputeBond::get();
+			T::Currency::reserve(&sender, bond)?;
+
+			<Reputations<T>>::mutate(&bill.cacher, |reputation| reputation.disputed.saturating_inc());
+			<PendingBills<T>>::remove(&id);
+			let lapses_at = now.saturating_add(T::DisputeResolutionPeriod::get());
+			<DisputedBills<T>>::insert(&id, DisputedBill { bill, bond, lapses_at });
+
+			Self::deposit_event(Event::<T>::BillDisputed { acc: sender, id, bond });
+
+			Ok(())
+		}
+
+		/// Pay cachers the bills whose dispute period is over. Anyone can settle bills.
+		///
+		/// Bills that are not pending, or still in their dispute window, are skipped and reported
+		/// in `BillsSkipped` rather than failing the others.
+		///
+		/// Parameters:
+		/// - `ids`: The ids of the bills to settle.
+		#[pallet::call_index(7)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::settle(ids.len() as u32))]
+		pub fn settle(origin: OriginFor<T>, ids: BoundedVec<BillId, T::BillsLimit>) -> DispatchResultWithPostInfo {
+			let _ = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let mut settled = 0u32;
+			let mut skipped = Vec::new();
+			for id in ids {
+				match <PendingBills<T>>::get(&id) {
+					Some(bill) if now >= bill.settles_at => {
+						<PendingBills<T>>::remove(&id);
+						Self::settle_bill(id, bill, now)?;
+						settled.saturating_inc();
+					},
+					_ => skipped.push(id),
+				}
+			}
+
+			let skipped_weight = T::DbWeight::get().reads(skipped.len() as u64);
+			if !skipped.is_empty() {
+				Self::deposit_event(Event::<T>::BillsSkipped { ids: BoundedVec::truncate_from(skipped) });
+			}
+
+			Ok(Some(T::WeightInfo::settle(settled).saturating_add(skipped_weight)).into())
+		}
+
+		/// Increase the deposit of a cacher, for instance to cover `CacherDeposit` again after
+		/// being slashed, or after registering before deposits were required without being able
+		/// to afford it when they were introduced.
+		///
+		/// Parameters:
+		/// - `amount`: The amount reserved from the signer into their deposit.
+		#[pallet::call_index(8)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::increase_deposit())]
+		pub fn increase_deposit(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(<Cachers<T>>::contains_key(&sender), Error::<T>::UnRegistered);
+
+			T::Currency::reserve(&sender, amount)?;
+			<CacherDeposits<T>>::mutate(&sender, |deposit| *deposit = deposit.saturating_add(amount));
 
-			for bill in bills.clone() {
-				T::Currency::transfer(&sender, &bill.to, bill.amount, KeepAlive)?;
+			Self::deposit_event(Event::<T>::DepositIncreased { acc: sender, amount });
+
+			Ok(())
+		}
+
+		/// Resolve a disputed bill.
+		///
+		/// An upheld dispute slashes `DisputeSlash` of `CacherDeposit` from the cacher's deposit,
+		/// releases the bill amount in the payer's escrow and returns the payer's bond. Otherwise
+		/// the bill is paid, and the payer forfeits their bond to the payee.
+		///
+		/// Can only be called by `GovernanceOrigin`.
+		///
+		/// Parameters:
+		/// - `id`: The id of the disputed bill.
+		/// - `upheld`: Whether the payer was right not to pay the bill.
+		#[pallet::call_index(9)]
+		#[transactional]
+		#[pallet::weight(T::WeightInfo::resolve_dispute())]
+		pub fn resolve_dispute(origin: OriginFor<T>, id: BillId, upheld: bool) -> DispatchResult {
+			T::GovernanceOrigin::ensure_origin(origin)?;
+			let DisputedBill { bill, bond, .. } =
+				<DisputedBills<T>>::take(&id).ok_or(Error::<T>::BillNonExistent)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			let mut slashed = BalanceOf::<T>::zero();
+			if upheld {
+				Self::close_dispute(&bill.payer);
+				Self::release_bill(&id, &bill, bond);
+				<Reputations<T>>::mutate(&bill.cacher, |reputation| reputation.upheld.saturating_inc());
+				<CacherDeposits<T>>::mutate(&bill.cacher, |deposit| {
+					let (imbalance, _) = T::Currency::slash_reserved(
+						&bill.cacher,
+						(T::DisputeSlash::get() * T::CacherDeposit::get()).min(*deposit),
+					);
+					slashed = imbalance.peek();
+					*deposit = deposit.saturating_sub(slashed);
+					T::Slash::on_unbalanced(imbalance);
+				});
+			} else {
+				Self::close_dispute(&bill.payer);
+				T::Currency::repatriate_reserved(&bill.payer, &bill.payee, bond, BalanceStatus::Free)?;
+				Self::settle_bill(id, bill, now)?;
 			}
 
-			Self::deposit_event(Event::<T>::Pay { acc: sender, bills });
+			Self::deposit_event(Event::<T>::DisputeResolved { id, upheld, slashed });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Checks a bill `payer` pays, returning the account the cacher is paid to.
+		fn check_bill(
+			payer: &AccountOf<T>,
+			bill: &BillOf<T>,
+			signature: &T::OffchainSignature,
+		) -> Result<AccountOf<T>, DispatchError> {
+			ensure!(
+				!<PendingBills<T>>::contains_key(&bill.id)
+					&& !<DisputedBills<T>>::contains_key(&bill.id)
+					&& !<ClosedBills<T>>::contains_key(&bill.id),
+				Error::<T>::BillExisted
+			);
+			ensure!(bill.expiration_time > T::UnixTime::now().as_secs(), Error::<T>::BillExpired);
+			ensure!(
+				signature.verify(&(payer, bill).encode()[..], &bill.to),
+				Error::<T>::InvalidSignature
+			);
+
+			let cacher = <Cachers<T>>::get(&bill.to).ok_or(Error::<T>::UnRegistered)?;
+			ensure!(
+				<CacherDeposits<T>>::get(&bill.to) >= T::CacherDeposit::get(),
+				Error::<T>::InsufficientDeposit
+			);
+			let price = cacher.byte_price.saturating_mul(FRAGMENT_SIZE.saturated_into());
+			ensure!(bill.amount == price, Error::<T>::AmountMismatch);
+			ensure!(
+				T::FragmentLookup::fragment_exists(&bill.file_hash, &bill.slice_hash),
+				Error::<T>::FragmentNonExistent
+			);
+
+			Ok(cacher.payee)
+		}
+
+		/// Pays the cacher a bill from the escrow of its payer.
+		fn settle_bill(
+			id: BillId,
+			bill: PendingBill<AccountOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+			now: BlockNumberFor<T>,
+		) -> DispatchResult {
+			<Escrows<T>>::try_mutate_exists(&bill.payer, |escrow_opt| -> DispatchResult {
+				let escrow = escrow_opt.as_mut().ok_or(Error::<T>::InsufficientEscrow)?;
+				escrow.balance = escrow.balance.saturating_sub(bill.amount);
+				escrow.pending = escrow.pending.saturating_sub(bill.amount);
+				if escrow.balance.is_zero() {
+					*escrow_opt = None;
+				}
+				Ok(())
+			})?;
+			let unpaid = T::Currency::repatriate_reserved(&bill.payer, &bill.payee, bill.amount, BalanceStatus::Free)?;
+			ensure!(unpaid.is_zero(), Error::<T>::InsufficientEscrow);
+			<Reputations<T>>::mutate(&bill.cacher, |reputation| {
+				reputation.pending.saturating_dec();
+				reputation.settled.saturating_inc();
+			});
+			<ServedFragments<T>>::insert(&bill.fragment_hash, &bill.cacher, now);
+			<ClosedBills<T>>::insert(&id, bill.expiration_time);
+
+			Self::deposit_event(Event::<T>::BillSettled { id, payee: bill.payee, amount: bill.amount });
 
 			Ok(())
 		}
+
+		/// Releases a disputed bill that is not paid, returning the bond of its payer.
+		fn release_bill(
+			id: &BillId,
+			bill: &PendingBill<AccountOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+			bond: BalanceOf<T>,
+		) {
+			T::Currency::unreserve(&bill.payer, bond);
+			<Escrows<T>>::mutate(&bill.payer, |escrow| escrow.pending = escrow.pending.saturating_sub(bill.amount));
+			<Reputations<T>>::mutate(&bill.cacher, |reputation| reputation.pending.saturating_dec());
+			<ClosedBills<T>>::insert(id, bill.expiration_time);
+		}
+
+		/// Counts a dispute of `payer` as no longer waiting for governance.
+		fn close_dispute(payer: &AccountOf<T>) {
+			<OpenDisputes<T>>::mutate_exists(payer, |open| {
+				*open = open.and_then(|count| count.checked_sub(1)).filter(|count| *count > 0);
+			});
+		}
+
+		/// How many entries costing `per_entry` to visit fit in `limit` besides `base`, up to
+		/// `MAX_PRUNE_PER_BLOCK`.
+		fn prune_count(limit: Weight, base: Weight, per_entry: Weight) -> usize {
+			limit.checked_sub(&base).map_or(0, |budget| {
+				budget.checked_div_per_component(&per_entry).unwrap_or(u64::MAX).min(MAX_PRUNE_PER_BLOCK as u64)
+					as usize
+			})
+		}
+
+		/// Lapses the disputes governance didn't resolve within `DisputeResolutionPeriod`.
+		///
+		/// A lapsed bill is paid as if it was never disputed, and the payer gets their bond back.
+		///
+		/// Visits as many disputes as `limit` allows, up to `MAX_PRUNE_PER_BLOCK`, from where the
+		/// last check stopped, and starts over once all were visited.
+		pub(crate) fn lapse_disputes(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// The cursor is read and written. A lapse reads the accounts of the payer and payee, the
+			// payer's escrow and open disputes and the cacher's reputation, and writes them with the
+			// bill, the served fragment and the closure.
+			let base = db_weight.reads_writes(1, 1);
+			let per_bill = db_weight.reads_writes(5, 8);
+			let max = Self::prune_count(limit, base, per_bill);
+			if max == 0 {
+				return Weight::zero();
+			}
+
+			let bills = match <DisputedBillsCursor<T>>::get() {
+				Some(last) => <DisputedBills<T>>::iter_from(<DisputedBills<T>>::hashed_key_for(&last)),
+				None => <DisputedBills<T>>::iter(),
+			};
+			let visited: Vec<_> = bills.take(max).collect();
+			match visited.last() {
+				Some((last, _)) if visited.len() == max => <DisputedBillsCursor<T>>::put(last),
+				_ => <DisputedBillsCursor<T>>::kill(),
+			}
+
+			let visited_count = visited.len() as u64;
+			for (id, disputed) in visited {
+				if now >= disputed.lapses_at {
+					let DisputedBill { bill, bond, .. } = disputed;
+					<DisputedBills<T>>::remove(&id);
+					Self::close_dispute(&bill.payer);
+					T::Currency::unreserve(&bill.payer, bond);
+					// Governance didn't uphold the dispute in time, so the bill is paid by default.
+					if let Err(e) = with_storage_layer(|| Self::settle_bill(id, bill.clone(), now)) {
+						log::warn!(target: "runtime::cacher", "lapsed bill {:?} can't be settled: {:?}", id, e);
+						Self::release_bill(&id, &bill, Zero::zero());
+					}
+					Self::deposit_event(Event::<T>::DisputeLapsed { id });
+				}
+			}
+
+			base.saturating_add(per_bill.saturating_mul(visited_count))
+		}
+
+		/// Removes the closed bills that have expired, as they can't be paid again anyway.
+		///
+		/// Visits as many bills as `limit` allows, up to `MAX_PRUNE_PER_BLOCK`, from where the last
+		/// pruning stopped, and starts over once all were visited.
+		pub(crate) fn prune_closed_bills(limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// The cursor is read and written, each bill visited read and maybe removed.
+			let base = db_weight.reads_writes(1, 1);
+			let per_bill = db_weight.reads_writes(1, 1);
+			let max = Self::prune_count(limit, base, per_bill);
+			if max == 0 {
+				return Weight::zero();
+			}
+
+			let bills = match <ClosedBillsCursor<T>>::get() {
+				Some(last) => <ClosedBills<T>>::iter_from(<ClosedBills<T>>::hashed_key_for(&last)),
+				None => <ClosedBills<T>>::iter(),
+			};
+			let visited: Vec<_> = bills.take(max).collect();
+			match visited.last() {
+				Some((last, _)) if visited.len() == max => <ClosedBillsCursor<T>>::put(last),
+				_ => <ClosedBillsCursor<T>>::kill(),
+			}
+
+			let now = T::UnixTime::now().as_secs();
+			for (id, expiration_time) in visited.iter() {
+				if *expiration_time <= now {
+					<ClosedBills<T>>::remove(id);
+				}
+			}
+
+			base.saturating_add(per_bill.saturating_mul(visited.len() as u64))
+		}
+
+		/// Removes the served fragments older than `ServedFragmentRetention`, or of cachers that
+		/// logged out.
+		///
+		/// Visits as many entries as `limit` allows, up to `MAX_PRUNE_PER_BLOCK`, from where the
+		/// last pruning stopped, and starts over once all were visited.
+		pub(crate) fn prune_served_fragments(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			// The cursor is read and written, each entry visited read with its cacher and maybe
+			// removed.
+			let base = db_weight.reads_writes(1, 1);
+			let per_entry = db_weight.reads_writes(2, 1);
+			let max = Self::prune_count(limit, base, per_entry);
+			if max == 0 {
+				return Weight::zero();
+			}
+
+			let served = match <ServedFragmentsCursor<T>>::get() {
+				Some((fragment, cacher)) =>
+					<ServedFragments<T>>::iter_from(<ServedFragments<T>>::hashed_key_for(&fragment, &cacher)),
+				None => <ServedFragments<T>>::iter(),
+			};
+			let visited: Vec<_> = served.take(max).collect();
+			match visited.last() {
+				Some((fragment, cacher, _)) if visited.len() == max =>
+					<ServedFragmentsCursor<T>>::put((*fragment, cacher.clone())),
+				_ => <ServedFragmentsCursor<T>>::kill(),
+			}
+
+			let retention = T::ServedFragmentRetention::get();
+			for (fragment, cacher, served_at) in visited.iter() {
+				if now >= served_at.saturating_add(retention) || !<Cachers<T>>::contains_key(cacher) {
+					<ServedFragments<T>>::remove(fragment, cacher);
+				}
+			}
+
+			base.saturating_add(per_entry.saturating_mul(visited.len() as u64))
+		}
+
+		/// Returns up to `limit` cachers whose deposit covers `CacherDeposit`.
+		///
+		/// Cachers paid for serving more of `fragments` come first, then the cheaper ones, then
+		/// those with the better reputation.
+		pub fn cachers(fragments: Vec<Hash>, limit: u32) -> Vec<CacherListing<AccountOf<T>, BalanceOf<T>>> {
+			let mut cachers: Vec<_> = <Cachers<T>>::iter()
+				.filter_map(|(account, info)| {
+					let deposit = <CacherDeposits<T>>::get(&account);
+					if deposit < T::CacherDeposit::get() {
+						return None;
+					}
+					let served = fragments
+						.iter()
+						.filter(|fragment| <ServedFragments<T>>::contains_key(*fragment, &account))
+						.count() as u32;
+					let reputation = <Reputations<T>>::get(&account);
+					Some(CacherListing { account, info, deposit, reputation, served })
+				})
+				.collect();
+
+			cachers.sort_by(|a, b| {
+				b.served
+					.cmp(&a.served)
+					.then_with(|| a.info.byte_price.cmp(&b.info.byte_price))
+					.then_with(|| b.reputation.score().cmp(&a.reputation.score()))
+			});
+			cachers.truncate(limit.min(MAX_CACHERS_PAGE) as usize);
+			cachers
+		}
+	}
+}
+
+/// Lets benchmarks sign bills for the runtime's signature type.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AccountId, Signature> {
+	/// Returns a new account that can sign bills.
+	fn cacher() -> AccountId;
+	/// Returns the signature of `message` by `cacher`.
+	fn sign(cacher: &AccountId, message: &[u8]) -> Signature;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<sp_runtime::AccountId32, sp_runtime::MultiSignature> for () {
+	fn cacher() -> sp_runtime::AccountId32 {
+		sp_io::crypto::sr25519_generate(BENCHMARK_KEY_TYPE, None).0.into()
+	}
+
+	fn sign(cacher: &sp_runtime::AccountId32, message: &[u8]) -> sp_runtime::MultiSignature {
+		let public = sp_runtime::app_crypto::sr25519::Public::from_raw(cacher.clone().into());
+		sp_io::crypto::sr25519_sign(BENCHMARK_KEY_TYPE, &public, message)
+			.expect("the key was generated by `cacher`; qed")
+			.into()
 	}
 }
+
+#[cfg(feature = "runtime-benchmarks")]
+const BENCHMARK_KEY_TYPE: sp_runtime::KeyTypeId = sp_runtime::KeyTypeId(*b"cach");
@@ -0,0 +1,39 @@
+use super::*;
+use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+use sp_runtime::traits::Saturating;
+
+/// Reserves the deposit from the cachers registered before it was introduced.
+pub mod v1 {
+	use super::*;
+
+	pub struct VersionUncheckedMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV0ToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let deposit = T::CacherDeposit::get();
+			let mut cachers = 0u64;
+
+			for acc in <Cachers<T>>::iter_keys() {
+				cachers.saturating_inc();
+				if <CacherDeposits<T>>::contains_key(&acc) {
+					continue;
+				}
+				// A cacher that can't afford the deposit keeps none, and isn't paid new bills until
+				// it calls `increase_deposit`.
+				match T::Currency::reserve(&acc, deposit) {
+					Ok(()) => <CacherDeposits<T>>::insert(&acc, deposit),
+					Err(_) => log::warn!(target: "runtime::cacher", "cacher {:?} can't afford the deposit", acc),
+				}
+			}
+
+			T::DbWeight::get().reads_writes(cachers.saturating_mul(3), cachers.saturating_mul(2))
+		}
+	}
+
+	pub type MigrateV0ToV1<T> = VersionedMigration<
+		0,
+		1,
+		VersionUncheckedMigrateV0ToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
@@ -3,83 +3,197 @@
 use super::*;
 use crate as pallet_cacher;
 
-use frame_support::traits::{ConstU128, ConstU32};
-use sp_core::H256;
-use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, Hooks},
 };
+use frame_system::EnsureRoot;
+use sp_runtime::{traits::Lazy, BuildStorage};
+
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
 
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 frame_support::construct_runtime!(
-	pub enum Test where
-		Block = Block,
-		NodeBlock = Block,
-		UncheckedExtrinsic = UncheckedExtrinsic,
-	{
-		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Cacher: pallet_cacher::{Pallet, Call, Storage, Event<T>},
+	pub enum Test {
+		System: frame_system,
+		Balances: pallet_balances,
+		Cacher: pallet_cacher,
 	}
 );
 
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
 impl frame_system::Config for Test {
-	type BaseCallFilter = frame_support::traits::Everything;
-	type BlockWeights = ();
-	type BlockLength = ();
-	type RuntimeOrigin = RuntimeOrigin;
-	type RuntimeCall = RuntimeCall;
-	type Index = u64;
-	type BlockNumber = u64;
-	type Hash = H256;
-	type Hashing = BlakeTwo256;
-	type AccountId = u64;
-	type Lookup = IdentityLookup<Self::AccountId>;
-	type Header = Header;
-	type RuntimeEvent = RuntimeEvent;
-	type BlockHashCount = Self::BlockHashCount;
-	type DbWeight = ();
-	type Version = ();
-	type PalletInfo = PalletInfo;
-	type AccountData = pallet_balances::AccountData<u64>;
-	type OnNewAccount = ();
-	type OnKilledAccount = ();
-	type SystemWeightInfo = ();
-	type SS58Prefix = Self::SS58Prefix;
-	type OnSetCode = ();
-	type MaxConsumers = frame_support::traits::ConstU32<16>;
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
 }
 
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
 impl pallet_balances::Config for Test {
-	type MaxLocks = ();
-	type MaxReserves = ();
-	type ReserveIdentifier = [u8; 8];
-	type Balance = u128;
-	type RuntimeEvent = RuntimeEvent;
-	type DustRemoval = ();
+	type Balance = Balance;
 	type ExistentialDeposit = ConstU128<1>;
 	type AccountStore = System;
-	type WeightInfo = ();
+}
+
+pub(crate) const DISPUTE_PERIOD: BlockNumber = 10;
+pub(crate) const CACHER_DEPOSIT: Balance = 1_000;
+pub(crate) const DISPUTE_BOND: Balance = 100;
+pub(crate) const DISPUTE_RESOLUTION_PERIOD: BlockNumber = 20;
+pub(crate) const MAX_OPEN_DISPUTES: u32 = 2;
+pub(crate) const SERVED_RETENTION: BlockNumber = 50;
+
+parameter_types! {
+	/// The Unix time in seconds of the mock.
+	pub static Now: u64 = 1_000;
+	/// The `(file, fragment)` pairs stored in file-bank.
+	pub static Fragments: Vec<(Hash, Hash)> = vec![];
+	pub const DisputeSlash: Perbill = Perbill::from_percent(10);
+}
+
+pub struct MockTime;
+impl UnixTime for MockTime {
+	fn now() -> core::time::Duration {
+		core::time::Duration::from_secs(Now::get())
+	}
+}
+
+pub struct MockFragments;
+impl FragmentLookup for MockFragments {
+	fn fragment_exists(file_hash: &Hash, fragment_hash: &Hash) -> bool {
+		Fragments::get().contains(&(*file_hash, *fragment_hash))
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn insert_fragment(file_hash: Hash, fragment_hash: Hash) {
+		Fragments::mutate(|fragments| fragments.push((file_hash, fragment_hash)));
+	}
+}
+
+/// The key of an account of the mock, which is the account itself.
+pub struct MockPublic(pub AccountId);
+impl IdentifyAccount for MockPublic {
+	type AccountId = AccountId;
+
+	fn into_account(self) -> AccountId {
+		self.0
+	}
+}
+
+/// A signature by the account it names, of the message it holds the hash of.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo, RuntimeDebug)]
+pub struct MockSignature(pub AccountId, pub [u8; 32]);
+impl Verify for MockSignature {
+	type Signer = MockPublic;
+
+	fn verify<L: Lazy<[u8]>>(&self, mut msg: L, signer: &AccountId) -> bool {
+		*signer == self.0 && sp_io::hashing::blake2_256(msg.get()) == self.1
+	}
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+impl BenchmarkHelper<AccountId, MockSignature> for MockSignature {
+	fn cacher() -> AccountId {
+		100
+	}
+
+	fn sign(cacher: &AccountId, message: &[u8]) -> MockSignature {
+		MockSignature(*cacher, sp_io::hashing::blake2_256(message))
+	}
 }
 
 impl pallet_cacher::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type BillsLimit = ConstU32<10>;
+	type UnixTime = MockTime;
+	type FragmentLookup = MockFragments;
+	type OffchainSignature = MockSignature;
+	type OffchainPublic = MockPublic;
+	type DisputePeriod = ConstU64<DISPUTE_PERIOD>;
+	type DisputeBond = ConstU128<DISPUTE_BOND>;
+	type DisputeResolutionPeriod = ConstU64<DISPUTE_RESOLUTION_PERIOD>;
+	type MaxOpenDisputes = ConstU32<MAX_OPEN_DISPUTES>;
+	type ServedFragmentRetention = ConstU64<SERVED_RETENTION>;
+	type CacherDeposit = ConstU128<CACHER_DEPOSIT>;
+	type DisputeSlash = DisputeSlash;
+	type Slash = ();
+	type GovernanceOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = MockSignature;
 }
 
+pub(crate) const ALICE: AccountId = 1;
+pub(crate) const BOB: AccountId = 2;
+pub(crate) const CHARLIE: AccountId = 3;
+
+/// The price of a fragment at a `byte_price` of one.
+pub(crate) const FRAGMENT_PRICE: Balance = FRAGMENT_SIZE;
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
 	pallet_balances::GenesisConfig::<Test> {
-		balances: vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100)],
+		balances: vec![(ALICE, 100 * FRAGMENT_PRICE), (BOB, 100 * FRAGMENT_PRICE), (CHARLIE, 100 * FRAGMENT_PRICE)],
+		..Default::default()
 	}
-	.assimilate_storage(&mut t)
+	.assimilate_storage(&mut storage)
 	.unwrap();
-
-	let mut ext: sp_io::TestExternalities = t.into();
-	ext.execute_with(|| System::set_block_number(1));
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		Now::set(1_000);
+		Fragments::set(vec![]);
+	});
 	ext
 }
+
+/// Run the pallet hooks of every block up to and including `n`.
+pub fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Cacher::on_idle(next, Weight::MAX);
+	}
+}
+
+pub fn hash(byte: u8) -> Hash {
+	Hash([byte; 64])
+}
+
+/// Cacher info paid to `payee`, charging `byte_price` per byte.
+pub fn cacher_info(payee: AccountId, byte_price: Balance) -> CacherInfo<AccountId, Balance> {
+	CacherInfo { payee, ip: IpAddress::IPV4([127, 0, 0, 1], 8080), byte_price }
+}
+
+/// Registers `cacher` at a `byte_price` of one, paid to itself.
+pub fn register(cacher: AccountId) {
+	Cacher::register(RuntimeOrigin::signed(cacher), cacher_info(cacher, 1)).unwrap();
+}
+
+/// A bill of `cacher` for a stored fragment, expiring in an hour.
+pub fn bill(id: u8, cacher: AccountId) -> BillOf<Test> {
+	Fragments::mutate(|fragments| fragments.push((hash(id), hash(id.wrapping_add(1)))));
+	Bill {
+		id: [id; 16],
+		to: cacher,
+		amount: FRAGMENT_PRICE,
+		file_hash: hash(id),
+		slice_hash: hash(id.wrapping_add(1)),
+		expiration_time: Now::get() + 3_600,
+	}
+}
+
+/// The signature of `bill` by its cacher, for `payer`.
+pub fn sign(payer: AccountId, bill: &BillOf<Test>) -> MockSignature {
+	MockSignature(bill.to, sp_io::hashing::blake2_256(&(payer, bill).encode()))
+}
+
+/// The bills signed by their cacher, for `payer`.
+pub fn signed(
+	payer: AccountId,
+	bills: Vec<BillOf<Test>>,
+) -> BoundedVec<(BillOf<Test>, MockSignature), ConstU32<10>> {
+	bills.into_iter().map(|bill| (bill.clone(), sign(payer, &bill))).collect::<Vec<_>>().try_into().unwrap()
+}
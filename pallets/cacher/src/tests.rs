@@ -1,121 +1,487 @@
 //! Tests for the module.
 
 use super::*;
+use crate::mock::*;
 use frame_support::{assert_noop, assert_ok};
-use mock::{new_test_ext, Cacher, RuntimeOrigin, Test};
-use sp_runtime::traits::Hash;
+
+const ESCROW: Balance = 10 * FRAGMENT_PRICE;
+
+/// Registers ALICE as a cacher and funds the escrow of BOB.
+fn setup() {
+	register(ALICE);
+	assert_ok!(Cacher::deposit_escrow(RuntimeOrigin::signed(BOB), ESCROW));
+}
+
+/// BOB pays ALICE the bills with ids `ids`.
+fn pay(ids: &[u8]) {
+	let bills = ids.iter().map(|id| bill(*id, ALICE)).collect();
+	assert_ok!(Cacher::pay(RuntimeOrigin::signed(BOB), signed(BOB, bills)));
+}
+
+fn ids(ids: &[u8]) -> BoundedVec<BillId, ConstU32<10>> {
+	ids.iter().map(|id| [*id; 16]).collect::<Vec<_>>().try_into().unwrap()
+}
 
 #[test]
-fn register_works() {
+fn register_reserves_deposit() {
 	new_test_ext().execute_with(|| {
-		let info = CacherInfo::<AccountOf<Test>, BalanceOf<Test>> {
-			payee: 1,
-			ip: IpAddress::IPV4([127, 0, 0, 1], 8080),
-			byte_price: 100u32.into(),
-		};
-		// Register works.
-		assert_ok!(Cacher::register(RuntimeOrigin::signed(1), info.clone()));
+		assert_ok!(Cacher::register(RuntimeOrigin::signed(ALICE), cacher_info(CHARLIE, 1)));
+		assert_eq!(Cachers::<Test>::get(ALICE), Some(cacher_info(CHARLIE, 1)));
+		assert_eq!(CacherDeposits::<Test>::get(ALICE), CACHER_DEPOSIT);
+		assert_eq!(Balances::reserved_balance(ALICE), CACHER_DEPOSIT);
 
-		let result_info = Cachers::<Test>::get(&1).unwrap();
-		assert_eq!(result_info, info);
+		assert_noop!(
+			Cacher::register(RuntimeOrigin::signed(ALICE), cacher_info(ALICE, 1)),
+			Error::<Test>::AlreadyRegistered
+		);
+	});
+}
 
-		// Register again fails.
+#[test]
+fn update_requires_registration() {
+	new_test_ext().execute_with(|| {
+		register(ALICE);
 		assert_noop!(
-			Cacher::register(RuntimeOrigin::signed(1), info.clone()),
-			Error::<Test>::Registered
+			Cacher::update(RuntimeOrigin::signed(BOB), cacher_info(BOB, 2)),
+			Error::<Test>::UnRegistered
 		);
+
+		assert_ok!(Cacher::update(RuntimeOrigin::signed(ALICE), cacher_info(ALICE, 2)));
+		assert_eq!(Cachers::<Test>::get(ALICE), Some(cacher_info(ALICE, 2)));
 	});
 }
 
 #[test]
-fn update_works() {
+fn logout_waits_for_pending_bills() {
 	new_test_ext().execute_with(|| {
-		let info = CacherInfo::<AccountOf<Test>, BalanceOf<Test>> {
-			payee: 1,
-			ip: IpAddress::IPV4([127, 0, 0, 1], 8080),
-			byte_price: 100u32.into(),
-		};
-		assert_ok!(Cacher::register(RuntimeOrigin::signed(1), info.clone()));
+		setup();
+		pay(&[1]);
+		assert_noop!(Cacher::logout(RuntimeOrigin::signed(ALICE)), Error::<Test>::BillsPending);
 
-		let new_info = CacherInfo::<AccountOf<Test>, BalanceOf<Test>> {
-			payee: 1,
-			ip: IpAddress::IPV4([127, 0, 0, 1], 80),
-			byte_price: 200u32.into(),
-		};
-		// Wrong account update fails.
+		run_to_block(1 + DISPUTE_PERIOD);
+		assert_ok!(Cacher::settle(RuntimeOrigin::signed(CHARLIE), ids(&[1])));
+		assert_ok!(Cacher::logout(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(Cachers::<Test>::get(ALICE), None);
+		assert_eq!(CacherDeposits::<Test>::get(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+
+		assert_noop!(Cacher::logout(RuntimeOrigin::signed(ALICE)), Error::<Test>::UnRegistered);
+	});
+}
+
+#[test]
+fn escrow_withdrawal_keeps_pending_bills_covered() {
+	new_test_ext().execute_with(|| {
+		setup();
+		assert_eq!(Balances::reserved_balance(BOB), ESCROW);
+		pay(&[1]);
+		assert_eq!(Cacher::escrow(BOB), Escrow { balance: ESCROW, pending: FRAGMENT_PRICE });
 
 		assert_noop!(
-			Cacher::update(RuntimeOrigin::signed(2), new_info.clone()),
-			Error::<Test>::UnRegister
+			Cacher::withdraw_escrow(RuntimeOrigin::signed(BOB), ESCROW),
+			Error::<Test>::InsufficientEscrow
 		);
-		// Update works.
-		assert_ok!(Cacher::update(RuntimeOrigin::signed(1), new_info.clone()));
+		assert_ok!(Cacher::withdraw_escrow(RuntimeOrigin::signed(BOB), ESCROW - FRAGMENT_PRICE));
+		assert_eq!(Cacher::escrow(BOB), Escrow { balance: FRAGMENT_PRICE, pending: FRAGMENT_PRICE });
+		assert_eq!(Balances::reserved_balance(BOB), FRAGMENT_PRICE);
 
-		let result_info = Cachers::<Test>::get(&1).unwrap();
-		assert_eq!(result_info, new_info);
+		assert_noop!(
+			Cacher::withdraw_escrow(RuntimeOrigin::signed(CHARLIE), 1),
+			Error::<Test>::InsufficientEscrow
+		);
 	});
 }
 
 #[test]
-fn logout_works() {
+fn pay_records_pending_bills() {
 	new_test_ext().execute_with(|| {
-		let info = CacherInfo::<AccountOf<Test>, BalanceOf<Test>> {
-			payee: 1,
-			ip: IpAddress::IPV4([127, 0, 0, 1], 8080),
-			byte_price: 100u32.into(),
+		setup();
+		let paid = bill(1, ALICE);
+		pay(&[1]);
+
+		assert_eq!(
+			Cacher::pending_bill([1; 16]),
+			Some(PendingBill {
+				payer: BOB,
+				cacher: ALICE,
+				payee: ALICE,
+				fragment_hash: paid.slice_hash,
+				amount: FRAGMENT_PRICE,
+				settles_at: 1 + DISPUTE_PERIOD,
+				expiration_time: paid.expiration_time,
+			})
+		);
+		assert_eq!(Cacher::reputation(ALICE).pending, 1);
+		// Nothing is paid before the dispute period is over.
+		assert_eq!(Balances::reserved_balance(BOB), ESCROW);
+	});
+}
+
+#[test]
+fn pay_checks_bills() {
+	new_test_ext().execute_with(|| {
+		setup();
+		let pay_one = |bill: BillOf<Test>, signature| {
+			let bills = vec![(bill, signature)].try_into().unwrap();
+			Cacher::pay(RuntimeOrigin::signed(BOB), bills)
 		};
-		assert_ok!(Cacher::register(RuntimeOrigin::signed(1), info.clone()));
-
-		// Wrong account logout fails.
-		assert_noop!(Cacher::logout(RuntimeOrigin::signed(2)), Error::<Test>::UnRegister);
-
-		// Logout works.
-		assert_ok!(Cacher::logout(RuntimeOrigin::signed(1)));
-	});
-}
-
-#[test]
-fn pay_works() {
-	new_test_ext().execute_with(|| {
-		let n = 10;
-		let amount: BalanceOf<Test> = 10000;
-		let s_file = String::from("file");
-		let s_slice = String::from("slice");
-		let mut bill_vec = Vec::new();
-		for i in 0..n {
-			let bill =
-				Bill::<AccountOf<Test>, BalanceOf<Test>, <Test as frame_system::Config>::Hash> {
-					id: [i as u8; 16],
-					to: 2,
-					amount,
-					file_hash: <Test as frame_system::Config>::Hashing::hash_of(&format!(
-						"{}{}",
-						s_file, i
-					)),
-					slice_hash: <Test as frame_system::Config>::Hashing::hash_of(&format!(
-						"{}{}",
-						s_slice, i
-					)),
-					expiration_time: 1675900800u64,
-				};
-			bill_vec.push(bill);
+
+		pay(&[1]);
+		let paid = bill(1, ALICE);
+		assert_noop!(pay_one(paid.clone(), sign(BOB, &paid)), Error::<Test>::BillExisted);
+
+		let expired = bill(2, ALICE);
+		Now::set(expired.expiration_time);
+		assert_noop!(pay_one(expired.clone(), sign(BOB, &expired)), Error::<Test>::BillExpired);
+		Now::set(1_000);
+
+		let for_other_payer = bill(3, ALICE);
+		assert_noop!(
+			pay_one(for_other_payer.clone(), sign(CHARLIE, &for_other_payer)),
+			Error::<Test>::InvalidSignature
+		);
+
+		let unregistered = bill(4, CHARLIE);
+		assert_noop!(pay_one(unregistered.clone(), sign(BOB, &unregistered)), Error::<Test>::UnRegistered);
+
+		let mut overcharged = bill(5, ALICE);
+		overcharged.amount += 1;
+		assert_noop!(pay_one(overcharged.clone(), sign(BOB, &overcharged)), Error::<Test>::AmountMismatch);
+
+		let mut unstored = bill(6, ALICE);
+		unstored.slice_hash = hash(200);
+		assert_noop!(pay_one(unstored.clone(), sign(BOB, &unstored)), Error::<Test>::FragmentNonExistent);
+
+		assert_ok!(Cacher::withdraw_escrow(RuntimeOrigin::signed(BOB), ESCROW - FRAGMENT_PRICE));
+		let uncovered = bill(7, ALICE);
+		assert_noop!(pay_one(uncovered.clone(), sign(BOB, &uncovered)), Error::<Test>::InsufficientEscrow);
+	});
+}
+
+#[test]
+fn dispute_reserves_bond() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1, 2]);
+
+		assert_noop!(Cacher::dispute(RuntimeOrigin::signed(CHARLIE), [1; 16]), Error::<Test>::NotPayer);
+		assert_noop!(Cacher::dispute(RuntimeOrigin::signed(BOB), [9; 16]), Error::<Test>::BillNonExistent);
+
+		assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [1; 16]));
+		System::assert_last_event(RuntimeEvent::Cacher(Event::BillDisputed {
+			acc: BOB,
+			id: [1; 16],
+			bond: DISPUTE_BOND,
+		}));
+		assert_eq!(Balances::reserved_balance(BOB), ESCROW + DISPUTE_BOND);
+		assert_eq!(Cacher::pending_bill([1; 16]), None);
+		assert_eq!(Cacher::disputed_bill([1; 16]).map(|disputed| disputed.bond), Some(DISPUTE_BOND));
+		assert_eq!(Cacher::reputation(ALICE).disputed, 1);
+
+		run_to_block(1 + DISPUTE_PERIOD);
+		assert_noop!(Cacher::dispute(RuntimeOrigin::signed(BOB), [2; 16]), Error::<Test>::DisputePeriodOver);
+	});
+}
+
+#[test]
+fn disputed_bill_cannot_be_paid_again() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1]);
+		assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [1; 16]));
+
+		let disputed = bill(1, ALICE);
+		assert_noop!(
+			Cacher::pay(RuntimeOrigin::signed(BOB), signed(BOB, vec![disputed])),
+			Error::<Test>::BillExisted
+		);
+	});
+}
+
+#[test]
+fn settle_skips_bills_it_cannot_settle() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1]);
+		run_to_block(5);
+		pay(&[2]);
+		run_to_block(1 + DISPUTE_PERIOD);
+
+		let free = Balances::free_balance(ALICE);
+		assert_ok!(Cacher::settle(RuntimeOrigin::signed(CHARLIE), ids(&[1, 2, 9])));
+		System::assert_has_event(RuntimeEvent::Cacher(Event::BillSettled {
+			id: [1; 16],
+			payee: ALICE,
+			amount: FRAGMENT_PRICE,
+		}));
+		System::assert_last_event(RuntimeEvent::Cacher(Event::BillsSkipped { ids: ids(&[2, 9]) }));
+
+		assert_eq!(Balances::free_balance(ALICE), free + FRAGMENT_PRICE);
+		assert_eq!(Balances::reserved_balance(BOB), ESCROW - FRAGMENT_PRICE);
+		assert_eq!(Cacher::escrow(BOB), Escrow { balance: ESCROW - FRAGMENT_PRICE, pending: FRAGMENT_PRICE });
+		assert_eq!(Cacher::pending_bill([1; 16]), None);
+		assert!(Cacher::pending_bill([2; 16]).is_some());
+		assert_eq!(Cacher::closed_bill([1; 16]), Some(bill(1, ALICE).expiration_time));
+
+		// A settled bill is skipped.
+		assert_ok!(Cacher::settle(RuntimeOrigin::signed(CHARLIE), ids(&[1])));
+		System::assert_last_event(RuntimeEvent::Cacher(Event::BillsSkipped { ids: ids(&[1]) }));
+		assert_eq!(Balances::free_balance(ALICE), free + FRAGMENT_PRICE);
+	});
+}
+
+#[test]
+fn rejected_dispute_forfeits_bond() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1]);
+		assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [1; 16]));
+
+		assert_noop!(
+			Cacher::resolve_dispute(RuntimeOrigin::signed(BOB), [1; 16], false),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		let free = Balances::free_balance(ALICE);
+		assert_ok!(Cacher::resolve_dispute(RuntimeOrigin::root(), [1; 16], false));
+
+		assert_eq!(Balances::free_balance(ALICE), free + FRAGMENT_PRICE + DISPUTE_BOND);
+		assert_eq!(Balances::reserved_balance(BOB), ESCROW - FRAGMENT_PRICE);
+		assert_eq!(Cacher::disputed_bill([1; 16]), None);
+		assert_eq!(Cacher::closed_bill([1; 16]), Some(bill(1, ALICE).expiration_time));
+		assert_noop!(
+			Cacher::resolve_dispute(RuntimeOrigin::root(), [1; 16], false),
+			Error::<Test>::BillNonExistent
+		);
+	});
+}
+
+#[test]
+fn upheld_dispute_returns_bond() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1]);
+		assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [1; 16]));
+		assert_ok!(Cacher::resolve_dispute(RuntimeOrigin::root(), [1; 16], true));
+
+		assert_eq!(Balances::reserved_balance(BOB), ESCROW);
+		assert_eq!(Cacher::escrow(BOB), Escrow { balance: ESCROW, pending: 0 });
+		assert_eq!(Cacher::closed_bill([1; 16]), Some(bill(1, ALICE).expiration_time));
+	});
+}
+
+#[test]
+fn closed_bills_are_pruned_once_expired() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1]);
+		run_to_block(1 + DISPUTE_PERIOD);
+		assert_ok!(Cacher::settle(RuntimeOrigin::signed(CHARLIE), ids(&[1])));
+
+		run_to_block(2 + DISPUTE_PERIOD);
+		let expiration_time = bill(1, ALICE).expiration_time;
+		assert_eq!(Cacher::closed_bill([1; 16]), Some(expiration_time));
+
+		Now::set(expiration_time);
+		run_to_block(3 + DISPUTE_PERIOD);
+		assert_eq!(Cacher::closed_bill([1; 16]), None);
+	});
+}
+
+#[test]
+fn pruning_resumes_where_it_stopped() {
+	new_test_ext().execute_with(|| {
+		for id in 0..(MAX_PRUNE_PER_BLOCK + 1) {
+			ClosedBills::<Test>::insert([id as u8; 16], 1_000);
 		}
-		let bills: BoundedVec<_, <Test as Config>::BillsLimit> = bill_vec.try_into().unwrap();
 
-		// Pay fails.
+		Cacher::on_idle(2, Weight::MAX);
+		assert_eq!(ClosedBills::<Test>::iter().count(), 1);
+		assert!(ClosedBillsCursor::<Test>::get().is_some());
+
+		Cacher::on_idle(3, Weight::MAX);
+		assert_eq!(ClosedBills::<Test>::iter().count(), 0);
+		assert_eq!(ClosedBillsCursor::<Test>::get(), None);
+	});
+}
+
+#[test]
+fn upheld_dispute_slashes_deposit() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1, 2]);
+		assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [1; 16]));
+		assert_ok!(Cacher::resolve_dispute(RuntimeOrigin::root(), [1; 16], true));
+
+		let slashed = DisputeSlash::get() * CACHER_DEPOSIT;
+		System::assert_last_event(RuntimeEvent::Cacher(Event::DisputeResolved { id: [1; 16], upheld: true, slashed }));
+		assert_eq!(CacherDeposits::<Test>::get(ALICE), CACHER_DEPOSIT - slashed);
+		assert_eq!(Balances::reserved_balance(ALICE), CACHER_DEPOSIT - slashed);
+		assert_eq!(Cacher::reputation(ALICE), Reputation { pending: 1, settled: 0, disputed: 1, upheld: 1 });
+
+		// A cacher whose deposit no longer covers `CacherDeposit` isn't paid until it tops it up.
+		let unpaid = bill(3, ALICE);
 		assert_noop!(
-			Cacher::pay(RuntimeOrigin::signed(1), bills.clone()),
-			Error::<Test>::InsufficientBalance
+			Cacher::pay(RuntimeOrigin::signed(BOB), signed(BOB, vec![unpaid.clone()])),
+			Error::<Test>::InsufficientDeposit
 		);
+		assert_ok!(Cacher::increase_deposit(RuntimeOrigin::signed(ALICE), slashed));
+		assert_ok!(Cacher::pay(RuntimeOrigin::signed(BOB), signed(BOB, vec![unpaid])));
+	});
+}
+
+#[test]
+fn slash_is_capped_by_deposit() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1]);
+		CacherDeposits::<Test>::insert(ALICE, 10);
+		assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [1; 16]));
+		assert_ok!(Cacher::resolve_dispute(RuntimeOrigin::root(), [1; 16], true));
+
+		System::assert_last_event(RuntimeEvent::Cacher(Event::DisputeResolved {
+			id: [1; 16],
+			upheld: true,
+			slashed: 10,
+		}));
+		assert_eq!(CacherDeposits::<Test>::get(ALICE), 0);
+	});
+}
+
+#[test]
+fn reputation_tracks_bills() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1, 2, 3, 4]);
+		for id in [3, 4] {
+			assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [id; 16]));
+		}
+		assert_ok!(Cacher::resolve_dispute(RuntimeOrigin::root(), [3; 16], true));
+		assert_ok!(Cacher::resolve_dispute(RuntimeOrigin::root(), [4; 16], false));
+		run_to_block(1 + DISPUTE_PERIOD);
+		assert_ok!(Cacher::settle(RuntimeOrigin::signed(CHARLIE), ids(&[1, 2])));
+
+		let reputation = Cacher::reputation(ALICE);
+		assert_eq!(reputation, Reputation { pending: 0, settled: 3, disputed: 2, upheld: 1 });
+		assert_eq!(reputation.score(), Perbill::from_percent(75));
+		assert_eq!(Reputation::default().score(), Perbill::zero());
+	});
+}
+
+#[test]
+fn open_disputes_are_bounded() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1, 2, 3]);
+		for id in 1..=MAX_OPEN_DISPUTES as u8 {
+			assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [id; 16]));
+		}
+		assert_eq!(Cacher::open_disputes(BOB), MAX_OPEN_DISPUTES);
+		assert_noop!(Cacher::dispute(RuntimeOrigin::signed(BOB), [3; 16]), Error::<Test>::TooManyDisputes);
+
+		assert_ok!(Cacher::resolve_dispute(RuntimeOrigin::root(), [1; 16], false));
+		assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [3; 16]));
+	});
+}
+
+#[test]
+fn unresolved_disputes_lapse_into_payment() {
+	new_test_ext().execute_with(|| {
+		setup();
+		pay(&[1]);
+		assert_ok!(Cacher::dispute(RuntimeOrigin::signed(BOB), [1; 16]));
+		let free = Balances::free_balance(ALICE);
+
+		run_to_block(DISPUTE_RESOLUTION_PERIOD);
+		assert!(Cacher::disputed_bill([1; 16]).is_some());
+
+		run_to_block(1 + DISPUTE_RESOLUTION_PERIOD);
+		System::assert_has_event(RuntimeEvent::Cacher(Event::DisputeLapsed { id: [1; 16] }));
+		assert_eq!(Cacher::disputed_bill([1; 16]), None);
+		assert_eq!(Cacher::open_disputes(BOB), 0);
+		// The bill is paid as if it wasn't disputed, the bond is returned and the cacher isn't
+		// slashed.
+		assert_eq!(Balances::free_balance(ALICE), free + FRAGMENT_PRICE);
+		assert_eq!(Balances::reserved_balance(BOB), ESCROW - FRAGMENT_PRICE);
+		assert_eq!(Cacher::escrow(BOB), Escrow { balance: ESCROW - FRAGMENT_PRICE, pending: 0 });
+		assert_eq!(CacherDeposits::<Test>::get(ALICE), CACHER_DEPOSIT);
+		assert_eq!(Cacher::reputation(ALICE), Reputation { pending: 0, settled: 1, disputed: 1, upheld: 0 });
+		assert_eq!(Cacher::closed_bill([1; 16]), Some(bill(1, ALICE).expiration_time));
+	});
+}
+
+#[test]
+fn served_fragments_are_pruned() {
+	new_test_ext().execute_with(|| {
+		setup();
+		register(CHARLIE);
+		ServedFragments::<Test>::insert(hash(1), ALICE, 1);
+		ServedFragments::<Test>::insert(hash(2), CHARLIE, 1);
+		assert_ok!(Cacher::logout(RuntimeOrigin::signed(CHARLIE)));
+
+		// Fragments of cachers that logged out are forgotten.
+		run_to_block(2);
+		assert!(ServedFragments::<Test>::contains_key(hash(1), ALICE));
+		assert!(!ServedFragments::<Test>::contains_key(hash(2), CHARLIE));
+
+		run_to_block(SERVED_RETENTION);
+		assert!(ServedFragments::<Test>::contains_key(hash(1), ALICE));
+		run_to_block(1 + SERVED_RETENTION);
+		assert!(!ServedFragments::<Test>::contains_key(hash(1), ALICE));
+	});
+}
+
+#[test]
+fn cachers_are_listed_by_served_fragments_price_and_reputation() {
+	new_test_ext().execute_with(|| {
+		let list = |limit| {
+			Cacher::cachers(vec![hash(1), hash(2)], limit).into_iter().map(|listing| listing.account).collect::<Vec<_>>()
+		};
+		let (cheap, served, reputable, trusted, poor) = (10, 11, 12, 13, 14);
+		for (acc, byte_price) in [(cheap, 1), (served, 5), (reputable, 2), (trusted, 2), (poor, 1)] {
+			Cachers::<Test>::insert(acc, cacher_info(acc, byte_price));
+			CacherDeposits::<Test>::insert(acc, CACHER_DEPOSIT);
+		}
+		ServedFragments::<Test>::insert(hash(1), served, 1);
+		ServedFragments::<Test>::insert(hash(2), served, 1);
+		ServedFragments::<Test>::insert(hash(1), cheap, 1);
+		Reputations::<Test>::insert(reputable, Reputation { settled: 3, upheld: 1, ..Default::default() });
+		Reputations::<Test>::insert(trusted, Reputation { settled: 4, ..Default::default() });
+		// Cachers whose deposit doesn't cover `CacherDeposit` aren't listed.
+		CacherDeposits::<Test>::insert(poor, CACHER_DEPOSIT - 1);
+
+		assert_eq!(list(10), vec![served, cheap, trusted, reputable]);
+		assert_eq!(list(2), vec![served, cheap]);
+		let listing = &Cacher::cachers(vec![hash(1), hash(2)], 1)[0];
+		assert_eq!(listing.served, 2);
+		assert_eq!(listing.deposit, CACHER_DEPOSIT);
+	});
+}
+
+#[test]
+fn migration_reserves_the_deposit_of_legacy_cachers() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<Cacher>();
+		let poor = 9;
+		for acc in [ALICE, poor] {
+			Cachers::<Test>::insert(acc, cacher_info(acc, 1));
+		}
+		// Cachers registered with a deposit already keep it.
+		register(BOB);
+
+		crate::migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Cacher::on_chain_storage_version(), 1);
+		assert_eq!(CacherDeposits::<Test>::get(ALICE), CACHER_DEPOSIT);
+		assert_eq!(Balances::reserved_balance(ALICE), CACHER_DEPOSIT);
+		assert_eq!(Balances::reserved_balance(BOB), CACHER_DEPOSIT);
+		assert_eq!(CacherDeposits::<Test>::get(poor), 0);
 
-		<Test as Config>::Currency::make_free_balance_be(&1, BalanceOf::<Test>::max_value());
-		let balance_befor_1 = <Test as Config>::Currency::free_balance(&1);
-		let balance_before_2 = <Test as Config>::Currency::free_balance(&2);
-		// Pay works.
-		assert_ok!(Cacher::pay(RuntimeOrigin::signed(1), bills));
-		let balance_after_1 = <Test as Config>::Currency::free_balance(&1);
-		let balance_after_2 = <Test as Config>::Currency::free_balance(&2);
-		assert_eq!(balance_befor_1 - balance_after_1, amount * n);
-		assert_eq!(balance_after_2 - balance_before_2, amount * n);
+		// A cacher without a deposit can top it up, and leaves like any other.
+		let _ = Balances::deposit_creating(&poor, 2 * CACHER_DEPOSIT);
+		assert_ok!(Cacher::increase_deposit(RuntimeOrigin::signed(poor), CACHER_DEPOSIT));
+		assert_ok!(Cacher::logout(RuntimeOrigin::signed(poor)));
+		assert_eq!(Balances::reserved_balance(poor), 0);
 	});
 }
@@ -14,15 +14,92 @@ pub struct CacherInfo<AccountId, Balance> {
 	pub byte_price: Balance,
 }
 
+/// The unique id of a bill.
+pub type BillId = [u8; 16];
+/// A bill for a fragment of a file stored in file-bank.
+pub type BillOf<T> = Bill<AccountOf<T>, BalanceOf<T>, Hash>;
+
 /// The custom struct for bill info.
+///
+/// The cacher `to` signs the SCALE encoding of `(payer, bill)`.
 #[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub struct Bill<AccountId, Balance, Hash> {
-	pub id: [u8; 16],
+	pub id: BillId,
+	// The cacher that served the download
 	pub to: AccountId,
 	pub amount: Balance,
 	// Hash of the file to download
 	pub file_hash: Hash,
 	// Hash of the file slice to download
 	pub slice_hash: Hash,
+	// Unix time in seconds after which the bill can no longer be paid
+	pub expiration_time: u64,
+}
+
+/// The prepaid escrow of a payer, reserved from their balance.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Escrow<Balance> {
+	pub balance: Balance,
+	// The part of the balance owed for bills still in their dispute window
+	pub pending: Balance,
+}
+
+/// A paid bill waiting out its dispute window, or the resolution of its dispute.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct PendingBill<AccountId, Balance, BlockNumber> {
+	pub payer: AccountId,
+	// The cacher that served the download
+	pub cacher: AccountId,
+	pub payee: AccountId,
+	// Hash of the fragment served
+	pub fragment_hash: Hash,
+	pub amount: Balance,
+	// The block from which the bill can be settled
+	pub settles_at: BlockNumber,
+	// Unix time in seconds after which the bill can no longer be paid
 	pub expiration_time: u64,
 }
+
+/// A disputed bill waiting for governance to resolve it.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct DisputedBill<AccountId, Balance, BlockNumber> {
+	pub bill: PendingBill<AccountId, Balance, BlockNumber>,
+	// Reserved from the payer, and forfeited to the payee if the dispute is rejected
+	pub bond: Balance,
+	// The block from which the dispute lapses if governance hasn't resolved it
+	pub lapses_at: BlockNumber,
+}
+
+/// The track record of a cacher's bills.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Reputation {
+	// Bills paid but not settled or resolved yet
+	pub pending: u32,
+	// Bills paid to the cacher
+	pub settled: u32,
+	// Bills disputed by their payer
+	pub disputed: u32,
+	// Disputes upheld by governance, for which the cacher was slashed
+	pub upheld: u32,
+}
+
+impl Reputation {
+	/// The share of closed bills the cacher was paid for, zero without any.
+	pub fn score(&self) -> Perbill {
+		match self.settled.saturating_add(self.upheld) {
+			0 => Perbill::zero(),
+			closed => Perbill::from_rational(self.settled, closed),
+		}
+	}
+}
+
+/// A cacher as returned by discovery.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct CacherListing<AccountId, Balance> {
+	pub account: AccountId,
+	pub info: CacherInfo<AccountId, Balance>,
+	pub deposit: Balance,
+	pub reputation: Reputation,
+	// How many of the requested fragments the cacher was paid for serving
+	pub served: u32,
+}
@@ -15,29 +15,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-//! Autogenerated weights for pallet_cacher
+//! Weights for pallet_cacher
 //!
-//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
-//! DATE: 2023-02-10, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
-//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("cess-initial-devnet"), DB CACHE: 1024
-
-// Executed Command:
-// ./target/release/cess-node
-// benchmark
-// --chain
-// cess-initial-devnet
-// --execution=wasm
-// --wasm-execution=compiled
-// --pallet
-// pallet_cacher
-// --extrinsic
-// *
-// --steps
-// 50
-// --repeat
-// 20
-// --template=./.maintain/frame-weight-template.hbs
-// --output=./pallets/cacher/src/weights.rs
+//! Not benchmarked yet: the weights below are estimated from the storage accesses of each call.
+//! Regenerate it with `scripts/run/benchmark.sh cacher` on reference hardware.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -52,16 +33,24 @@ pub trait WeightInfo {
 	fn update() -> Weight;
 	fn logout() -> Weight;
 	fn pay(v: u32, ) -> Weight;
+	fn deposit_escrow() -> Weight;
+	fn withdraw_escrow() -> Weight;
+	fn dispute() -> Weight;
+	fn settle(v: u32, ) -> Weight;
+	fn increase_deposit() -> Weight;
+	fn resolve_dispute() -> Weight;
 }
 
 /// Weights for pallet_cacher using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	// Storage: Cacher Cachers (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher CacherDeposits (r:0 w:1)
 	fn register() -> Weight {
-		Weight::from_parts(36_000_000, 0)
-			.saturating_add(T::DbWeight::get().reads(1 as u64))
-			.saturating_add(T::DbWeight::get().writes(1 as u64))
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
 	// Storage: Cacher Cachers (r:1 w:1)
 	fn update() -> Weight {
@@ -70,28 +59,96 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1 as u64))
 	}
 	// Storage: Cacher Cachers (r:1 w:1)
+	// Storage: Cacher Reputations (r:1 w:0)
+	// Storage: Cacher CacherDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
 	fn logout() -> Weight {
-		Weight::from_parts(37_000_000, 0)
-			.saturating_add(T::DbWeight::get().reads(1 as u64))
-			.saturating_add(T::DbWeight::get().writes(1 as u64))
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
 	}
-	// Storage: System Account (r:1 w:0)
+	// Storage: Cacher PendingBills (r:1 w:1)
+	// Storage: Cacher DisputedBills (r:1 w:0)
+	// Storage: Cacher ClosedBills (r:1 w:0)
+	// Storage: Cacher Cachers (r:1 w:0)
+	// Storage: Cacher CacherDeposits (r:1 w:0)
+	// Storage: FileBank File (r:1 w:0)
+	// Storage: Cacher Reputations (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
 	fn pay(v: u32, ) -> Weight {
 		Weight::from_parts(43_773_000, 0)
-			// Standard Error: 171_000
-			.saturating_add(Weight::from_parts(26_133_000, 0).saturating_mul(v as u64))
+			.saturating_add(Weight::from_parts(92_410_000, 0).saturating_mul(v as u64))
+			.saturating_add(T::DbWeight::get().reads((8 as u64).saturating_mul(v as u64)))
+			.saturating_add(T::DbWeight::get().writes((3 as u64).saturating_mul(v as u64)))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
+	fn deposit_escrow() -> Weight {
+		Weight::from_parts(38_000_000, 0)
 			.saturating_add(T::DbWeight::get().reads(2 as u64))
-			.saturating_add(T::DbWeight::get().writes(1 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
+	fn withdraw_escrow() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(2 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Cacher PendingBills (r:1 w:1)
+	// Storage: Cacher OpenDisputes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher Reputations (r:1 w:1)
+	// Storage: Cacher DisputedBills (r:0 w:1)
+	fn dispute() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(5 as u64))
+	}
+	// Storage: Cacher PendingBills (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Cacher Reputations (r:1 w:1)
+	// Storage: Cacher ServedFragments (r:0 w:1)
+	// Storage: Cacher ClosedBills (r:0 w:1)
+	fn settle(v: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(58_000_000, 0).saturating_mul(v as u64))
+			.saturating_add(T::DbWeight::get().reads((5 as u64).saturating_mul(v as u64)))
+			.saturating_add(T::DbWeight::get().writes((7 as u64).saturating_mul(v as u64)))
+	}
+	// Storage: Cacher Cachers (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher CacherDeposits (r:1 w:1)
+	fn increase_deposit() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Cacher DisputedBills (r:1 w:1)
+	// Storage: Cacher OpenDisputes (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
+	// Storage: System Account (r:4 w:4)
+	// Storage: Cacher Reputations (r:1 w:1)
+	// Storage: Cacher CacherDeposits (r:1 w:1)
+	// Storage: Cacher ServedFragments (r:0 w:1)
+	// Storage: Cacher ClosedBills (r:0 w:1)
+	fn resolve_dispute() -> Weight {
+		Weight::from_parts(85_000_000, 0)
+			.saturating_add(T::DbWeight::get().reads(9 as u64))
+			.saturating_add(T::DbWeight::get().writes(11 as u64))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	// Storage: Cacher Cachers (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher CacherDeposits (r:0 w:1)
 	fn register() -> Weight {
-		Weight::from_parts(36_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(1 as u64))
-			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+		Weight::from_parts(52_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
 	// Storage: Cacher Cachers (r:1 w:1)
 	fn update() -> Weight {
@@ -100,17 +157,83 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1 as u64))
 	}
 	// Storage: Cacher Cachers (r:1 w:1)
+	// Storage: Cacher Reputations (r:1 w:0)
+	// Storage: Cacher CacherDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
 	fn logout() -> Weight {
-		Weight::from_parts(37_000_000, 0)
-			.saturating_add(RocksDbWeight::get().reads(1 as u64))
-			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+		Weight::from_parts(55_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
 	}
-	// Storage: System Account (r:1 w:0)
+	// Storage: Cacher PendingBills (r:1 w:1)
+	// Storage: Cacher DisputedBills (r:1 w:0)
+	// Storage: Cacher ClosedBills (r:1 w:0)
+	// Storage: Cacher Cachers (r:1 w:0)
+	// Storage: Cacher CacherDeposits (r:1 w:0)
+	// Storage: FileBank File (r:1 w:0)
+	// Storage: Cacher Reputations (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
 	fn pay(v: u32, ) -> Weight {
 		Weight::from_parts(43_773_000, 0)
-			// Standard Error: 171_000
-			.saturating_add(Weight::from_parts(26_133_000, 0).saturating_mul(v as u64))
+			.saturating_add(Weight::from_parts(92_410_000, 0).saturating_mul(v as u64))
+			.saturating_add(RocksDbWeight::get().reads((8 as u64).saturating_mul(v as u64)))
+			.saturating_add(RocksDbWeight::get().writes((3 as u64).saturating_mul(v as u64)))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
+	fn deposit_escrow() -> Weight {
+		Weight::from_parts(38_000_000, 0)
 			.saturating_add(RocksDbWeight::get().reads(2 as u64))
-			.saturating_add(RocksDbWeight::get().writes(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
+	fn withdraw_escrow() -> Weight {
+		Weight::from_parts(38_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(2 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Cacher PendingBills (r:1 w:1)
+	// Storage: Cacher OpenDisputes (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher Reputations (r:1 w:1)
+	// Storage: Cacher DisputedBills (r:0 w:1)
+	fn dispute() -> Weight {
+		Weight::from_parts(48_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(5 as u64))
+	}
+	// Storage: Cacher PendingBills (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Cacher Reputations (r:1 w:1)
+	// Storage: Cacher ServedFragments (r:0 w:1)
+	// Storage: Cacher ClosedBills (r:0 w:1)
+	fn settle(v: u32, ) -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(Weight::from_parts(58_000_000, 0).saturating_mul(v as u64))
+			.saturating_add(RocksDbWeight::get().reads((5 as u64).saturating_mul(v as u64)))
+			.saturating_add(RocksDbWeight::get().writes((7 as u64).saturating_mul(v as u64)))
+	}
+	// Storage: Cacher Cachers (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Cacher CacherDeposits (r:1 w:1)
+	fn increase_deposit() -> Weight {
+		Weight::from_parts(40_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	// Storage: Cacher DisputedBills (r:1 w:1)
+	// Storage: Cacher OpenDisputes (r:1 w:1)
+	// Storage: Cacher Escrows (r:1 w:1)
+	// Storage: System Account (r:4 w:4)
+	// Storage: Cacher Reputations (r:1 w:1)
+	// Storage: Cacher CacherDeposits (r:1 w:1)
+	// Storage: Cacher ServedFragments (r:0 w:1)
+	// Storage: Cacher ClosedBills (r:0 w:1)
+	fn resolve_dispute() -> Weight {
+		Weight::from_parts(85_000_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(9 as u64))
+			.saturating_add(RocksDbWeight::get().writes(11 as u64))
 	}
 }
\ No newline at end of file
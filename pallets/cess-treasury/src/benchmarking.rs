@@ -1,6 +1,11 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+const SEED: u32 = 0;
 
 pub fn initialize_reward<T: Config>() {
     let era_reward: BalanceOf<T> = 365_000_000_000_000_000_000_000u128.try_into().map_err(|_| "tryinto error!").expect("tryinto error!");
@@ -22,3 +27,67 @@ pub fn initialize_reward<T: Config>() {
     CurrencyReward::<T>::put(currency_reward);
     ReserveReward::<T>::put(reserve_reward);
 }
+
+fn funds<T: Config>() -> BalanceOf<T> {
+    T::Currency::minimum_balance().saturating_mul(100u32.into())
+}
+
+fn fund_treasury<T: Config>(treasury: TreasuryAccount) -> AccountOf<T> {
+    let acc = treasury.account_id::<T>();
+    T::Currency::make_free_balance_be(&acc, funds::<T>().saturating_mul(10u32.into()));
+    acc
+}
+
+benchmarks! {
+    send_funds_to_pid {
+        let caller: AccountOf<T> = account("caller", 0, SEED);
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+        let pid = TreasuryAccount::Punish.account_id::<T>();
+        let before = T::Currency::free_balance(&pid);
+    }: _(RawOrigin::Signed(caller), funds::<T>())
+    verify {
+        assert_eq!(T::Currency::free_balance(&pid), before + funds::<T>());
+    }
+
+    send_funds_to_sid {
+        let caller: AccountOf<T> = account("caller", 0, SEED);
+        T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+        let sid = TreasuryAccount::Space.account_id::<T>();
+        let before = T::Currency::free_balance(&sid);
+    }: _(RawOrigin::Signed(caller), funds::<T>())
+    verify {
+        assert_eq!(T::Currency::free_balance(&sid), before + funds::<T>());
+    }
+
+    pid_burn_funds {
+        let pid = fund_treasury::<T>(TreasuryAccount::Punish);
+        let before = T::Currency::free_balance(&pid);
+    }: _(RawOrigin::Root, funds::<T>())
+    verify {
+        assert_eq!(T::Currency::free_balance(&pid), before - funds::<T>());
+    }
+
+    sid_burn_funds {
+        let sid = fund_treasury::<T>(TreasuryAccount::Space);
+        let before = T::Currency::free_balance(&sid);
+    }: _(RawOrigin::Root, funds::<T>())
+    verify {
+        assert_eq!(T::Currency::free_balance(&sid), before - funds::<T>());
+    }
+
+    pid_send_funds {
+        fund_treasury::<T>(TreasuryAccount::Punish);
+        let dest: AccountOf<T> = account("dest", 0, SEED);
+    }: _(RawOrigin::Root, dest.clone(), funds::<T>())
+    verify {
+        assert_eq!(T::Currency::free_balance(&dest), funds::<T>());
+    }
+
+    sid_send_funds {
+        fund_treasury::<T>(TreasuryAccount::Space);
+        let dest: AccountOf<T> = account("dest", 0, SEED);
+    }: _(RawOrigin::Root, dest.clone(), funds::<T>())
+    verify {
+        assert_eq!(T::Currency::free_balance(&dest), funds::<T>());
+    }
+}
@@ -1,3 +1,6 @@
 use super::*;
 
-pub(super) const REWARD_BASE_MUTI: Perbill = Perbill::from_percent(75);
\ No newline at end of file
+pub(super) const REWARD_BASE_MUTI: Perbill = Perbill::from_percent(75);
+
+/// The maximum number of eras an income statement covers.
+pub(super) const MAX_STATEMENT_ERAS: u32 = 1000;
@@ -4,13 +4,13 @@ use frame_support::{
 		Currency, ReservableCurrency, WithdrawReasons, Imbalance,
 		ExistenceRequirement::KeepAlive, OnUnbalanced,
 	},
-	dispatch::{DispatchResult}, PalletId, Blake2_128Concat, ensure,
-    pallet_prelude::{Weight, StorageValue, StorageMap, ValueQuery, Get, IsType},
+	dispatch::{DispatchResult}, PalletId, Blake2_128Concat, Twox64Concat, ensure,
+    pallet_prelude::{StorageValue, StorageMap, ValueQuery, Get, IsType},
 };
-// use sp_std::prelude::*;
+use sp_std::prelude::*;
 use sp_runtime::{
-    SaturatedConversion, Perbill,
-	traits::{CheckedAdd, CheckedSub, AccountIdConversion},
+    SaturatedConversion, Perbill, DispatchError, RuntimeDebug,
+	traits::{CheckedAdd, CheckedSub, AccountIdConversion, Saturating, Zero},
 };
 use frame_system::{
 	pallet_prelude::OriginFor,
@@ -21,9 +21,23 @@ use sp_staking::StakingInterface;
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarking;
 
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
 mod constants;
 use constants::*;
 
+mod types;
+pub use types::*;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+
 pub use pallet::*;
 
 type BalanceOf<T> =
@@ -64,11 +78,29 @@ pub mod pallet {
 
 		type ReserveRewardId: Get<PalletId>;
 
+		/// The account of the insurance fund compensating lost user data.
+		type InsuranceFundId: Get<PalletId>;
+
+		/// The share of miner punishments paid into the insurance fund.
+		#[pallet::constant]
+		type PunishInsuranceShare: Get<Perbill>;
+
+		/// The share of storage purchase revenue paid into the insurance fund.
+		#[pallet::constant]
+		type RevenueInsuranceShare: Get<Perbill>;
+
 		type BurnDestination: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
 		type OneDay: Get<BlockNumberFor<Self>>;
 
+		/// The number of eras whose flows are kept in the ledger.
+		#[pallet::constant]
+		type LedgerHistoryDepth: Get<u32>;
+
 		type Staking: StakingInterface;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
     }
 	
 	#[pallet::event]
@@ -77,6 +109,32 @@ pub mod pallet {
 		Deposit {
 			balance: BalanceOf<T>,
 		},
+		ReserveDeposit {
+			balance: BalanceOf<T>,
+		},
+		/// Funds paid into a treasury account.
+		Received {
+			treasury: TreasuryAccount,
+			from: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// Funds paid out of a treasury account.
+		Paid {
+			treasury: TreasuryAccount,
+			to: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+		/// Funds moved between treasury accounts.
+		Moved {
+			from: TreasuryAccount,
+			to: TreasuryAccount,
+			amount: BalanceOf<T>,
+		},
+		/// Funds burned from a treasury account.
+		Burned {
+			treasury: TreasuryAccount,
+			amount: BalanceOf<T>,
+		},
 	}
 
     #[pallet::error]
@@ -99,6 +157,28 @@ pub mod pallet {
 	#[pallet::getter(fn reserve_reward)]
 	pub(super) type ReserveReward<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+	/// The balance of the insurance fund available for compensation.
+	#[pallet::storage]
+	#[pallet::getter(fn insurance_fund)]
+	pub(super) type InsuranceFund<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The flows of each treasury account, per era, for the last `LedgerHistoryDepth` eras.
+	#[pallet::storage]
+	#[pallet::getter(fn era_ledger)]
+	pub(super) type EraLedger<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		u32,
+		Twox64Concat,
+		TreasuryAccount,
+		LedgerEntry<BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// The latest era recorded in [`EraLedger`].
+	#[pallet::storage]
+	pub(super) type LastLedgerEra<T: Config> = StorageValue<_, u32>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn round_reward)]
 	pub(super) type RoundReward<T: Config> = StorageMap<_, Blake2_128Concat, u32, (BalanceOf<T>, BalanceOf<T>), ValueQuery>;
@@ -106,8 +186,7 @@ pub mod pallet {
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::call_index(0)]
-		// FIX ME!
-		#[pallet::weight(Weight::zero())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::send_funds_to_pid())]
 		pub fn send_funds_to_pid(
 			origin: OriginFor<T>,
 			funds: BalanceOf<T>,
@@ -120,8 +199,7 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(1)]
-		// FIX ME!
-		#[pallet::weight(Weight::zero())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::send_funds_to_sid())]
 		pub fn send_funds_to_sid(
 			origin: OriginFor<T>,
 			funds: BalanceOf<T>,
@@ -134,8 +212,7 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(2)]
-		// FIX ME!
-		#[pallet::weight(Weight::zero())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::pid_burn_funds())]
 		pub fn pid_burn_funds(
 			origin: OriginFor<T>,
 			burn_amount: BalanceOf<T>,
@@ -148,16 +225,16 @@ pub mod pallet {
 
 			T::BurnDestination::on_unbalanced(credit);
 
-			if let Err(problem) = T::Currency::settle(&pid, debit, WithdrawReasons::TRANSFER, KeepAlive) {
-				drop(problem);
+			match T::Currency::settle(&pid, debit, WithdrawReasons::TRANSFER, KeepAlive) {
+				Ok(()) => Self::note_burn(TreasuryAccount::Punish, burn_amount),
+				Err(problem) => drop(problem),
 			}
 
 			Ok(())
 		}
 
 		#[pallet::call_index(3)]
-		// FIX ME!
-		#[pallet::weight(Weight::zero())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::sid_burn_funds())]
 		pub fn sid_burn_funds(
 			origin: OriginFor<T>,
 			burn_amount: BalanceOf<T>,
@@ -170,16 +247,16 @@ pub mod pallet {
 
 			T::BurnDestination::on_unbalanced(credit);
 
-			if let Err(problem) = T::Currency::settle(&sid, debit, WithdrawReasons::TRANSFER, KeepAlive) {
-				drop(problem);
+			match T::Currency::settle(&sid, debit, WithdrawReasons::TRANSFER, KeepAlive) {
+				Ok(()) => Self::note_burn(TreasuryAccount::Space, burn_amount),
+				Err(problem) => drop(problem),
 			}
 
 			Ok(())
 		}
 
 		#[pallet::call_index(4)]
-		// FIX ME!
-		#[pallet::weight(Weight::zero())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::pid_send_funds())]
 		pub fn pid_send_funds(
 			origin: OriginFor<T>,
 			acc: AccountOf<T>,
@@ -190,13 +267,13 @@ pub mod pallet {
 			let pid = T::PunishTreasuryId::get().into_account_truncating();
 
 			<T as pallet::Config>::Currency::transfer(&pid, &acc, funds, KeepAlive)?;
+			Self::note_payout(TreasuryAccount::Punish, &acc, funds);
 
 			Ok(())
 		}
 
 		#[pallet::call_index(5)]
-		// FIX ME!
-		#[pallet::weight(Weight::zero())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::sid_send_funds())]
 		pub fn sid_send_funds(
 			origin: OriginFor<T>,
 			acc: AccountOf<T>,
@@ -207,6 +284,7 @@ pub mod pallet {
 			let sid = T::SpaceTreasuryId::get().into_account_truncating();
 
 			<T as pallet::Config>::Currency::transfer(&sid, &acc, funds, KeepAlive)?;
+			Self::note_payout(TreasuryAccount::Space, &acc, funds);
 
 			Ok(())
 		}
@@ -254,27 +332,67 @@ impl<T: Config> Pallet<T> {
 	} 
 
 	pub fn send_to_pid(acc: AccountOf<T>, amount: BalanceOf<T>) -> DispatchResult {
-		let pid = T::PunishTreasuryId::get().into_account_truncating();
-		<T as pallet::Config>::Currency::transfer(&acc, &pid, amount, KeepAlive)
+		Self::send_to(acc, TreasuryAccount::Punish, amount)
 	}
 
 	pub fn send_to_sid(acc: AccountOf<T>, amount: BalanceOf<T>) -> DispatchResult {
-		let sid = T::SpaceTreasuryId::get().into_account_truncating();
-		<T as pallet::Config>::Currency::transfer(&acc, &sid, amount, KeepAlive)
+		Self::send_to(acc, TreasuryAccount::Space, amount)
+	}
+
+	fn send_to(acc: AccountOf<T>, treasury: TreasuryAccount, amount: BalanceOf<T>) -> DispatchResult {
+		<T as pallet::Config>::Currency::transfer(&acc, &treasury.account_id::<T>(), amount, KeepAlive)?;
+		Self::note_receipt(treasury, &acc, amount);
+		Ok(())
+	}
+
+	/// Send `amount` to `treasury`, after paying `share` of it into the insurance fund.
+	pub fn send_with_insurance(
+		acc: AccountOf<T>,
+		treasury: TreasuryAccount,
+		amount: BalanceOf<T>,
+		share: Perbill,
+	) -> DispatchResult {
+		let insurance = share.mul_floor(amount);
+		if !insurance.is_zero() {
+			let imbalance = T::Currency::withdraw(&acc, insurance, WithdrawReasons::TRANSFER, KeepAlive)?;
+			T::Currency::resolve_creating(&TreasuryAccount::Insurance.account_id::<T>(), imbalance);
+			<InsuranceFund<T>>::mutate(|v| *v = v.saturating_add(insurance));
+			Self::note_receipt(TreasuryAccount::Insurance, &acc, insurance);
+		}
+
+		Self::send_to(acc, treasury, amount.saturating_sub(insurance))
+	}
+
+	/// Pay up to `amount` from the insurance fund to `acc`, returning what was paid.
+	pub fn compensate(acc: &AccountOf<T>, amount: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+		let iid = TreasuryAccount::Insurance.account_id::<T>();
+		let spendable = T::Currency::free_balance(&iid).saturating_sub(T::Currency::minimum_balance());
+		let paid = amount.min(<InsuranceFund<T>>::get()).min(spendable);
+		if paid.is_zero() {
+			return Ok(paid);
+		}
+
+		<T as pallet::Config>::Currency::transfer(&iid, acc, paid, KeepAlive)?;
+		<InsuranceFund<T>>::mutate(|v| *v = v.saturating_sub(paid));
+		Self::note_payout(TreasuryAccount::Insurance, acc, paid);
+
+		Ok(paid)
 	}
 
 	pub fn send_to_rid(acc: AccountOf<T>, amount: BalanceOf<T>) -> DispatchResult {
-		let rid = T::ReserveRewardId::get().into_account_truncating();
 		<ReserveReward<T>>::mutate(|v| -> DispatchResult {
 			*v = v.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
 			Ok(())
 		})?;
-		<T as pallet::Config>::Currency::transfer(&acc, &rid, amount, KeepAlive)
+		Self::send_to(acc, TreasuryAccount::Reserve, amount)
 	}
 
 	pub fn reward_reserve(amount: BalanceOf<T>) -> DispatchResult {
-		let mrid = T::MinerRewardId::get().into_account_truncating();
-		Self::send_to_rid(mrid, amount)
+		<ReserveReward<T>>::mutate(|v| -> DispatchResult {
+			*v = v.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+			Ok(())
+		})?;
+		Self::move_funds(TreasuryAccount::MinerReward, TreasuryAccount::Reserve, amount)
 	}
 
 	pub fn sluice(amount: BalanceOf<T>) -> DispatchResult {
@@ -288,9 +406,91 @@ impl<T: Config> Pallet<T> {
 			Ok(())
 		})?;
 
-		let rid = T::ReserveRewardId::get().into_account_truncating();
-		let mrid = T::MinerRewardId::get().into_account_truncating();
-		<T as pallet::Config>::Currency::transfer(&rid, &mrid, amount, KeepAlive)
+		Self::move_funds(TreasuryAccount::Reserve, TreasuryAccount::MinerReward, amount)
+	}
+
+	fn move_funds(from: TreasuryAccount, to: TreasuryAccount, amount: BalanceOf<T>) -> DispatchResult {
+		<T as pallet::Config>::Currency::transfer(&from.account_id::<T>(), &to.account_id::<T>(), amount, KeepAlive)?;
+		Self::note_flow(from, |entry| entry.paid = entry.paid.saturating_add(amount));
+		Self::note_flow(to, |entry| entry.received = entry.received.saturating_add(amount));
+		Self::deposit_event(Event::Moved { from, to, amount });
+		Ok(())
+	}
+
+	fn note_flow(treasury: TreasuryAccount, f: impl FnOnce(&mut LedgerEntry<BalanceOf<T>>)) {
+		let era = T::Staking::current_era();
+		Self::prune_ledger(era);
+		<EraLedger<T>>::mutate(era, treasury, f);
+	}
+
+	/// Drop the ledger of the eras that fall out of the history depth once `era` is recorded.
+	fn prune_ledger(era: u32) {
+		let last = <LastLedgerEra<T>>::get();
+		if last.map_or(false, |last| era <= last) {
+			return;
+		}
+		<LastLedgerEra<T>>::put(era);
+
+		let depth = T::LedgerHistoryDepth::get();
+		let Some(expired) = era.checked_sub(depth) else { return };
+		// Only eras up to the last recorded one have entries, and the ones that fell out of the
+		// history depth then are already gone.
+		let (from, to) = match last {
+			Some(last) => ((last + 1).saturating_sub(depth), expired.min(last)),
+			None => (expired, expired),
+		};
+		for old_era in from..=to {
+			let _ = <EraLedger<T>>::clear_prefix(old_era, TreasuryAccount::ALL.len() as u32, None);
+		}
+	}
+
+	fn note_mint(treasury: TreasuryAccount, amount: BalanceOf<T>) {
+		Self::note_flow(treasury, |entry| entry.minted = entry.minted.saturating_add(amount));
+	}
+
+	fn note_receipt(treasury: TreasuryAccount, from: &AccountOf<T>, amount: BalanceOf<T>) {
+		Self::note_flow(treasury, |entry| entry.received = entry.received.saturating_add(amount));
+		Self::deposit_event(Event::Received { treasury, from: from.clone(), amount });
+	}
+
+	fn note_payout(treasury: TreasuryAccount, to: &AccountOf<T>, amount: BalanceOf<T>) {
+		Self::note_flow(treasury, |entry| entry.paid = entry.paid.saturating_add(amount));
+		Self::deposit_event(Event::Paid { treasury, to: to.clone(), amount });
+	}
+
+	fn note_burn(treasury: TreasuryAccount, amount: BalanceOf<T>) {
+		Self::note_flow(treasury, |entry| entry.burned = entry.burned.saturating_add(amount));
+		Self::deposit_event(Event::Burned { treasury, amount });
+	}
+
+	/// The flows of every treasury account from `from_era` to `to_era`, both included.
+	///
+	/// The range is cut to `MAX_STATEMENT_ERAS` eras. Eras older than `LedgerHistoryDepth` are
+	/// no longer recorded.
+	pub fn income_statement(from_era: u32, to_era: u32) -> IncomeStatement<BalanceOf<T>> {
+		let to_era = to_era.min(from_era.saturating_add(MAX_STATEMENT_ERAS - 1));
+		let mut totals: Vec<(TreasuryAccount, LedgerEntry<BalanceOf<T>>)> =
+			TreasuryAccount::ALL.iter().map(|treasury| (*treasury, Default::default())).collect();
+		let mut eras = Vec::new();
+
+		for era in from_era..=to_era {
+			let entries: Vec<_> = TreasuryAccount::ALL
+				.iter()
+				.filter(|treasury| <EraLedger<T>>::contains_key(era, treasury))
+				.map(|treasury| (*treasury, <EraLedger<T>>::get(era, treasury)))
+				.collect();
+			if entries.is_empty() {
+				continue;
+			}
+			for (treasury, entry) in entries.iter() {
+				if let Some((_, total)) = totals.iter_mut().find(|(t, _)| t == treasury) {
+					total.merge(entry);
+				}
+			}
+			eras.push((era, entries));
+		}
+
+		IncomeStatement { from_era, to_era, eras, totals }
 	}
 }
 
@@ -362,8 +562,10 @@ impl<T: Config> RewardPool<AccountOf<T>, BalanceOf<T>> for Pallet<T> {
     }
 
 	fn send_reward_to_miner(beneficiary: AccountOf<T>, amount: BalanceOf<T>) -> DispatchResult {
-		let reward_acc = T::MinerRewardId::get().into_account_truncating();
-		<T as pallet::Config>::Currency::transfer(&reward_acc, &beneficiary, amount, KeepAlive)
+		let reward_acc = TreasuryAccount::MinerReward.account_id::<T>();
+		<T as pallet::Config>::Currency::transfer(&reward_acc, &beneficiary, amount, KeepAlive)?;
+		Self::note_payout(TreasuryAccount::MinerReward, &beneficiary, amount);
+		Ok(())
 	}
 }
 
@@ -374,11 +576,39 @@ pub trait TreasuryHandle<AccountId, Balance> {
 
 impl<T: Config> TreasuryHandle<AccountOf<T>, BalanceOf<T>> for Pallet<T> {
 	fn send_to_pid(acc: AccountOf<T>, amount: BalanceOf<T>) -> DispatchResult {
-		Self::send_to_pid(acc, amount)
+		Self::send_with_insurance(acc, TreasuryAccount::Punish, amount, T::PunishInsuranceShare::get())
 	}
 
 	fn send_to_sid(acc: AccountOf<T>, amount: BalanceOf<T>) -> DispatchResult {
-		Self::send_to_sid(acc, amount)
+		Self::send_with_insurance(acc, TreasuryAccount::Space, amount, T::RevenueInsuranceShare::get())
+	}
+}
+
+pub trait InsuranceHandle<AccountId> {
+	/// Pay up to `amount` to `acc` from the insurance fund, returning what was paid.
+	fn compensate(acc: &AccountId, amount: u128) -> Result<u128, DispatchError>;
+}
+
+impl<T: Config> InsuranceHandle<AccountOf<T>> for Pallet<T> {
+	fn compensate(acc: &AccountOf<T>, amount: u128) -> Result<u128, DispatchError> {
+		let amount: BalanceOf<T> = amount.saturated_into();
+		Ok(Self::compensate(acc, amount)?.saturated_into())
+	}
+}
+
+/// Puts newly minted tokens into the reward reserve, where `sluice` can release them to the
+/// miner reward pool later.
+pub struct ReserveRewardPool<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for ReserveRewardPool<T> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<T>) {
+		let numeric_amount = amount.peek();
+
+		let _ = T::Currency::resolve_creating(&T::ReserveRewardId::get().into_account_truncating(), amount);
+		<ReserveReward<T>>::mutate(|v| *v = v.saturating_add(numeric_amount));
+		Pallet::<T>::note_mint(TreasuryAccount::Reserve, numeric_amount);
+
+		Pallet::<T>::deposit_event(Event::ReserveDeposit { balance: numeric_amount });
 	}
 }
 
@@ -390,6 +620,7 @@ impl<T: Config> OnUnbalanced<NegativeImbalanceOf<T>> for Pallet<T> {
 		let _ = T::Currency::resolve_creating(&T::MinerRewardId::get().into_account_truncating(), amount);
 		// The total issuance amount will not exceed u128::Max, so there is no overflow risk
 		Self::add_miner_reward_pool(numeric_amount).unwrap();
+		Self::note_mint(TreasuryAccount::MinerReward, numeric_amount);
 
 		Self::deposit_event(Event::Deposit { balance: numeric_amount });
 	}
@@ -0,0 +1,121 @@
+//! Test utilities
+
+use super::*;
+use crate as pallet_cess_treasury;
+use frame_election_provider_support::NoElection;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64},
+};
+use frame_system::EnsureRoot;
+use sp_runtime::BuildStorage;
+
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Timestamp: pallet_timestamp,
+		Balances: pallet_balances,
+		Staking: pallet_cess_staking,
+		CessTreasury: pallet_cess_treasury,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
+	type AccountStore = System;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<5>;
+	type WeightInfo = ();
+}
+
+#[derive_impl(pallet_cess_staking::config_preludes::TestDefaultConfig)]
+impl pallet_cess_staking::Config for Test {
+	type Currency = Balances;
+	type UnixTime = Timestamp;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type EraPayout = ();
+	type ElectionProvider = NoElection<(AccountId, BlockNumber, Staking, ConstU32<100>)>;
+	type GenesisElectionProvider = Self::ElectionProvider;
+	type VoterList = pallet_cess_staking::UseNominatorsAndValidatorsMap<Self>;
+	type TargetList = pallet_cess_staking::UseValidatorsMap<Self>;
+
+	const ERAS_PER_YEAR: u64 = 8766;
+	const FIRST_YEAR_VALIDATOR_REWARDS: Balance = 238_500_000 * 1000;
+	const FIRST_YEAR_SMINER_REWARDS: Balance = 477_000_000 * 1000;
+	const REWARD_DECREASE_RATIO: Perbill = Perbill::from_perthousand(841);
+	const REWARD_DECREASE_YEARS: u64 = 30;
+	type SminerRewardPool = ();
+	type SminerPayout = ();
+	type SminerReserve = ();
+}
+
+pub(crate) const EXISTENTIAL_DEPOSIT: Balance = 1;
+pub(crate) const ALICE: AccountId = 1;
+pub(crate) const BOB: AccountId = 2;
+pub(crate) const LEDGER_DEPTH: u32 = 3;
+
+parameter_types! {
+	pub const MinerRewardId: PalletId = PalletId(*b"sminerpt");
+	pub const PunishTreasuryId: PalletId = PalletId(*b"punisdpt");
+	pub const SpaceTreasuryId: PalletId = PalletId(*b"spacedpt");
+	pub const ReserveRewardId: PalletId = PalletId(*b"sererdpt");
+	pub const InsuranceFundId: PalletId = PalletId(*b"insurdpt");
+	pub const PunishInsuranceShare: Perbill = Perbill::from_percent(20);
+	pub const RevenueInsuranceShare: Perbill = Perbill::from_percent(5);
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type MinerRewardId = MinerRewardId;
+	type PunishTreasuryId = PunishTreasuryId;
+	type SpaceTreasuryId = SpaceTreasuryId;
+	type ReserveRewardId = ReserveRewardId;
+	type InsuranceFundId = InsuranceFundId;
+	type PunishInsuranceShare = PunishInsuranceShare;
+	type RevenueInsuranceShare = RevenueInsuranceShare;
+	type BurnDestination = ();
+	type OneDay = ConstU64<14400>;
+	type Staking = Staking;
+	type LedgerHistoryDepth = ConstU32<LEDGER_DEPTH>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(ALICE, 1_000_000), (BOB, 1_000_000)], ..Default::default() }
+		.assimilate_storage(&mut storage)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// The balance of a treasury account.
+pub fn balance_of(treasury: TreasuryAccount) -> Balance {
+	Balances::free_balance(treasury.account_id::<Test>())
+}
+
+/// Make `era` the current era of staking.
+pub fn start_era(era: u32) {
+	pallet_cess_staking::CurrentEra::<Test>::put(era);
+}
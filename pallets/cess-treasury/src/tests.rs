@@ -0,0 +1,146 @@
+use super::*;
+use crate::mock::{
+	balance_of, new_test_ext, start_era, Balances, CessTreasury, RuntimeOrigin, Test, ALICE, BOB, LEDGER_DEPTH,
+};
+use frame_support::assert_ok;
+
+fn entry(minted: u128, received: u128, paid: u128, burned: u128) -> LedgerEntry<u128> {
+	LedgerEntry { minted, received, paid, burned }
+}
+
+#[test]
+fn punishments_pay_their_share_into_the_insurance_fund() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(<CessTreasury as TreasuryHandle<_, _>>::send_to_pid(ALICE, 1_000));
+
+		assert_eq!(Balances::free_balance(ALICE), 999_000);
+		assert_eq!(balance_of(TreasuryAccount::Insurance), 200);
+		assert_eq!(balance_of(TreasuryAccount::Punish), 800);
+		assert_eq!(CessTreasury::insurance_fund(), 200);
+	});
+}
+
+#[test]
+fn revenue_pays_its_share_into_the_insurance_fund() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(<CessTreasury as TreasuryHandle<_, _>>::send_to_sid(ALICE, 1_000));
+
+		assert_eq!(Balances::free_balance(ALICE), 999_000);
+		assert_eq!(balance_of(TreasuryAccount::Insurance), 50);
+		assert_eq!(balance_of(TreasuryAccount::Space), 950);
+		assert_eq!(CessTreasury::insurance_fund(), 50);
+	});
+}
+
+#[test]
+fn direct_payments_skip_the_insurance_fund() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CessTreasury::send_to_pid(ALICE, 1_000));
+
+		assert_eq!(balance_of(TreasuryAccount::Punish), 1_000);
+		assert_eq!(balance_of(TreasuryAccount::Insurance), 0);
+		assert_eq!(CessTreasury::insurance_fund(), 0);
+	});
+}
+
+#[test]
+fn compensation_is_paid_from_the_insurance_fund() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(<CessTreasury as TreasuryHandle<_, _>>::send_to_pid(ALICE, 10_000));
+		assert_eq!(CessTreasury::insurance_fund(), 2_000);
+
+		assert_eq!(<CessTreasury as InsuranceHandle<_>>::compensate(&BOB, 500), Ok(500));
+		assert_eq!(Balances::free_balance(BOB), 1_000_500);
+		assert_eq!(balance_of(TreasuryAccount::Insurance), 1_500);
+		assert_eq!(CessTreasury::insurance_fund(), 1_500);
+	});
+}
+
+#[test]
+fn compensation_is_capped_by_the_insurance_fund() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(<CessTreasury as TreasuryHandle<_, _>>::send_to_pid(ALICE, 1_000));
+
+		// The fund account is kept alive.
+		assert_eq!(<CessTreasury as InsuranceHandle<_>>::compensate(&BOB, 500), Ok(199));
+		assert_eq!(Balances::free_balance(BOB), 1_000_199);
+		assert_eq!(balance_of(TreasuryAccount::Insurance), 1);
+		assert_eq!(CessTreasury::insurance_fund(), 1);
+	});
+}
+
+#[test]
+fn compensation_from_an_empty_fund_pays_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(<CessTreasury as InsuranceHandle<_>>::compensate(&BOB, 500), Ok(0));
+		assert_eq!(Balances::free_balance(BOB), 1_000_000);
+
+		// Funds sent to the fund account directly are not part of the insurance fund.
+		assert_ok!(<Balances as Currency<_>>::transfer(
+			&ALICE,
+			&TreasuryAccount::Insurance.account_id::<Test>(),
+			1_000,
+			KeepAlive
+		));
+		assert_eq!(<CessTreasury as InsuranceHandle<_>>::compensate(&BOB, 500), Ok(0));
+		assert_eq!(Balances::free_balance(BOB), 1_000_000);
+	});
+}
+
+#[test]
+fn flows_are_recorded_per_era() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(<CessTreasury as TreasuryHandle<_, _>>::send_to_pid(ALICE, 1_000));
+		assert_ok!(<CessTreasury as InsuranceHandle<_>>::compensate(&BOB, 50));
+
+		start_era(1);
+		assert_ok!(CessTreasury::pid_burn_funds(RuntimeOrigin::root(), 300));
+		assert_ok!(CessTreasury::pid_send_funds(RuntimeOrigin::root(), BOB, 100));
+
+		assert_eq!(CessTreasury::era_ledger(0, TreasuryAccount::Punish), entry(0, 800, 0, 0));
+		assert_eq!(CessTreasury::era_ledger(0, TreasuryAccount::Insurance), entry(0, 200, 50, 0));
+		assert_eq!(CessTreasury::era_ledger(1, TreasuryAccount::Punish), entry(0, 0, 100, 300));
+		assert_eq!(balance_of(TreasuryAccount::Punish), 400);
+
+		let statement = CessTreasury::income_statement(0, 5);
+		assert_eq!((statement.from_era, statement.to_era), (0, 5));
+		assert_eq!(statement.eras.iter().map(|(era, _)| *era).collect::<Vec<_>>(), vec![0, 1]);
+		let total = |treasury| statement.totals.iter().find(|(t, _)| *t == treasury).unwrap().1.clone();
+		assert_eq!(total(TreasuryAccount::Punish), entry(0, 800, 100, 300));
+		assert_eq!(total(TreasuryAccount::Insurance), entry(0, 200, 50, 0));
+		assert_eq!(total(TreasuryAccount::Space), entry(0, 0, 0, 0));
+	});
+}
+
+#[test]
+fn ledger_keeps_the_history_depth() {
+	new_test_ext().execute_with(|| {
+		for era in 0..=LEDGER_DEPTH + 1 {
+			start_era(era);
+			assert_ok!(CessTreasury::send_to_sid(ALICE, 10));
+		}
+
+		let recorded = |era| EraLedger::<Test>::contains_key(era, TreasuryAccount::Space);
+		assert!(!recorded(0));
+		assert!(!recorded(1));
+		for era in 2..=LEDGER_DEPTH + 1 {
+			assert!(recorded(era));
+		}
+		assert_eq!(LastLedgerEra::<Test>::get(), Some(LEDGER_DEPTH + 1));
+	});
+}
+
+#[test]
+fn ledger_prunes_skipped_eras() {
+	new_test_ext().execute_with(|| {
+		for era in 0..LEDGER_DEPTH {
+			start_era(era);
+			assert_ok!(CessTreasury::send_to_sid(ALICE, 10));
+		}
+
+		start_era(10 * LEDGER_DEPTH);
+		assert_ok!(CessTreasury::send_to_sid(ALICE, 10));
+
+		assert_eq!(EraLedger::<Test>::iter_keys().map(|(era, _)| era).collect::<Vec<_>>(), vec![10 * LEDGER_DEPTH]);
+	});
+}
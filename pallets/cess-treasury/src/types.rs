@@ -0,0 +1,64 @@
+use super::*;
+
+/// The pallet accounts holding treasury funds.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum TreasuryAccount {
+	MinerReward,
+	Punish,
+	Space,
+	Reserve,
+	Insurance,
+}
+
+impl TreasuryAccount {
+	pub const ALL: [TreasuryAccount; 5] = [
+		TreasuryAccount::MinerReward,
+		TreasuryAccount::Punish,
+		TreasuryAccount::Space,
+		TreasuryAccount::Reserve,
+		TreasuryAccount::Insurance,
+	];
+
+	pub fn account_id<T: Config>(&self) -> AccountOf<T> {
+		match self {
+			TreasuryAccount::MinerReward => T::MinerRewardId::get().into_account_truncating(),
+			TreasuryAccount::Punish => T::PunishTreasuryId::get().into_account_truncating(),
+			TreasuryAccount::Space => T::SpaceTreasuryId::get().into_account_truncating(),
+			TreasuryAccount::Reserve => T::ReserveRewardId::get().into_account_truncating(),
+			TreasuryAccount::Insurance => T::InsuranceFundId::get().into_account_truncating(),
+		}
+	}
+}
+
+/// The funds moved in and out of a treasury account during an era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct LedgerEntry<Balance> {
+	/// Newly issued into the account.
+	pub minted: Balance,
+	/// Received from users, miners or other treasury accounts.
+	pub received: Balance,
+	/// Paid to users, miners or other treasury accounts.
+	pub paid: Balance,
+	/// Burned from the account.
+	pub burned: Balance,
+}
+
+impl<Balance: Saturating + Copy> LedgerEntry<Balance> {
+	pub fn merge(&mut self, other: &Self) {
+		self.minted = self.minted.saturating_add(other.minted);
+		self.received = self.received.saturating_add(other.received);
+		self.paid = self.paid.saturating_add(other.paid);
+		self.burned = self.burned.saturating_add(other.burned);
+	}
+}
+
+/// The treasury flows of a range of eras, per account.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct IncomeStatement<Balance> {
+	pub from_era: u32,
+	pub to_era: u32,
+	/// The flows of each era of the range, skipping eras without any.
+	pub eras: Vec<(u32, Vec<(TreasuryAccount, LedgerEntry<Balance>)>)>,
+	/// The flows of the whole range.
+	pub totals: Vec<(TreasuryAccount, LedgerEntry<Balance>)>,
+}
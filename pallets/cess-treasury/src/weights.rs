@@ -0,0 +1,125 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_cess_treasury
+//!
+//! Not benchmarked yet: the weights below are estimated from the storage accesses of each call.
+//! Regenerate it with `scripts/run/benchmark.sh cess-treasury` on reference hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_cess_treasury.
+pub trait WeightInfo {
+	fn send_funds_to_pid() -> Weight;
+	fn send_funds_to_sid() -> Weight;
+	fn pid_burn_funds() -> Weight;
+	fn sid_burn_funds() -> Weight;
+	fn pid_send_funds() -> Weight;
+	fn sid_send_funds() -> Weight;
+}
+
+/// Weights for pallet_cess_treasury using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: System Account (r:2 w:2)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: CessTreasury EraLedger (r:1 w:1)
+	fn send_funds_to_pid() -> Weight {
+		Weight::from_parts(52_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: CessTreasury EraLedger (r:1 w:1)
+	fn send_funds_to_sid() -> Weight {
+		Weight::from_parts(52_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: CessTreasury EraLedger (r:1 w:1)
+	fn pid_burn_funds() -> Weight {
+		Weight::from_parts(38_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: System Account (r:1 w:1)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: CessTreasury EraLedger (r:1 w:1)
+	fn sid_burn_funds() -> Weight {
+		Weight::from_parts(38_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: CessTreasury EraLedger (r:1 w:1)
+	fn pid_send_funds() -> Weight {
+		Weight::from_parts(50_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: Staking CurrentEra (r:1 w:0)
+	// Storage: CessTreasury EraLedger (r:1 w:1)
+	fn sid_send_funds() -> Weight {
+		Weight::from_parts(50_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn send_funds_to_pid() -> Weight {
+		Weight::from_parts(52_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn send_funds_to_sid() -> Weight {
+		Weight::from_parts(52_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn pid_burn_funds() -> Weight {
+		Weight::from_parts(38_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn sid_burn_funds() -> Weight {
+		Weight::from_parts(38_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn pid_send_funds() -> Weight {
+		Weight::from_parts(50_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn sid_send_funds() -> Weight {
+		Weight::from_parts(50_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+}
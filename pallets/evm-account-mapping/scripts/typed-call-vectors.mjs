@@ -0,0 +1,76 @@
+// Prints the EIP-712 vectors of `tests.rs` the way wallets compute them, with ethers.
+//
+//   npm install ethers@6 && node typed-call-vectors.mjs
+
+import { TypedDataEncoder } from "ethers";
+
+const who = "5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF";
+const one = "5C62Ck4UrFPiBtoCmeSrgF7x9yv9mn38446dhCpsi2mLHiFT"; // [1u8; 32]
+const two = "5C7LYpP2ZH3tpKbvVvwiVe54AapxErdPBbvkYhe6y9ZBkqWt"; // [2u8; 32]
+const dollars = 100_000_000_000n;
+const fileHash = "a".repeat(64);
+
+const schema = (fields) => fields.map(([name, type]) => ({ name, type }));
+const transfer = schema([["who", "string"], ["dest", "string"], ["value", "uint256"], ["nonce", "uint64"]]);
+
+const vectors = [
+	["SubstrateTransferAllowDeath", transfer, { who, dest: one, value: dollars, nonce: 0 }],
+	["SubstrateTransferKeepAlive", transfer, { who, dest: one, value: dollars, nonce: 1 }],
+	[
+		"SubstrateUploadDeclaration",
+		schema([
+			["who", "string"],
+			["owner", "string"],
+			["fileHash", "string"],
+			["fileName", "string"],
+			["territoryName", "string"],
+			["fileSize", "uint128"],
+			["dealInfo", "bytes"],
+			["nonce", "uint64"],
+		]),
+		{
+			who,
+			owner: one,
+			fileHash,
+			fileName: "cat.jpg",
+			territoryName: "photos",
+			fileSize: 1_048_576,
+			dealInfo: "0x0000",
+			nonce: 3,
+		},
+	],
+	[
+		"SubstrateDeleteFile",
+		schema([["who", "string"], ["owner", "string"], ["fileHash", "string"], ["nonce", "uint64"]]),
+		{ who, owner: one, fileHash, nonce: 4 },
+	],
+	[
+		"SubstrateMintTerritory",
+		schema([["who", "string"], ["territoryName", "string"], ["gibCount", "uint32"], ["days", "uint32"], ["nonce", "uint64"]]),
+		{ who, territoryName: "photos", gibCount: 10, days: 30, nonce: 5 },
+	],
+	[
+		"SubstrateExpandTerritory",
+		schema([["who", "string"], ["territoryName", "string"], ["gibCount", "uint32"], ["nonce", "uint64"]]),
+		{ who, territoryName: "photos", gibCount: 5, nonce: 6 },
+	],
+	[
+		"SubstrateRenewTerritory",
+		schema([["who", "string"], ["territoryName", "string"], ["days", "uint32"], ["nonce", "uint64"]]),
+		{ who, territoryName: "photos", days: 60, nonce: 7 },
+	],
+	[
+		"SubstrateAuthorize",
+		schema([["who", "string"], ["operator", "string"], ["nonce", "uint64"]]),
+		{ who, operator: two, nonce: 8 },
+	],
+];
+
+for (const [name, fields, value] of vectors) {
+	console.log(name, TypedDataEncoder.hashStruct(name, { [name]: fields }, value));
+}
+
+// The digest signed for the first transfer under the domain of the mock runtime.
+const domain = { name: "Substrate", version: "1", chainId: 0, verifyingContract: "0x" + "00".repeat(20) };
+const [name, fields, value] = vectors[0];
+console.log("digest", TypedDataEncoder.hash(domain, { [name]: fields }, value));
@@ -34,6 +34,14 @@ fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
 	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
 }
 
+/// A distinct call name of the longest allowed length.
+fn sponsored_call_name<T: Config>(index: u32) -> CallNameOf<T> {
+	let mut name = vec![b'c'; T::MaxCallNameLen::get() as usize];
+	let index = index.to_le_bytes();
+	name[..index.len()].copy_from_slice(&index);
+	(BoundedVec::truncate_from(name.clone()), BoundedVec::truncate_from(name))
+}
+
 #[benchmarks( where PaymentBalanceOf<T>: FixedPointOperand, <T as frame_system::Config>::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>, T: frame_system::Config<AccountId = sp_runtime::AccountId32>,)]
 mod benchmarks {
 	use super::*;
@@ -59,5 +67,126 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn batch_meta_call(c: Linear<1, { T::MaxBatchCalls::get() }>) -> Result<(), BenchmarkError> {
+		let account =
+			T::AccountId::from_ss58check("5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF")
+				.unwrap();
+		let call_data = hex::decode("00071448656c6c6f").expect("Valid"); // system.remarkWithEvent("Hello")
+		let call = <T as frame_system::Config>::RuntimeCall::decode(&mut TrailingZeroInput::new(
+			&call_data,
+		))
+		.expect("Valid");
+		let call: <T as Config>::RuntimeCall = call.into();
+		let calls: BoundedVec<<T as Config>::RuntimeCall, T::MaxBatchCalls> =
+			vec![call; c as usize].try_into().expect("Within bound");
+		let nonce: u64 = 0;
+		// Signatures are checked by `validate_unsigned`, not by the call itself.
+		let signature: [u8; 65] = [0; 65];
+
+		// The worst case is a sponsored batch, which reads the sponsor's budget and allowlist.
+		let sponsor: T::AccountId = account("sponsor", 0, 0);
+		T::Currency::make_free_balance_be(&sponsor, BalanceOf::<T>::max_value() / 2u32.into());
+		Sponsors::<T>::insert(
+			&sponsor,
+			SponsorInfo { limit: BalanceOf::<T>::max_value() / 2u32.into(), spent: 0u32.into() },
+		);
+		let metadata = calls[0].get_call_metadata();
+		let call_name: CallNameOf<T> = (
+			metadata.pallet_name.as_bytes().to_vec().try_into().expect("Within bound"),
+			metadata.function_name.as_bytes().to_vec().try_into().expect("Within bound"),
+		);
+		SponsoredCalls::<T>::insert(&sponsor, BoundedVec::truncate_from(vec![call_name]));
+		let sponsorship = Sponsorship { sponsor: sponsor.clone(), signature };
+
+		#[extrinsic_call]
+		_(RawOrigin::None, account, calls, nonce, signature, None, Some(sponsorship));
+
+		assert!(Sponsors::<T>::get(&sponsor).expect("Still sponsoring").spent > 0u32.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_sponsor() -> Result<(), BenchmarkError> {
+		let sponsor: T::AccountId = whitelisted_caller();
+		let limit = BalanceOf::<T>::max_value() / 2u32.into();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sponsor.clone()), limit);
+
+		assert_last_event::<T>(Event::SponsorSet { sponsor, limit }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_sponsor() -> Result<(), BenchmarkError> {
+		let sponsor: T::AccountId = whitelisted_caller();
+		Sponsors::<T>::insert(&sponsor, SponsorInfo::default());
+		let calls = (0..T::MaxSponsoredCalls::get()).map(sponsored_call_name::<T>).collect::<Vec<_>>();
+		SponsoredCalls::<T>::insert(&sponsor, BoundedVec::truncate_from(calls));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sponsor.clone()));
+
+		assert_last_event::<T>(Event::SponsorRemoved { sponsor }.into());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_sponsored_calls(n: Linear<1, { T::MaxSponsoredCalls::get() }>) -> Result<(), BenchmarkError> {
+		let sponsor: T::AccountId = whitelisted_caller();
+		Sponsors::<T>::insert(&sponsor, SponsorInfo::default());
+		let calls = (0..n).map(sponsored_call_name::<T>).collect::<Vec<_>>();
+		let calls: BoundedVec<CallNameOf<T>, T::MaxSponsoredCalls> =
+			calls.try_into().expect("Within bound");
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(sponsor.clone()), calls, true);
+
+		assert_eq!(SponsoredCalls::<T>::get(&sponsor).len() as u32, n);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn bind() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let key_type = sp_core::crypto::KeyTypeId(*b"evmb");
+		let public = sp_io::crypto::ecdsa_generate(key_type, None);
+		let message_hash = ThisPallet::<T>::eip712_binding_hash(caller.clone(), 0);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(key_type, &public, &message_hash)
+			.ok_or(BenchmarkError::Stop("Can't sign the binding"))?;
+		let evm_public_key = sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &message_hash)
+			.map_err(|_| BenchmarkError::Stop("Can't recover the binding key"))?;
+		let evm_address = sp_core::H160::from_slice(&sp_io::hashing::keccak_256(&evm_public_key)[12..]);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), evm_address, signature.0);
+
+		assert!(Bindings::<T>::contains_key(&caller));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn unbind() -> Result<(), BenchmarkError> {
+		let caller: T::AccountId = whitelisted_caller();
+		let evm_address = sp_core::H160::repeat_byte(1);
+		let mapped_account: T::AccountId = account("mapped", 0, 0);
+		EvmBindings::<T>::insert(evm_address, &caller);
+		MappedAccounts::<T>::insert(&mapped_account, &caller);
+		Bindings::<T>::insert(
+			&caller,
+			BindingInfo { evm_address, mapped_account, bound_at: frame_system::Pallet::<T>::block_number() },
+		);
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number() + T::MinBindingPeriod::get(),
+		);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert_last_event::<T>(Event::Unbound { who: caller, evm_address }.into());
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(ThisPallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
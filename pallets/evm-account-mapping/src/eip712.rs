@@ -22,6 +22,8 @@ use ethabi::{
 	ethereum_types::{Address, U256},
 	token::Token,
 };
+use crate::encode::{eip712::hash_struct, StructMember};
+use sp_core::crypto::{AccountId32, Ss58AddressFormat, Ss58Codec};
 use sp_io::hashing::keccak_256;
 use sp_std::{vec, vec::Vec};
 
@@ -103,3 +105,139 @@ impl EIP712Domain {
 		keccak_256(&abi_encode(&tokens))
 	}
 }
+
+/// A value of a typed call, rendered the way wallets display it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypedValue {
+	/// An account, shown as its SS58 address.
+	Account(AccountId32),
+	/// UTF-8 text, or the hex string CESS uses as file hash.
+	String(Vec<u8>),
+	/// Opaque bytes, e.g. SCALE encoded data with no readable form.
+	Bytes(Vec<u8>),
+	Number(U256),
+}
+
+/// A call signed as a typed EIP-712 struct rather than as opaque call data.
+///
+/// The struct always starts with the `string who` signing the call and ends with its
+/// `uint64 nonce`, with the call's own fields in between.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypedCall {
+	/// The encoded type of the struct, e.g. `SubstrateTransferKeepAlive(string who,...,uint64 nonce)`.
+	pub encoded_type: &'static str,
+	/// The call's own fields, in the order of `encoded_type`.
+	pub fields: Vec<TypedValue>,
+}
+
+impl TypedCall {
+	/// `hashStruct` of the call signed by `who` under `nonce`.
+	pub fn hash_struct(&self, who: &AccountId32, nonce: u64, ss58_prefix: u16) -> [u8; 32] {
+		let ss58 = |account: &AccountId32| {
+			account.to_ss58check_with_version(Ss58AddressFormat::custom(ss58_prefix)).into_bytes()
+		};
+		let who = ss58(who);
+		// Accounts are signed as the SS58 address the wallet shows.
+		let addresses = self
+			.fields
+			.iter()
+			.map(|field| match field {
+				TypedValue::Account(account) => ss58(account),
+				_ => Vec::new(),
+			})
+			.collect::<Vec<_>>();
+
+		let mut members = vec![StructMember::String(&who)];
+		members.extend(self.fields.iter().zip(addresses.iter()).map(|(field, address)| match field {
+			TypedValue::Account(_) => StructMember::String(address),
+			TypedValue::String(value) => StructMember::String(value),
+			TypedValue::Bytes(value) => StructMember::Bytes(value),
+			TypedValue::Number(value) => StructMember::Number(*value),
+		}));
+		members.push(StructMember::Number(nonce.into()));
+		hash_struct(self.encoded_type, &members)
+	}
+
+	/// `balances.transfer_allow_death`.
+	pub fn transfer_allow_death(dest: AccountId32, value: U256) -> Self {
+		Self {
+			encoded_type: "SubstrateTransferAllowDeath(string who,string dest,uint256 value,uint64 nonce)",
+			fields: vec![TypedValue::Account(dest), TypedValue::Number(value)],
+		}
+	}
+
+	/// `balances.transfer_keep_alive`, a schema of its own so that a signature for a transfer
+	/// that keeps the sender alive can't be replayed as one that may reap it.
+	pub fn transfer_keep_alive(dest: AccountId32, value: U256) -> Self {
+		Self {
+			encoded_type: "SubstrateTransferKeepAlive(string who,string dest,uint256 value,uint64 nonce)",
+			fields: vec![TypedValue::Account(dest), TypedValue::Number(value)],
+		}
+	}
+
+	/// `fileBank.upload_declaration`, whose segments are only shown as SCALE encoded `dealInfo`.
+	pub fn upload_declaration(
+		owner: AccountId32,
+		file_hash: Vec<u8>,
+		file_name: Vec<u8>,
+		territory_name: Vec<u8>,
+		file_size: u128,
+		deal_info: Vec<u8>,
+	) -> Self {
+		Self {
+			encoded_type: "SubstrateUploadDeclaration(string who,string owner,string fileHash,string fileName,string territoryName,uint128 fileSize,bytes dealInfo,uint64 nonce)",
+			fields: vec![
+				TypedValue::Account(owner),
+				TypedValue::String(file_hash),
+				TypedValue::String(file_name),
+				TypedValue::String(territory_name),
+				TypedValue::Number(file_size.into()),
+				TypedValue::Bytes(deal_info),
+			],
+		}
+	}
+
+	/// `fileBank.delete_file`.
+	pub fn delete_file(owner: AccountId32, file_hash: Vec<u8>) -> Self {
+		Self {
+			encoded_type: "SubstrateDeleteFile(string who,string owner,string fileHash,uint64 nonce)",
+			fields: vec![TypedValue::Account(owner), TypedValue::String(file_hash)],
+		}
+	}
+
+	/// `storageHandler.mint_territory`.
+	pub fn mint_territory(territory_name: Vec<u8>, gib_count: u32, days: u32) -> Self {
+		Self {
+			encoded_type: "SubstrateMintTerritory(string who,string territoryName,uint32 gibCount,uint32 days,uint64 nonce)",
+			fields: vec![
+				TypedValue::String(territory_name),
+				TypedValue::Number(gib_count.into()),
+				TypedValue::Number(days.into()),
+			],
+		}
+	}
+
+	/// `storageHandler.expanding_territory`.
+	pub fn expanding_territory(territory_name: Vec<u8>, gib_count: u32) -> Self {
+		Self {
+			encoded_type: "SubstrateExpandTerritory(string who,string territoryName,uint32 gibCount,uint64 nonce)",
+			fields: vec![TypedValue::String(territory_name), TypedValue::Number(gib_count.into())],
+		}
+	}
+
+	/// `storageHandler.renewal_territory`.
+	pub fn renewal_territory(territory_name: Vec<u8>, days: u32) -> Self {
+		Self {
+			encoded_type: "SubstrateRenewTerritory(string who,string territoryName,uint32 days,uint64 nonce)",
+			fields: vec![TypedValue::String(territory_name), TypedValue::Number(days.into())],
+		}
+	}
+
+	/// `oss.authorize`.
+	pub fn authorize(operator: AccountId32) -> Self {
+		Self {
+			encoded_type: "SubstrateAuthorize(string who,string operator,uint64 nonce)",
+			fields: vec![TypedValue::Account(operator)],
+		}
+	}
+}
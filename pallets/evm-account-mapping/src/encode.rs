@@ -83,3 +83,31 @@ pub mod abi {
 		res.join(&[][..])
 	}
 }
+
+/// A member of an EIP-712 struct of atomic and dynamic types.
+pub enum StructMember<'a> {
+	String(&'a [u8]),
+	Bytes(&'a [u8]),
+	Number(U256),
+}
+
+pub mod eip712 {
+	use super::StructMember;
+	use ethabi::{encode as abi_encode, token::Token};
+	use sp_io::hashing::keccak_256;
+	use sp_std::vec::Vec;
+
+	/// `hashStruct` of the struct described by `encoded_type`, e.g.
+	/// `SubstrateCall(string who,bytes callData,uint64 nonce)`, with its `members` in order.
+	pub fn hash_struct(encoded_type: &str, members: &[StructMember]) -> [u8; 32] {
+		let mut tokens = Vec::with_capacity(members.len() + 1);
+		tokens.push(Token::FixedBytes(keccak_256(encoded_type.as_bytes()).to_vec()));
+		// Dynamic values are encoded as their hash, atomic ones as a 32 bytes word.
+		tokens.extend(members.iter().map(|member| match member {
+			StructMember::String(value) | StructMember::Bytes(value) =>
+				Token::FixedBytes(keccak_256(value).to_vec()),
+			StructMember::Number(value) => Token::Uint(*value),
+		}));
+		keccak_256(&abi_encode(&tokens))
+	}
+}
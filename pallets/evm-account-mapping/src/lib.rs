@@ -19,7 +19,7 @@
 
 pub use pallet::*;
 
-mod eip712;
+pub mod eip712;
 mod encode;
 
 #[cfg(test)]
@@ -47,17 +47,20 @@ macro_rules! log {
 	};
 }
 
-use codec::Decode;
-use frame_support::{dispatch::{DispatchInfo, GetDispatchInfo, PostDispatchInfo, RawOrigin}, Parameter, traits::{
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{dispatch::{DispatchClass, DispatchInfo, GetDispatchInfo, Pays, PostDispatchInfo, RawOrigin}, Parameter, traits::{
 	tokens::{Fortitude, Preservation},
 	fungible::Inspect as InspectFungible,
-	Contains, Imbalance, OriginTrait,
+	Contains, GetCallMetadata, Imbalance, OriginTrait,
 	Currency,
-}, weights::Weight};
+}, weights::Weight, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+use encode::StructMember;
 use pallet_transaction_payment::OnChargeTransaction;
 use sp_core::crypto::AccountId32;
 use sp_io::hashing::blake2_256;
-use sp_runtime::{traits::Dispatchable, FixedPointOperand};
+use sp_core::H160;
+use sp_runtime::{traits::{Convert, Dispatchable}, FixedPointOperand};
 
 type PaymentOnChargeTransaction<T> = <T as pallet_transaction_payment::Config>::OnChargeTransaction;
 
@@ -73,6 +76,40 @@ pub type Nonce = u64;
 pub type AccountId32Bytes = [u8; 32];
 pub type Keccak256Signature = [u8; 32];
 
+/// The pallet and call name of a call a sponsor pays for.
+pub type CallNameOf<T> = (
+	BoundedVec<u8, <T as Config>::MaxCallNameLen>,
+	BoundedVec<u8, <T as Config>::MaxCallNameLen>,
+);
+
+/// The fee budget of a sponsor.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SponsorInfo<Balance> {
+	/// The most the sponsor pays in service and transaction fees.
+	pub limit: Balance,
+	/// What the sponsor has paid so far.
+	pub spent: Balance,
+}
+
+/// The EVM key bound to a substrate account.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct BindingInfo<AccountId, BlockNumber> {
+	/// The address of the EVM key.
+	pub evm_address: H160,
+	/// The account `AddressConverter` derives from the EVM key, whose meta calls act as the
+	/// bound account.
+	pub mapped_account: AccountId,
+	/// The block the binding was made at.
+	pub bound_at: BlockNumber,
+}
+
+/// A sponsor's EIP-712 approval to pay the fees of a batch.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub struct Sponsorship<AccountId> {
+	pub sponsor: AccountId,
+	pub signature: EIP712Signature,
+}
+
 pub enum Secp256K1PublicKeyForm {
 	Compressed,
 	Uncompressed,
@@ -84,6 +121,31 @@ pub trait AddressConversion<AccountId>: Sized {
 	fn try_convert(evm_public_key: &[u8]) -> Option<AccountId>;
 }
 
+/// Picks the typed EIP-712 schema wallets sign a call with, so they can show its fields.
+///
+/// Calls without a schema are signed as opaque call data.
+pub trait TypedCallConversion<RuntimeCall> {
+	fn try_convert(call: &RuntimeCall) -> Option<eip712::TypedCall>;
+}
+
+impl<RuntimeCall> TypedCallConversion<RuntimeCall> for () {
+	fn try_convert(_call: &RuntimeCall) -> Option<eip712::TypedCall> {
+		None
+	}
+}
+
+/// Whether an account owns storage, such as territories or files, which a binding would strand
+/// since the accounts of the bound EVM key resolve to the bound account instead.
+pub trait StorageHoldings<AccountId> {
+	fn holds_storage(who: &AccountId) -> bool;
+}
+
+impl<AccountId> StorageHoldings<AccountId> for () {
+	fn holds_storage(_who: &AccountId) -> bool {
+		false
+	}
+}
+
 pub struct SubstrateAddressConverter;
 impl AddressConversion<AccountId32> for SubstrateAddressConverter {
 	const SECP256K1_PUBLIC_KEY_FORM: Secp256K1PublicKeyForm = Secp256K1PublicKeyForm::Compressed;
@@ -133,6 +195,7 @@ pub mod pallet {
 				Info = DispatchInfo,
 				PostInfo = PostDispatchInfo,
 			> + GetDispatchInfo
+			+ GetCallMetadata
 			+ codec::Decode
 			+ codec::Encode
 			+ scale_info::TypeInfo
@@ -143,6 +206,19 @@ pub mod pallet {
 
 		type AddressConverter: AddressConversion<Self::AccountId>;
 
+		/// The typed schemas `meta_call` may be signed with.
+		type TypedCallConverter: TypedCallConversion<<Self as Config>::RuntimeCall>;
+
+		/// The account the EVM maps an address to when it isn't bound.
+		type EvmAddressMapping: Convert<H160, Self::AccountId>;
+
+		/// The storage owned by the accounts an EVM key maps to, which keeps it from being bound.
+		type StorageHoldings: StorageHoldings<Self::AccountId>;
+
+		/// How long a binding lasts before it may be undone.
+		#[pallet::constant]
+		type MinBindingPeriod: Get<BlockNumberFor<Self>>;
+
 		#[pallet::constant]
 		type ServiceFee: Get<BalanceOf<Self>>;
 
@@ -162,6 +238,18 @@ pub mod pallet {
 		#[pallet::constant]
 		type EIP712VerifyingContractAddress: Get<EIP712VerifyingContractAddress>;
 
+		/// The maximum number of calls in a `batch_meta_call`.
+		#[pallet::constant]
+		type MaxBatchCalls: Get<u32>;
+
+		/// The maximum number of calls a sponsor may pay for.
+		#[pallet::constant]
+		type MaxSponsoredCalls: Get<u32>;
+
+		/// The maximum length of the pallet and call names in a sponsor's allowlist.
+		#[pallet::constant]
+		type MaxCallNameLen: Get<u32>;
+
 		/// Type representing the weight of this pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -185,6 +273,42 @@ pub mod pallet {
 			who: T::AccountId,
 			call_result: DispatchResultWithPostInfo,
 		},
+		/// Every call of a batch succeeded.
+		BatchCompleted {
+			who: T::AccountId,
+		},
+		/// A call of a batch failed, and the effects of the whole batch were reverted.
+		BatchInterrupted {
+			who: T::AccountId,
+			index: u32,
+			error: DispatchError,
+		},
+		/// A sponsor paid the fees of `who`'s batch.
+		Sponsored {
+			sponsor: T::AccountId,
+			who: T::AccountId,
+			fee: BalanceOf<T>,
+		},
+		SponsorSet {
+			sponsor: T::AccountId,
+			limit: BalanceOf<T>,
+		},
+		SponsorRemoved {
+			sponsor: T::AccountId,
+		},
+		SponsoredCallsUpdated {
+			sponsor: T::AccountId,
+			allowed: bool,
+		},
+		/// An EVM key was bound to `who`.
+		Bound {
+			who: T::AccountId,
+			evm_address: H160,
+		},
+		Unbound {
+			who: T::AccountId,
+			evm_address: H160,
+		},
 	}
 
 	// Errors inform users that something went wrong.
@@ -193,12 +317,64 @@ pub mod pallet {
 		Unexpected,
 		NonceError,
 		PaymentError,
+		/// A batch must hold at least one call.
+		EmptyBatch,
+		/// The account is not a sponsor.
+		NotSponsor,
+		/// The sponsor's fee budget can't cover the batch.
+		SponsorLimitExceeded,
+		/// The sponsor doesn't pay for one of the calls.
+		CallNotSponsored,
+		/// The sponsor's allowlist is full.
+		TooManySponsoredCalls,
+		/// The account or the EVM key is already bound.
+		AlreadyBound,
+		/// The account has no binding.
+		NotBound,
+		/// The binding isn't signed by the EVM key.
+		BadBindingSignature,
+		/// An account the EVM key maps to still holds funds, which binding would put out of reach.
+		MappedAccountNotEmpty,
+		/// An account the EVM key maps to owns territories or files, which binding would put out
+		/// of reach.
+		MappedAccountHoldsStorage,
+		/// The binding can't be undone before `MinBindingPeriod` has passed.
+		BindingLocked,
 	}
 
 	#[pallet::storage]
 	pub(crate) type AccountNonce<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
 
+	/// The fee budget of each sponsor.
+	#[pallet::storage]
+	pub type Sponsors<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, SponsorInfo<BalanceOf<T>>>;
+
+	/// The calls each sponsor pays for.
+	#[pallet::storage]
+	pub type SponsoredCalls<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<CallNameOf<T>, T::MaxSponsoredCalls>,
+		ValueQuery,
+	>;
+
+	/// The EVM key bound to each account.
+	#[pallet::storage]
+	pub type Bindings<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BindingInfo<T::AccountId, BlockNumberFor<T>>>;
+
+	/// The account each bound EVM address resolves to.
+	#[pallet::storage]
+	pub type EvmBindings<T: Config> = StorageMap<_, Blake2_128Concat, H160, T::AccountId>;
+
+	/// The account the meta calls of each bound EVM key act as.
+	#[pallet::storage]
+	pub type MappedAccounts<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, T::AccountId>;
+
 	#[pallet::validate_unsigned]
 	impl<T: Config> ValidateUnsigned for Pallet<T>
 	where
@@ -215,29 +391,492 @@ pub mod pallet {
 			_source: TransactionSource,
 			unsigned_call: &Self::Call,
 		) -> TransactionValidity {
-			// Only allow `meta_call`
-			let Call::meta_call {
-				who,
-				call,
-				nonce,
-				signature,
-				tip,
-			} = unsigned_call
-			else {
-				return Err(InvalidTransaction::Call.into())
+			match unsigned_call {
+				Call::meta_call { who, call, nonce, signature, tip } => {
+					// Check the signature and get the public key.
+					// Wallets sign the typed schema of the call if it has one, or its call data.
+					let typed_signed = Self::eip712_typed_call_hash(who.clone(), call, *nonce)
+						.is_some_and(|message_hash| Self::ensure_signer(who, signature, &message_hash).is_ok());
+					if !typed_signed {
+						let call_data = <T as Config>::RuntimeCall::encode(call);
+						let message_hash = Self::eip712_message_hash(who.clone(), &call_data, *nonce);
+						Self::ensure_signer(who, signature, &message_hash)?;
+					}
+
+					let tip = tip.unwrap_or(0u32.into());
+					let len = call.encoded_size();
+					let info = call.get_dispatch_info();
+					Self::validate_meta_transaction(who, None, nonce, len, &info, tip)
+				},
+				Call::batch_meta_call { who, calls, nonce, signature, tip, sponsorship } => {
+					if calls.is_empty() {
+						return Err(InvalidTransaction::Call.into())
+					}
+					let calls_data = calls.encode();
+					let message_hash = Self::eip712_batch_message_hash(who.clone(), &calls_data, *nonce);
+					Self::ensure_signer(who, signature, &message_hash)?;
+
+					// The sponsor co-signs the exact batch, nonce and tip it pays for.
+					let tip = tip.unwrap_or(0u32.into());
+					if let Some(Sponsorship { sponsor, signature }) = sponsorship {
+						let sponsorship_hash = Self::eip712_sponsorship_hash(
+							sponsor.clone(),
+							who.clone(),
+							&calls_data,
+							*nonce,
+							tip,
+						);
+						Self::ensure_signer(sponsor, signature, &sponsorship_hash)?;
+						if !calls.iter().all(|call| Self::is_sponsored_call(sponsor, call)) {
+							return Err(InvalidTransaction::Call.into())
+						}
+					}
+
+					let len = calls.encoded_size();
+					let info = Self::batch_dispatch_info(calls);
+					let sponsor = sponsorship.as_ref().map(|sponsorship| &sponsorship.sponsor);
+					Self::validate_meta_transaction(who, sponsor, nonce, len, &info, tip)
+				},
+				// Only allow meta calls
+				_ => Err(InvalidTransaction::Call.into()),
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T>
+	where
+		PaymentBalanceOf<T>: FixedPointOperand,
+		BalanceOf<T>: FixedPointOperand,
+		<T as frame_system::Config>::RuntimeCall:
+			Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+		T: frame_system::Config<AccountId = sp_runtime::AccountId32>,
+	{
+		/// Meta-transaction from EVM compatible chains
+		#[pallet::call_index(0)]
+		#[pallet::weight({
+			let di = call.get_dispatch_info();
+			(
+				<T as pallet::Config>::WeightInfo::meta_call().saturating_add(di.total_weight()),
+				di.class
+			)
+		})]
+		pub fn meta_call(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			call: Box<<T as Config>::RuntimeCall>,
+			nonce: Nonce,
+			#[allow(unused_variables)] signature: EIP712Signature,
+			tip: Option<PaymentBalanceOf<T>>,
+		) -> DispatchResult {
+			// This is an unsigned transaction
+			ensure_none(origin)?;
+			// A bound EVM key acts as, and pays from, the account it is bound to.
+			let signer = who.clone();
+			let who = Self::canonical_account(&signer);
+
+			// We don't need to re-validate the signature here,
+			// because it already validated in `validate_unsigned` stage,
+			// and it should no way to skip.
+			// TODO: Confirm this.
+
+			// It is possible that an account passed `validate_unsigned` check,
+			// but for some reason, its balance isn't enough for the service fee.
+			use frame_support::traits::tokens::{WithdrawReasons, ExistenceRequirement};
+			// NOTE: it is possible that the account doesn't have enough fee, which is a vulnerable.
+			let withdrawn = T::Currency::withdraw(
+				&who,
+				T::ServiceFee::get(),
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive
+			).map_err(|_err| Error::<T>::PaymentError)?;
+			let withdrawn_fee = withdrawn.peek();
+			T::OnUnbalancedForServiceFee::on_unbalanced(withdrawn);
+			Self::deposit_event(Event::ServiceFeePaid {
+				who: who.clone(),
+				actual_fee: withdrawn_fee,
+				expected_fee: T::ServiceFee::get(),
+			});
+
+			// Bump the nonce
+			AccountNonce::<T>::try_mutate(&signer, |value| {
+				if *value != nonce {
+					return Err(Error::<T>::NonceError)
+				}
+				*value += 1;
+				Ok(())
+			})?;
+
+			// Call
+			let mut origin: T::RuntimeOrigin = RawOrigin::Signed(who.clone()).into();
+			origin.add_filter(T::CallFilter::contains);
+			let len = call.encoded_size();
+			let info = call.get_dispatch_info();
+			let tip = tip.unwrap_or(0u32.into());
+			let est_fee =
+				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, &info, tip);
+			// Add the service fee
+			let already_withdrawn =
+				<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::withdraw_fee(
+					&who,
+					&(*call).clone().into(),
+					&info,
+					est_fee,
+					tip,
+				)
+				.map_err(|_err| Error::<T>::PaymentError)?;
+
+			let call_result = call.dispatch(origin);
+			let post_info = match call_result {
+				Ok(post_info) => post_info,
+				Err(error_and_info) => error_and_info.post_info,
 			};
+			// Deposit the call's result
+			Self::deposit_event(Event::CallDone { who: who.clone(), call_result });
+
+			let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+				len as u32, &info, &post_info, tip,
+			);
+			// frame/transaction-payment/src/payment.rs
+			<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::correct_and_deposit_fee(
+				&who,
+				&info,
+				&post_info,
+				actual_fee,
+				tip,
+				already_withdrawn,
+			)
+			.map_err(|_err| Error::<T>::PaymentError)?;
+			Self::deposit_event(Event::TransactionFeePaid { who: who.clone(), actual_fee, tip });
+
+			Ok(())
+		}
 
-			// Check the signature and get the public key
-			let call_data = <T as Config>::RuntimeCall::encode(call);
-			let message_hash = Self::eip712_message_hash(who.clone(), &call_data, *nonce);
+		/// Meta-transaction dispatching a batch of calls signed under one nonce.
+		///
+		/// The calls apply all-or-nothing: if one fails, the effects of the whole batch are
+		/// reverted, while the nonce is still used and the fees are still paid. With a
+		/// `sponsorship`, the sponsor pays the fees, within its budget and allowlist.
+		#[pallet::call_index(1)]
+		#[pallet::weight({
+			let dispatch_weight = calls.iter().fold(Weight::zero(), |total, call| {
+				total.saturating_add(call.get_dispatch_info().total_weight())
+			});
+			(
+				<T as pallet::Config>::WeightInfo::batch_meta_call(calls.len() as u32)
+					.saturating_add(dispatch_weight),
+				DispatchClass::Normal
+			)
+		})]
+		pub fn batch_meta_call(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			calls: BoundedVec<<T as Config>::RuntimeCall, T::MaxBatchCalls>,
+			nonce: Nonce,
+			#[allow(unused_variables)] signature: EIP712Signature,
+			tip: Option<PaymentBalanceOf<T>>,
+			sponsorship: Option<Sponsorship<T::AccountId>>,
+		) -> DispatchResultWithPostInfo {
+			// This is an unsigned transaction, whose signatures were checked by `validate_unsigned`.
+			ensure_none(origin)?;
+			ensure!(!calls.is_empty(), Error::<T>::EmptyBatch);
+			use sp_runtime::{SaturatedConversion, Saturating};
+			let signer = who.clone();
+			let who = Self::canonical_account(&signer);
+
+			let sponsor = sponsorship.map(|sponsorship| sponsorship.sponsor);
+			if let Some(sponsor) = &sponsor {
+				ensure!(Sponsors::<T>::contains_key(sponsor), Error::<T>::NotSponsor);
+				ensure!(
+					calls.iter().all(|call| Self::is_sponsored_call(sponsor, call)),
+					Error::<T>::CallNotSponsored
+				);
+			}
+			let payer = sponsor.clone().unwrap_or_else(|| who.clone());
 
+			let len = calls.encoded_size();
+			let info = Self::batch_dispatch_info(&calls);
+			let tip = tip.unwrap_or(0u32.into());
+			let est_fee =
+				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, &info, tip);
+			if let Some(sponsor) = &sponsor {
+				let expected = T::ServiceFee::get().saturating_add(est_fee.saturated_into::<u128>().saturated_into());
+				Self::ensure_sponsor_budget(sponsor, expected)?;
+			}
+
+			use frame_support::traits::tokens::{WithdrawReasons, ExistenceRequirement};
+			let withdrawn = T::Currency::withdraw(
+				&payer,
+				T::ServiceFee::get(),
+				WithdrawReasons::FEE,
+				ExistenceRequirement::KeepAlive
+			).map_err(|_err| Error::<T>::PaymentError)?;
+			let service_fee = withdrawn.peek();
+			T::OnUnbalancedForServiceFee::on_unbalanced(withdrawn);
+			Self::deposit_event(Event::ServiceFeePaid {
+				who: payer.clone(),
+				actual_fee: service_fee,
+				expected_fee: T::ServiceFee::get(),
+			});
+
+			// Bump the nonce
+			AccountNonce::<T>::try_mutate(&signer, |value| {
+				if *value != nonce {
+					return Err(Error::<T>::NonceError)
+				}
+				*value += 1;
+				Ok(())
+			})?;
+
+			// `OnChargeTransaction` only needs a representative call of the batch.
+			let already_withdrawn =
+				<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::withdraw_fee(
+					&payer,
+					&calls[0].clone().into(),
+					&info,
+					est_fee,
+					tip,
+				)
+				.map_err(|_err| Error::<T>::PaymentError)?;
+
+			let calls_len = calls.len() as u32;
+			let mut actual_weight = Weight::zero();
+			let mut interrupted = None;
+			let _ = frame_support::storage::with_transaction(|| {
+				for (index, call) in calls.into_iter().enumerate() {
+					let mut origin: T::RuntimeOrigin = RawOrigin::Signed(who.clone()).into();
+					origin.add_filter(T::CallFilter::contains);
+					let call_info = call.get_dispatch_info();
+					match call.dispatch(origin) {
+						Ok(post_info) => {
+							actual_weight = actual_weight.saturating_add(post_info.calc_actual_weight(&call_info));
+						},
+						Err(error_and_info) => {
+							actual_weight = actual_weight
+								.saturating_add(error_and_info.post_info.calc_actual_weight(&call_info));
+							interrupted = Some((index as u32, error_and_info.error));
+							return sp_runtime::TransactionOutcome::Rollback(Ok::<(), DispatchError>(()))
+						},
+					}
+				}
+				sp_runtime::TransactionOutcome::Commit(Ok(()))
+			});
+			match interrupted {
+				Some((index, error)) =>
+					Self::deposit_event(Event::BatchInterrupted { who: who.clone(), index, error }),
+				None => Self::deposit_event(Event::BatchCompleted { who: who.clone() }),
+			}
+
+			let post_info = PostDispatchInfo { actual_weight: Some(actual_weight), pays_fee: Pays::Yes };
+			let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+				len as u32, &info, &post_info, tip,
+			);
+			<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::correct_and_deposit_fee(
+				&payer,
+				&info,
+				&post_info,
+				actual_fee,
+				tip,
+				already_withdrawn,
+			)
+			.map_err(|_err| Error::<T>::PaymentError)?;
+			Self::deposit_event(Event::TransactionFeePaid { who: payer.clone(), actual_fee, tip });
+
+			if let Some(sponsor) = sponsor {
+				let fee = service_fee.saturating_add(actual_fee.saturated_into::<u128>().saturated_into());
+				Sponsors::<T>::mutate(&sponsor, |info| {
+					if let Some(info) = info {
+						info.spent = info.spent.saturating_add(fee);
+					}
+				});
+				Self::deposit_event(Event::Sponsored { sponsor, who, fee });
+			}
+
+			Ok(Some(
+				<T as pallet::Config>::WeightInfo::batch_meta_call(calls_len).saturating_add(actual_weight),
+			)
+			.into())
+		}
+
+		/// Become a sponsor, or update the fee budget of one, keeping what it already paid.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_sponsor())]
+		pub fn set_sponsor(origin: OriginFor<T>, limit: BalanceOf<T>) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+
+			Sponsors::<T>::mutate(&sponsor, |info| {
+				info.get_or_insert_with(Default::default).limit = limit;
+			});
+			Self::deposit_event(Event::SponsorSet { sponsor, limit });
+
+			Ok(())
+		}
+
+		/// Stop sponsoring, dropping the budget and allowlist.
+		#[pallet::call_index(3)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::remove_sponsor())]
+		pub fn remove_sponsor(origin: OriginFor<T>) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(Sponsors::<T>::contains_key(&sponsor), Error::<T>::NotSponsor);
+
+			Sponsors::<T>::remove(&sponsor);
+			SponsoredCalls::<T>::remove(&sponsor);
+			Self::deposit_event(Event::SponsorRemoved { sponsor });
+
+			Ok(())
+		}
+
+		/// Add `calls` to, or remove them from, the calls the sponsor pays for.
+		#[pallet::call_index(4)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::set_sponsored_calls(calls.len() as u32))]
+		pub fn set_sponsored_calls(
+			origin: OriginFor<T>,
+			calls: BoundedVec<CallNameOf<T>, T::MaxSponsoredCalls>,
+			allowed: bool,
+		) -> DispatchResult {
+			let sponsor = ensure_signed(origin)?;
+			ensure!(Sponsors::<T>::contains_key(&sponsor), Error::<T>::NotSponsor);
+
+			SponsoredCalls::<T>::try_mutate(&sponsor, |list| -> DispatchResult {
+				for call in calls {
+					match (allowed, list.contains(&call)) {
+						(true, false) =>
+							list.try_push(call).map_err(|_| Error::<T>::TooManySponsoredCalls)?,
+						(false, true) => list.retain(|allowed_call| allowed_call != &call),
+						_ => {},
+					}
+				}
+				Ok(())
+			})?;
+			Self::deposit_event(Event::SponsoredCallsUpdated { sponsor, allowed });
+
+			Ok(())
+		}
+
+		/// Bind the EVM key of `evm_address` to the caller.
+		///
+		/// The key signs `SubstrateBinding(string who,uint64 nonce)` with the caller's address and
+		/// nonce. Until unbound, the EVM address and the meta calls of the key resolve to the
+		/// caller, so the accounts they map to on their own must hold no funds, territories or
+		/// files. The caller's nonce is raised to the one of the account the EVM maps the address
+		/// to, so Ethereum transactions the key signed before can't be replayed.
+		#[pallet::call_index(5)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::bind())]
+		pub fn bind(
+			origin: OriginFor<T>,
+			evm_address: H160,
+			signature: EIP712Signature,
+		) -> DispatchResult {
+			use sp_runtime::traits::Zero;
+			let who = ensure_signed(origin)?;
+			ensure!(
+				!Bindings::<T>::contains_key(&who) && !MappedAccounts::<T>::contains_key(&who),
+				Error::<T>::AlreadyBound
+			);
+
+			let nonce = AccountNonce::<T>::get(&who);
+			let message_hash = Self::eip712_binding_hash(who.clone(), nonce);
+			let (signer, mapped_account) = Self::recover_evm_key(&signature, &message_hash)
+				.ok_or(Error::<T>::BadBindingSignature)?;
+			ensure!(signer == evm_address, Error::<T>::BadBindingSignature);
+			ensure!(!EvmBindings::<T>::contains_key(evm_address), Error::<T>::AlreadyBound);
+			let evm_account = T::EvmAddressMapping::convert(evm_address);
+			for account in [&mapped_account, &evm_account] {
+				if account == &who {
+					continue
+				}
+				ensure!(
+					<T::Currency as InspectFungible<T::AccountId>>::total_balance(account).is_zero(),
+					Error::<T>::MappedAccountNotEmpty
+				);
+				ensure!(!T::StorageHoldings::holds_storage(account), Error::<T>::MappedAccountHoldsStorage);
+			}
+
+			Self::carry_nonce_over(&evm_account, &who);
+			AccountNonce::<T>::insert(&who, nonce + 1);
+			EvmBindings::<T>::insert(evm_address, &who);
+			MappedAccounts::<T>::insert(&mapped_account, &who);
+			Bindings::<T>::insert(
+				&who,
+				BindingInfo {
+					evm_address,
+					mapped_account,
+					bound_at: frame_system::Pallet::<T>::block_number(),
+				},
+			);
+			Self::deposit_event(Event::Bound { who, evm_address });
+
+			Ok(())
+		}
+
+		/// Undo the caller's binding, once `MinBindingPeriod` has passed since it was made.
+		///
+		/// The bound EVM key may undo it too, through a meta call acting as the caller. The nonce
+		/// of the account the EVM maps the address to again is raised to the caller's, so
+		/// Ethereum transactions the key signed while bound can't be replayed.
+		#[pallet::call_index(6)]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::unbind())]
+		pub fn unbind(origin: OriginFor<T>) -> DispatchResult {
+			use sp_runtime::Saturating;
+			let who = ensure_signed(origin)?;
+			let info = Bindings::<T>::get(&who).ok_or(Error::<T>::NotBound)?;
+			let unlocked_at = info.bound_at.saturating_add(T::MinBindingPeriod::get());
+			ensure!(frame_system::Pallet::<T>::block_number() >= unlocked_at, Error::<T>::BindingLocked);
+
+			Bindings::<T>::remove(&who);
+			EvmBindings::<T>::remove(info.evm_address);
+			MappedAccounts::<T>::remove(&info.mapped_account);
+			Self::carry_nonce_over(&who, &T::EvmAddressMapping::convert(info.evm_address));
+			Self::deposit_event(Event::Unbound { who, evm_address: info.evm_address });
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account the meta calls of `who` act as: the account its EVM key is bound to, if
+		/// any, or `who` itself.
+		pub fn canonical_account(who: &T::AccountId) -> T::AccountId {
+			MappedAccounts::<T>::get(who).unwrap_or_else(|| who.clone())
+		}
+
+		/// Raise the system nonce of `to` to the one of `from`, as the Ethereum transactions of an
+		/// EVM address move from one account to the other.
+		fn carry_nonce_over(from: &T::AccountId, to: &T::AccountId) {
+			let nonce = frame_system::Pallet::<T>::account_nonce(from);
+			if frame_system::Pallet::<T>::account_nonce(to) < nonce {
+				frame_system::Account::<T>::mutate(to, |account| account.nonce = nonce);
+			}
+		}
+
+		/// The account `evm_address` resolves to: the account it is bound to, if any, or the one
+		/// the EVM maps it to.
+		pub fn evm_account(evm_address: H160) -> T::AccountId {
+			EvmBindings::<T>::get(evm_address).unwrap_or_else(|| T::EvmAddressMapping::convert(evm_address))
+		}
+	}
+
+	impl<T: Config> Pallet<T>
+	where
+		PaymentBalanceOf<T>: FixedPointOperand,
+		BalanceOf<T>: FixedPointOperand,
+		<T as frame_system::Config>::RuntimeCall:
+			Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+		T: frame_system::Config<AccountId = sp_runtime::AccountId32>,
+	{
+		/// Check that `signature` over `message_hash` was made by the key behind `who`.
+		fn ensure_signer(
+			who: &T::AccountId,
+			signature: &EIP712Signature,
+			message_hash: &Keccak256Signature,
+		) -> Result<(), TransactionValidityError> {
 			let Ok(recovered_public_key) = (match <T as Config>::AddressConverter::SECP256K1_PUBLIC_KEY_FORM {
 				Secp256K1PublicKeyForm::Compressed => {
-					sp_io::crypto::secp256k1_ecdsa_recover_compressed(signature, &message_hash)
+					sp_io::crypto::secp256k1_ecdsa_recover_compressed(signature, message_hash)
 						.map(|i| i.to_vec())
 				},
 				Secp256K1PublicKeyForm::Uncompressed => {
-					sp_io::crypto::secp256k1_ecdsa_recover(signature, &message_hash)
+					sp_io::crypto::secp256k1_ecdsa_recover(signature, message_hash)
 						.map(|i| i.to_vec())
 				}
 			}) else {
@@ -253,6 +892,35 @@ pub mod pallet {
 				return Err(InvalidTransaction::BadSigner.into())
 			}
 
+			Ok(())
+		}
+
+		/// The address of the EVM key that made `signature`, and the account `AddressConverter`
+		/// derives from it.
+		fn recover_evm_key(
+			signature: &EIP712Signature,
+			message_hash: &Keccak256Signature,
+		) -> Option<(H160, T::AccountId)> {
+			let public_key = sp_io::crypto::secp256k1_ecdsa_recover(signature, message_hash).ok()?;
+			let evm_address = H160::from_slice(&sp_io::hashing::keccak_256(&public_key)[12..]);
+			let converted_key = match <T as Config>::AddressConverter::SECP256K1_PUBLIC_KEY_FORM {
+				Secp256K1PublicKeyForm::Compressed =>
+					sp_io::crypto::secp256k1_ecdsa_recover_compressed(signature, message_hash).ok()?.to_vec(),
+				Secp256K1PublicKeyForm::Uncompressed => public_key.to_vec(),
+			};
+			let mapped_account = <T as Config>::AddressConverter::try_convert(&converted_key)?;
+			Some((evm_address, mapped_account))
+		}
+
+		/// Check the nonce and fees of a meta transaction of `who`, paid by `sponsor` if any.
+		fn validate_meta_transaction(
+			who: &T::AccountId,
+			sponsor: Option<&T::AccountId>,
+			nonce: &Nonce,
+			len: usize,
+			info: &DispatchInfo,
+			tip: PaymentBalanceOf<T>,
+		) -> TransactionValidity {
 			// Skip frame_system::CheckNonZeroSender
 			// Skip frame_system::CheckSpecVersion<Runtime>
 			// Skip frame_system::CheckTxVersion<Runtime>
@@ -284,12 +952,9 @@ pub mod pallet {
 			// do the validate here.
 
 			// pallet_transaction_payment::ChargeTransactionPayment<Runtime>
-			let tip = tip.unwrap_or(0u32.into());
-			let len = call.encoded_size();
-			let info = call.get_dispatch_info();
 			// We shall get the same `fee` later
 			let est_fee =
-				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, &info, tip);
+				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, info, tip);
 			// TODO: Need check this work with assets-payment
 			// We don't withdraw the fee here, because we can't cache the imbalance
 			// Instead, we check the account has enough fee
@@ -298,12 +963,17 @@ pub mod pallet {
 			// We can't get the actual size of the meta-tx itself,
 			// so we have to introducing service fee.
 			let service_fee = T::ServiceFee::get().saturated_into::<u128>();
+			let payer = sponsor.cloned().unwrap_or_else(|| Self::canonical_account(who));
 			let usable_balance_for_fees =
-				T::Currency::reducible_balance(who, Preservation::Preserve, Fortitude::Polite)
+				T::Currency::reducible_balance(&payer, Preservation::Preserve, Fortitude::Polite)
 					.saturated_into::<u128>();
 			if est_fee.saturating_add(service_fee) > usable_balance_for_fees {
 				return Err(InvalidTransaction::Payment.into())
 			}
+			if let Some(sponsor) = sponsor {
+				Self::ensure_sponsor_budget(sponsor, est_fee.saturating_add(service_fee).saturated_into())
+					.map_err(|_| InvalidTransaction::Payment)?;
+			}
 
 			// Calculate priority
 			// Cheat from `get_priority` in frame/transaction-payment/src/lib.rs
@@ -352,111 +1022,28 @@ pub mod pallet {
 			let Some(requires) = requires else { return valid_transaction_builder.build() };
 			valid_transaction_builder.and_requires(requires).build()
 		}
-	}
 
-	#[pallet::call]
-	impl<T: Config> Pallet<T>
-	where
-		PaymentBalanceOf<T>: FixedPointOperand,
-		BalanceOf<T>: FixedPointOperand,
-		<T as frame_system::Config>::RuntimeCall:
-			Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
-		T: frame_system::Config<AccountId = sp_runtime::AccountId32>,
-	{
-		/// Meta-transaction from EVM compatible chains
-		#[pallet::call_index(0)]
-		#[pallet::weight({
-			let di = call.get_dispatch_info();
-			(
-				<T as pallet::Config>::WeightInfo::meta_call().saturating_add(di.total_weight()),
-				di.class
-			)
-		})]
-		pub fn meta_call(
-			origin: OriginFor<T>,
-			who: T::AccountId,
-			call: Box<<T as Config>::RuntimeCall>,
-			nonce: Nonce,
-			#[allow(unused_variables)] signature: EIP712Signature,
-			tip: Option<PaymentBalanceOf<T>>,
-		) -> DispatchResult {
-			// This is an unsigned transaction
-			ensure_none(origin)?;
-
-			// We don't need to re-validate the signature here,
-			// because it already validated in `validate_unsigned` stage,
-			// and it should no way to skip.
-			// TODO: Confirm this.
-
-			// It is possible that an account passed `validate_unsigned` check,
-			// but for some reason, its balance isn't enough for the service fee.
-			use frame_support::traits::tokens::{WithdrawReasons, ExistenceRequirement};
-			// NOTE: it is possible that the account doesn't have enough fee, which is a vulnerable.
-			let withdrawn = T::Currency::withdraw(
-				&who,
-				T::ServiceFee::get(),
-				WithdrawReasons::FEE,
-				ExistenceRequirement::KeepAlive
-			).map_err(|_err| Error::<T>::PaymentError)?;
-			let withdrawn_fee = withdrawn.peek();
-			T::OnUnbalancedForServiceFee::on_unbalanced(withdrawn);
-			Self::deposit_event(Event::ServiceFeePaid {
-				who: who.clone(),
-				actual_fee: withdrawn_fee,
-				expected_fee: T::ServiceFee::get(),
+		/// The dispatch info a batch is charged for.
+		fn batch_dispatch_info(calls: &[<T as Config>::RuntimeCall]) -> DispatchInfo {
+			let call_weight = calls.iter().fold(Weight::zero(), |total, call| {
+				total.saturating_add(call.get_dispatch_info().total_weight())
 			});
+			DispatchInfo { call_weight, class: DispatchClass::Normal, pays_fee: Pays::Yes, ..Default::default() }
+		}
 
-			// Bump the nonce
-			AccountNonce::<T>::try_mutate(&who, |value| {
-				if *value != nonce {
-					return Err(Error::<T>::NonceError)
-				}
-				*value += 1;
-				Ok(())
-			})?;
-
-			// Call
-			let mut origin: T::RuntimeOrigin = RawOrigin::Signed(who.clone()).into();
-			origin.add_filter(T::CallFilter::contains);
-			let len = call.encoded_size();
-			let info = call.get_dispatch_info();
-			let tip = tip.unwrap_or(0u32.into());
-			let est_fee =
-				pallet_transaction_payment::Pallet::<T>::compute_fee(len as u32, &info, tip);
-			// Add the service fee
-			let already_withdrawn =
-				<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::withdraw_fee(
-					&who,
-					&(*call).clone().into(),
-					&info,
-					est_fee,
-					tip,
-				)
-				.map_err(|_err| Error::<T>::PaymentError)?;
-
-			let call_result = call.dispatch(origin);
-			let post_info = match call_result {
-				Ok(post_info) => post_info,
-				Err(error_and_info) => error_and_info.post_info,
-			};
-			// Deposit the call's result
-			Self::deposit_event(Event::CallDone { who: who.clone(), call_result });
-
-			let actual_fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
-				len as u32, &info, &post_info, tip,
-			);
-			// frame/transaction-payment/src/payment.rs
-			<PaymentOnChargeTransaction<T> as OnChargeTransaction<T>>::correct_and_deposit_fee(
-				&who,
-				&info,
-				&post_info,
-				actual_fee,
-				tip,
-				already_withdrawn,
-			)
-			.map_err(|_err| Error::<T>::PaymentError)?;
-			Self::deposit_event(Event::TransactionFeePaid { who: who.clone(), actual_fee, tip });
+		/// Whether `sponsor` pays for `call`.
+		fn is_sponsored_call(sponsor: &T::AccountId, call: &<T as Config>::RuntimeCall) -> bool {
+			let metadata = call.get_call_metadata();
+			SponsoredCalls::<T>::get(sponsor).iter().any(|(pallet_name, function_name)| {
+				pallet_name.as_slice() == metadata.pallet_name.as_bytes() &&
+					function_name.as_slice() == metadata.function_name.as_bytes()
+			})
+		}
 
+		/// Check that `sponsor` can still pay `fee` within its budget.
+		fn ensure_sponsor_budget(sponsor: &T::AccountId, fee: BalanceOf<T>) -> DispatchResult {
+			let info = Sponsors::<T>::get(sponsor).ok_or(Error::<T>::NotSponsor)?;
+			ensure!(info.spent.saturating_add(fee) <= info.limit, Error::<T>::SponsorLimitExceeded);
 			Ok(())
 		}
 	}
@@ -470,6 +1057,82 @@ pub mod pallet {
 			call_data: &[u8],
 			nonce: Nonce,
 		) -> Keccak256Signature {
+			let message_hash = crate::encode::eip712::hash_struct(
+				"SubstrateCall(string who,bytes callData,uint64 nonce)",
+				&[
+					StructMember::String(&Self::ss58(who)),
+					StructMember::Bytes(call_data),
+					StructMember::Number(nonce.into()),
+				],
+			);
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The message hash of `call` under its typed schema, if it has one.
+		pub(crate) fn eip712_typed_call_hash(
+			who: T::AccountId,
+			call: &<T as Config>::RuntimeCall,
+			nonce: Nonce,
+		) -> Option<Keccak256Signature> {
+			let typed_call = T::TypedCallConverter::try_convert(call)?;
+			let message_hash = typed_call.hash_struct(&who, nonce, T::SS58Prefix::get().into());
+			Some(Self::eip712_typed_data_hash(&message_hash))
+		}
+
+		/// `calls_data` is the SCALE encoded list of calls.
+		pub(crate) fn eip712_batch_message_hash(
+			who: T::AccountId,
+			calls_data: &[u8],
+			nonce: Nonce,
+		) -> Keccak256Signature {
+			let message_hash = crate::encode::eip712::hash_struct(
+				"SubstrateBatchCall(string who,bytes callData,uint64 nonce)",
+				&[
+					StructMember::String(&Self::ss58(who)),
+					StructMember::Bytes(calls_data),
+					StructMember::Number(nonce.into()),
+				],
+			);
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The message a sponsor signs to pay for `who`'s batch, including the tip it pays on top.
+		pub(crate) fn eip712_sponsorship_hash(
+			sponsor: T::AccountId,
+			who: T::AccountId,
+			calls_data: &[u8],
+			nonce: Nonce,
+			tip: PaymentBalanceOf<T>,
+		) -> Keccak256Signature {
+			use sp_runtime::SaturatedConversion;
+			let message_hash = crate::encode::eip712::hash_struct(
+				"SubstrateSponsorship(string sponsor,string who,bytes callData,uint64 nonce,uint256 tip)",
+				&[
+					StructMember::String(&Self::ss58(sponsor)),
+					StructMember::String(&Self::ss58(who)),
+					StructMember::Bytes(calls_data),
+					StructMember::Number(nonce.into()),
+					StructMember::Number(tip.saturated_into::<u128>().into()),
+				],
+			);
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		/// The message an EVM key signs to be bound to `who`.
+		pub(crate) fn eip712_binding_hash(who: T::AccountId, nonce: Nonce) -> Keccak256Signature {
+			let message_hash = crate::encode::eip712::hash_struct(
+				"SubstrateBinding(string who,uint64 nonce)",
+				&[StructMember::String(&Self::ss58(who)), StructMember::Number(nonce.into())],
+			);
+			Self::eip712_typed_data_hash(&message_hash)
+		}
+
+		fn ss58(who: T::AccountId) -> Vec<u8> {
+			use sp_core::crypto::Ss58Codec;
+			who.to_ss58check_with_version(T::SS58Prefix::get().into()).into_bytes()
+		}
+
+		fn eip712_typed_data_hash(message_hash: &Keccak256Signature) -> Keccak256Signature {
 			use sp_std::vec;
 
 			// TODO: will refactor this in Kevin's way for performance.
@@ -482,24 +1145,10 @@ pub mod pallet {
 			};
 			let domain_separator = eip712_domain.separator();
 
-			let type_hash = sp_io::hashing::keccak_256(
-				"SubstrateCall(string who,bytes callData,uint64 nonce)".as_bytes(),
-			);
-			// Token::Uint(U256::from(keccak_256(&self.name)))
-			use sp_core::crypto::Ss58Codec;
-			let ss58_who = who.to_ss58check_with_version(T::SS58Prefix::get().into());
-			let hashed_call_data = sp_io::hashing::keccak_256(call_data);
-			let message_hash = sp_io::hashing::keccak_256(&ethabi::encode(&[
-				ethabi::Token::FixedBytes(type_hash.to_vec()),
-				ethabi::Token::FixedBytes(sp_io::hashing::keccak_256(ss58_who.as_bytes()).to_vec()),
-				ethabi::Token::FixedBytes(hashed_call_data.to_vec()),
-				ethabi::Token::Uint(nonce.into()),
-			]));
-
 			let typed_data_hash_input = &vec![
 				crate::encode::SolidityDataType::String("\x19\x01"),
 				crate::encode::SolidityDataType::Bytes(&domain_separator),
-				crate::encode::SolidityDataType::Bytes(&message_hash),
+				crate::encode::SolidityDataType::Bytes(message_hash),
 			];
 			let bytes = crate::encode::abi::encode_packed(typed_data_hash_input);
 			sp_io::hashing::keccak_256(bytes.as_slice())
@@ -182,11 +182,52 @@ parameter_types! {
 	pub EIP712VerifyingContractAddress: crate::EIP712VerifyingContractAddress = sp_core::H160::from([0u8; 20]);
 }
 
+pub struct TypedCalls;
+impl pallet_evm_account_mapping::TypedCallConversion<RuntimeCall> for TypedCalls {
+	fn try_convert(call: &RuntimeCall) -> Option<pallet_evm_account_mapping::eip712::TypedCall> {
+		use pallet_evm_account_mapping::eip712::TypedCall;
+
+		match call {
+			RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death { dest, value }) =>
+				Some(TypedCall::transfer_allow_death(dest.clone(), (*value).into())),
+			RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive { dest, value }) =>
+				Some(TypedCall::transfer_keep_alive(dest.clone(), (*value).into())),
+			_ => None,
+		}
+	}
+}
+
+/// Pads EVM addresses with zeros, standing in for the EVM's own mapping.
+pub struct PaddedEvmAddress;
+impl sp_runtime::traits::Convert<sp_core::H160, AccountId> for PaddedEvmAddress {
+	fn convert(address: sp_core::H160) -> AccountId {
+		let mut raw_account = [0u8; 32];
+		raw_account[..20].copy_from_slice(address.as_bytes());
+		AccountId::from(raw_account)
+	}
+}
+
+parameter_types! {
+	/// The accounts that own territories or files.
+	pub static StorageHolders: Vec<AccountId> = vec![];
+}
+
+pub struct MockStorageHoldings;
+impl pallet_evm_account_mapping::StorageHoldings<AccountId> for MockStorageHoldings {
+	fn holds_storage(who: &AccountId) -> bool {
+		StorageHolders::get().contains(who)
+	}
+}
+
 impl pallet_evm_account_mapping::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
 	type Currency = Balances;
 	type AddressConverter = pallet_evm_account_mapping::SubstrateAddressConverter;
+	type TypedCallConverter = TypedCalls;
+	type EvmAddressMapping = PaddedEvmAddress;
+	type StorageHoldings = MockStorageHoldings;
+	type MinBindingPeriod = ConstU64<10>;
 	type ServiceFee = ConstU128<1000>;
 	type OnUnbalancedForServiceFee = ();
 	type CallFilter = frame_support::traits::Everything;
@@ -194,6 +235,9 @@ impl pallet_evm_account_mapping::Config for Test {
 	type EIP712Version = EIP712Version;
 	type EIP712ChainID = EIP712ChainID;
 	type EIP712VerifyingContractAddress = EIP712VerifyingContractAddress;
+	type MaxBatchCalls = ConstU32<8>;
+	type MaxSponsoredCalls = ConstU32<8>;
+	type MaxCallNameLen = ConstU32<32>;
 	type WeightInfo = ();
 }
 
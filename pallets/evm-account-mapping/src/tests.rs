@@ -16,13 +16,53 @@
 // limitations under the License.
 
 #[allow(unused)]
-use crate::{mock::*, Error, Event};
-use codec::Decode;
-use frame_support::assert_ok;
+use crate::{
+	mock::*, AccountNonce, Bindings, CallNameOf, Error, EvmBindings, Event, MappedAccounts, Sponsors,
+	Sponsorship,
+};
+use codec::{Decode, Encode};
+use frame_support::{assert_noop, assert_ok, pallet_prelude::*, traits::fungible::Inspect};
 
-use sp_core::crypto::Ss58Codec;
-use sp_runtime::traits::TrailingZeroInput;
-use crate::AddressConversion;
+use sp_core::{crypto::Ss58Codec, Pair};
+use sp_runtime::{
+	traits::{Convert, TrailingZeroInput},
+	transaction_validity::TransactionSource,
+};
+use crate::{eip712::TypedCall, AddressConversion};
+
+fn remark_with_event() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark_with_event { remark: b"Hello".to_vec() })
+}
+
+fn batch(calls: Vec<RuntimeCall>) -> BoundedVec<RuntimeCall, ConstU32<8>> {
+	calls.try_into().expect("Within bound")
+}
+
+fn call_name(pallet_name: &[u8], function_name: &[u8]) -> CallNameOf<Test> {
+	(pallet_name.to_vec().try_into().unwrap(), function_name.to_vec().try_into().unwrap())
+}
+
+fn evm_account(pair: &sp_core::ecdsa::Pair) -> AccountId {
+	crate::SubstrateAddressConverter::try_convert(&pair.public().0).expect("Convertable")
+}
+
+/// Signs the binding of `pair`'s EVM key to `who`, returning its EVM address and signature.
+fn sign_binding(pair: &sp_core::ecdsa::Pair, who: &AccountId) -> (sp_core::H160, [u8; 65]) {
+	let message_hash = EvmAccountMapping::eip712_binding_hash(who.clone(), AccountNonce::<Test>::get(who));
+	let signature = pair.sign_prehashed(&message_hash).0;
+	let public_key = sp_io::crypto::secp256k1_ecdsa_recover(&signature, &message_hash).expect("Recoverable");
+	(sp_core::H160::from_slice(&sp_io::hashing::keccak_256(&public_key)[12..]), signature)
+}
+
+fn set_up_sponsor(sponsor: &AccountId, limit: Balance) {
+	set_balance(sponsor.clone(), DOLLARS);
+	assert_ok!(EvmAccountMapping::set_sponsor(RuntimeOrigin::signed(sponsor.clone()), limit));
+	assert_ok!(EvmAccountMapping::set_sponsored_calls(
+		RuntimeOrigin::signed(sponsor.clone()),
+		vec![call_name(b"System", b"remark_with_event")].try_into().unwrap(),
+		true,
+	));
+}
 
 #[test]
 fn it_works() {
@@ -167,3 +207,471 @@ fn eip712() {
 			.expect("Decodable");
 	assert_eq!(decoded_account.to_ss58check(), who);
 }
+
+#[test]
+fn batch_meta_call_dispatches_every_call() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = AccountId::from_ss58check("5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF").unwrap();
+		set_balance(account.clone(), DOLLARS);
+
+		assert_ok!(EvmAccountMapping::batch_meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			batch(vec![remark_with_event(), remark_with_event()]),
+			0,
+			[0; 65],
+			None,
+			None,
+		));
+
+		assert_eq!(AccountNonce::<Test>::get(&account), 1);
+		System::assert_has_event(Event::BatchCompleted { who: account.clone() }.into());
+		let remarked = System::events()
+			.iter()
+			.filter(|record| matches!(record.event, RuntimeEvent::System(frame_system::Event::Remarked { .. })))
+			.count();
+		assert_eq!(remarked, 2);
+		assert!(Balances::balance(&account) < DOLLARS);
+	});
+}
+
+#[test]
+fn batch_meta_call_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = AccountId::from_ss58check("5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF").unwrap();
+		let dest = AccountId::from([1u8; 32]);
+		set_balance(account.clone(), DOLLARS);
+
+		let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+			dest: dest.clone(),
+			value: CENTS,
+		});
+		// Only root may set the heap pages.
+		let root_only = RuntimeCall::System(frame_system::Call::set_heap_pages { pages: 1 });
+		assert_ok!(EvmAccountMapping::batch_meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			batch(vec![transfer, root_only]),
+			0,
+			[0; 65],
+			None,
+			None,
+		));
+
+		// The transfer is reverted, while the nonce is used and the fees are paid.
+		assert_eq!(Balances::balance(&dest), 0);
+		assert_eq!(AccountNonce::<Test>::get(&account), 1);
+		assert!(Balances::balance(&account) < DOLLARS);
+		System::assert_has_event(
+			Event::BatchInterrupted { who: account, index: 1, error: DispatchError::BadOrigin }.into(),
+		);
+	});
+}
+
+#[test]
+fn sponsor_pays_for_batch() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = AccountId::from_ss58check("5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF").unwrap();
+		let sponsor = AccountId::from([2u8; 32]);
+		set_up_sponsor(&sponsor, DOLLARS / 2);
+
+		assert_ok!(EvmAccountMapping::batch_meta_call(
+			RuntimeOrigin::none(),
+			account.clone(),
+			batch(vec![remark_with_event()]),
+			0,
+			[0; 65],
+			None,
+			Some(Sponsorship { sponsor: sponsor.clone(), signature: [0; 65] }),
+		));
+
+		let paid = DOLLARS - Balances::balance(&sponsor);
+		assert!(paid > 0);
+		assert_eq!(Balances::balance(&account), 0);
+		assert_eq!(Sponsors::<Test>::get(&sponsor).unwrap().spent, paid);
+		System::assert_has_event(Event::Sponsored { sponsor, who: account, fee: paid }.into());
+	});
+}
+
+#[test]
+fn sponsor_only_pays_for_allowed_calls_within_limit() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let account = AccountId::from_ss58check("5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF").unwrap();
+		let sponsor = AccountId::from([2u8; 32]);
+		set_up_sponsor(&sponsor, 1);
+		let sponsorship = Some(Sponsorship { sponsor: sponsor.clone(), signature: [0; 65] });
+
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: b"Hello".to_vec() });
+		assert_noop!(
+			EvmAccountMapping::batch_meta_call(
+				RuntimeOrigin::none(),
+				account.clone(),
+				batch(vec![remark_with_event(), remark]),
+				0,
+				[0; 65],
+				None,
+				sponsorship.clone(),
+			),
+			Error::<Test>::CallNotSponsored
+		);
+		assert_noop!(
+			EvmAccountMapping::batch_meta_call(
+				RuntimeOrigin::none(),
+				account,
+				batch(vec![remark_with_event()]),
+				0,
+				[0; 65],
+				None,
+				sponsorship,
+			),
+			Error::<Test>::SponsorLimitExceeded
+		);
+	});
+}
+
+#[test]
+fn batch_meta_call_validates_both_signatures() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let user = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+		let sponsor_pair = sp_core::ecdsa::Pair::from_seed(&[2u8; 32]);
+		let (account, sponsor) = (evm_account(&user), evm_account(&sponsor_pair));
+		set_up_sponsor(&sponsor, DOLLARS / 2);
+
+		let calls = batch(vec![remark_with_event()]);
+		let calls_data = calls.encode();
+		let message_hash = EvmAccountMapping::eip712_batch_message_hash(account.clone(), &calls_data, 0);
+		let sponsorship_hash =
+			EvmAccountMapping::eip712_sponsorship_hash(sponsor.clone(), account.clone(), &calls_data, 0, 0);
+		let signature = user.sign_prehashed(&message_hash).0;
+		let sponsorship = Sponsorship { sponsor, signature: sponsor_pair.sign_prehashed(&sponsorship_hash).0 };
+
+		let validate = |signature, sponsorship, tip| {
+			<EvmAccountMapping as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::batch_meta_call {
+					who: account.clone(),
+					calls: calls.clone(),
+					nonce: 0,
+					signature,
+					tip,
+					sponsorship: Some(sponsorship),
+				},
+			)
+		};
+		// The user has no funds of its own, the sponsor pays.
+		assert_ok!(validate(signature, sponsorship.clone(), None));
+		// The user can't sign on the sponsor's behalf.
+		assert_eq!(
+			validate(signature, Sponsorship { signature, ..sponsorship.clone() }, None),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+		// Nor raise the tip the sponsor agreed to pay.
+		assert_eq!(
+			validate(signature, sponsorship, Some(DOLLARS / 4)),
+			Err(InvalidTransaction::BadSigner.into())
+		);
+	});
+}
+
+#[test]
+fn eip712_domain_separator_matches_spec_example() {
+	// The `Mail` example of EIP-712.
+	let eip712_domain = crate::eip712::EIP712Domain {
+		name: b"Ether Mail".to_vec(),
+		version: b"1".to_vec(),
+		chain_id: sp_core::U256::from(1),
+		verifying_contract: sp_core::H160::from_slice(
+			&hex::decode("CcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").expect("Decodable"),
+		),
+		salt: None,
+	};
+	assert_eq!(
+		hex::encode(eip712_domain.separator()),
+		"f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+	);
+}
+
+#[test]
+fn eip712_hash_struct_matches_spec_example() {
+	use crate::encode::{eip712::hash_struct, StructMember};
+
+	// The `Mail` example of EIP-712, whose nested `Person`s are encoded as their `hashStruct`.
+	let person = |name: &[u8], wallet: &str| {
+		let wallet = sp_core::H160::from_slice(&hex::decode(wallet).expect("Decodable"));
+		let person = hash_struct(
+			"Person(string name,address wallet)",
+			&[StructMember::String(name), StructMember::Number(sp_core::U256::from_big_endian(wallet.as_bytes()))],
+		);
+		StructMember::Number(sp_core::U256::from_big_endian(&person))
+	};
+	let mail = hash_struct(
+		"Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+		&[
+			person(b"Cow", "CD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826"),
+			person(b"Bob", "bBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB"),
+			StructMember::String(b"Hello, Bob!"),
+		],
+	);
+	assert_eq!(hex::encode(mail), "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e");
+}
+
+#[test]
+fn typed_call_schemas_match_vectors() {
+	// Vectors printed by `scripts/typed-call-vectors.mjs` with ethers' `TypedDataEncoder`, over
+	// the SS58 addresses (prefix 42) of the accounts below.
+	let who = AccountId::from_ss58check("5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF").unwrap();
+	let one = AccountId::from([1u8; 32]); // 5C62Ck4UrFPiBtoCmeSrgF7x9yv9mn38446dhCpsi2mLHiFT
+	let two = AccountId::from([2u8; 32]); // 5C7LYpP2ZH3tpKbvVvwiVe54AapxErdPBbvkYhe6y9ZBkqWt
+	let file_hash = vec![b'a'; 64];
+	let vectors = [
+		(
+			TypedCall::transfer_allow_death(one.clone(), DOLLARS.into()),
+			0,
+			"ec5279484fd0c8c57bf3e21a43aef724ab2a8f4ecda8409fcc3afeaad39b27f0",
+		),
+		(
+			TypedCall::transfer_keep_alive(one.clone(), DOLLARS.into()),
+			1,
+			"b3bcd07b7a332e536901e8eec6fa7491ca176ac03ac26f20bcd6d805831dda9b",
+		),
+		(
+			TypedCall::upload_declaration(
+				one.clone(),
+				file_hash.clone(),
+				b"cat.jpg".to_vec(),
+				b"photos".to_vec(),
+				1_048_576,
+				vec![0, 0],
+			),
+			3,
+			"d74d11bcebc2b183ee239184282a555531d054dfeddc134b121437e98dfbfb13",
+		),
+		(
+			TypedCall::delete_file(one, file_hash),
+			4,
+			"59340702bdd8202e43458f219311ebaec8b0a1d8f573a29a9d50a1271e617654",
+		),
+		(
+			TypedCall::mint_territory(b"photos".to_vec(), 10, 30),
+			5,
+			"d8d067af9db1f3a6c1e45ee61935eaf3738c42e1a90e9edcf8db3400c6fc4e4e",
+		),
+		(
+			TypedCall::expanding_territory(b"photos".to_vec(), 5),
+			6,
+			"9c0e21f98b5468e5db5c37c64f8aad88f7e3c79f1570449465e1c285630df8b4",
+		),
+		(
+			TypedCall::renewal_territory(b"photos".to_vec(), 60),
+			7,
+			"54f9a7ba8051d6cd29a916e2a87bce750acf1672b47cc173cfcbc75de7fab15f",
+		),
+		(
+			TypedCall::authorize(two),
+			8,
+			"35a4654bb4e14527e5d24b665360112bd8f7af2ba546772cf3aa9ccaec0fff55",
+		),
+	];
+	for (typed_call, nonce, expected) in vectors {
+		assert_eq!(hex::encode(typed_call.hash_struct(&who, nonce, 42)), expected, "{}", typed_call.encoded_type);
+	}
+}
+
+#[test]
+fn typed_schema_is_picked_from_the_call() {
+	let who = AccountId::from_ss58check("5DT96geTS2iLpkH8fAhYAAphNpxddKCV36s5ShVFavf1xQiF").unwrap();
+	let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+		dest: AccountId::from([1u8; 32]),
+		value: DOLLARS,
+	});
+
+	// The digest a wallet signs for the transfer under the mock's domain.
+	assert_eq!(
+		EvmAccountMapping::eip712_typed_call_hash(who.clone(), &transfer, 0).map(hex::encode),
+		Some("9b9193cb41b5fea0210ba7d6def37b0970e1915cbb701f29bcbc0d7454928b49".to_string())
+	);
+	// The same transfer keeping the sender alive is signed under a schema of its own.
+	let keep_alive = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+		dest: AccountId::from([1u8; 32]),
+		value: DOLLARS,
+	});
+	assert_ne!(
+		EvmAccountMapping::eip712_typed_call_hash(who.clone(), &keep_alive, 0),
+		EvmAccountMapping::eip712_typed_call_hash(who.clone(), &transfer, 0)
+	);
+	// Calls without a schema are only signed as call data.
+	assert_eq!(EvmAccountMapping::eip712_typed_call_hash(who, &remark_with_event(), 0), None);
+}
+
+#[test]
+fn meta_call_accepts_typed_and_generic_signatures() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let user = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+		let account = evm_account(&user);
+		set_balance(account.clone(), DOLLARS);
+
+		let call = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+			dest: AccountId::from([1u8; 32]),
+			value: CENTS,
+		});
+		let typed_hash = EvmAccountMapping::eip712_typed_call_hash(account.clone(), &call, 0).unwrap();
+		let message_hash = EvmAccountMapping::eip712_message_hash(account.clone(), &call.encode(), 0);
+
+		let validate = |signature| {
+			<EvmAccountMapping as ValidateUnsigned>::validate_unsigned(
+				TransactionSource::External,
+				&crate::Call::meta_call {
+					who: account.clone(),
+					call: Box::new(call.clone()),
+					nonce: 0,
+					signature,
+					tip: None,
+				},
+			)
+		};
+		assert_ok!(validate(user.sign_prehashed(&typed_hash).0));
+		assert_ok!(validate(user.sign_prehashed(&message_hash).0));
+		let other = sp_core::ecdsa::Pair::from_seed(&[2u8; 32]);
+		assert_eq!(validate(other.sign_prehashed(&typed_hash).0), Err(InvalidTransaction::BadSigner.into()));
+	});
+}
+
+#[test]
+fn bound_evm_key_acts_as_the_bound_account() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = AccountId::from([7u8; 32]);
+		let evm_key = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+		let mapped_account = evm_account(&evm_key);
+		set_balance(who.clone(), DOLLARS);
+
+		let (evm_address, signature) = sign_binding(&evm_key, &who);
+		assert_ok!(EvmAccountMapping::bind(RuntimeOrigin::signed(who.clone()), evm_address, signature));
+		System::assert_last_event(Event::Bound { who: who.clone(), evm_address }.into());
+		assert_eq!(EvmAccountMapping::evm_account(evm_address), who);
+		assert_eq!(EvmAccountMapping::canonical_account(&mapped_account), who);
+		assert_eq!(Bindings::<Test>::get(&who).unwrap().mapped_account, mapped_account);
+
+		// The key's meta calls spend from, and pay with, the bound account.
+		let dest = AccountId::from([1u8; 32]);
+		let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death {
+			dest: dest.clone(),
+			value: CENTS,
+		});
+		assert_ok!(EvmAccountMapping::meta_call(
+			RuntimeOrigin::none(),
+			mapped_account.clone(),
+			Box::new(transfer),
+			0,
+			[0; 65],
+			None,
+		));
+		assert_eq!(Balances::balance(&dest), CENTS);
+		assert!(Balances::balance(&who) < DOLLARS - CENTS);
+		assert_eq!(Balances::balance(&mapped_account), 0);
+		assert_eq!(AccountNonce::<Test>::get(&mapped_account), 1);
+	});
+}
+
+#[test]
+fn bind_requires_the_evm_key_and_empty_mapped_accounts() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = AccountId::from([7u8; 32]);
+		let evm_key = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+		let (evm_address, signature) = sign_binding(&evm_key, &who);
+
+		// Signed by the key, but for another account.
+		let other = AccountId::from([8u8; 32]);
+		assert_noop!(
+			EvmAccountMapping::bind(RuntimeOrigin::signed(other.clone()), evm_address, signature),
+			Error::<Test>::BadBindingSignature
+		);
+
+		set_balance(evm_account(&evm_key), DOLLARS);
+		assert_noop!(
+			EvmAccountMapping::bind(RuntimeOrigin::signed(who.clone()), evm_address, signature),
+			Error::<Test>::MappedAccountNotEmpty
+		);
+		set_balance(evm_account(&evm_key), 0);
+		StorageHolders::set(vec![evm_account(&evm_key)]);
+		assert_noop!(
+			EvmAccountMapping::bind(RuntimeOrigin::signed(who.clone()), evm_address, signature),
+			Error::<Test>::MappedAccountHoldsStorage
+		);
+		StorageHolders::set(vec![]);
+
+		assert_ok!(EvmAccountMapping::bind(RuntimeOrigin::signed(who.clone()), evm_address, signature));
+		// Neither the account nor the key can be bound twice.
+		let (other_address, other_signature) =
+			sign_binding(&sp_core::ecdsa::Pair::from_seed(&[2u8; 32]), &who);
+		assert_noop!(
+			EvmAccountMapping::bind(RuntimeOrigin::signed(who), other_address, other_signature),
+			Error::<Test>::AlreadyBound
+		);
+		let (evm_address, signature) = sign_binding(&evm_key, &other);
+		assert_noop!(
+			EvmAccountMapping::bind(RuntimeOrigin::signed(other), evm_address, signature),
+			Error::<Test>::AlreadyBound
+		);
+	});
+}
+
+#[test]
+fn binding_carries_the_ethereum_nonce_over() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = AccountId::from([7u8; 32]);
+		let evm_key = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+		let (evm_address, signature) = sign_binding(&evm_key, &who);
+		let unbound_account = PaddedEvmAddress::convert(evm_address);
+		// The address sent three Ethereum transactions before it was bound.
+		frame_system::Account::<Test>::mutate(&unbound_account, |account| account.nonce = 3);
+
+		assert_ok!(EvmAccountMapping::bind(RuntimeOrigin::signed(who.clone()), evm_address, signature));
+		assert_eq!(System::account_nonce(&who), 3);
+
+		// Bound, its next Ethereum transaction uses the nonce of the bound account.
+		System::inc_account_nonce(&who);
+		run_to_block(11);
+		assert_ok!(EvmAccountMapping::unbind(RuntimeOrigin::signed(who.clone())));
+		assert_eq!(System::account_nonce(&unbound_account), 4);
+	});
+}
+
+#[test]
+fn unbind_waits_for_the_min_binding_period() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		let who = AccountId::from([7u8; 32]);
+		let evm_key = sp_core::ecdsa::Pair::from_seed(&[1u8; 32]);
+		let (evm_address, signature) = sign_binding(&evm_key, &who);
+		assert_ok!(EvmAccountMapping::bind(RuntimeOrigin::signed(who.clone()), evm_address, signature));
+
+		assert_noop!(EvmAccountMapping::unbind(RuntimeOrigin::signed(who.clone())), Error::<Test>::BindingLocked);
+		run_to_block(11);
+		assert_ok!(EvmAccountMapping::unbind(RuntimeOrigin::signed(who.clone())));
+		System::assert_last_event(Event::Unbound { who: who.clone(), evm_address }.into());
+
+		assert!(EvmBindings::<Test>::get(evm_address).is_none());
+		assert!(MappedAccounts::<Test>::get(evm_account(&evm_key)).is_none());
+		assert_eq!(EvmAccountMapping::canonical_account(&evm_account(&evm_key)), evm_account(&evm_key));
+		assert_noop!(EvmAccountMapping::unbind(RuntimeOrigin::signed(who)), Error::<Test>::NotBound);
+	});
+}
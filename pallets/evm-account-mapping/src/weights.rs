@@ -53,6 +53,12 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_evm_account_mapping.
 pub trait WeightInfo {
     fn meta_call() -> Weight;
+    fn batch_meta_call(c: u32, ) -> Weight;
+    fn set_sponsor() -> Weight;
+    fn remove_sponsor() -> Weight;
+    fn set_sponsored_calls(n: u32, ) -> Weight;
+    fn bind() -> Weight;
+    fn unbind() -> Weight;
 }
 
 /// Weights for pallet_evm_account_mapping using the Substrate node and recommended hardware.
@@ -73,6 +79,88 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(3_u64))
             .saturating_add(T::DbWeight::get().writes(2_u64))
     }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Sponsors` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsors` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::SponsoredCalls` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::SponsoredCalls` (`max_values`: None, `max_size`: Some(8369), added: 10844, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+    /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// The range of component `c` is `[1, 16]`.
+    fn batch_meta_call(c: u32, ) -> Weight {
+        Weight::from_parts(40_512_000, 11834)
+            .saturating_add(Weight::from_parts(5_328_000, 0).saturating_mul(c.into()))
+            .saturating_add(T::DbWeight::get().reads(5_u64))
+            .saturating_add(T::DbWeight::get().writes(3_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Sponsors` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsors` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    fn set_sponsor() -> Weight {
+        Weight::from_parts(10_000_000, 3545)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Sponsors` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsors` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::SponsoredCalls` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::SponsoredCalls` (`max_values`: None, `max_size`: Some(8369), added: 10844, mode: `MaxEncodedLen`)
+    fn remove_sponsor() -> Weight {
+        Weight::from_parts(13_000_000, 3545)
+            .saturating_add(T::DbWeight::get().reads(1_u64))
+            .saturating_add(T::DbWeight::get().writes(2_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Sponsors` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Sponsors` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::SponsoredCalls` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::SponsoredCalls` (`max_values`: None, `max_size`: Some(8369), added: 10844, mode: `MaxEncodedLen`)
+    /// The range of component `n` is `[1, 64]`.
+    fn set_sponsored_calls(n: u32, ) -> Weight {
+        Weight::from_parts(14_209_000, 11834)
+            .saturating_add(Weight::from_parts(412_000, 0).saturating_mul(n.into()))
+            .saturating_add(T::DbWeight::get().reads(2_u64))
+            .saturating_add(T::DbWeight::get().writes(1_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Bindings` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Bindings` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmBindings` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmBindings` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:3 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `StorageHandler::Territory` (r:2 w:0)
+    /// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+    /// Storage: `FileBank::UserHoldFileList` (r:2 w:0)
+    /// Proof: `FileBank::UserHoldFileList` (`max_values`: None, `max_size`: None, mode: `Measured`)
+    fn bind() -> Weight {
+        Weight::from_parts(101_000_000, 8799)
+            .saturating_add(T::DbWeight::get().reads(11_u64))
+            .saturating_add(T::DbWeight::get().writes(5_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Bindings` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Bindings` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmBindings` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::EvmBindings` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:2 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn unbind() -> Weight {
+        Weight::from_parts(22_000_000, 6196)
+            .saturating_add(T::DbWeight::get().reads(3_u64))
+            .saturating_add(T::DbWeight::get().writes(4_u64))
+    }
 }
 
 // For backwards compatibility and tests.
@@ -92,4 +180,86 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(3_u64))
             .saturating_add(RocksDbWeight::get().writes(2_u64))
     }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Sponsors` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsors` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::SponsoredCalls` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::SponsoredCalls` (`max_values`: None, `max_size`: Some(8369), added: 10844, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:1 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `TransactionPayment::NextFeeMultiplier` (r:1 w:0)
+    /// Proof: `TransactionPayment::NextFeeMultiplier` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+    /// The range of component `c` is `[1, 16]`.
+    fn batch_meta_call(c: u32, ) -> Weight {
+        Weight::from_parts(40_512_000, 11834)
+            .saturating_add(Weight::from_parts(5_328_000, 0).saturating_mul(c.into()))
+            .saturating_add(RocksDbWeight::get().reads(5_u64))
+            .saturating_add(RocksDbWeight::get().writes(3_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Sponsors` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsors` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    fn set_sponsor() -> Weight {
+        Weight::from_parts(10_000_000, 3545)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Sponsors` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Sponsors` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::SponsoredCalls` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::SponsoredCalls` (`max_values`: None, `max_size`: Some(8369), added: 10844, mode: `MaxEncodedLen`)
+    fn remove_sponsor() -> Weight {
+        Weight::from_parts(13_000_000, 3545)
+            .saturating_add(RocksDbWeight::get().reads(1_u64))
+            .saturating_add(RocksDbWeight::get().writes(2_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Sponsors` (r:1 w:0)
+    /// Proof: `EvmAccountMapping::Sponsors` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::SponsoredCalls` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::SponsoredCalls` (`max_values`: None, `max_size`: Some(8369), added: 10844, mode: `MaxEncodedLen`)
+    /// The range of component `n` is `[1, 64]`.
+    fn set_sponsored_calls(n: u32, ) -> Weight {
+        Weight::from_parts(14_209_000, 11834)
+            .saturating_add(Weight::from_parts(412_000, 0).saturating_mul(n.into()))
+            .saturating_add(RocksDbWeight::get().reads(2_u64))
+            .saturating_add(RocksDbWeight::get().writes(1_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Bindings` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Bindings` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::AccountNonce` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::AccountNonce` (`max_values`: None, `max_size`: Some(56), added: 2531, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmBindings` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::EvmBindings` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:3 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    /// Storage: `StorageHandler::Territory` (r:2 w:0)
+    /// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+    /// Storage: `FileBank::UserHoldFileList` (r:2 w:0)
+    /// Proof: `FileBank::UserHoldFileList` (`max_values`: None, `max_size`: None, mode: `Measured`)
+    fn bind() -> Weight {
+        Weight::from_parts(101_000_000, 8799)
+            .saturating_add(RocksDbWeight::get().reads(11_u64))
+            .saturating_add(RocksDbWeight::get().writes(5_u64))
+    }
+    /// Not benchmarked yet: estimated from the storage accesses of the call.
+    /// Storage: `EvmAccountMapping::Bindings` (r:1 w:1)
+    /// Proof: `EvmAccountMapping::Bindings` (`max_values`: None, `max_size`: Some(108), added: 2583, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::EvmBindings` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::EvmBindings` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+    /// Storage: `EvmAccountMapping::MappedAccounts` (r:0 w:1)
+    /// Proof: `EvmAccountMapping::MappedAccounts` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+    /// Storage: `System::Account` (r:2 w:1)
+    /// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+    fn unbind() -> Weight {
+        Weight::from_parts(22_000_000, 6196)
+            .saturating_add(RocksDbWeight::get().reads(3_u64))
+            .saturating_add(RocksDbWeight::get().writes(4_u64))
+    }
 }
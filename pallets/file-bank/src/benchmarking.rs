@@ -31,11 +31,16 @@ use pallet_sminer::{Config as SminerConfig, Pallet as Sminer};
 // use sp_std::prelude::*;
 // use scale_info::prelude::format;
 use frame_system::RawOrigin;
+use sp_runtime::Perbill;
 // use sp_runtime::traits::BlakeTwo256;
 // use cessp_consensus_rrsc::{Slot, RRSC_ENGINE_ID};
 pub struct Pallet<T: Config>(FileBank<T>);
 pub trait Config:
-	crate::Config + pallet_sminer::benchmarking::Config + pallet_storage_handler::Config + pallet_tee_worker::Config
+	crate::Config
+	+ pallet_sminer::benchmarking::Config
+	+ pallet_storage_handler::Config
+	+ pallet_tee_worker::Config
+	+ pallet_cess_treasury::Config
 {
 }
 // type SminerBalanceOf<T> = <<T as pallet_storage_handler::Config>::Currency as Currency<
@@ -148,6 +153,35 @@ pub fn initialize_file_from_scratch<T: Config>() -> Result<(), &'static str> {
 	Ok(())
 }
 
+/// Store an active file of one segment that `user` holds in territory `t1`.
+pub fn hold_file_for_objects<T: Config>(user: AccountOf<T>, file_hash: Hash) -> Result<(), &'static str> {
+	let fragment = FragmentInfo::<T> { hash: Hash([97u8; 64]), avail: true, tag: None, miner: user.clone() };
+	let user_brief = UserBrief::<T> {
+		user,
+		file_name: "test-file".as_bytes().to_vec().try_into().map_err(|_| "file name convert err")?,
+		territory_name: "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?,
+	};
+	let segment_info = SegmentInfo::<T> {
+		hash: Hash([65u8; 64]),
+		fragment_list: vec![fragment; FRAGMENT_COUNT as usize].try_into().map_err(|_| "boundedvec error")?,
+	};
+	let file_info = FileInfo::<T> {
+		segment_list: vec![segment_info].try_into().map_err(|_| "boundedvec error")?,
+		owner: vec![user_brief].try_into().map_err(|_| "boundedvec error")?,
+		file_size: SEGMENT_SIZE,
+		completion: frame_system::Pallet::<T>::block_number(),
+		stat: FileState::Active,
+	};
+	File::<T>::insert(&file_hash, file_info);
+
+	Ok(())
+}
+
+/// An object key of `len` characters.
+fn object_key<T: Config>(len: u32) -> Result<ObjectKey<T>, &'static str> {
+	vec![b'a'; len as usize].try_into().map_err(|_| "object key convert err")
+}
+
 fn create_file_for_v3_migrate<T: Config>() {
 	let user: AccountOf<T> = account("user1", 100, SEED);
 	let file_name = "test-file".as_bytes().to_vec();
@@ -292,6 +326,131 @@ mod migrate {
 		assert_eq!(MigrationInProgress::<T>::get(), v);
 	}
 
+	#[benchmark]
+	fn claim_data_loss_compensation() -> Result<(), BenchmarkError> {
+		let user: AccountOf<T> = account("user1", 100, SEED);
+		buy_space::<T>(user.clone())?;
+
+		let file_hash: Hash = Hash([80u8; 64]);
+		let fragment = FragmentInfo::<T> { hash: Hash([97u8; 64]), avail: false, tag: None, miner: user.clone() };
+		let user_brief = UserBrief::<T> {
+			user: user.clone(),
+			file_name: "test-file".as_bytes().to_vec().try_into().map_err(|_| "file name convert err")?,
+			territory_name: "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?,
+		};
+		let segment_info = SegmentInfo::<T> {
+			hash: Hash([65u8; 64]),
+			fragment_list: vec![fragment; FRAGMENT_COUNT as usize].try_into().map_err(|_| "boundedvec error")?,
+		};
+		let now = frame_system::Pallet::<T>::block_number();
+		let file_info = FileInfo::<T> {
+			segment_list: vec![segment_info].try_into().map_err(|_| "boundedvec error")?,
+			owner: vec![user_brief].try_into().map_err(|_| "boundedvec error")?,
+			file_size: SEGMENT_SIZE,
+			completion: now,
+			stat: FileState::Missing,
+		};
+		File::<T>::insert(&file_hash, file_info);
+		MissingSince::<T>::insert(&file_hash, now);
+		frame_system::Pallet::<T>::set_block_number(now.saturating_add(T::MissingGracePeriod::get()));
+
+		// Fund the insurance so that the compensation is paid.
+		let funder: AccountOf<T> = account("funder", 100, SEED);
+		<T as pallet_cess_treasury::Config>::Currency::make_free_balance_be(
+			&funder,
+			365_000_000_000_000_000_000_000u128.saturated_into(),
+		);
+		pallet_cess_treasury::Pallet::<T>::send_with_insurance(
+			funder,
+			pallet_cess_treasury::TreasuryAccount::Space,
+			100_000_000_000_000_000_000_000u128.saturated_into(),
+			Perbill::from_percent(100),
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(user.clone()), file_hash);
+
+		assert!(InsuranceClaims::<T>::contains_key(&file_hash, &user));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn put_object() -> Result<(), BenchmarkError> {
+		let user: AccountOf<T> = account("user1", 100, SEED);
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&user,
+			365_000_000_000_000_000_000_000u128.saturated_into(),
+		);
+		let territory_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+		let (replaced_hash, file_hash) = (Hash([80u8; 64]), Hash([81u8; 64]));
+		hold_file_for_objects::<T>(user.clone(), replaced_hash)?;
+		hold_file_for_objects::<T>(user.clone(), file_hash)?;
+
+		// The longest key replaces the one naming another file, and joins as many other keys
+		// of the file as allowed.
+		let key = object_key::<T>(T::ObjectKeyLimit::get())?;
+		FileBank::<T>::put_object(
+			RawOrigin::Signed(user.clone()).into(),
+			user.clone(),
+			territory_name.clone(),
+			key.clone(),
+			replaced_hash,
+		)?;
+		for len in 1 .. MAX_FILE_OBJECTS {
+			FileBank::<T>::put_object(
+				RawOrigin::Signed(user.clone()).into(),
+				user.clone(),
+				territory_name.clone(),
+				object_key::<T>(len)?,
+				file_hash,
+			)?;
+		}
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(user.clone()), user.clone(), territory_name.clone(), key.clone(), file_hash);
+
+		let info = Objects::<T>::get((user, territory_name), ObjectPath(key)).ok_or("object not put")?;
+		assert_eq!(info.file_hash, file_hash);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn delete_object() -> Result<(), BenchmarkError> {
+		let user: AccountOf<T> = account("user1", 100, SEED);
+		<T as crate::Config>::Currency::make_free_balance_be(
+			&user,
+			365_000_000_000_000_000_000_000u128.saturated_into(),
+		);
+		let territory_name: TerrName = "t1".as_bytes().to_vec().try_into().map_err(|_| "boundedvec error")?;
+		let file_hash = Hash([80u8; 64]);
+		hold_file_for_objects::<T>(user.clone(), file_hash)?;
+
+		// The longest key, among as many keys of the file as allowed.
+		let key = object_key::<T>(T::ObjectKeyLimit::get())?;
+		for len in 1 .. MAX_FILE_OBJECTS {
+			FileBank::<T>::put_object(
+				RawOrigin::Signed(user.clone()).into(),
+				user.clone(),
+				territory_name.clone(),
+				object_key::<T>(len)?,
+				file_hash,
+			)?;
+		}
+		FileBank::<T>::put_object(
+			RawOrigin::Signed(user.clone()).into(),
+			user.clone(),
+			territory_name.clone(),
+			key.clone(),
+			file_hash,
+		)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(user.clone()), user.clone(), territory_name.clone(), key.clone());
+
+		assert!(!Objects::<T>::contains_key((user, territory_name), ObjectPath(key)));
+		Ok(())
+	}
+
 	// This benchmarks the weight of running on_runtime_upgrade when there is a migration to
 	// process.
 	#[benchmark(pov_mode = Measured)]
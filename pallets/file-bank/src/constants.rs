@@ -1 +1,5 @@
-pub(super) const ONCE_MAX_CLEAR_FILE: u32 = 300;
\ No newline at end of file
+pub(super) const ONCE_MAX_CLEAR_FILE: u32 = 300;
+/// The most objects a listing returns at once.
+pub(super) const MAX_OBJECTS_PAGE: u32 = 1000;
+/// The most keys of a namespace that can name the same file.
+pub(super) const MAX_FILE_OBJECTS: u32 = 16;
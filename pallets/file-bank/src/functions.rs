@@ -136,6 +136,7 @@ impl<T: Config> Pallet<T> {
                     if user_clear {
                         T::StorageHandle::sub_territory_used_space(acc, &user_brief.territory_name, file_size)?;
                     }
+                    Self::remove_file_objects(acc, &user_brief.territory_name, file_hash);
                     file.owner.remove(index);
                     break;
                 }
@@ -204,7 +205,7 @@ impl<T: Config> Pallet<T> {
         }
 
         if user_clear {
-            for user_brief in file.owner {
+            for user_brief in file.owner.iter() {
                 if &user_brief.user == acc {
                     T::StorageHandle::sub_territory_used_space(acc, &user_brief.territory_name, total_fragment_dec as u128 * FRAGMENT_SIZE)?;
                     weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
@@ -215,11 +216,39 @@ impl<T: Config> Pallet<T> {
         T::StorageHandle::sub_total_service_space(total_fragment_dec as u128 * FRAGMENT_SIZE)?;
         weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
 
+        for user_brief in file.owner.iter() {
+            let temp_weight = Self::remove_file_objects(&user_brief.user, &user_brief.territory_name, file_hash);
+            weight = weight.saturating_add(temp_weight);
+        }
+
         <File<T>>::remove(file_hash);
-        weight = weight.saturating_add(T::DbWeight::get().writes(1));
+        <MissingSince<T>>::remove(file_hash);
+        let _ = <InsuranceClaims<T>>::clear_prefix(file_hash, T::OwnerLimit::get(), None);
+        weight = weight.saturating_add(T::DbWeight::get().writes(2 + file.owner.len() as u64));
 
         Ok(weight)
     }
+
+    /// helper: update the state of a file after the availability of its fragments changed.
+    ///
+    /// A file is missing as soon as one of its segments has fewer available fragments than
+    /// needed to restore it, and recovers once every segment can be restored again.
+    pub(super) fn refresh_file_state(file_hash: &Hash, file: &mut FileInfo<T>) {
+        let data_fragments = (SEGMENT_SIZE / FRAGMENT_SIZE) as usize;
+        let restorable = file.segment_list.iter().all(|segment| {
+            segment.fragment_list.iter().filter(|fragment| fragment.avail).count() >= data_fragments
+        });
+
+        if !restorable && file.stat != FileState::Missing {
+            file.stat = FileState::Missing;
+            <MissingSince<T>>::insert(file_hash, <frame_system::Pallet<T>>::block_number());
+            Self::deposit_event(Event::<T>::FileMissing { file_hash: *file_hash });
+        } else if restorable && file.stat == FileState::Missing {
+            file.stat = FileState::Active;
+            <MissingSince<T>>::remove(file_hash);
+            Self::deposit_event(Event::<T>::FileRecovered { file_hash: *file_hash });
+        }
+    }
     /// helper: generate random number.
     ///
     /// Get a random number.
@@ -318,20 +347,155 @@ impl<T: Config> Pallet<T> {
     }
     /// helper: Permission check method.
     /// Check whether the origin has the owner's authorization
-    /// or whether the origin is the owner
+    /// covering the action, or whether the origin is the owner
     ///
     /// Parameters:
-    /// - `acc`: AccountId.
+    /// - `operator`: The account taking the action.
+    /// - `owner`: The account owning the files.
+    /// - `action`: The action taken, checked against the scope, expiry and quota of the authorization.
     ///
     /// Result:
     /// - bool: True means there is permission, false means there is no permission.
-    pub fn check_permission(operator: AccountOf<T>, owner: AccountOf<T>) -> bool {
-        if owner == operator || T::OssFindAuthor::is_authorized(owner, operator) {
+    pub fn check_permission(operator: AccountOf<T>, owner: AccountOf<T>, action: OssAction) -> bool {
+        if owner == operator || T::OssFindAuthor::authorize_action(owner, operator, action) {
             return true;
         }
         false
     }
 
+    /// helper: Object key check.
+    /// Keys are `/`-delimited segments of `BUCKET_ALLOW_CHAR` characters,
+    /// none of them empty, `.` or `..`.
+    pub(super) fn check_object_key(key: &[u8]) -> bool {
+        !key.is_empty() && key.split(|elem| *elem == b'/').all(|segment| {
+            !segment.is_empty()
+                && segment != b"."
+                && segment != b".."
+                && segment.iter().all(|elem| BUCKET_ALLOW_CHAR.contains(elem))
+        })
+    }
+
+    /// helper: Whether `owner` holds the file in the territory.
+    pub(super) fn holds_file_in(owner: &AccountOf<T>, territory_name: &TerrName, file_hash: &Hash) -> bool {
+        <File<T>>::get(file_hash).is_some_and(|file| {
+            file.owner.iter().any(|user_brief| &user_brief.user == owner && &user_brief.territory_name == territory_name)
+        })
+    }
+
+    /// helper: The deposit reserved for an object under `key`.
+    pub(super) fn object_deposit(key: &[u8]) -> BalanceOf<T> {
+        T::ObjectDepositPerByte::get()
+            .saturating_mul((key.len() as u32).into())
+            .saturating_add(T::ObjectDepositBase::get())
+    }
+
+    /// helper: Forget that `key` names the file in the namespace.
+    pub(super) fn forget_object_key(namespace: &(AccountOf<T>, TerrName), file_hash: &Hash, key: &ObjectKey<T>) {
+        <FileObjects<T>>::mutate_exists(namespace, file_hash, |keys| {
+            if let Some(list) = keys {
+                list.retain(|named| named != key);
+                if list.is_empty() {
+                    *keys = None;
+                }
+            }
+        });
+    }
+
+    /// helper: Removes the keys naming the file in the namespace of the owner's territory
+    /// and returns their deposits.
+    pub(super) fn remove_file_objects(owner: &AccountOf<T>, territory_name: &TerrName, file_hash: &Hash) -> Weight {
+        let namespace = (owner.clone(), territory_name.clone());
+        let keys = <FileObjects<T>>::take(&namespace, file_hash).unwrap_or_default();
+        for key in keys.iter() {
+            if let Some(info) = <Objects<T>>::take(&namespace, ObjectPath(key.clone())) {
+                T::Currency::unreserve(&info.depositor, info.deposit);
+            }
+        }
+
+        let count = keys.len() as u64;
+        T::DbWeight::get().reads_writes(1 + 2 * count, 1 + 2 * count)
+    }
+
+    /// The most `remove_file_objects` weighs, for the calls that may remove a file's keys.
+    pub fn remove_file_objects_weight() -> Weight {
+        let count = MAX_FILE_OBJECTS as u64;
+        T::DbWeight::get().reads_writes(1 + 2 * count, 1 + 2 * count)
+    }
+
+    /// Returns the object `key` names in the owner's territory.
+    pub fn object(
+        owner: AccountOf<T>,
+        territory_name: TerrName,
+        key: Vec<u8>,
+    ) -> Option<ObjectInfo<AccountOf<T>, BalanceOf<T>, BlockNumberFor<T>>> {
+        let key = ObjectKey::<T>::try_from(key).ok()?;
+        <Objects<T>>::get((owner, territory_name), ObjectPath(key))
+    }
+
+    /// Returns up to `limit` objects of the owner's territory whose key starts with `prefix`,
+    /// following `start_after`, in key order.
+    ///
+    /// With a `delimiter`, the keys that contain it after the prefix are listed once, as the
+    /// prefix they share up to and including it. The last key or common prefix returned is the
+    /// `start_after` of the next page. At most `MAX_OBJECTS_PAGE` entries are returned at once.
+    pub fn list_objects(
+        owner: AccountOf<T>,
+        territory_name: TerrName,
+        prefix: Vec<u8>,
+        delimiter: Option<Vec<u8>>,
+        start_after: Option<Vec<u8>>,
+        limit: u32,
+    ) -> Vec<ListedObject<AccountOf<T>, BalanceOf<T>, BlockNumberFor<T>>> {
+        let namespace = (owner, territory_name);
+        let delimiter = delimiter.filter(|delimiter| !delimiter.is_empty());
+
+        // Keys are stored padded with NULs, so the raw key of the empty key without its padding is
+        // the start of the namespace. The keys starting with some bytes come right after these
+        // bytes, and before these bytes followed by 0xFF, which no key contains.
+        let mut base = <Objects<T>>::hashed_key_for(&namespace, ObjectPath::<T::ObjectKeyLimit>(Default::default()));
+        let key_limit = T::ObjectKeyLimit::get() as usize;
+        base.truncate(base.len() - key_limit);
+        let raw_key = |bytes: &[u8], last: &[u8]| [&base[..], bytes, last].concat();
+
+        let mut from = raw_key(&prefix, &[]);
+        if let Some(start_after) = start_after {
+            // A common prefix stands for all the keys sharing it.
+            let after = match Self::common_prefix(&start_after, &prefix, delimiter.as_deref()) {
+                Some(common) => raw_key(&common, &[0xFF]),
+                None => raw_key(&start_after, &vec![0; key_limit.saturating_sub(start_after.len())]),
+            };
+            from = from.max(after);
+        }
+
+        let limit = limit.min(MAX_OBJECTS_PAGE) as usize;
+        let mut objects = <Objects<T>>::iter_prefix_from(&namespace, from);
+        let mut entries = Vec::new();
+        while entries.len() < limit {
+            let Some((ObjectPath(key), info)) = objects.next() else { break };
+            if !key.starts_with(&prefix) {
+                break;
+            }
+            match Self::common_prefix(&key, &prefix, delimiter.as_deref()) {
+                Some(common) => {
+                    objects = <Objects<T>>::iter_prefix_from(&namespace, raw_key(&common, &[0xFF]));
+                    entries.push(ListedObject::CommonPrefix(common));
+                },
+                None => entries.push(ListedObject::Object { key: key.into_inner(), info }),
+            }
+        }
+
+        entries
+    }
+
+    /// helper: The prefix `key` shares with the other keys under `prefix`, up to and including
+    /// the first `delimiter` after it.
+    fn common_prefix(key: &[u8], prefix: &[u8], delimiter: Option<&[u8]>) -> Option<Vec<u8>> {
+        let delimiter = delimiter?;
+        let rest = key.strip_prefix(prefix)?;
+        let at = rest.windows(delimiter.len()).position(|window| window == delimiter)?;
+        Some(key[..prefix.len() + at + delimiter.len()].to_vec())
+    }
+
     // FIXME: Will this function still be used?
     #[allow(dead_code)]
     pub(super) fn check_name_spec(name: Vec<u8>) -> bool {
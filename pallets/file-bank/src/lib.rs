@@ -18,14 +18,14 @@
 //! * `buyfile` - Buy file with download fee.
 #![cfg_attr(not(feature = "std"), no_std)]
 
-// #[cfg(test)]
-// mod mock;
+#[cfg(test)]
+mod mock;
 
-// #[cfg(test)]
-// mod tests;
+#[cfg(test)]
+mod tests;
 
 use frame_support::traits::{
-	FindAuthor, Randomness,
+	Currency, FindAuthor, Randomness, ReservableCurrency,
 	StorageVersion,
 };
 // use sc_network::Multiaddr;
@@ -60,10 +60,11 @@ use frame_system::pallet_prelude::*;
 use scale_info::TypeInfo;
 use cp_cess_common::*;
 use pallet_storage_handler::StorageHandle;
+use pallet_cess_treasury::InsuranceHandle;
 use cp_scheduler_credit::SchedulerCreditCounter;
 use sp_runtime::{
 	traits::{
-		BlockNumberProvider, CheckedAdd
+		BlockNumberProvider, CheckedAdd, Saturating,
 	},
 	RuntimeDebug, SaturatedConversion,
 };
@@ -76,12 +77,14 @@ use sp_std::{
 };
 use pallet_sminer::MinerControl;
 use pallet_tee_worker::TeeWorkerHandler;
-use pallet_oss::OssFindAuthor;
+use pallet_oss::{OssAction, OssFindAuthor};
 use ces_types::WorkerPublicKey;
 
 pub use weights::WeightInfo;
 
 type AccountOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> =
+	<<T as pallet::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
@@ -151,6 +154,22 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type MissionCount: Get<u32> + Clone + Eq + PartialEq;
+		// Used to compensate owners of lost files
+		type InsuranceHandle: InsuranceHandle<Self::AccountId>;
+		/// How long a file stays missing before its owners can claim compensation.
+		#[pallet::constant]
+		type MissingGracePeriod: Get<BlockNumberFor<Self>>;
+		/// The maximum length of an object key.
+		#[pallet::constant]
+		type ObjectKeyLimit: Get<u32> + Clone + Eq + PartialEq;
+		/// The currency the deposits of objects are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The deposit reserved for each object.
+		#[pallet::constant]
+		type ObjectDepositBase: Get<BalanceOf<Self>>;
+		/// The deposit reserved for each byte of an object key.
+		#[pallet::constant]
+		type ObjectDepositPerByte: Get<BalanceOf<Self>>;
 	}
 
 	#[pallet::event]
@@ -180,6 +199,16 @@ pub mod pallet {
 		CalculateReport { miner: AccountOf<T>, file_hash: Hash },
 
 		TerritoryFileDelivery { file_hash: Hash, new_territory: TerrName },
+		// Too few fragments of a segment are available to restore it.
+		FileMissing { file_hash: Hash },
+		// Enough fragments of every segment are available again.
+		FileRecovered { file_hash: Hash },
+
+		DataLossCompensated { owner: AccountOf<T>, file_hash: Hash, amount: u128 },
+
+		ObjectPut { operator: AccountOf<T>, owner: AccountOf<T>, territory_name: TerrName, key: ObjectKey<T>, file_hash: Hash },
+
+		ObjectDeleted { operator: AccountOf<T>, owner: AccountOf<T>, territory_name: TerrName, key: ObjectKey<T> },
 	}
 
 	#[pallet::error]
@@ -249,6 +278,16 @@ pub mod pallet {
 		RulesNotAllowed,
 		/// The status of the file needs to be Active
 		NotActive,
+		/// The file is not missing
+		NotMissing,
+		/// The file has not been missing for long enough
+		MissingGracePeriod,
+		/// Compensation for this file was already claimed
+		AlreadyCompensated,
+		/// The object key is not `/`-delimited segments of allowed characters
+		InvalidObjectKey,
+		/// The file is already named by as many keys of the namespace as allowed
+		TooManyObjectKeys,
 	}
 
 	#[pallet::storage]
@@ -286,6 +325,42 @@ pub mod pallet {
 	pub(super) type TaskFailedCount<T: Config> = 
 		StorageMap<_, Blake2_128Concat, AccountOf<T>, u8, ValueQuery>;
 
+	/// The block a missing file was found missing at.
+	#[pallet::storage]
+	#[pallet::getter(fn missing_since)]
+	pub(super) type MissingSince<T: Config> =
+		StorageMap<_, Blake2_128Concat, Hash, BlockNumberFor<T>>;
+
+	/// The compensation paid to each owner of a lost file.
+	#[pallet::storage]
+	#[pallet::getter(fn insurance_claims)]
+	pub(super) type InsuranceClaims<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, Hash, Blake2_128Concat, AccountOf<T>, u128>;
+
+	/// The objects in the namespace of each owner's territory, in key order.
+	#[pallet::storage]
+	#[pallet::getter(fn objects)]
+	pub(super) type Objects<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(AccountOf<T>, TerrName),
+		Identity,
+		ObjectPath<T::ObjectKeyLimit>,
+		ObjectInfo<AccountOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+	>;
+
+	/// The keys naming each file in the namespace of each owner's territory.
+	#[pallet::storage]
+	#[pallet::getter(fn file_objects)]
+	pub(super) type FileObjects<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		(AccountOf<T>, TerrName),
+		Blake2_128Concat,
+		Hash,
+		BoundedVec<ObjectKey<T>, ConstU32<MAX_FILE_OBJECTS>>,
+	>;
+
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
@@ -377,7 +452,14 @@ pub mod pallet {
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			// Check if you have operation permissions.
-			ensure!(Self::check_permission(sender.clone(), user_brief.user.clone()), Error::<T>::NoPermission);
+			ensure!(
+				Self::check_permission(
+					sender.clone(),
+					user_brief.user.clone(),
+					OssAction::Upload { territory: &user_brief.territory_name, size: file_size },
+				),
+				Error::<T>::NoPermission
+			);
 			// Check file specifications.
 			ensure!(Self::check_file_spec(&deal_info), Error::<T>::SpecError);
 			// Check whether the user-defined name meets the rules.
@@ -402,7 +484,10 @@ pub mod pallet {
 
 		#[pallet::call_index(2)]
 		#[transactional]
-		#[pallet::weight(<T as pallet::Config>::WeightInfo::calculate_report())]
+		#[pallet::weight(
+			<T as pallet::Config>::WeightInfo::calculate_report()
+				.saturating_add(Pallet::<T>::remove_file_objects_weight())
+		)]
 		pub fn territory_file_delivery(
 			origin: OriginFor<T>,
 			user: AccountOf<T>,
@@ -410,7 +495,14 @@ pub mod pallet {
 			target_territory: TerrName,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			ensure!(Self::check_permission(sender.clone(), user.clone()), Error::<T>::NoPermission);
+			ensure!(
+				Self::check_permission(
+					sender.clone(),
+					user.clone(),
+					OssAction::TerritoryDelivery { territory: &target_territory },
+				),
+				Error::<T>::NoPermission
+			);
 			ensure!(Self::check_is_file_owner(&user, &file_hash), Error::<T>::NotOwner);
 			let mut file_info = <File<T>>::try_get(&file_hash).map_err(|_| Error::<T>::NonExistent)?;
 			ensure!(file_info.stat == FileState::Active, Error::<T>::NotActive);
@@ -423,6 +515,10 @@ pub mod pallet {
 						.checked_mul(file_info.segment_list.len() as u128).ok_or(Error::<T>::Overflow)?;
 					T::StorageHandle::sub_territory_used_space(&user, &user_brief.territory_name, space)?;
 					T::StorageHandle::add_territory_used_space(&user, &target_territory, space)?;
+					// The keys of the old territory can't name a file kept in another one.
+					if user_brief.territory_name != target_territory {
+						Self::remove_file_objects(&user, &user_brief.territory_name, &file_hash);
+					}
 					user_brief.territory_name = target_territory.clone();
 				}
 			}
@@ -731,13 +827,21 @@ pub mod pallet {
 		#[transactional]
 		#[pallet::weight({
 			<T as pallet::Config>::WeightInfo::delete_file()
+				.saturating_add(Pallet::<T>::remove_file_objects_weight())
 		})]
 		pub fn delete_file(origin: OriginFor<T>, owner: AccountOf<T>, file_hash: Hash) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			// Check if you have operation permissions.
-			ensure!(Self::check_permission(sender.clone(), owner.clone()), Error::<T>::NoPermission);
-
 			let file = <File<T>>::try_get(&file_hash).map_err(|_| Error::<T>::NonExistent)?;
+			// Check if you have operation permissions in the territory the owner keeps the file in.
+			let territory = file.owner
+				.iter()
+				.find(|user_brief| user_brief.user == owner)
+				.map(|user_brief| user_brief.territory_name.clone())
+				.unwrap_or_default();
+			ensure!(
+				Self::check_permission(sender.clone(), owner.clone(), OssAction::Delete { territory: &territory }),
+				Error::<T>::NoPermission
+			);
 			let _ = Self::delete_user_file(&file_hash, &owner, &file)?;
 			Self::remove_user_hold_file_list(&file_hash, &owner)?;
 			Self::deposit_event(Event::<T>::DeleteFile{ operator: sender, owner, file_hash });
@@ -855,6 +959,8 @@ pub mod pallet {
 
 								<RestoralOrder<T>>::insert(&restoral_fragment, restoral_order);
 
+								Self::refresh_file_state(&file_hash, file);
+
 								Self::deposit_event(Event::<T>::GenerateRestoralOrder{ miner: sender, fragment_hash: restoral_fragment});
 
 								return Ok(())
@@ -963,6 +1069,8 @@ pub mod pallet {
 
 								<RestoralOrder<T>>::insert(&restoral_fragment, restoral_order);
 
+								Self::refresh_file_state(&file_hash, file);
+
 								return Ok(())
 							}
 						}
@@ -1041,6 +1149,7 @@ pub mod pallet {
 									fragment.tag = Some(now);
 									fragment.avail = true;
 									fragment.miner = sender.clone();
+									Self::refresh_file_state(&order.file_hash, file);
 									return Ok(());
 								}
 							}
@@ -1071,6 +1180,131 @@ pub mod pallet {
 
 			Ok(())
 		}
+
+		/// Claim Compensation for a Lost File
+		///
+		/// This function allows an owner of a file that has stayed missing for longer than the
+		/// grace period to be compensated from the insurance fund. The compensation is the price
+		/// of the file size for the remaining lifetime of the owner's territory, and is paid once
+		/// per owner.
+		///
+		/// Parameters:
+		/// - `origin`: The owner of the file.
+		/// - `file_hash`: The hash of the missing file.
+		#[pallet::call_index(23)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::claim_data_loss_compensation())]
+		pub fn claim_data_loss_compensation(origin: OriginFor<T>, file_hash: Hash) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let file = <File<T>>::try_get(&file_hash).map_err(|_| Error::<T>::NonExistent)?;
+			ensure!(file.stat == FileState::Missing, Error::<T>::NotMissing);
+			let user_brief = file.owner.iter().find(|brief| brief.user == sender).ok_or(Error::<T>::NotOwner)?;
+			ensure!(!<InsuranceClaims<T>>::contains_key(&file_hash, &sender), Error::<T>::AlreadyCompensated);
+
+			let since = <MissingSince<T>>::try_get(&file_hash).map_err(|_| Error::<T>::BugInvalid)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= since.saturating_add(T::MissingGracePeriod::get()), Error::<T>::MissingGracePeriod);
+
+			let value = T::StorageHandle::remaining_value(&sender, &user_brief.territory_name, file.file_size)?;
+			let amount = T::InsuranceHandle::compensate(&sender, value)?;
+			<InsuranceClaims<T>>::insert(&file_hash, &sender, amount);
+
+			Self::deposit_event(Event::<T>::DataLossCompensated { owner: sender, file_hash, amount });
+
+			Ok(())
+		}
+
+		/// Put Object
+		///
+		/// This function names a file with an S3 style key in the namespace of one of the owner's
+		/// territories, replacing the file the key named before. A deposit for the key is reserved
+		/// from the caller, and returned once the key is deleted, or removed with its file.
+		///
+		/// Parameters:
+		/// - `origin`: The owner, or an operator authorized to upload for them.
+		/// - `owner`: The owner of the territory and of the file.
+		/// - `territory_name`: The territory whose namespace holds the key.
+		/// - `key`: The object key, `/`-delimited segments of `BUCKET_ALLOW_CHAR` characters.
+		/// - `file_hash`: The file the key names, which the owner must hold in the territory.
+		#[pallet::call_index(24)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::put_object())]
+		pub fn put_object(
+			origin: OriginFor<T>,
+			owner: AccountOf<T>,
+			territory_name: TerrName,
+			key: ObjectKey<T>,
+			file_hash: Hash,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				Self::check_permission(
+					sender.clone(),
+					owner.clone(),
+					OssAction::Upload { territory: &territory_name, size: 0 },
+				),
+				Error::<T>::NoPermission
+			);
+			ensure!(Self::check_object_key(&key), Error::<T>::InvalidObjectKey);
+			ensure!(<File<T>>::contains_key(&file_hash), Error::<T>::NonExistent);
+			ensure!(Self::holds_file_in(&owner, &territory_name, &file_hash), Error::<T>::NotOwner);
+
+			let namespace = (owner.clone(), territory_name.clone());
+			let path = ObjectPath(key.clone());
+			if let Some(replaced) = <Objects<T>>::take(&namespace, &path) {
+				T::Currency::unreserve(&replaced.depositor, replaced.deposit);
+				Self::forget_object_key(&namespace, &replaced.file_hash, &key);
+			}
+			<FileObjects<T>>::try_mutate(&namespace, &file_hash, |keys| {
+				keys.get_or_insert_with(Default::default).try_push(key.clone())
+			})
+			.map_err(|_| Error::<T>::TooManyObjectKeys)?;
+			let deposit = Self::object_deposit(&key);
+			T::Currency::reserve(&sender, deposit)?;
+
+			let modified_at = <frame_system::Pallet<T>>::block_number();
+			<Objects<T>>::insert(&namespace, &path, ObjectInfo { file_hash, modified_at, depositor: sender.clone(), deposit });
+
+			Self::deposit_event(Event::<T>::ObjectPut { operator: sender, owner, territory_name, key, file_hash });
+
+			Ok(())
+		}
+
+		/// Delete Object
+		///
+		/// This function removes a key from the namespace of one of the owner's territories and
+		/// returns its deposit. The file it named is kept, `delete_file` deletes it.
+		///
+		/// Parameters:
+		/// - `origin`: The owner, or an operator authorized to delete for them.
+		/// - `owner`: The owner of the territory.
+		/// - `territory_name`: The territory whose namespace holds the key.
+		/// - `key`: The object key to remove.
+		#[pallet::call_index(25)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::delete_object())]
+		pub fn delete_object(
+			origin: OriginFor<T>,
+			owner: AccountOf<T>,
+			territory_name: TerrName,
+			key: ObjectKey<T>,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(
+				Self::check_permission(sender.clone(), owner.clone(), OssAction::Delete { territory: &territory_name }),
+				Error::<T>::NoPermission
+			);
+
+			let namespace = (owner.clone(), territory_name.clone());
+			let info = <Objects<T>>::take(&namespace, ObjectPath(key.clone())).ok_or(Error::<T>::NonExistent)?;
+			T::Currency::unreserve(&info.depositor, info.deposit);
+			Self::forget_object_key(&namespace, &info.file_hash, &key);
+
+			Self::deposit_event(Event::<T>::ObjectDeleted { operator: sender, owner, territory_name, key });
+
+			Ok(())
+		}
 	}
 }
 
@@ -1080,4 +1314,43 @@ impl<T: Config> BlockNumberProvider for Pallet<T> {
 	fn current_block_number() -> Self::BlockNumber {
 		<frame_system::Pallet<T>>::block_number()
 	}
-}
\ No newline at end of file
+}
+
+/// Lets other pallets look up the fragments of stored files.
+pub trait FragmentLookup {
+	/// Whether `fragment_hash` is a fragment of the stored file `file_hash`.
+	fn fragment_exists(file_hash: &Hash, fragment_hash: &Hash) -> bool;
+
+	/// Stores a file made of a single fragment.
+	#[cfg(feature = "runtime-benchmarks")]
+	fn insert_fragment(file_hash: Hash, fragment_hash: Hash);
+}
+
+impl<T: Config> FragmentLookup for Pallet<T> {
+	fn fragment_exists(file_hash: &Hash, fragment_hash: &Hash) -> bool {
+		<File<T>>::get(file_hash).is_some_and(|file| {
+			file.segment_list
+				.iter()
+				.any(|segment| segment.fragment_list.iter().any(|fragment| &fragment.hash == fragment_hash))
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn insert_fragment(file_hash: Hash, fragment_hash: Hash) {
+		let miner = AccountOf::<T>::decode(&mut sp_runtime::traits::TrailingZeroInput::zeroes())
+			.expect("infinite input; no invalid input; qed");
+		let fragment = FragmentInfo::<T> { hash: fragment_hash, avail: true, tag: None, miner };
+		let segment = SegmentInfo::<T> {
+			hash: fragment_hash,
+			fragment_list: vec![fragment].try_into().expect("a segment holds a fragment; qed"),
+		};
+		let file = FileInfo::<T> {
+			segment_list: vec![segment].try_into().expect("a file holds a segment; qed"),
+			owner: Default::default(),
+			file_size: FRAGMENT_SIZE,
+			completion: <frame_system::Pallet<T>>::block_number(),
+			stat: FileState::Active,
+		};
+		<File<T>>::insert(&file_hash, file);
+	}
+}
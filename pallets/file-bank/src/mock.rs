@@ -1,494 +1,409 @@
-// // This file is part of Substrate.
-
-// // Copyright (C) 2020-2021 Parity Technologies (UK) Ltd.
-// // SPDX-License-Identifier: Apache-2.0
-
-// // Licensed under the Apache License, Version 2.0 (the "License");
-// // you may not use this file except in compliance with the License.
-// // You may obtain a copy of the License at
-// //
-// // 	http://www.apache.org/licenses/LICENSE-2.0
-// //
-// // Unless required by applicable law or agreed to in writing, software
-// // distributed under the License is distributed on an "AS IS" BASIS,
-// // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
-// // See the License for the specific language governing permissions and
-// // limitations under the License.
-
-// //! Test utilities
-
-// use super::*;
-// use crate as file_bank;
-// use frame_support::{
-//     parameter_types,
-//     weights::Weight,
-//     traits::{ConstU32, EqualPrivilegeOnly, OneSessionHandler},
-// };
-// use frame_system::{EnsureRoot};
-// use sp_core::{H256, sr25519::Signature};
-// use sp_runtime::{
-//     testing::{Header, TestXt, UintAuthorityId},
-//     traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup, IdentifyAccount, Verify},
-//     Perbill,
-// };
-// use frame_support_test::TestRandomness;
-// use frame_benchmarking::account;
-// use pallet_cess_staking::{StashOf, Exposure, ExposureOf};
-// use frame_election_provider_support::{
-//     onchain, SequentialPhragmen, VoteWeight,
-// };
-// use sp_staking::{
-//     EraIndex, SessionIndex,
-// };
-// use std::{
-// 	cell::RefCell,
-// 	convert::TryFrom,
-// };
-// use cp_scheduler_credit::SchedulerStashAccountFinder;
-// /// The AccountId alias in this test module.
-// pub(crate) type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
-// type BlockNumber = u64;
-// type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
-// type Block = frame_system::mocking::MockBlock<Test>;
-// type Balance = u64;
-
-// frame_support::construct_runtime!(
-// 	pub enum Test where
-// 		Block = Block,
-// 		NodeBlock = Block,
-// 		UncheckedExtrinsic = UncheckedExtrinsic,
-// 	{
-// 		System: frame_system,
-// 		Balances: pallet_balances,
-// 		FileBank: file_bank,
-// 		Sminer: pallet_sminer,
-// 		Scheduler: pallet_scheduler,
-// 		Timestamp: pallet_timestamp,
-// 		Staking: pallet_cess_staking,
-// 		Session: pallet_session,
-// 		Historical: pallet_session::historical,
-// 		BagsList: pallet_bags_list,
-// 		TeeWorker: pallet_tee_worker,
-// 		SchedulerCredit: pallet_scheduler_credit,
-// 		Oss: pallet_oss,
-// 		Preimage: pallet_preimage
-// 	}
-// );
-
-// impl pallet_oss::Config for Test {
-// 	type RuntimeEvent = RuntimeEvent;
-// 	type WeightInfo = ();
-// }
-
-// parameter_types! {
-// 	#[derive(Clone, PartialEq, Eq)]
-// 	pub const StringLimit: u32 = 100;
-// 	#[derive(Clone, PartialEq, Eq)]
-// 	pub const OneHours: u32 = 60 * 10;
-// 	#[derive(Clone, PartialEq, Eq)]
-// 	pub const OneDay: u32 = 60 * 10 * 24;
-// }
-
-// parameter_types! {
-// 	pub const MinimumPeriod: u64 = 1;
-// }
-
-// impl pallet_timestamp::Config for Test {
-//     type Moment = u64;
-//     type OnTimestampSet = ();
-//     type MinimumPeriod = MinimumPeriod;
-//     type WeightInfo = ();
-// }
-
-// parameter_types! {
-// 	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) * BlockWeights::get().max_block;
-// }
-
-// impl pallet_scheduler::Config for Test {
-// 	type RuntimeEvent = RuntimeEvent;
-// 	type RuntimeOrigin = RuntimeOrigin;
-// 	type PalletsOrigin = OriginCaller;
-// 	type RuntimeCall = RuntimeCall;
-// 	type MaximumWeight = MaximumSchedulerWeight;
-// 	type ScheduleOrigin = EnsureRoot<AccountId>;
-// 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
-// 	type MaxScheduledPerBlock = ();
-// 	type WeightInfo = ();
-// 	type Preimages = Preimage;
-// }
-
-// parameter_types! {
-// 	pub const RewardPalletId: PalletId = PalletId(*b"sminerpt");
-// 	pub const MultipleFines: u8 = 7;
-// 	pub const DepositBufferPeriod: u32 = 3;
-// 	pub const ItemLimit: u32 = 1024;
-// 	pub const MaxAward: u128 = 1_306_849_000_000_000_000;
-// 	pub const LockInPeriod: u8 = 2;
-// }
-
-// impl pallet_sminer::Config for Test {
-// 	type Currency = Balances;
-// 	// The ubiquitous event type.
-// 	type RuntimeEvent = RuntimeEvent;
-// 	type PalletId = RewardPalletId;
-// 	type SScheduler = Scheduler;
-// 	type AScheduler = Scheduler;
-// 	type SPalletsOrigin = OriginCaller;
-// 	type SProposal = RuntimeCall;
-// 	type WeightInfo = ();
-// 	type ItemLimit = ItemLimit;
-// 	type MultipleFines = MultipleFines;
-// 	type DepositBufferPeriod = DepositBufferPeriod;
-// 	type OneDayBlock = OneDay;
-// 	type MaxAward = MaxAward;
-// 	type LockInPeriod = LockInPeriod;
-// }
-
-// parameter_types! {
-// 	pub const TeeWorkerPalletId: PalletId = PalletId(*b"filmpdpt");
-// 	#[derive(Clone, PartialEq, Eq)]
-// 	pub const SchedulerMaximum: u32 = 10000;
-// 	#[derive(Clone, PartialEq, Eq)]
-// 	pub const ParamsLimit: u32 = 359;
-// }
-
-// impl pallet_tee_worker::Config for Test {
-// 	type RuntimeEvent = RuntimeEvent;
-// 	type Currency = Balances;
-// 	type TeeWorkerPalletId = TeeWorkerPalletId;
-// 	type StringLimit = StringLimit;
-// 	type WeightInfo = ();
-// 	type CreditCounter = SchedulerCredit;
-// 	type SchedulerMaximum = SchedulerMaximum;
-// 	type ParamsLimit = ParamsLimit;
-// }
-
-// const THRESHOLDS: [sp_npos_elections::VoteWeight; 9] =
-//     [10, 20, 30, 40, 50, 60, 1_000, 2_000, 10_000];
-
-// parameter_types! {
-// 	pub static BagThresholds: &'static [sp_npos_elections::VoteWeight] = &THRESHOLDS;
-// 	pub static MaxNominations: u32 = 16;
-// }
-
-// impl pallet_bags_list::Config for Test {
-// 	type RuntimeEvent = RuntimeEvent;
-// 	type WeightInfo = ();
-// 	type ScoreProvider = Staking;
-// 	type BagThresholds = BagThresholds;
-// 	type Score = VoteWeight;
-// }
-
-// parameter_types! {
-// 	pub static SessionsPerEra: SessionIndex = 3;
-// 	pub static SlashDeferDuration: EraIndex = 0;
-// 	pub static Period: BlockNumber = 5;
-// 	pub static Offset: BlockNumber = 0;
-// }
-
-// sp_runtime::impl_opaque_keys! {
-// 	pub struct SessionKeys {
-// 		pub other: OtherSessionHandler,
-// 	}
-// }
-// impl pallet_session::Config for Test {
-// 	type RuntimeEvent = RuntimeEvent;
-// 	type ValidatorId = AccountId;
-// 	type ValidatorIdOf = StashOf<Test>;
-// 	type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
-// 	type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
-// 	type SessionManager = pallet_session::historical::NoteHistoricalRoot<Test, Staking>;
-// 	type SessionHandler = (OtherSessionHandler, );
-// 	type Keys = SessionKeys;
-// 	type WeightInfo = ();
-// }
-
-// impl pallet_session::historical::Config for Test {
-// 	type FullIdentification = Exposure<AccountId, Balance>;
-// 	type FullIdentificationOf = ExposureOf<Test>;
-// }
-
-// thread_local! {
-// 	pub static REWARD_REMAINDER_UNBALANCED: RefCell<u128> = RefCell::new(0);
-// }
-
-// pub struct OnChainSeqPhragmen;
-
-// impl onchain::Config for OnChainSeqPhragmen {
-// 	type System = Test;
-// 	type Solver = SequentialPhragmen<AccountId, Perbill>;
-// 	type DataProvider = Staking;
-// 	type WeightInfo = ();
-// 	type MaxWinners = ConstU32<100>;
-// 	type VotersBound = ConstU32<{ u32::MAX }>;
-// 	type TargetsBound = ConstU32<{ u32::MAX }>;
-// }
-
-// impl pallet_cess_staking::Config for Test {
-//     const ERAS_PER_YEAR: u64 = 8766;
-//     const FIRST_YEAR_VALIDATOR_REWARDS: BalanceOf<Test> = 618_000_000;
-//     const FIRST_YEAR_SMINER_REWARDS: BalanceOf<Test> = 309_000_000;
-//     const REWARD_DECREASE_RATIO: Perbill = Perbill::from_perthousand(794);
-//     type SminerRewardPool = ();
-//     type Currency = Balances;
-// 	type CurrencyBalance = <Self as pallet_balances::Config>::Balance;
-//     type UnixTime = Timestamp;
-//     type CurrencyToVote = frame_support::traits::SaturatingCurrencyToVote;
-//     type ElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
-//     type GenesisElectionProvider = Self::ElectionProvider;
-//     type MaxNominations = MaxNominations;
-//     type RewardRemainder = ();
-//     type RuntimeEvent = RuntimeEvent;
-//     type Slash = ();
-//     type Reward = ();
-//     type SessionsPerEra = ();
-//     type BondingDuration = ();
-//     type SlashDeferDuration = ();
-//     type SlashCancelOrigin = frame_system::EnsureRoot<Self::AccountId>;
-//     type SessionInterface = Self;
-//     type EraPayout = ();
-//     type NextNewSession = ();
-//     type MaxNominatorRewardedPerValidator = ConstU32<64>;
-//     type OffendingValidatorsThreshold = ();
-//     type VoterList = BagsList;
-//     type TargetList = pallet_cess_staking::UseValidatorsMap<Self>;
-// 	type MaxUnlockingChunks = ConstU32<32>;
-// 	type HistoryDepth = ConstU32<84>;
-//     type OnStakerSlash = ();
-// 	type BenchmarkingConfig = pallet_cess_staking::TestBenchmarkingConfig;
-//     type WeightInfo = ();
-// }
-
-// pub type Extrinsic = TestXt<RuntimeCall, ()>;
-
-// impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
-//     where
-//         RuntimeCall: From<LocalCall>,
-// {
-//     type Extrinsic = Extrinsic;
-//     type OverarchingCall = RuntimeCall;
-// }
-
-// pub struct ExtBuilder;
-
-// impl Default for ExtBuilder {
-//     fn default() -> Self {
-//         Self {}
-//     }
-// }
-
-// pub struct OtherSessionHandler;
-
-// impl OneSessionHandler<AccountId> for OtherSessionHandler {
-//     type Key = UintAuthorityId;
-
-//     fn on_genesis_session<'a, I: 'a>(_: I)
-//         where
-//             I: Iterator<Item=(&'a AccountId, Self::Key)>,
-//             AccountId: 'a,
-//     {}
-
-//     fn on_new_session<'a, I: 'a>(_: bool, _: I, _: I)
-//         where
-//             I: Iterator<Item=(&'a AccountId, Self::Key)>,
-//             AccountId: 'a,
-//     {}
-
-//     fn on_disabled(_validator_index: u32) {}
-// }
-
-// impl sp_runtime::BoundToRuntimeAppPublic for OtherSessionHandler {
-//     type Public = UintAuthorityId;
-// }
-
-// // impl ExtBuilder {
-// // 		#[warn(dead_code)]
-// //     fn build(self) -> sp_io::TestExternalities {
-// //         let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
-// //         let ext = sp_io::TestExternalities::from(storage);
-// //         ext
-// //     }
-// // 		#[warn(dead_code)]
-// //     pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
-// //         self.build().execute_with(test);
-// //     }
-// // }
-
-// impl frame_system::offchain::SigningTypes for Test {
-//     type Public = <Signature as Verify>::Signer;
-//     type Signature = Signature;
-// }
-
-// impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
-//     where
-//         RuntimeCall: From<LocalCall>,
-// {
-//     fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
-//         call: RuntimeCall,
-//         _public: <Signature as Verify>::Signer,
-//         _account: AccountId,
-//         nonce: u64,
-//     ) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
-//         Some((call, (nonce, ())))
-//     }
-// }
-
-// parameter_types! {
-// 	pub const BlockHashCount: u64 = 250;
-// 	pub BlockWeights: frame_system::limits::BlockWeights =
-// 		frame_system::limits::BlockWeights::simple_max(frame_support::weights::Weight::from_parts(1024));
-// }
-
-// impl frame_system::Config for Test {
-//     type BaseCallFilter = frame_support::traits::Everything;
-//     type BlockWeights = ();
-//     type BlockLength = ();
-//     type RuntimeOrigin = RuntimeOrigin;
-//     type RuntimeCall = RuntimeCall;
-//     type Index = u64;
-//     type BlockNumber = u64;
-//     type Hash = H256;
-//     type Hashing = BlakeTwo256;
-//     type AccountId = AccountId;
-//     type Lookup = IdentityLookup<Self::AccountId>;
-//     type Header = Header;
-//     type RuntimeEvent = RuntimeEvent;
-//     type BlockHashCount = BlockHashCount;
-//     type DbWeight = ();
-//     type Version = ();
-//     type PalletInfo = PalletInfo;
-//     type AccountData = pallet_balances::AccountData<u64>;
-//     type OnNewAccount = ();
-//     type OnKilledAccount = ();
-//     type SystemWeightInfo = ();
-//     type SS58Prefix = ();
-//     type OnSetCode = ();
-//     type MaxConsumers = ConstU32<16>;
-// }
-
-// pub struct MockStashAccountFinder<AccountId>(PhantomData<AccountId>);
-
-// impl<AccountId: Clone> SchedulerStashAccountFinder<AccountId>
-// for MockStashAccountFinder<AccountId>
-// {
-// 	fn find_stash_account_id(ctrl_account_id: &AccountId) -> Option<AccountId> {
-// 		Some(ctrl_account_id.clone())
-// 	}
-// }
-
-// parameter_types! {
-// 	pub const PeriodDuration: BlockNumber = 64_000;
-// }
-// impl pallet_scheduler_credit::Config for Test {
-// 	type StashAccountFinder = MockStashAccountFinder<Self::AccountId>;
-// 	type PeriodDuration = PeriodDuration;
-// }
-
-// parameter_types! {
-// 	pub const ExistentialDeposit: u64 = 1;
-// }
-
-// impl pallet_balances::Config for Test {
-// 	type Balance = u64;
-// 	type DustRemoval = ();
-// 	type RuntimeEvent = RuntimeEvent;
-// 	type ExistentialDeposit = ExistentialDeposit;
-// 	type AccountStore = System;
-// 	type WeightInfo = ();
-// 	type MaxLocks = ();
-// 	type MaxReserves = ();
-// 	type ReserveIdentifier = [u8; 8];
-// }
-
-// impl pallet_preimage::Config for Test {
-// 	type RuntimeEvent = RuntimeEvent;
-// 	type WeightInfo = ();
-// 	type Currency = ();
-// 	type ManagerOrigin = EnsureRoot<AccountId>;
-// 	type BaseDeposit = ();
-// 	type ByteDeposit = ();
-// }
-
-// parameter_types! {
-// 	pub const FilbakPalletId: PalletId = PalletId(*b"filebank");
-// 	#[derive(Clone, Eq, PartialEq)]
-// 	pub const InvalidLimit: u32 = 100000;
-// 	#[derive(Clone, Eq, PartialEq)]
-// 	pub const BucketLimit: u32 = 1000;
-// 	#[derive(Clone, Eq, PartialEq)]
-// 	pub const NameStrLimit: u32 = 63;
-// 	#[derive(Clone, Eq, PartialEq)]
-// 	pub const MinLength: u32 = 3;
-// 	#[derive(Clone, Eq, PartialEq)]
-// 	pub const FileListLimit: u32 = 500000;
-// 	#[derive(Clone, Eq, PartialEq)]
-// 	pub const FrozenDays: BlockNumber = 60 * 10 * 24 * 7;
-// }
-
-// impl Config for Test {
-// 	type RuntimeEvent = RuntimeEvent;
-// 	type Currency = Balances;
-// 	type WeightInfo = ();
-// 	type RuntimeCall = RuntimeCall;
-// 	type FindAuthor = ();
-// 	type CreditCounter = SchedulerCredit;
-// 	type Scheduler = pallet_tee_worker::Pallet::<Test>;
-// 	type MinerControl = pallet_sminer::Pallet::<Test>;
-// 	type MyRandomness = TestRandomness<Self>;
-// 	type FilbakPalletId = FilbakPalletId;
-// 	type StringLimit = StringLimit;
-// 	type OneDay = OneDay;
-// 	type FileListLimit = FileListLimit;
-// 	type NameStrLimit = NameStrLimit;
-// 	type BucketLimit = BucketLimit;
-// 	type OssFindAuthor = Oss;
-// 	type FrozenDays = FrozenDays;
-// 	type InvalidLimit = InvalidLimit;
-// 	type MinLength = MinLength;
-// }
-
-// pub fn account1() -> AccountId {
-//     account("account1", 0, 0)
-// }
-
-// pub fn account2() -> AccountId {
-//     account("account2", 0, 0)
-// }
-
-// pub fn miner1() -> AccountId {
-//     account("miner1", 0, 0)
-// }
-
-// pub fn stash1() -> AccountId {
-//     account("stash1", 0, 0)
-// }
-
-// pub fn controller1() -> AccountId {
-//     account("controller1", 0, 0)
-// }
-
-// pub fn new_test_ext() -> sp_io::TestExternalities {
-//     let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
-//     pallet_balances::GenesisConfig::<Test> {
-//         balances: vec![
-//             (account1(), 18_000_000_000_000_000_000),
-//             (account2(), 1_000_000_000_000),
-//             (miner1(), 1_000_000_000_000),
-//             (stash1(), 1_000_000_000_000),
-//             (controller1(), 1_000_000_000_000),
-//         ],
-//     }
-//         .assimilate_storage(&mut t)
-//         .unwrap();
-// 		file_bank::GenesisConfig::<Test> {
-// 			price: 30
-// 		}
-// 			.assimilate_storage(&mut t)
-// 			.unwrap();
-//     let mut ext = sp_io::TestExternalities::new(t);
-//     ext.execute_with(|| {
-//         System::set_block_number(1); //must set block_number, otherwise the deposit_event() don't work
-//     });
-//     ext
-// }
-
+//! Test utilities
+
+use super::*;
+use crate as pallet_file_bank;
+use ces_types::TeeSig;
+use cp_bloom_filter::BloomFilter;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU128, ConstU64},
+};
+use sp_core::H256;
+use sp_runtime::{BuildStorage, DispatchError};
+
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		FileBank: pallet_file_bank,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+}
+
+pub struct MockMinerControl;
+impl MinerControl<AccountId, BlockNumber> for MockMinerControl {
+	fn add_miner_idle_space(_: &AccountId, _: Accumulator, _: u64, _: u64, _: TeeSig) -> Result<u128, DispatchError> {
+		Ok(0)
+	}
+	fn delete_idle_update_accu(_: &AccountId, _: Accumulator, _: u64, _: u64, _: TeeSig) -> Result<u64, DispatchError> {
+		Ok(0)
+	}
+	fn delete_idle_update_space(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn add_miner_service_space(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn sub_miner_service_space(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn get_power(_: &AccountId) -> Result<(u128, u128), DispatchError> {
+		Ok((0, 0))
+	}
+	fn miner_is_exist(_: AccountId) -> bool {
+		false
+	}
+	fn get_miner_state(_: &AccountId) -> Result<Vec<u8>, DispatchError> {
+		Err(DispatchError::Other("no miner"))
+	}
+	fn get_staking_account(_: &AccountId) -> Result<AccountId, DispatchError> {
+		Err(DispatchError::Other("no miner"))
+	}
+	fn get_all_miner() -> Result<Vec<AccountId>, DispatchError> {
+		Ok(Vec::new())
+	}
+	fn insert_service_bloom(_: &AccountId, _: Vec<Box<[u8; 256]>>) -> DispatchResult {
+		Ok(())
+	}
+	fn delete_service_bloom(_: &AccountId, _: Vec<Box<[u8; 256]>>) -> DispatchResult {
+		Ok(())
+	}
+	fn lock_space(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn unlock_space(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn unlock_space_direct(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn unlock_space_to_service(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn get_miner_idle_space(_: &AccountId) -> Result<u128, DispatchError> {
+		Ok(0)
+	}
+	fn get_miner_count() -> u32 {
+		0
+	}
+	fn record_snap_shot(_: &AccountId, _: u128, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn clear_punish(_: &AccountId, _: u128, _: u128, _: u8) -> DispatchResult {
+		Ok(())
+	}
+	fn idle_punish(_: &AccountId, _: u128, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn service_punish(_: &AccountId, _: u128, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn force_miner_exit(_: &AccountId) -> DispatchResult {
+		Ok(())
+	}
+	fn update_restoral_target(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn restoral_target_is_exist(_: &AccountId) -> bool {
+		false
+	}
+	fn is_positive(_: &AccountId) -> Result<bool, DispatchError> {
+		Ok(false)
+	}
+	fn is_lock(_: &AccountId) -> Result<bool, DispatchError> {
+		Ok(false)
+	}
+	fn update_miner_state(_: &AccountId, _: &str) -> DispatchResult {
+		Ok(())
+	}
+	fn get_expenders() -> Result<(u64, u64, u64), DispatchError> {
+		Ok((0, 0, 0))
+	}
+	fn get_miner_snapshot(
+		_: &AccountId,
+	) -> Result<(u128, u128, BloomFilter, SpaceProofInfo<AccountId>, TeeSig), DispatchError> {
+		Err(DispatchError::Other("no miner"))
+	}
+	fn increase_replace_space(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn decrease_replace_space(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+}
+
+pub struct MockTeeWorker;
+impl TeeWorkerHandler<AccountId, BlockNumber> for MockTeeWorker {
+	fn can_tag(_: &WorkerPublicKey) -> bool {
+		false
+	}
+	fn can_verify(_: &WorkerPublicKey) -> bool {
+		false
+	}
+	fn can_cert(_: &WorkerPublicKey) -> bool {
+		false
+	}
+	fn contains_scheduler(_: WorkerPublicKey) -> bool {
+		false
+	}
+	fn is_bonded(_: &WorkerPublicKey) -> bool {
+		false
+	}
+	fn get_stash(_: &WorkerPublicKey) -> Result<AccountId, DispatchError> {
+		Err(DispatchError::Other("no worker"))
+	}
+	fn punish_scheduler(_: WorkerPublicKey) -> DispatchResult {
+		Ok(())
+	}
+	fn get_pubkey_list() -> Vec<WorkerPublicKey> {
+		Vec::new()
+	}
+	fn update_work_block(_: BlockNumber, _: &WorkerPublicKey) -> DispatchResult {
+		Ok(())
+	}
+	fn verify_master_sig(_: &sp_core::sr25519::Signature, _: [u8; 32]) -> bool {
+		false
+	}
+}
+
+parameter_types! {
+	/// What `remaining_value` gives for any file.
+	pub static RemainingValue: u128 = 1_000;
+	/// The balance of the mock insurance fund.
+	pub static InsuranceFund: u128 = 0;
+}
+
+/// Every territory exists, is owned by whoever asks and is worth [`RemainingValue`].
+pub struct MockStorageHandle;
+impl StorageHandle<AccountId> for MockStorageHandle {
+	fn check_territry_owner(_: &AccountId, _: &TerrName) -> DispatchResult {
+		Ok(())
+	}
+	fn has_territory(_: &AccountId) -> bool {
+		true
+	}
+	fn check_expired(_: &AccountId, _: &TerrName) -> bool {
+		false
+	}
+	fn add_territory_used_space(_: &AccountId, _: &TerrName, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn sub_territory_used_space(_: &AccountId, _: &TerrName, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn add_total_idle_space(_: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn sub_total_idle_space(_: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn add_total_service_space(_: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn sub_total_service_space(_: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn get_total_idle_space() -> u128 {
+		0
+	}
+	fn get_total_service_space() -> u128 {
+		0
+	}
+	fn get_avail_space() -> Result<u128, DispatchError> {
+		Ok(u128::MAX)
+	}
+	fn lock_user_space(_: &AccountId, _: &TerrName, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn unlock_user_space(_: &AccountId, _: &TerrName, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn unlock_and_used_user_space(_: &AccountId, _: &TerrName, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn get_user_avail_space(_: &AccountId, _: &TerrName) -> Result<u128, DispatchError> {
+		Ok(u128::MAX)
+	}
+	fn frozen_task() -> (Weight, Vec<(AccountId, TerrName)>) {
+		(Weight::zero(), Vec::new())
+	}
+	fn remaining_value(_: &AccountId, _: &TerrName, _: u128) -> Result<u128, DispatchError> {
+		Ok(RemainingValue::get())
+	}
+}
+
+/// Pays compensations out of [`InsuranceFund`].
+pub struct MockInsurance;
+impl InsuranceHandle<AccountId> for MockInsurance {
+	fn compensate(_: &AccountId, amount: u128) -> Result<u128, DispatchError> {
+		let paid = amount.min(InsuranceFund::get());
+		InsuranceFund::set(InsuranceFund::get() - paid);
+		Ok(paid)
+	}
+}
+
+pub struct MockCreditCounter;
+impl SchedulerCreditCounter<AccountId> for MockCreditCounter {
+	fn increase_point_for_tag(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn increase_point_for_cert(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn increase_point_for_idle_verify(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn increase_point_for_service_verify(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn increase_point_for_replace(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn record_proceed_block_size(_: &AccountId, _: u64) -> DispatchResult {
+		Ok(())
+	}
+	fn record_punishment(_: &AccountId) -> DispatchResult {
+		Ok(())
+	}
+	fn credit_score(_: &AccountId) -> u32 {
+		0
+	}
+}
+
+/// Only owners act for themselves.
+pub struct MockOss;
+impl OssFindAuthor<AccountId> for MockOss {
+	fn is_authorized(owner: AccountId, operator: AccountId) -> bool {
+		owner == operator
+	}
+	fn authorize_action(owner: AccountId, operator: AccountId, _: OssAction) -> bool {
+		owner == operator
+	}
+}
+
+pub struct MockRandomness;
+impl Randomness<Option<H256>, BlockNumber> for MockRandomness {
+	fn random(subject: &[u8]) -> (Option<H256>, BlockNumber) {
+		(Some(H256::from(sp_io::hashing::blake2_256(subject))), 0)
+	}
+}
+
+pub(crate) const MISSING_GRACE: BlockNumber = 100;
+pub(crate) const OBJECT_DEPOSIT_BASE: Balance = 100;
+pub(crate) const OBJECT_DEPOSIT_PER_BYTE: Balance = 1;
+
+parameter_types! {
+	pub const FilbakPalletId: PalletId = PalletId(*b"rewardpt");
+	#[derive(Clone, Eq, PartialEq)]
+	pub const NameStrLimit: u32 = 63;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const SegmentCount: u32 = 10;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const FragmentCount: u32 = FRAGMENT_COUNT;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const OwnerLimit: u32 = 50;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const UserFileLimit: u32 = 500;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const NameMinLength: u32 = 3;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const RestoralOrderLife: u32 = 250;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const MissionCount: u32 = 10 * FRAGMENT_COUNT;
+	pub const MissingGracePeriod: BlockNumber = MISSING_GRACE;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const ObjectKeyLimit: u32 = 128;
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type FilbakPalletId = FilbakPalletId;
+	type FindAuthor = ();
+	type WeightInfo = ();
+	type MinerControl = MockMinerControl;
+	type StorageHandle = MockStorageHandle;
+	type MyRandomness = MockRandomness;
+	type TeeWorkerHandler = MockTeeWorker;
+	type UserFileLimit = UserFileLimit;
+	type OneDay = ConstU64<14400>;
+	type CreditCounter = MockCreditCounter;
+	type OssFindAuthor = MockOss;
+	type NameStrLimit = NameStrLimit;
+	type SegmentCount = SegmentCount;
+	type FragmentCount = FragmentCount;
+	type OwnerLimit = OwnerLimit;
+	type NameMinLength = NameMinLength;
+	type RestoralOrderLife = RestoralOrderLife;
+	type MissionCount = MissionCount;
+	type InsuranceHandle = MockInsurance;
+	type MissingGracePeriod = MissingGracePeriod;
+	type ObjectKeyLimit = ObjectKeyLimit;
+	type Currency = Balances;
+	type ObjectDepositBase = ConstU128<OBJECT_DEPOSIT_BASE>;
+	type ObjectDepositPerByte = ConstU128<OBJECT_DEPOSIT_PER_BYTE>;
+}
+
+pub(crate) const ALICE: AccountId = 1;
+pub(crate) const BOB: AccountId = 2;
+pub(crate) const CHARLIE: AccountId = 3;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1_000_000), (BOB, 1_000_000), (CHARLIE, 1_000_000)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn run_to_block(n: BlockNumber) {
+	System::set_block_number(n);
+}
+
+pub fn territory() -> TerrName {
+	b"t1".to_vec().try_into().unwrap()
+}
+
+/// A file of one segment owned by `owners`, with `available` of its fragments available.
+pub fn file_info(owners: &[AccountId], available: usize) -> FileInfo<Test> {
+	let fragment_list = (0..FRAGMENT_COUNT as usize)
+		.map(|i| FragmentInfo::<Test> {
+			hash: Hash([b"0123456789abcdef"[i]; 64]),
+			avail: i < available,
+			tag: Some(1),
+			miner: 100 + i as u64,
+		})
+		.collect::<Vec<_>>();
+	let owner = owners
+		.iter()
+		.map(|user| UserBrief::<Test> {
+			user: *user,
+			file_name: b"test-file".to_vec().try_into().unwrap(),
+			territory_name: territory(),
+		})
+		.collect::<Vec<_>>();
+
+	FileInfo::<Test> {
+		segment_list: vec![SegmentInfo::<Test> { hash: Hash([b'f'; 64]), fragment_list: fragment_list.try_into().unwrap() }]
+			.try_into()
+			.unwrap(),
+		owner: owner.try_into().unwrap(),
+		file_size: SEGMENT_SIZE,
+		completion: 1,
+		stat: FileState::Active,
+	}
+}
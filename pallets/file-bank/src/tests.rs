@@ -1,3 +1,335 @@
+use super::*;
+use crate::mock::{
+	file_info, new_test_ext, run_to_block, territory, Balances, FileBank, InsuranceFund, RemainingValue, RuntimeEvent,
+	RuntimeOrigin, System, Test, ALICE, MISSING_GRACE, OBJECT_DEPOSIT_BASE, OBJECT_DEPOSIT_PER_BYTE,
+};
+use frame_support::traits::ReservableCurrency;
+use frame_support::{assert_noop, assert_ok};
+
+const FILE_HASH: Hash = Hash([b'e'; 64]);
+
+/// Store a file of one segment owned by `owners`, with `available` of its fragments available.
+fn store_file(owners: &[u64], available: usize) {
+	let mut file = file_info(owners, available);
+	FileBank::refresh_file_state(&FILE_HASH, &mut file);
+	File::<Test>::insert(&FILE_HASH, file);
+}
+
+fn set_fragments_available(available: usize) {
+	File::<Test>::mutate(&FILE_HASH, |file| {
+		let file = file.as_mut().unwrap();
+		for segment in file.segment_list.iter_mut() {
+			for (i, fragment) in segment.fragment_list.iter_mut().enumerate() {
+				fragment.avail = i < available;
+			}
+		}
+		FileBank::refresh_file_state(&FILE_HASH, file);
+	});
+}
+
+#[test]
+fn file_goes_missing_once_a_segment_cannot_be_restored() {
+	new_test_ext().execute_with(|| {
+		let data_fragments = (SEGMENT_SIZE / FRAGMENT_SIZE) as usize;
+		store_file(&[1], FRAGMENT_COUNT as usize);
+		assert_eq!(File::<Test>::get(&FILE_HASH).unwrap().stat, FileState::Active);
+
+		set_fragments_available(data_fragments);
+		assert_eq!(File::<Test>::get(&FILE_HASH).unwrap().stat, FileState::Active);
+		assert_eq!(MissingSince::<Test>::get(&FILE_HASH), None);
+
+		run_to_block(5);
+		set_fragments_available(data_fragments - 1);
+		assert_eq!(File::<Test>::get(&FILE_HASH).unwrap().stat, FileState::Missing);
+		assert_eq!(MissingSince::<Test>::get(&FILE_HASH), Some(5));
+		System::assert_last_event(RuntimeEvent::FileBank(Event::FileMissing { file_hash: FILE_HASH }));
+
+		// Staying missing keeps the first block it went missing at.
+		run_to_block(6);
+		set_fragments_available(0);
+		assert_eq!(MissingSince::<Test>::get(&FILE_HASH), Some(5));
+
+		set_fragments_available(data_fragments);
+		assert_eq!(File::<Test>::get(&FILE_HASH).unwrap().stat, FileState::Active);
+		assert_eq!(MissingSince::<Test>::get(&FILE_HASH), None);
+		System::assert_last_event(RuntimeEvent::FileBank(Event::FileRecovered { file_hash: FILE_HASH }));
+	});
+}
+
+#[test]
+fn only_owners_of_missing_files_can_claim() {
+	new_test_ext().execute_with(|| {
+		InsuranceFund::set(10_000);
+		assert_noop!(
+			FileBank::claim_data_loss_compensation(RuntimeOrigin::signed(1), FILE_HASH),
+			Error::<Test>::NonExistent
+		);
+
+		store_file(&[1], FRAGMENT_COUNT as usize);
+		run_to_block(1 + MISSING_GRACE);
+		assert_noop!(
+			FileBank::claim_data_loss_compensation(RuntimeOrigin::signed(1), FILE_HASH),
+			Error::<Test>::NotMissing
+		);
+
+		set_fragments_available(0);
+		assert_noop!(
+			FileBank::claim_data_loss_compensation(RuntimeOrigin::signed(2), FILE_HASH),
+			Error::<Test>::NotOwner
+		);
+	});
+}
+
+#[test]
+fn claims_wait_for_the_grace_period() {
+	new_test_ext().execute_with(|| {
+		InsuranceFund::set(10_000);
+		store_file(&[1], 0);
+		assert_eq!(MissingSince::<Test>::get(&FILE_HASH), Some(1));
+
+		run_to_block(MISSING_GRACE);
+		assert_noop!(
+			FileBank::claim_data_loss_compensation(RuntimeOrigin::signed(1), FILE_HASH),
+			Error::<Test>::MissingGracePeriod
+		);
+
+		run_to_block(1 + MISSING_GRACE);
+		assert_ok!(FileBank::claim_data_loss_compensation(RuntimeOrigin::signed(1), FILE_HASH));
+	});
+}
+
+#[test]
+fn each_owner_is_compensated_once() {
+	new_test_ext().execute_with(|| {
+		InsuranceFund::set(1_500);
+		RemainingValue::set(1_000);
+		store_file(&[1, 2], 0);
+		run_to_block(1 + MISSING_GRACE);
+
+		assert_ok!(FileBank::claim_data_loss_compensation(RuntimeOrigin::signed(1), FILE_HASH));
+		assert_eq!(InsuranceClaims::<Test>::get(&FILE_HASH, 1), Some(1_000));
+		System::assert_last_event(RuntimeEvent::FileBank(Event::DataLossCompensated {
+			owner: 1,
+			file_hash: FILE_HASH,
+			amount: 1_000,
+		}));
+		assert_noop!(
+			FileBank::claim_data_loss_compensation(RuntimeOrigin::signed(1), FILE_HASH),
+			Error::<Test>::AlreadyCompensated
+		);
+
+		// The fund only holds part of the second compensation.
+		assert_ok!(FileBank::claim_data_loss_compensation(RuntimeOrigin::signed(2), FILE_HASH));
+		assert_eq!(InsuranceClaims::<Test>::get(&FILE_HASH, 2), Some(500));
+		assert_eq!(InsuranceFund::get(), 0);
+	});
+}
+
+#[test]
+fn removing_a_lost_file_clears_its_claims() {
+	new_test_ext().execute_with(|| {
+		InsuranceFund::set(10_000);
+		store_file(&[1], 0);
+		run_to_block(1 + MISSING_GRACE);
+		assert_ok!(FileBank::claim_data_loss_compensation(RuntimeOrigin::signed(1), FILE_HASH));
+
+		assert_ok!(FileBank::remove_file_last_owner(&FILE_HASH, &1, true));
+		assert!(!File::<Test>::contains_key(&FILE_HASH));
+		assert_eq!(MissingSince::<Test>::get(&FILE_HASH), None);
+		assert_eq!(InsuranceClaims::<Test>::get(&FILE_HASH, 1), None);
+	});
+}
+
+const OTHER_FILE_HASH: Hash = Hash([b'd'; 64]);
+
+/// Store an available file that Alice holds in the mock territory.
+fn hold_file(file_hash: Hash) {
+	File::<Test>::insert(&file_hash, file_info(&[ALICE], FRAGMENT_COUNT as usize));
+}
+
+fn object_key(key: &str) -> ObjectKey<Test> {
+	key.as_bytes().to_vec().try_into().unwrap()
+}
+
+fn put(key: &str, file_hash: Hash) -> DispatchResult {
+	FileBank::put_object(RuntimeOrigin::signed(ALICE), ALICE, territory(), object_key(key), file_hash)
+}
+
+fn deposit_of(key: &str) -> u128 {
+	OBJECT_DEPOSIT_BASE + OBJECT_DEPOSIT_PER_BYTE * key.len() as u128
+}
+
+/// The keys of Alice's listing, with the common prefixes followed by `*`.
+fn listing(prefix: &str, delimiter: Option<&str>, start_after: Option<&str>, limit: u32) -> Vec<String> {
+	FileBank::list_objects(
+		ALICE,
+		territory(),
+		prefix.as_bytes().to_vec(),
+		delimiter.map(|delimiter| delimiter.as_bytes().to_vec()),
+		start_after.map(|start_after| start_after.as_bytes().to_vec()),
+		limit,
+	)
+	.into_iter()
+	.map(|entry| match entry {
+		ListedObject::Object { key, .. } => String::from_utf8(key).unwrap(),
+		ListedObject::CommonPrefix(prefix) => String::from_utf8(prefix).unwrap() + "*",
+	})
+	.collect()
+}
+
+fn put_photos() {
+	hold_file(FILE_HASH);
+	for key in ["photos/b.jpg", "photosx", "docs/x", "photos/2024/c.jpg", "photos-old", "photos/a.jpg"] {
+		assert_ok!(put(key, FILE_HASH));
+	}
+}
+
+#[test]
+fn object_paths_keep_keys_in_string_order() {
+	let path = |key: &str| ObjectPath::<ConstU32<8>>(key.as_bytes().to_vec().try_into().unwrap()).encode();
+	assert!(path("a") < path("a-b"));
+	assert!(path("a-b") < path("a/b"));
+	assert!(path("a/b") < path("ab"));
+
+	let encoded = path("a/b");
+	assert_eq!(encoded, b"a/b\0\0\0\0\0".to_vec());
+	assert_eq!(encoded.len(), ObjectPath::<ConstU32<8>>::max_encoded_len());
+	assert_eq!(ObjectPath::<ConstU32<8>>::decode(&mut &encoded[..]).unwrap().0.into_inner(), b"a/b".to_vec());
+	assert!(ObjectPath::<ConstU32<16>>::decode(&mut &encoded[..]).is_err());
+	assert!(ObjectPath::<ConstU32<8>>::decode(&mut &b"a\0b\0\0\0\0\0"[..]).is_err());
+
+	// The metadata describes the padded array the key is encoded as.
+	let mut registry = scale_info::Registry::new();
+	let id = registry.register_type(&scale_info::meta_type::<ObjectPath<ConstU32<8>>>());
+	let registry: scale_info::PortableRegistry = registry.into();
+	match &registry.resolve(id.id).unwrap().type_def {
+		scale_info::TypeDef::Array(array) => assert_eq!(array.len, 8),
+		def => panic!("ObjectPath described as {:?}", def),
+	}
+}
+
+#[test]
+fn objects_are_listed_in_key_order_under_a_prefix() {
+	new_test_ext().execute_with(|| {
+		put_photos();
+
+		assert_eq!(
+			listing("", None, None, 10),
+			vec!["docs/x", "photos-old", "photos/2024/c.jpg", "photos/a.jpg", "photos/b.jpg", "photosx"],
+		);
+		assert_eq!(listing("photos/", None, None, 10), vec!["photos/2024/c.jpg", "photos/a.jpg", "photos/b.jpg"]);
+		assert_eq!(listing("videos/", None, None, 10), Vec::<String>::new());
+		assert_eq!(listing("photos/", None, Some("photos/2024/c.jpg"), 1), vec!["photos/a.jpg"]);
+		// A cursor before the prefix starts at the prefix.
+		assert_eq!(listing("photos/", None, Some("docs/x"), 1), vec!["photos/2024/c.jpg"]);
+	});
+}
+
+#[test]
+fn delimiters_list_common_prefixes_once() {
+	new_test_ext().execute_with(|| {
+		put_photos();
+
+		assert_eq!(listing("", Some("/"), None, 10), vec!["docs/*", "photos-old", "photos/*", "photosx"]);
+		assert_eq!(listing("photos/", Some("/"), None, 10), vec!["photos/2024/*", "photos/a.jpg", "photos/b.jpg"]);
+
+		// Pages go on after the last entry, and a common prefix skips all the keys sharing it.
+		assert_eq!(listing("", Some("/"), None, 2), vec!["docs/*", "photos-old"]);
+		assert_eq!(listing("", Some("/"), Some("photos-old"), 2), vec!["photos/*", "photosx"]);
+		assert_eq!(listing("", Some("/"), Some("photos/"), 2), vec!["photosx"]);
+	});
+}
+
+#[test]
+fn objects_name_files_the_owner_holds_in_the_territory() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(put("a/b", FILE_HASH), Error::<Test>::NonExistent);
+
+		File::<Test>::insert(&FILE_HASH, file_info(&[2], FRAGMENT_COUNT as usize));
+		assert_noop!(put("a/b", FILE_HASH), Error::<Test>::NotOwner);
+
+		hold_file(OTHER_FILE_HASH);
+		for key in ["", "/a", "a/", "a//b", "a/./b", "a/../b", "a b"] {
+			assert_noop!(put(key, OTHER_FILE_HASH), Error::<Test>::InvalidObjectKey);
+		}
+		assert_ok!(put("a/b", OTHER_FILE_HASH));
+		assert_eq!(FileBank::object(ALICE, territory(), b"a/b".to_vec()).unwrap().file_hash, OTHER_FILE_HASH);
+	});
+}
+
+#[test]
+fn objects_hold_a_deposit_until_removed() {
+	new_test_ext().execute_with(|| {
+		hold_file(FILE_HASH);
+		hold_file(OTHER_FILE_HASH);
+
+		assert_ok!(put("a/b", FILE_HASH));
+		assert_eq!(Balances::reserved_balance(ALICE), deposit_of("a/b"));
+		assert_eq!(FileBank::object(ALICE, territory(), b"a/b".to_vec()).unwrap().deposit, deposit_of("a/b"));
+
+		// Replacing the file a key names keeps one deposit for the key.
+		assert_ok!(put("a/b", OTHER_FILE_HASH));
+		assert_eq!(Balances::reserved_balance(ALICE), deposit_of("a/b"));
+		assert_eq!(FileObjects::<Test>::get((ALICE, territory()), FILE_HASH), None);
+		assert_eq!(FileObjects::<Test>::get((ALICE, territory()), OTHER_FILE_HASH).unwrap().to_vec(), vec![object_key("a/b")]);
+
+		assert_ok!(FileBank::delete_object(RuntimeOrigin::signed(ALICE), ALICE, territory(), object_key("a/b")));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(FileObjects::<Test>::get((ALICE, territory()), OTHER_FILE_HASH), None);
+		assert_noop!(
+			FileBank::delete_object(RuntimeOrigin::signed(ALICE), ALICE, territory(), object_key("a/b")),
+			Error::<Test>::NonExistent
+		);
+
+		let free = Balances::free_balance(ALICE);
+		assert_ok!(Balances::reserve(&ALICE, free - deposit_of("a/b") + 1));
+		assert!(put("a/b", FILE_HASH).is_err());
+	});
+}
+
+#[test]
+fn files_are_named_by_a_bounded_number_of_keys() {
+	new_test_ext().execute_with(|| {
+		hold_file(FILE_HASH);
+		for i in 0..MAX_FILE_OBJECTS {
+			assert_ok!(put(&format!("k{}", i), FILE_HASH));
+		}
+		assert_noop!(put("one-more", FILE_HASH), Error::<Test>::TooManyObjectKeys);
+
+		// Putting a key again doesn't count twice.
+		assert_ok!(put("k0", FILE_HASH));
+	});
+}
+
+#[test]
+fn deleting_a_file_removes_the_keys_naming_it() {
+	new_test_ext().execute_with(|| {
+		hold_file(FILE_HASH);
+		hold_file(OTHER_FILE_HASH);
+		assert_ok!(put("a/b", FILE_HASH));
+		assert_ok!(put("a/c", FILE_HASH));
+		assert_ok!(put("a/d", OTHER_FILE_HASH));
+
+		assert_ok!(FileBank::delete_file(RuntimeOrigin::signed(ALICE), ALICE, FILE_HASH));
+		assert_eq!(listing("", None, None, 10), vec!["a/d"]);
+		assert_eq!(FileObjects::<Test>::get((ALICE, territory()), FILE_HASH), None);
+		assert_eq!(Balances::reserved_balance(ALICE), deposit_of("a/d"));
+	});
+}
+
+#[test]
+fn delivering_a_file_removes_the_keys_naming_it() {
+	new_test_ext().execute_with(|| {
+		hold_file(FILE_HASH);
+		assert_ok!(put("a/b", FILE_HASH));
+
+		let other_territory: TerrName = b"t2".to_vec().try_into().unwrap();
+		assert_ok!(FileBank::territory_file_delivery(RuntimeOrigin::signed(ALICE), ALICE, FILE_HASH, other_territory));
+		assert_eq!(listing("", None, None, 10), Vec::<String>::new());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+	});
+}
+
 // //! This file is part of CESS.
 // //!
 // //! Tests for the module.
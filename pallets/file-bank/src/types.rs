@@ -10,8 +10,8 @@ type AccountOf<T> = <T as frame_system::Config>::AccountId;
 #[scale_info(skip_type_params(T))]
 #[codec(mel_bound())]
 pub struct SegmentList<T: Config> {
-	pub(super) hash: Hash,
-	pub(super) fragment_list: BoundedVec<Hash, <T as pallet::Config>::FragmentCount>,
+	pub hash: Hash,
+	pub fragment_list: BoundedVec<Hash, <T as pallet::Config>::FragmentCount>,
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -58,9 +58,9 @@ pub struct CompleteInfo<T: Config> {
 pub struct FileInfo<T: Config> {
 	pub(super) segment_list: BoundedVec<SegmentInfo<T>, T::SegmentCount>,
 	pub(super) owner: BoundedVec<UserBrief<T>, T::OwnerLimit>,
-	pub(super) file_size: u128,
+	pub file_size: u128,
 	pub(super) completion: BlockNumberFor<T>,
-	pub(super) stat: FileState,
+	pub stat: FileState,
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -97,6 +97,79 @@ pub struct UserBrief<T: Config> {
 	pub territory_name: TerrName,
 }
 
+/// An S3 style object key: `/`-delimited segments of `BUCKET_ALLOW_CHAR` characters.
+pub type ObjectKey<T> = BoundedVec<u8, <T as pallet::Config>::ObjectKeyLimit>;
+
+/// An object key as it is stored: a `[u8; S]` array holding its bytes padded with NULs, which
+/// no key contains.
+///
+/// Unlike a length prefix, the padding keeps the keys of a namespace in string order under the
+/// `Identity` hasher, so that the keys sharing a prefix are stored next to each other.
+#[derive(CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+pub struct ObjectPath<S: Get<u32>>(pub BoundedVec<u8, S>);
+
+impl<S: Get<u32>> Encode for ObjectPath<S> {
+	fn size_hint(&self) -> usize {
+		S::get() as usize
+	}
+
+	fn encode_to<O: codec::Output + ?Sized>(&self, dest: &mut O) {
+		dest.write(&self.0);
+		for _ in self.0.len()..S::get() as usize {
+			dest.push_byte(0);
+		}
+	}
+}
+
+impl<S: Get<u32>> codec::EncodeLike for ObjectPath<S> {}
+
+impl<S: Get<u32>> Decode for ObjectPath<S> {
+	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
+		let mut bytes = vec![0u8; S::get() as usize];
+		input.read(&mut bytes)?;
+		let len = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
+		if bytes[len..].iter().any(|byte| *byte != 0) {
+			return Err("object key not padded with NULs".into());
+		}
+		bytes.truncate(len);
+		Ok(Self(BoundedVec::truncate_from(bytes)))
+	}
+}
+
+impl<S: Get<u32>> MaxEncodedLen for ObjectPath<S> {
+	fn max_encoded_len() -> usize {
+		S::get() as usize
+	}
+}
+
+impl<S: Get<u32> + 'static> TypeInfo for ObjectPath<S> {
+	type Identity = Self;
+
+	fn type_info() -> scale_info::Type {
+		scale_info::TypeDefArray::new(S::get(), scale_info::meta_type::<u8>()).into()
+	}
+}
+
+/// The file an object key names in a territory.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct ObjectInfo<AccountId, Balance, BlockNumber> {
+	pub file_hash: Hash,
+	/// The block the object was last put at.
+	pub modified_at: BlockNumber,
+	/// The account that put the object, whose deposit is returned once it is removed.
+	pub depositor: AccountId,
+	pub deposit: Balance,
+}
+
+/// An entry of an object listing.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub enum ListedObject<AccountId, Balance, BlockNumber> {
+	/// An object and its key.
+	Object { key: Vec<u8>, info: ObjectInfo<AccountId, Balance, BlockNumber> },
+	/// The prefix the keys listed in its place share, up to and including the delimiter.
+	CommonPrefix(Vec<u8>),
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub struct RestoralTargetInfo<Account, Block> {
 	pub(super) miner: Account,
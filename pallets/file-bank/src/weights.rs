@@ -49,6 +49,9 @@ pub trait WeightInfo {
 	fn claim_restoral_noexist_order() -> Weight;
 	fn restoral_order_complete() -> Weight;
 	fn migration_step() -> Weight;
+	fn claim_data_loss_compensation() -> Weight;
+	fn put_object() -> Weight;
+	fn delete_object() -> Weight;
 }
 
 /// Weights for `pallet_file_bank` using the Substrate node and recommended hardware.
@@ -293,6 +296,62 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: `FileBank::File` (r:1 w:0)
+	/// Proof: `FileBank::File` (`max_values`: None, `max_size`: Some(12489107), added: 12491582, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::InsuranceClaims` (r:1 w:1)
+	/// Proof: `FileBank::InsuranceClaims` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::MissingSince` (r:1 w:0)
+	/// Proof: `FileBank::MissingSince` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Territory` (r:1 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::UnitPrice` (r:1 w:0)
+	/// Proof: `StorageHandler::UnitPrice` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `CessTreasury::InsuranceFund` (r:1 w:1)
+	/// Proof: `CessTreasury::InsuranceFund` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	///
+	/// Not benchmarked yet: the proof size sums the entries above and the time is estimated
+	/// from `delete_file`. Regenerate with the `claim_data_loss_compensation` benchmark.
+	fn claim_data_loss_compensation() -> Weight {
+		Weight::from_parts(90_000_000, 12505696)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Oss::AuthorityList` (r:1 w:0)
+	/// Storage: `Oss::AuthorityScopes` (r:1 w:1)
+	/// Storage: `FileBank::File` (r:1 w:0)
+	/// Proof: `FileBank::File` (`max_values`: None, `max_size`: Some(12489107), added: 12491582, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::Objects` (r:1 w:1)
+	/// Proof: `FileBank::Objects` (`max_values`: None, `max_size`: Some(1331), added: 3806, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::FileObjects` (r:2 w:2)
+	/// Proof: `FileBank::FileObjects` (`max_values`: None, `max_size`: Some(16654), added: 19129, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	///
+	/// Not benchmarked yet: estimated from the storage accesses of the call.
+	/// Regenerate with the `put_object` benchmark.
+	fn put_object() -> Weight {
+		Weight::from_parts(60_000_000, 12540000)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `Oss::AuthorityList` (r:1 w:0)
+	/// Storage: `Oss::AuthorityScopes` (r:1 w:1)
+	/// Storage: `FileBank::Objects` (r:1 w:1)
+	/// Proof: `FileBank::Objects` (`max_values`: None, `max_size`: Some(1331), added: 3806, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::FileObjects` (r:1 w:1)
+	/// Proof: `FileBank::FileObjects` (`max_values`: None, `max_size`: Some(16654), added: 19129, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	///
+	/// Not benchmarked yet: estimated from the storage accesses of the call.
+	/// Regenerate with the `delete_object` benchmark.
+	fn delete_object() -> Weight {
+		Weight::from_parts(40_000_000, 30408)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -535,4 +594,60 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
-}
\ No newline at end of file
+	/// Storage: `FileBank::File` (r:1 w:0)
+	/// Proof: `FileBank::File` (`max_values`: None, `max_size`: Some(12489107), added: 12491582, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::InsuranceClaims` (r:1 w:1)
+	/// Proof: `FileBank::InsuranceClaims` (`max_values`: None, `max_size`: Some(144), added: 2619, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::MissingSince` (r:1 w:0)
+	/// Proof: `FileBank::MissingSince` (`max_values`: None, `max_size`: Some(84), added: 2559, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::Territory` (r:1 w:0)
+	/// Proof: `StorageHandler::Territory` (`max_values`: None, `max_size`: Some(233), added: 2708, mode: `MaxEncodedLen`)
+	/// Storage: `StorageHandler::UnitPrice` (r:1 w:0)
+	/// Proof: `StorageHandler::UnitPrice` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `CessTreasury::InsuranceFund` (r:1 w:1)
+	/// Proof: `CessTreasury::InsuranceFund` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	///
+	/// Not benchmarked yet: the proof size sums the entries above and the time is estimated
+	/// from `delete_file`. Regenerate with the `claim_data_loss_compensation` benchmark.
+	fn claim_data_loss_compensation() -> Weight {
+		Weight::from_parts(90_000_000, 12505696)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Oss::AuthorityList` (r:1 w:0)
+	/// Storage: `Oss::AuthorityScopes` (r:1 w:1)
+	/// Storage: `FileBank::File` (r:1 w:0)
+	/// Proof: `FileBank::File` (`max_values`: None, `max_size`: Some(12489107), added: 12491582, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::Objects` (r:1 w:1)
+	/// Proof: `FileBank::Objects` (`max_values`: None, `max_size`: Some(1331), added: 3806, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::FileObjects` (r:2 w:2)
+	/// Proof: `FileBank::FileObjects` (`max_values`: None, `max_size`: Some(16654), added: 19129, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	///
+	/// Not benchmarked yet: estimated from the storage accesses of the call.
+	/// Regenerate with the `put_object` benchmark.
+	fn put_object() -> Weight {
+		Weight::from_parts(60_000_000, 12540000)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `Oss::AuthorityList` (r:1 w:0)
+	/// Storage: `Oss::AuthorityScopes` (r:1 w:1)
+	/// Storage: `FileBank::Objects` (r:1 w:1)
+	/// Proof: `FileBank::Objects` (`max_values`: None, `max_size`: Some(1331), added: 3806, mode: `MaxEncodedLen`)
+	/// Storage: `FileBank::FileObjects` (r:1 w:1)
+	/// Proof: `FileBank::FileObjects` (`max_values`: None, `max_size`: Some(16654), added: 19129, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	///
+	/// Not benchmarked yet: estimated from the storage accesses of the call.
+	/// Regenerate with the `delete_object` benchmark.
+	fn delete_object() -> Weight {
+		Weight::from_parts(40_000_000, 30408)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+}
@@ -5,12 +5,52 @@ use frame_benchmarking::{
 use frame_support::dispatch::RawOrigin;
 use sp_runtime::{
 	AccountId32, MultiSignature, MultiSigner,
-	SaturatedConversion, 
+	SaturatedConversion, traits::Bounded,
 };
 use sp_io::crypto::{sr25519_generate, sr25519_sign};
+use sp_std::vec;
 
 const SEED: u32 = 2190502;
 
+fn scoped_authorization<T: Config>() -> OssAuthorization<T> {
+	let mut territories: BoundedVec<TerrName, T::MaxScopeTerritories> = Default::default();
+	for i in 0..T::MaxScopeTerritories::get() {
+		let name: TerrName = i.to_string().as_bytes().to_vec().try_into().unwrap();
+		territories.try_push(name).unwrap();
+	}
+
+	OssAuthorization::<T> {
+		expires_at: Some(1000u32.saturated_into()),
+		scope: AuthorityScope::<T> { upload: true, delete: true, territory_delivery: true, territories },
+		quota: Some(u128::MAX),
+	}
+}
+
+fn gateway_metadata<T: Config>() -> OssMetadata<BalanceOf<T>> {
+	let endpoint: Endpoint = vec![b'a'; 128].try_into().unwrap();
+
+	OssMetadata {
+		region: *b"DE",
+		http_endpoint: Some(endpoint.clone()),
+		s3_endpoint: Some(endpoint.clone()),
+		libp2p_endpoint: Some(endpoint),
+		max_object_size: u128::MAX,
+		fee_per_gib: 1u32.into(),
+	}
+}
+
+fn payload_message<T: Config>(payload: &ProxyAuthPayload<T>) -> Vec<u8> {
+	let mut payload_encode = payload.encode();
+	let mut b1 = "<Bytes>".to_string().as_bytes().to_vec();
+	let mut b2 = "</Bytes>".to_string().as_bytes().to_vec();
+
+	let mut origin: Vec<u8> = Default::default();
+	origin.append(&mut b1);
+	origin.append(&mut payload_encode);
+	origin.append(&mut b2);
+	origin
+}
+
 benchmarks! {
 	authorize {
 		let owner: AccountOf<T> = account("owner", 100, SEED);
@@ -36,11 +76,14 @@ benchmarks! {
 
 	register {
 		let oss: AccountOf<T> = account("oss", 100, SEED);
+		T::Currency::make_free_balance_be(&oss, BalanceOf::<T>::max_value());
 		let domain: BoundedVec<u8, ConstU32<50>> = Default::default();
 		let peer_id = [0u8; 38];
-	}: _(RawOrigin::Signed(oss.clone()), peer_id.clone(), domain.clone())
+	}: _(RawOrigin::Signed(oss.clone()), peer_id.clone(), domain.clone(), gateway_metadata::<T>())
 	verify {
 		assert!(<Oss<T>>::contains_key(&oss));
+		assert_eq!(<OssMetadataOf<T>>::get(&oss), Some(gateway_metadata::<T>()));
+		assert_eq!(<OssDeposits<T>>::get(&oss), Some(T::RegistrationDeposit::get()));
 		let oss_info_right = <Oss<T>>::get(&oss).unwrap();
 		let oss_info = OssInfo {
 			peer_id: peer_id,
@@ -59,9 +102,10 @@ benchmarks! {
 		};
 		<Oss<T>>::insert(&oss, oss_info);
 		let new_peer_id = [6u8; 38];
-	}: _(RawOrigin::Signed(oss.clone()), new_peer_id.clone(), domain.clone())
+	}: _(RawOrigin::Signed(oss.clone()), new_peer_id.clone(), domain.clone(), gateway_metadata::<T>())
 	verify {
 		assert!(<Oss<T>>::contains_key(&oss));
+		assert_eq!(<OssMetadataOf<T>>::get(&oss), Some(gateway_metadata::<T>()));
 		let oss_info = OssInfo {
 			peer_id: new_peer_id,
 			domain: domain,
@@ -79,9 +123,26 @@ benchmarks! {
 			domain: domain.clone(),
 		};
 		<Oss<T>>::insert(&oss, oss_info);
+		<OssMetadataOf<T>>::insert(&oss, gateway_metadata::<T>());
+		let deposit = T::RegistrationDeposit::get();
+		T::Currency::make_free_balance_be(&oss, BalanceOf::<T>::max_value());
+		T::Currency::reserve(&oss, deposit)?;
+		<OssDeposits<T>>::insert(&oss, deposit);
 	}: _(RawOrigin::Signed(oss.clone()))
 	verify {
 		assert!(!<Oss<T>>::contains_key(&oss));
+		assert!(!<OssDeposits<T>>::contains_key(&oss));
+		assert_eq!(T::Currency::reserved_balance(&oss), 0u32.into());
+	}
+
+	scoped_authorize {
+		let owner: AccountOf<T> = account("owner", 100, SEED);
+		let operator: AccountOf<T> = account("operator", 100, SEED);
+		let authorization = scoped_authorization::<T>();
+	}: _(RawOrigin::Signed(owner.clone()), operator.clone(), authorization.clone())
+	verify {
+		assert!(<AuthorityList<T>>::get(&owner).contains(&operator));
+		assert_eq!(<AuthorityScopes<T>>::get(&owner, &operator), Some(authorization));
 	}
 
 	proxy_authorzie {
@@ -91,16 +152,9 @@ benchmarks! {
 		let payload = ProxyAuthPayload::<T> {
 			oss: oss.clone(),
 			exp: 32u32.saturated_into(),
+			authorization: scoped_authorization::<T>(),
 		};
-
-		let mut payload_encode = payload.encode();
-		let mut b1 = "<Bytes>".to_string().as_bytes().to_vec();
-		let mut b2 = "</Bytes>".to_string().as_bytes().to_vec();
-
-		let mut origin: Vec<u8> = Default::default();
-		origin.append(&mut b1);
-		origin.append(&mut payload_encode);
-		origin.append(&mut b2);
+		let origin = payload_message(&payload);
 
 		let caller_public = sr25519_generate(0.into(), None);
 		let signature = MultiSignature::Sr25519(sr25519_sign(0.into(), &caller_public, &origin).unwrap());
@@ -120,36 +174,39 @@ benchmarks! {
 		});
 
 		let authorty_list = <AuthorityList<T>>::try_get(&account).unwrap();
-		assert!(authorty_list.contains(&oss))
+		assert!(authorty_list.contains(&oss));
+		assert!(<AuthorityScopes<T>>::contains_key(&account, &oss));
 	}
 
 	evm_proxy_authorzie {
 		let sender = account("origin", 100, SEED);
-
-		let auth_puk = hex::decode("dfa6d7b776c4cac2e9a506e1b9def79c75a53488e3dc320643bb792fbdbe90d1").expect("Valid");
-		let auth_puk: sp_core::sr25519::Public = sp_core::sr25519::Public::from_raw(auth_puk.try_into().unwrap());
-		let account = auth_puk.using_encoded(|entropy| {
-			AccountOf::<T>::decode(&mut TrailingZeroInput::new(entropy))
-				.expect("infinite input; no invalid input; qed")
-		});
-
-		let oss = hex::decode("02f17b57f801914fd35f95b73b199efe4e37d121f79e39f20ba29f6acd9e355b").expect("Valid");
-		let oss: sp_core::sr25519::Public = sp_core::sr25519::Public::from_raw(oss.try_into().unwrap());
-		let oss_acc = oss.using_encoded(|entropy| {
-			AccountOf::<T>::decode(&mut TrailingZeroInput::new(entropy))
-				.expect("infinite input; no invalid input; qed")
-		});
+		let oss_acc: AccountOf<T> = account("oss", 100, SEED);
 
 		let payload = ProxyAuthPayload::<T> {
 			oss: oss_acc.clone(),
-			exp: 583561u32.saturated_into(),
+			exp: 32u32.saturated_into(),
+			authorization: scoped_authorization::<T>(),
 		};
-
-		let sig = hex::decode("65665a76bd886775766c77c8c5b43edb3ebb9b5f9860d4ded786b6537cf0fc4e00a5625d8a83d86c02435c82e70132bdb265a7fdb0fb14f7d724540be58b148b1c").expect("Valid");
-		let sig: EIP712Signature = sig.try_into().unwrap();
+		let message_hash = OssPallet::<T>::eip191_message_hash(&mut payload_message(&payload));
+
+		let key_type = sp_core::crypto::KeyTypeId(*b"oss_");
+		let public = sp_io::crypto::ecdsa_generate(key_type, None);
+		let sig: EIP712Signature = sp_io::crypto::ecdsa_sign_prehashed(key_type, &public, &message_hash)
+			.ok_or("can't sign the payload")?
+			.0;
+		let recovered_public_key = match <T as Config>::AddressConverter::SECP256K1_PUBLIC_KEY_FORM {
+			Secp256K1PublicKeyForm::Compressed => public.0.to_vec(),
+			Secp256K1PublicKeyForm::Uncompressed => sp_io::crypto::secp256k1_ecdsa_recover(&sig, &message_hash)
+				.map_err(|_| "can't recover the signing key")?
+				.to_vec(),
+		};
+		let account = <T as Config>::AddressConverter::try_convert(&recovered_public_key).ok_or("can't convert the signing key")?;
+		let auth_puk: [u8; 32] = account.encode().try_into().map_err(|_| "account is not 32 bytes")?;
+		let auth_puk = sp_core::sr25519::Public::from_raw(auth_puk);
 	}: _(RawOrigin::Signed(sender), auth_puk, sig, payload)
 	verify {
 		let authorty_list = <AuthorityList<T>>::try_get(&account).unwrap();
-		assert!(authorty_list.contains(&oss_acc))
+		assert!(authorty_list.contains(&oss_acc));
+		assert!(<AuthorityScopes<T>>::contains_key(&account, &oss_acc));
 	}
 }
@@ -5,8 +5,10 @@ pub mod benchmarking;
 
 pub mod weights;
 
+pub mod migrations;
+
 mod types;
-use types::*;
+pub use types::*;
 
 #[cfg(test)]
 mod mock;
@@ -17,6 +19,7 @@ use codec::alloc::string::ToString;
 use frame_system::pallet_prelude::*;
 use frame_support::{
 	pallet_prelude::*, transactional,
+	traits::{Currency, ReservableCurrency},
 };
 use cp_cess_common::*;
 use sp_std::vec::Vec;
@@ -28,6 +31,13 @@ pub use pallet::*;
 pub use weights::WeightInfo;
 
 type AccountOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> =
+	<<T as pallet::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+/// The most gateways discovery returns at once.
+pub const MAX_GATEWAYS_PAGE: u32 = 100;
 
 pub type Keccak256Signature = [u8; 32];
 pub type EIP712Signature = [u8; 65];
@@ -44,6 +54,12 @@ pub mod pallet {
 
 		type WeightInfo: WeightInfo;
 
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The deposit reserved from an OSS while it is registered.
+		#[pallet::constant]
+		type RegistrationDeposit: Get<BalanceOf<Self>>;
+
 		#[pallet::constant]
 		type P2PLength: Get<u32> + Clone;
 
@@ -55,6 +71,10 @@ pub mod pallet {
 
 		type AddressConverter: AddressConversion<Self::AccountId>;
 
+		/// The maximum number of territories an authorization can be scoped to.
+		#[pallet::constant]
+		type MaxScopeTerritories: Get<u32> + Clone;
+
 		// type AccountIdConvertor: AccountIdConvertor<Self::AccountId>;
 	}
 
@@ -63,6 +83,8 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		//Successful Authorization Events
 		Authorize { acc: AccountOf<T>, operator: AccountOf<T> },
+		//Scoped authorization success event
+		ScopedAuthorize { acc: AccountOf<T>, operator: AccountOf<T>, authorization: OssAuthorization<T> },
 		//Cancel authorization success event
 		CancelAuthorize { acc: AccountOf<T>, oss: AccountOf<T> },
 		//The event of successful Oss registration
@@ -95,17 +117,42 @@ pub mod pallet {
 		Expired,
 		/// Public key conversion address failed
 		ConvertError,
+		/// The region is not an uppercase ISO 3166-1 alpha-2 code
+		InvalidRegion,
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn authority_list)]
 	pub(super) type AuthorityList<T: Config> = StorageMap<_, Blake2_128Concat, AccountOf<T>, BoundedVec<AccountOf<T>, T::AuthorLimit>, ValueQuery>;
 
+	/// The limits of an operator's authorization, absent for an unrestricted one.
+	#[pallet::storage]
+	#[pallet::getter(fn authority_scope)]
+	pub(super) type AuthorityScopes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		AccountOf<T>,
+		Blake2_128Concat,
+		AccountOf<T>,
+		OssAuthorization<T>,
+	>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn oss)]
 	pub(super) type Oss<T: Config> = StorageMap<_, Blake2_128Concat, AccountOf<T>, OssInfo>;
 
+	/// The metadata clients discover a registered OSS by.
+	#[pallet::storage]
+	#[pallet::getter(fn oss_metadata)]
+	pub(super) type OssMetadataOf<T: Config> = StorageMap<_, Blake2_128Concat, AccountOf<T>, OssMetadata<BalanceOf<T>>>;
+
+	/// The deposit reserved from a registered OSS, refunded by `destroy`.
+	#[pallet::storage]
+	#[pallet::getter(fn oss_deposit)]
+	pub(super) type OssDeposits<T: Config> = StorageMap<_, Blake2_128Concat, AccountOf<T>, BalanceOf<T>>;
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::call]
@@ -132,6 +179,7 @@ pub mod pallet {
 
 				Ok(())
 			})?;
+			<AuthorityScopes<T>>::remove(&sender, &operator);
 
 			Self::deposit_event(Event::<T>::Authorize {
 				acc: sender,
@@ -161,6 +209,7 @@ pub mod pallet {
 
 				Ok(())
 			})?;
+			<AuthorityScopes<T>>::remove(&sender, &oss);
 
 			Self::deposit_event(Event::<T>::CancelAuthorize {
 				acc: sender,
@@ -180,17 +229,32 @@ pub mod pallet {
 		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically, this is the account registering as an OSS.
 		/// - `endpoint`: The unique peer ID or endpoint that identifies the OSS and its services.
 		/// - `domain`: A bounded vector of up to 50 bytes representing the domain or description of the OSS.
+		/// - `metadata`: The region, endpoints, limits and pricing clients discover the OSS by.
+		///
+		/// `RegistrationDeposit` is reserved from the OSS until it is destroyed.
 		#[pallet::call_index(2)]
 		#[transactional]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::register())]
-		pub fn register(origin: OriginFor<T>, endpoint: PeerId, domain: BoundedVec<u8, ConstU32<50>>) -> DispatchResult {
+		pub fn register(
+			origin: OriginFor<T>,
+			endpoint: PeerId,
+			domain: BoundedVec<u8, ConstU32<50>>,
+			metadata: OssMetadata<BalanceOf<T>>,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			ensure!(!<Oss<T>>::contains_key(&sender), Error::<T>::Registered);
+			ensure!(metadata.has_valid_region(), Error::<T>::InvalidRegion);
+
+			let deposit = T::RegistrationDeposit::get();
+			T::Currency::reserve(&sender, deposit)?;
+			<OssDeposits<T>>::insert(&sender, deposit);
+
 			let oss_info = OssInfo {
 				peer_id: endpoint.clone(),
 				domain,
 			};
 			<Oss<T>>::insert(&sender, oss_info);
+			<OssMetadataOf<T>>::insert(&sender, metadata);
 
 			Self::deposit_event(Event::<T>::OssRegister {acc: sender, endpoint});
 
@@ -208,12 +272,20 @@ pub mod pallet {
 		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically, this is the registered OSS that wishes to update its information.
 		/// - `endpoint`: The new unique peer ID or endpoint that identifies the OSS and its services.
 		/// - `domain`: A bounded vector of up to 50 bytes representing the updated domain or description of the OSS's services.
+		/// - `metadata`: The updated region, endpoints, limits and pricing of the OSS.
 		#[pallet::call_index(3)]
 		#[transactional]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::update())]
-		pub fn update(origin: OriginFor<T>, endpoint: PeerId, domain: BoundedVec<u8, ConstU32<50>>) -> DispatchResult {
+		pub fn update(
+			origin: OriginFor<T>,
+			endpoint: PeerId,
+			domain: BoundedVec<u8, ConstU32<50>>,
+			metadata: OssMetadata<BalanceOf<T>>,
+		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
 			ensure!(<Oss<T>>::contains_key(&sender), Error::<T>::UnRegister);
+			ensure!(metadata.has_valid_region(), Error::<T>::InvalidRegion);
+			<OssMetadataOf<T>>::insert(&sender, metadata);
 
 			<Oss<T>>::try_mutate(&sender, |oss_info_opt| -> DispatchResult {
 				let oss_info = oss_info_opt.as_mut().ok_or(Error::<T>::OptionParseError)?;
@@ -235,6 +307,8 @@ pub mod pallet {
 		///
 		/// Parameters:
 		/// - `origin`: The origin from which the function is called, ensuring the caller's authorization. Typically, this is the registered OSS that wishes to destroy its registration.
+		///
+		/// The registration deposit is refunded.
 		#[pallet::call_index(4)]
 		#[transactional]
 		#[pallet::weight(<T as pallet::Config>::WeightInfo::destroy())]
@@ -243,6 +317,10 @@ pub mod pallet {
 			ensure!(<Oss<T>>::contains_key(&sender), Error::<T>::UnRegister);
 
 			<Oss<T>>::remove(&sender);
+			<OssMetadataOf<T>>::remove(&sender);
+			if let Some(deposit) = <OssDeposits<T>>::take(&sender) {
+				T::Currency::unreserve(&sender, deposit);
+			}
 
 			Self::deposit_event(Event::<T>::OssDestroy { acc: sender });
 
@@ -284,10 +362,11 @@ pub mod pallet {
 			AuthorityList::<T>::try_mutate(&account, |list| -> DispatchResult {
 				ensure!(!list.contains(&payload.oss), Error::<T>::Existed);
 
-				list.try_push(payload.oss).map_err(|_| Error::<T>::BoundedVecError)?;
+				list.try_push(payload.oss.clone()).map_err(|_| Error::<T>::BoundedVecError)?;
 
 				Ok(())
 			})?; 
+			Self::set_authorization(&account, &payload.oss, payload.authorization)?;
 
 			Ok(())
 		}
@@ -325,16 +404,95 @@ pub mod pallet {
 			AuthorityList::<T>::try_mutate(&account, |list| -> DispatchResult {
 				ensure!(!list.contains(&payload.oss), Error::<T>::Existed);
 
-				list.try_push(payload.oss).map_err(|_| Error::<T>::BoundedVecError)?;
+				list.try_push(payload.oss.clone()).map_err(|_| Error::<T>::BoundedVecError)?;
 
 				Ok(())
 			})?; 
+			Self::set_authorization(&account, &payload.oss, payload.authorization)?;
+
+			Ok(())
+		}
+
+		/// Authorize Operator With Limits
+		///
+		/// Like `authorize`, but the operator may only act until `expires_at`, within `scope`
+		/// and for at most `quota` uploaded bytes. Calling it again replaces the previous limits.
+		///
+		/// Parameters:
+		/// - `origin`: The authorizing account.
+		/// - `operator`: The account that will be authorized as an operator.
+		/// - `authorization`: The limits of the authorization.
+		#[pallet::call_index(7)]
+		#[transactional]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::scoped_authorize())]
+		pub fn scoped_authorize(origin: OriginFor<T>, operator: AccountOf<T>, authorization: OssAuthorization<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			AuthorityList::<T>::try_mutate(&sender, |authority_list| -> DispatchResult {
+				if !authority_list.contains(&operator) {
+					authority_list.try_push(operator.clone()).map_err(|_| Error::<T>::BoundedVecError)?;
+				}
+
+				Ok(())
+			})?;
+			Self::set_authorization(&sender, &operator, authorization.clone())?;
+
+			Self::deposit_event(Event::<T>::ScopedAuthorize {
+				acc: sender,
+				operator,
+				authorization,
+			});
 
 			Ok(())
 		}
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Returns up to `limit` registered gateways following `start_after`, in storage order,
+		/// serving `region` and supporting `capability` when given.
+		///
+		/// The last account returned is the `start_after` of the next page. At most
+		/// `MAX_GATEWAYS_PAGE` gateways are returned at once.
+		pub fn gateways(
+			region: Option<RegionCode>,
+			capability: Option<OssCapability>,
+			start_after: Option<AccountOf<T>>,
+			limit: u32,
+		) -> Vec<OssGateway<AccountOf<T>, BalanceOf<T>>> {
+			let gateways = match start_after {
+				Some(account) => <Oss<T>>::iter_from(<Oss<T>>::hashed_key_for(&account)),
+				None => <Oss<T>>::iter(),
+			};
+
+			gateways
+				.map(|(account, info)| {
+					let metadata = <OssMetadataOf<T>>::get(&account);
+					OssGateway { account, info, metadata }
+				})
+				.filter(|gateway| match (&gateway.metadata, region, capability) {
+					(_, None, None) => true,
+					(None, _, _) => false,
+					(Some(metadata), region, capability) =>
+						region.is_none_or(|region| metadata.region == region) &&
+							capability.is_none_or(|capability| metadata.supports(capability)),
+				})
+				.take(limit.min(MAX_GATEWAYS_PAGE) as usize)
+				.collect()
+		}
+
+		fn set_authorization(owner: &AccountOf<T>, operator: &AccountOf<T>, authorization: OssAuthorization<T>) -> DispatchResult {
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(!authorization.is_expired(now), Error::<T>::Expired);
+
+			if authorization == OssAuthorization::unlimited() {
+				<AuthorityScopes<T>>::remove(owner, operator);
+			} else {
+				<AuthorityScopes<T>>::insert(owner, operator, authorization);
+			}
+
+			Ok(())
+		}
+
 		pub(crate) fn eip712_verify_sign(auth_puk: &AccountOf<T>, signature: &EIP712Signature, message_hash: Keccak256Signature) -> bool {
 			let Ok(recovered_public_key) = (match <T as Config>::AddressConverter::SECP256K1_PUBLIC_KEY_FORM {
 				Secp256K1PublicKeyForm::Compressed => {
@@ -373,13 +531,42 @@ pub mod pallet {
 	}
 }
 
+/// An action an operator takes on the owner's files.
+pub enum OssAction<'a> {
+	/// Declaring a file of `size` bytes in `territory`.
+	Upload { territory: &'a TerrName, size: u128 },
+	/// Deleting a file stored in `territory`.
+	Delete { territory: &'a TerrName },
+	/// Moving a file into `territory`.
+	TerritoryDelivery { territory: &'a TerrName },
+}
+
 pub trait OssFindAuthor<AccountId> {
 	fn is_authorized(owner: AccountId, operator: AccountId) -> bool;
+
+	/// Whether `operator` may take `action` for `owner`, using up the quota of an upload.
+	fn authorize_action(owner: AccountId, operator: AccountId, action: OssAction) -> bool;
 }
 
 impl<T: Config> OssFindAuthor<AccountOf<T>> for Pallet<T> {
 	fn is_authorized(owner: AccountOf<T>, operator: AccountOf<T>) -> bool {
 		let acc_list = <AuthorityList<T>>::get(&owner);
-		acc_list.contains(&operator)
+		if !acc_list.contains(&operator) {
+			return false;
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		<AuthorityScopes<T>>::get(&owner, &operator).is_none_or(|authorization| !authorization.is_expired(now))
+	}
+
+	fn authorize_action(owner: AccountOf<T>, operator: AccountOf<T>, action: OssAction) -> bool {
+		if !Self::is_authorized(owner.clone(), operator.clone()) {
+			return false;
+		}
+
+		<AuthorityScopes<T>>::mutate_exists(&owner, &operator, |authorization| match authorization {
+			Some(authorization) => authorization.use_for(&action),
+			None => true,
+		})
 	}
 }
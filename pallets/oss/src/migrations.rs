@@ -0,0 +1,43 @@
+use super::*;
+use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+use sp_runtime::traits::{Saturating, Zero};
+
+/// Reserves the registration deposit from the gateways registered before it was introduced.
+pub mod v1 {
+	use super::*;
+
+	pub struct VersionUncheckedMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+	impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV0ToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let deposit = T::RegistrationDeposit::get();
+			let mut gateways = 0u64;
+
+			for acc in <Oss<T>>::iter_keys() {
+				gateways.saturating_inc();
+				if <OssDeposits<T>>::contains_key(&acc) {
+					continue;
+				}
+				// A gateway that can't afford the deposit is recorded with none, and keeps serving
+				// until it is destroyed.
+				let reserved = match T::Currency::reserve(&acc, deposit) {
+					Ok(()) => deposit,
+					Err(_) => {
+						log::warn!(target: "runtime::oss", "gateway {:?} can't afford the registration deposit", acc);
+						BalanceOf::<T>::zero()
+					},
+				};
+				<OssDeposits<T>>::insert(&acc, reserved);
+			}
+
+			T::DbWeight::get().reads_writes(gateways.saturating_mul(3), gateways.saturating_mul(2))
+		}
+	}
+
+	pub type MigrateV0ToV1<T> = VersionedMigration<
+		0,
+		1,
+		VersionUncheckedMigrateV0ToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
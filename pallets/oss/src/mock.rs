@@ -1,105 +1,83 @@
 use crate as pallet_oss;
-use frame_benchmarking::account;
-use frame_support:: {
-	parameter_types,
-	traits::ConstU32,
+use crate::{OssMetadata, RegionCode};
+use cp_cess_common::TerrName;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU128, ConstU32},
 };
-use sp_runtime::{
-	BuildStorage,
-	traits::{BlakeTwo256, IdentityLookup},
-};
-use sp_core::H256;
+use pallet_evm_account_mapping::{AddressConversion, Secp256K1PublicKeyForm};
+use sp_runtime::BuildStorage;
 
-pub(crate) type AccountId = u32;
-// type BlockNumber = u64;
-// type Balance = u64;
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
 frame_support::construct_runtime!(
 	pub enum Test {
 		System: frame_system,
+		Balances: pallet_balances,
 		Oss: pallet_oss,
-		// Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
-// parameter_types! {
-// 	pub const ExistentialDeposit: u64 = 1;
-// }
-//
-// impl pallet_balances::Config for Test {
-// 	type Balance = u64;
-// 	type DustRemoval = ();
-// 	type Event = Event;
-// 	type ExistentialDeposit = ExistentialDeposit;
-// 	type AccountStore = System;
-// 	type WeightInfo = ();
-// 	type MaxLocks = ();
-// 	type MaxReserves = ();
-// 	type ReserveIdentifier = [u8; 8];
-// }
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+}
+
+pub(crate) const REGISTRATION_DEPOSIT: Balance = 1_000;
+pub(crate) const PAYLOAD_EXPIRED: u32 = 10;
 
 parameter_types! {
-	pub const BlockHashCount: u64 = 250;
 	#[derive(Clone, Eq, PartialEq)]
 	pub const P2PLength: u32 = 10;
 	#[derive(Clone, Eq, PartialEq)]
 	pub const AuthorLimit: u32 = 2;
 }
 
-impl frame_system::Config for Test {
-	type BaseCallFilter = frame_support::traits::Everything;
-	type BlockWeights = ();
-	type BlockLength = ();
-	type RuntimeOrigin = RuntimeOrigin;
-	type RuntimeCall = RuntimeCall;
-	type Block = Block;
-	type Nonce = u64;
-	type Hash = H256;
-	type Hashing = BlakeTwo256;
-	type AccountId = AccountId;
-	type Lookup = IdentityLookup<Self::AccountId>;
-	type RuntimeEvent = RuntimeEvent;
-	type BlockHashCount = BlockHashCount;
-	type DbWeight = ();
-	type Version = ();
-	type PalletInfo = PalletInfo;
-	type AccountData = ();
-	type OnNewAccount = ();
-	type OnKilledAccount = ();
-	type SystemWeightInfo = ();
-	type SS58Prefix = ();
-	type OnSetCode = ();
-	type MaxConsumers = ConstU32<16>;
+/// EVM keys don't map to accounts of the mock.
+pub struct NoAddressConverter;
+impl AddressConversion<AccountId> for NoAddressConverter {
+	const SECP256K1_PUBLIC_KEY_FORM: Secp256K1PublicKeyForm = Secp256K1PublicKeyForm::Compressed;
+
+	fn try_convert(_evm_public_key: &[u8]) -> Option<AccountId> {
+		None
+	}
 }
 
 impl pallet_oss::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type Currency = Balances;
+	type RegistrationDeposit = ConstU128<REGISTRATION_DEPOSIT>;
 	type P2PLength = P2PLength;
 	type AuthorLimit = AuthorLimit;
-	type WeightInfo = ();
+	type PayloadExpired = ConstU32<PAYLOAD_EXPIRED>;
+	type AddressConverter = NoAddressConverter;
+	type MaxScopeTerritories = ConstU32<2>;
 }
 
-	pub fn account1() -> AccountId {
-	account("account1", 0, 0)
-	}
+pub(crate) const ALICE: AccountId = 1;
+pub(crate) const BOB: AccountId = 2;
+pub(crate) const CHARLIE: AccountId = 3;
 
-	pub fn account2() -> AccountId {
-	account("account2", 0, 0)
-	}
-
-	pub fn miner1() -> AccountId {
-	account("miner1", 0, 0)
-	}
-
-	pub fn stash1() -> AccountId {
-	account("stash1", 0, 0)
-	}
+pub fn account1() -> AccountId {
+	ALICE
+}
 
-	pub fn controller1() -> AccountId {
-	account("controller1", 0, 0)
-	}
+pub fn account2() -> AccountId {
+	BOB
+}
 
 pub struct ExtBuilder;
 
@@ -111,8 +89,15 @@ impl Default for ExtBuilder {
 
 impl ExtBuilder {
 	fn build(self) -> sp_io::TestExternalities {
-		let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
-		let ext = sp_io::TestExternalities::from(storage);
+		let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(ALICE, 1_000_000), (BOB, 1_000_000), (CHARLIE, 1_000_000)],
+			..Default::default()
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+		let mut ext = sp_io::TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
 		ext
 	}
 
@@ -121,22 +106,22 @@ impl ExtBuilder {
 	}
 }
 
-// pub fn new_test_ext() -> sp_io::TestExternalities {
-// 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
-// 	pallet_balances::GenesisConfig::<Test> {
-// 		balances: vec![
-// 			(account1(), 18_000_000_000_000_000_000),
-// 			(account2(), 1_000_000_000_000),
-// 			(miner1(), 1_000_000_000_000),
-// 			(stash1(), 1_000_000_000_000),
-// 			(controller1(), 1_000_000_000_000),
-// 		],
-// 	}
-// 		.assimilate_storage(&mut t)
-// 		.unwrap();
-// 	let mut ext = sp_io::TestExternalities::new(t);
-// 	ext.execute_with(|| {
-// 		System::set_block_number(1); //must set block_number, otherwise the deposit_event() don't work
-// 	});
-// 	ext
-// }
+pub fn run_to_block(n: BlockNumber) {
+	System::set_block_number(n);
+}
+
+pub fn territory(name: &[u8]) -> TerrName {
+	name.to_vec().try_into().unwrap()
+}
+
+/// Metadata of a gateway in `region` serving objects over HTTP.
+pub fn http_metadata(region: RegionCode) -> OssMetadata<Balance> {
+	OssMetadata {
+		region,
+		http_endpoint: Some(b"https://gateway.example".to_vec().try_into().unwrap()),
+		s3_endpoint: None,
+		libp2p_endpoint: None,
+		max_object_size: 1 << 30,
+		fee_per_gib: 10,
+	}
+}
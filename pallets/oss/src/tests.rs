@@ -1,7 +1,42 @@
 use super::*;
 use crate::mock::{*, Oss};
 use crate::Oss as OssList;
-use frame_support::{assert_err, assert_ok};
+use frame_support::{assert_noop, assert_ok};
+
+const ENDPOINT: PeerId = [1u8; 38];
+
+fn domain() -> BoundedVec<u8, ConstU32<50>> {
+	b"gateway.example".to_vec().try_into().unwrap()
+}
+
+fn scope(upload: bool, delete: bool, territory_delivery: bool, territories: &[&str]) -> AuthorityScope<Test> {
+	AuthorityScope {
+		upload,
+		delete,
+		territory_delivery,
+		territories: territories.iter().map(|name| territory(name.as_bytes())).collect::<Vec<_>>().try_into().unwrap(),
+	}
+}
+
+fn scoped_authorize(expires_at: Option<BlockNumber>, scope: AuthorityScope<Test>, quota: Option<u128>) {
+	assert_ok!(Oss::scoped_authorize(
+		RuntimeOrigin::signed(ALICE),
+		BOB,
+		OssAuthorization { expires_at, scope, quota },
+	));
+}
+
+fn may_upload(territory_name: &[u8], size: u128) -> bool {
+	Oss::authorize_action(ALICE, BOB, OssAction::Upload { territory: &territory(territory_name), size })
+}
+
+fn may_delete(territory_name: &[u8]) -> bool {
+	Oss::authorize_action(ALICE, BOB, OssAction::Delete { territory: &territory(territory_name) })
+}
+
+fn may_deliver(territory_name: &[u8]) -> bool {
+	Oss::authorize_action(ALICE, BOB, OssAction::TerritoryDelivery { territory: &territory(territory_name) })
+}
 
 #[test]
 fn authorize_work() {
@@ -9,9 +44,10 @@ fn authorize_work() {
 		let owner = account1();
 		let operator = account2();
 
-		assert_ok!(Oss::authorize(RuntimeOrigin::signed(owner.clone()), operator.clone()));
-		let verify_operator = AuthorityList::<Test>::get(&owner).unwrap();
-		assert_eq!(verify_operator, operator);
+		assert_ok!(Oss::authorize(RuntimeOrigin::signed(owner), operator));
+		let verify_operator = AuthorityList::<Test>::get(&owner);
+		assert!(verify_operator.contains(&operator));
+		assert!(Oss::is_authorized(owner, operator));
 	});
 }
 
@@ -20,11 +56,12 @@ fn cancel_authorize_work() {
 	ExtBuilder::default().build_and_execute(|| {
 		let owner = account1();
 		let operator = account2();
-		assert_ok!(Oss::authorize(RuntimeOrigin::signed(owner.clone()), operator.clone()));
+		assert_ok!(Oss::authorize(RuntimeOrigin::signed(owner), operator));
 		assert!(AuthorityList::<Test>::contains_key(&owner));
 
-		assert_ok!(Oss::cancel_authorize(RuntimeOrigin::signed(owner.clone())));
-		assert!(!AuthorityList::<Test>::contains_key(&owner));
+		assert_ok!(Oss::cancel_authorize(RuntimeOrigin::signed(owner), operator));
+		assert!(!AuthorityList::<Test>::get(&owner).contains(&operator));
+		assert!(!Oss::is_authorized(owner, operator));
 	});
 }
 
@@ -32,11 +69,11 @@ fn cancel_authorize_work() {
 fn register_work() {
 	ExtBuilder::default().build_and_execute(|| {
 		let oss = account1();
-		let ip = IpAddress::IPV4([127,0,0,1], 15000);
-		assert_ok!(Oss::register(RuntimeOrigin::signed(oss.clone()), ip.clone()));
+		assert_ok!(Oss::register(RuntimeOrigin::signed(oss), ENDPOINT, domain(), http_metadata(*b"DE")));
 
-		let result_ip = OssList::<Test>::get(&oss).unwrap();
-		assert_eq!(result_ip, ip);
+		let result = OssList::<Test>::get(&oss).unwrap();
+		assert_eq!(result.peer_id, ENDPOINT);
+		assert_eq!(OssMetadataOf::<Test>::get(&oss), Some(http_metadata(*b"DE")));
 	});
 }
 
@@ -44,9 +81,11 @@ fn register_work() {
 fn register_err_registered() {
 	ExtBuilder::default().build_and_execute(|| {
 		let oss = account1();
-		let ip = IpAddress::IPV4([127,0,0,1], 15000);
-		assert_ok!(Oss::register(RuntimeOrigin::signed(oss.clone()), ip.clone()));
-		assert_err!(Oss::register(RuntimeOrigin::signed(oss.clone()), ip.clone()), Error::<Test>::Registered);
+		assert_ok!(Oss::register(RuntimeOrigin::signed(oss), ENDPOINT, domain(), http_metadata(*b"DE")));
+		assert_noop!(
+			Oss::register(RuntimeOrigin::signed(oss), ENDPOINT, domain(), http_metadata(*b"DE")),
+			Error::<Test>::Registered
+		);
 	});
 }
 
@@ -54,16 +93,195 @@ fn register_err_registered() {
 fn update_work() {
 	ExtBuilder::default().build_and_execute(|| {
 		let oss = account1();
-		let ip = IpAddress::IPV4([127,0,0,1], 15000);
-		assert_ok!(Oss::register(RuntimeOrigin::signed(oss.clone()), ip.clone()));
+		assert_ok!(Oss::register(RuntimeOrigin::signed(oss), ENDPOINT, domain(), http_metadata(*b"DE")));
+
+		let new_endpoint = [2u8; 38];
+		assert_ok!(Oss::update(RuntimeOrigin::signed(oss), new_endpoint, domain(), http_metadata(*b"FR")));
+
+		let result = OssList::<Test>::get(&oss).unwrap();
+		assert_eq!(result.peer_id, new_endpoint);
+		assert_eq!(OssMetadataOf::<Test>::get(&oss).unwrap().region, *b"FR");
+	});
+}
+
+#[test]
+fn scoped_authorization_expires() {
+	ExtBuilder::default().build_and_execute(|| {
+		// An authorization can't be granted already expired.
+		assert_noop!(
+			Oss::scoped_authorize(
+				RuntimeOrigin::signed(ALICE),
+				BOB,
+				OssAuthorization { expires_at: Some(1), scope: AuthorityScope::full(), quota: None },
+			),
+			Error::<Test>::Expired
+		);
+
+		scoped_authorize(Some(10), AuthorityScope::full(), None);
+		run_to_block(9);
+		assert!(Oss::is_authorized(ALICE, BOB));
+		assert!(may_delete(b"photos"));
+
+		run_to_block(10);
+		assert!(!Oss::is_authorized(ALICE, BOB));
+		assert!(!may_upload(b"photos", 1));
+		assert!(!may_delete(b"photos"));
+	});
+}
+
+#[test]
+fn scoped_authorization_limits_actions() {
+	ExtBuilder::default().build_and_execute(|| {
+		scoped_authorize(None, scope(true, false, false, &[]), None);
+
+		assert!(may_upload(b"photos", 1));
+		assert!(!may_delete(b"photos"));
+		assert!(!may_deliver(b"photos"));
+		// Other operators have no authorization at all.
+		assert!(!Oss::authorize_action(ALICE, CHARLIE, OssAction::Upload { territory: &territory(b"photos"), size: 1 }));
+	});
+}
+
+#[test]
+fn scoped_authorization_limits_territories() {
+	ExtBuilder::default().build_and_execute(|| {
+		scoped_authorize(None, scope(true, true, true, &["photos"]), None);
+
+		assert!(may_upload(b"photos", 1));
+		assert!(may_delete(b"photos"));
+		assert!(may_deliver(b"photos"));
+		assert!(!may_upload(b"documents", 1));
+		assert!(!may_delete(b"documents"));
+		assert!(!may_deliver(b"documents"));
+	});
+}
+
+#[test]
+fn uploads_use_up_the_quota() {
+	ExtBuilder::default().build_and_execute(|| {
+		scoped_authorize(None, AuthorityScope::full(), Some(100));
+
+		assert!(may_upload(b"photos", 60));
+		assert_eq!(AuthorityScopes::<Test>::get(ALICE, BOB).unwrap().quota, Some(40));
+		// An upload beyond what is left is refused without using the quota up.
+		assert!(!may_upload(b"photos", 50));
+		assert_eq!(AuthorityScopes::<Test>::get(ALICE, BOB).unwrap().quota, Some(40));
+		assert!(may_upload(b"photos", 40));
+		assert_eq!(AuthorityScopes::<Test>::get(ALICE, BOB).unwrap().quota, Some(0));
+		// The quota only limits uploads.
+		assert!(!may_upload(b"photos", 1));
+		assert!(may_delete(b"photos"));
+	});
+}
+
+#[test]
+fn authorize_lifts_the_limits() {
+	ExtBuilder::default().build_and_execute(|| {
+		scoped_authorize(None, scope(true, false, false, &["photos"]), Some(0));
+		assert!(!may_upload(b"photos", 1));
+
+		assert_ok!(Oss::authorize(RuntimeOrigin::signed(ALICE), BOB));
+		assert!(AuthorityScopes::<Test>::get(ALICE, BOB).is_none());
+		assert!(may_upload(b"documents", 1));
+		assert!(may_delete(b"documents"));
+	});
+}
+
+#[test]
+fn register_reserves_the_deposit_until_destroyed() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_noop!(
+			Oss::register(RuntimeOrigin::signed(ALICE), ENDPOINT, domain(), http_metadata(*b"de")),
+			Error::<Test>::InvalidRegion
+		);
+
+		assert_ok!(Oss::register(RuntimeOrigin::signed(ALICE), ENDPOINT, domain(), http_metadata(*b"DE")));
+		assert_eq!(Balances::reserved_balance(ALICE), REGISTRATION_DEPOSIT);
+		assert_eq!(OssDeposits::<Test>::get(ALICE), Some(REGISTRATION_DEPOSIT));
+
+		assert_ok!(Oss::destroy(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert!(OssDeposits::<Test>::get(ALICE).is_none());
+		assert!(OssMetadataOf::<Test>::get(ALICE).is_none());
+
+		// An account that can't afford the deposit can't register.
+		let poor = 9;
+		assert_noop!(
+			Oss::register(RuntimeOrigin::signed(poor), ENDPOINT, domain(), http_metadata(*b"DE")),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn gateways_are_discovered_by_region_and_capability() {
+	ExtBuilder::default().build_and_execute(|| {
+		assert_ok!(Oss::register(RuntimeOrigin::signed(ALICE), ENDPOINT, domain(), http_metadata(*b"DE")));
+		assert_ok!(Oss::register(RuntimeOrigin::signed(BOB), ENDPOINT, domain(), http_metadata(*b"FR")));
+		let s3_only = OssMetadata {
+			http_endpoint: None,
+			s3_endpoint: Some(b"https://s3.gateway.example".to_vec().try_into().unwrap()),
+			..http_metadata(*b"DE")
+		};
+		assert_ok!(Oss::register(RuntimeOrigin::signed(CHARLIE), ENDPOINT, domain(), s3_only));
+		// A gateway registered before metadata, which only shows up unfiltered.
+		let legacy = 4;
+		OssList::<Test>::insert(legacy, OssInfo { peer_id: ENDPOINT, domain: domain() });
+
+		let accounts = |gateways: Vec<OssGateway<AccountId, Balance>>| {
+			let mut accounts = gateways.into_iter().map(|gateway| gateway.account).collect::<Vec<_>>();
+			accounts.sort();
+			accounts
+		};
+		assert_eq!(accounts(Oss::gateways(None, None, None, 10)), vec![ALICE, BOB, CHARLIE, legacy]);
+		assert_eq!(accounts(Oss::gateways(Some(*b"DE"), None, None, 10)), vec![ALICE, CHARLIE]);
+		assert_eq!(accounts(Oss::gateways(None, Some(OssCapability::S3), None, 10)), vec![CHARLIE]);
+		assert_eq!(accounts(Oss::gateways(Some(*b"DE"), Some(OssCapability::Http), None, 10)), vec![ALICE]);
+		assert!(Oss::gateways(Some(*b"US"), None, None, 10).is_empty());
+	});
+}
+
+#[test]
+fn gateways_are_paged() {
+	ExtBuilder::default().build_and_execute(|| {
+		for acc in [ALICE, BOB, CHARLIE] {
+			assert_ok!(Oss::register(RuntimeOrigin::signed(acc), ENDPOINT, domain(), http_metadata(*b"DE")));
+		}
+
+		let all = Oss::gateways(None, None, None, 10);
+		assert_eq!(all.len(), 3);
+		let first_page = Oss::gateways(None, None, None, 2);
+		assert_eq!(first_page, all[..2].to_vec());
+		let next_page = Oss::gateways(None, None, Some(first_page[1].account), 2);
+		assert_eq!(next_page, all[2..].to_vec());
+		assert!(Oss::gateways(None, None, Some(next_page[0].account), 2).is_empty());
+	});
+}
+
+#[test]
+fn migration_reserves_the_deposit_of_legacy_gateways() {
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	ExtBuilder::default().build_and_execute(|| {
+		StorageVersion::new(0).put::<Oss>();
+		let poor = 9;
+		for acc in [ALICE, poor] {
+			OssList::<Test>::insert(acc, OssInfo { peer_id: ENDPOINT, domain: domain() });
+		}
+		// Gateways registered with a deposit already keep it.
+		assert_ok!(Oss::register(RuntimeOrigin::signed(BOB), ENDPOINT, domain(), http_metadata(*b"DE")));
 
-		let result_ip = OssList::<Test>::get(&oss).unwrap();
-		assert_eq!(result_ip, ip);
+		crate::migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
 
-		let new_ip = IpAddress::IPV4([127,0,0,1], 15001);
-		assert_ok!(Oss::update(RuntimeOrigin::signed(oss.clone()), new_ip.clone()));
+		assert_eq!(Oss::on_chain_storage_version(), 1);
+		assert_eq!(Balances::reserved_balance(ALICE), REGISTRATION_DEPOSIT);
+		assert_eq!(OssDeposits::<Test>::get(ALICE), Some(REGISTRATION_DEPOSIT));
+		assert_eq!(Balances::reserved_balance(BOB), REGISTRATION_DEPOSIT);
+		assert_eq!(OssDeposits::<Test>::get(poor), Some(0));
 
-		let result_ip = OssList::<Test>::get(&oss).unwrap();
-		assert_eq!(result_ip, new_ip);
+		// The deposit is refunded like any other.
+		assert_ok!(Oss::destroy(RuntimeOrigin::signed(ALICE)));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_ok!(Oss::destroy(RuntimeOrigin::signed(poor)));
 	});
 }
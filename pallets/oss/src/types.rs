@@ -6,10 +6,131 @@ pub struct OssInfo {
     pub(super) domain: BoundedVec<u8, ConstU32<50>>,
 }
 
+/// An ISO 3166-1 alpha-2 country code, such as `*b"DE"`.
+pub type RegionCode = [u8; 2];
+
+/// A gateway endpoint, such as a URL or a multiaddr.
+pub type Endpoint = BoundedVec<u8, ConstU32<128>>;
+
+/// A protocol a gateway serves objects over.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum OssCapability {
+    Http,
+    S3,
+    Libp2p,
+}
+
+/// What clients need to know to choose a gateway.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct OssMetadata<Balance> {
+    pub region: RegionCode,
+    pub http_endpoint: Option<Endpoint>,
+    pub s3_endpoint: Option<Endpoint>,
+    pub libp2p_endpoint: Option<Endpoint>,
+    /// The largest object the gateway accepts, in bytes.
+    pub max_object_size: u128,
+    pub fee_per_gib: Balance,
+}
+
+impl<Balance> OssMetadata<Balance> {
+    pub fn supports(&self, capability: OssCapability) -> bool {
+        match capability {
+            OssCapability::Http => self.http_endpoint.is_some(),
+            OssCapability::S3 => self.s3_endpoint.is_some(),
+            OssCapability::Libp2p => self.libp2p_endpoint.is_some(),
+        }
+    }
+
+    pub(super) fn has_valid_region(&self) -> bool {
+        self.region.iter().all(u8::is_ascii_uppercase)
+    }
+}
+
+/// A registered gateway, as returned by discovery.
+///
+/// `metadata` is `None` for gateways registered before metadata was introduced.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct OssGateway<AccountId, Balance> {
+    pub account: AccountId,
+    pub info: OssInfo,
+    pub metadata: Option<OssMetadata<Balance>>,
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[scale_info(skip_type_params(T))]
 #[codec(mel_bound())]
 pub struct ProxyAuthPayload<T: Config> {
     pub(super) oss: AccountOf<T>,
+    /// The block until which the signed payload may be submitted.
     pub(super) exp: BlockNumberFor<T>,
+    /// The authorization granted to `oss`.
+    pub(super) authorization: OssAuthorization<T>,
+}
+
+/// What an authorized operator may do with the owner's files.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct AuthorityScope<T: Config> {
+    pub upload: bool,
+    pub delete: bool,
+    pub territory_delivery: bool,
+    /// The territories the operator may act in, any if empty.
+    pub territories: BoundedVec<TerrName, T::MaxScopeTerritories>,
+}
+
+impl<T: Config> AuthorityScope<T> {
+    /// Every action, in every territory.
+    pub fn full() -> Self {
+        Self { upload: true, delete: true, territory_delivery: true, territories: Default::default() }
+    }
+
+    fn permits(&self, action: &OssAction) -> bool {
+        let (allowed, territory) = match action {
+            OssAction::Upload { territory, .. } => (self.upload, territory),
+            OssAction::Delete { territory } => (self.delete, territory),
+            OssAction::TerritoryDelivery { territory } => (self.territory_delivery, territory),
+        };
+        allowed && (self.territories.is_empty() || self.territories.contains(territory))
+    }
+}
+
+/// The limits of an operator's authorization.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct OssAuthorization<T: Config> {
+    /// The block from which the authorization no longer holds, if it expires.
+    pub expires_at: Option<BlockNumberFor<T>>,
+    pub scope: AuthorityScope<T>,
+    /// The bytes the operator may still upload, if limited.
+    pub quota: Option<u128>,
+}
+
+impl<T: Config> OssAuthorization<T> {
+    /// The authorization `authorize` grants: unscoped, unlimited and never expiring.
+    pub fn unlimited() -> Self {
+        Self { expires_at: None, scope: AuthorityScope::full(), quota: None }
+    }
+
+    pub(super) fn is_expired(&self, now: BlockNumberFor<T>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Whether the authorization covers `action`, using up the quota of an upload.
+    pub(super) fn use_for(&mut self, action: &OssAction) -> bool {
+        if !self.scope.permits(action) {
+            return false;
+        }
+        match (action, self.quota.as_mut()) {
+            (OssAction::Upload { size, .. }, Some(quota)) => match quota.checked_sub(*size) {
+                Some(left) => {
+                    *quota = left;
+                    true
+                },
+                None => false,
+            },
+            _ => true,
+        }
+    }
 }
\ No newline at end of file
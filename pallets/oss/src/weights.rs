@@ -43,6 +43,7 @@ pub trait WeightInfo {
 	fn destroy() -> Weight;
 	fn proxy_authorzie() -> Weight;
 	fn evm_proxy_authorzie() -> Weight;
+	fn scoped_authorize() -> Weight;
 }
 
 /// Weights for `pallet_oss` using the Substrate node and recommended hardware.
@@ -72,17 +73,25 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	}
 	/// Storage: `Oss::Oss` (r:1 w:1)
 	/// Proof: `Oss::Oss` (`max_values`: None, `max_size`: Some(137), added: 2612, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssDeposits` (r:0 w:1)
+	/// Proof: `Oss::OssDeposits` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssMetadataOf` (r:0 w:1)
+	/// Proof: `Oss::OssMetadataOf` (`max_values`: None, `max_size`: Some(438), added: 2913, mode: `MaxEncodedLen`)
 	fn register() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `76`
 		//  Estimated: `3602`
 		// Minimum execution time: 10_065_000 picoseconds.
 		Weight::from_parts(10_680_000, 3602)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Oss::Oss` (r:1 w:1)
 	/// Proof: `Oss::Oss` (`max_values`: None, `max_size`: Some(137), added: 2612, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssMetadataOf` (r:0 w:1)
+	/// Proof: `Oss::OssMetadataOf` (`max_values`: None, `max_size`: Some(438), added: 2913, mode: `MaxEncodedLen`)
 	fn update() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `191`
@@ -90,21 +99,29 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 11_928_000 picoseconds.
 		Weight::from_parts(13_720_000, 3602)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Oss::Oss` (r:1 w:1)
 	/// Proof: `Oss::Oss` (`max_values`: None, `max_size`: Some(137), added: 2612, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssDeposits` (r:1 w:1)
+	/// Proof: `Oss::OssDeposits` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssMetadataOf` (r:0 w:1)
+	/// Proof: `Oss::OssMetadataOf` (`max_values`: None, `max_size`: Some(438), added: 2913, mode: `MaxEncodedLen`)
 	fn destroy() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `181`
 		//  Estimated: `3602`
 		// Minimum execution time: 10_777_000 picoseconds.
 		Weight::from_parts(11_667_000, 3602)
-			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Oss::AuthorityList` (r:1 w:1)
 	/// Proof: `Oss::AuthorityList` (`max_values`: None, `max_size`: Some(689), added: 3164, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::AuthorityScopes` (r:0 w:1)
+	/// Proof: `Oss::AuthorityScopes` (`max_values`: None, `max_size`: Some(1190), added: 3665, mode: `MaxEncodedLen`)
 	fn proxy_authorzie() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `76`
@@ -112,10 +129,12 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 46_094_000 picoseconds.
 		Weight::from_parts(52_311_000, 4154)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Oss::AuthorityList` (r:1 w:1)
 	/// Proof: `Oss::AuthorityList` (`max_values`: None, `max_size`: Some(689), added: 3164, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::AuthorityScopes` (r:0 w:1)
+	/// Proof: `Oss::AuthorityScopes` (`max_values`: None, `max_size`: Some(1190), added: 3665, mode: `MaxEncodedLen`)
 	fn evm_proxy_authorzie() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `76`
@@ -123,7 +142,20 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 39_028_000 picoseconds.
 		Weight::from_parts(41_898_000, 4154)
 			.saturating_add(T::DbWeight::get().reads(1_u64))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Oss::AuthorityList` (r:1 w:1)
+	/// Proof: `Oss::AuthorityList` (`max_values`: None, `max_size`: Some(689), added: 3164, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::AuthorityScopes` (r:0 w:1)
+	/// Proof: `Oss::AuthorityScopes` (`max_values`: None, `max_size`: Some(1190), added: 3665, mode: `MaxEncodedLen`)
+	fn scoped_authorize() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `4154`
+		// Minimum execution time: 14_512_000 picoseconds.
+		Weight::from_parts(15_873_000, 4154)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 }
 
@@ -153,17 +185,25 @@ impl WeightInfo for () {
 	}
 	/// Storage: `Oss::Oss` (r:1 w:1)
 	/// Proof: `Oss::Oss` (`max_values`: None, `max_size`: Some(137), added: 2612, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssDeposits` (r:0 w:1)
+	/// Proof: `Oss::OssDeposits` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssMetadataOf` (r:0 w:1)
+	/// Proof: `Oss::OssMetadataOf` (`max_values`: None, `max_size`: Some(438), added: 2913, mode: `MaxEncodedLen`)
 	fn register() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `76`
 		//  Estimated: `3602`
 		// Minimum execution time: 10_065_000 picoseconds.
 		Weight::from_parts(10_680_000, 3602)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Oss::Oss` (r:1 w:1)
 	/// Proof: `Oss::Oss` (`max_values`: None, `max_size`: Some(137), added: 2612, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssMetadataOf` (r:0 w:1)
+	/// Proof: `Oss::OssMetadataOf` (`max_values`: None, `max_size`: Some(438), added: 2913, mode: `MaxEncodedLen`)
 	fn update() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `191`
@@ -171,21 +211,29 @@ impl WeightInfo for () {
 		// Minimum execution time: 11_928_000 picoseconds.
 		Weight::from_parts(13_720_000, 3602)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Oss::Oss` (r:1 w:1)
 	/// Proof: `Oss::Oss` (`max_values`: None, `max_size`: Some(137), added: 2612, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssDeposits` (r:1 w:1)
+	/// Proof: `Oss::OssDeposits` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::OssMetadataOf` (r:0 w:1)
+	/// Proof: `Oss::OssMetadataOf` (`max_values`: None, `max_size`: Some(438), added: 2913, mode: `MaxEncodedLen`)
 	fn destroy() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `181`
 		//  Estimated: `3602`
 		// Minimum execution time: 10_777_000 picoseconds.
 		Weight::from_parts(11_667_000, 3602)
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: `Oss::AuthorityList` (r:1 w:1)
 	/// Proof: `Oss::AuthorityList` (`max_values`: None, `max_size`: Some(689), added: 3164, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::AuthorityScopes` (r:0 w:1)
+	/// Proof: `Oss::AuthorityScopes` (`max_values`: None, `max_size`: Some(1190), added: 3665, mode: `MaxEncodedLen`)
 	fn proxy_authorzie() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `76`
@@ -193,10 +241,12 @@ impl WeightInfo for () {
 		// Minimum execution time: 46_094_000 picoseconds.
 		Weight::from_parts(52_311_000, 4154)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: `Oss::AuthorityList` (r:1 w:1)
 	/// Proof: `Oss::AuthorityList` (`max_values`: None, `max_size`: Some(689), added: 3164, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::AuthorityScopes` (r:0 w:1)
+	/// Proof: `Oss::AuthorityScopes` (`max_values`: None, `max_size`: Some(1190), added: 3665, mode: `MaxEncodedLen`)
 	fn evm_proxy_authorzie() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `76`
@@ -204,6 +254,19 @@ impl WeightInfo for () {
 		// Minimum execution time: 39_028_000 picoseconds.
 		Weight::from_parts(41_898_000, 4154)
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Oss::AuthorityList` (r:1 w:1)
+	/// Proof: `Oss::AuthorityList` (`max_values`: None, `max_size`: Some(689), added: 3164, mode: `MaxEncodedLen`)
+	/// Storage: `Oss::AuthorityScopes` (r:0 w:1)
+	/// Proof: `Oss::AuthorityScopes` (`max_values`: None, `max_size`: Some(1190), added: 3665, mode: `MaxEncodedLen`)
+	fn scoped_authorize() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `76`
+		//  Estimated: `4154`
+		// Minimum execution time: 14_512_000 picoseconds.
+		Weight::from_parts(15_873_000, 4154)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 }
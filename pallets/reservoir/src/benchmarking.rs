@@ -0,0 +1,160 @@
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::vec::Vec;
+
+const SEED: u32 = 0;
+
+fn amount<T: Config>() -> BalanceOf<T> {
+    T::Currency::minimum_balance().saturating_mul(1000u32.into())
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> AccountOf<T> {
+    let acc: AccountOf<T> = account(name, index, SEED);
+    T::Currency::make_free_balance_be(&acc, BalanceOf::<T>::max_value() / 4u32.into());
+    acc
+}
+
+fn event_id<T: Config>(name: &[u8]) -> BoundedVec<u8, T::IdLength> {
+    name.to_vec().try_into().expect("event id fits the id length")
+}
+
+fn fill_reservoir<T: Config>() -> Result<(), &'static str> {
+    let filler = funded_account::<T>("filler", 0);
+    Pallet::<T>::filling(RawOrigin::Signed(filler).into(), amount::<T>().saturating_mul(100u32.into()))
+        .map_err(|_| "filling failed")?;
+    Ok(())
+}
+
+fn new_event<T: Config>(
+    id: &BoundedVec<u8, T::IdLength>,
+    use_type: UseType,
+    rules: EventRules<BlockNumberFor<T>>,
+) -> Result<(), &'static str> {
+    Pallet::<T>::create_event(
+        RawOrigin::Root.into(),
+        id.clone(),
+        10,
+        100u32.into(),
+        amount::<T>(),
+        10u32.into(),
+        use_type,
+        Perbill::from_percent(10),
+        rules,
+    )
+    .map_err(|_| "create_event failed")?;
+    Ok(())
+}
+
+benchmarks! {
+    filling {
+        let caller = funded_account::<T>("caller", 0);
+    }: _(RawOrigin::Signed(caller), amount::<T>())
+    verify {
+        assert_eq!(<Reservoir<T>>::get().free_balance, amount::<T>());
+    }
+
+    store {
+        let caller = funded_account::<T>("caller", 0);
+    }: _(RawOrigin::Signed(caller.clone()), amount::<T>())
+    verify {
+        assert_eq!(<UserPassbook<T>>::get(&caller).free, amount::<T>());
+    }
+
+    withdraw {
+        let caller = funded_account::<T>("caller", 0);
+        Pallet::<T>::store(RawOrigin::Signed(caller.clone()).into(), amount::<T>().saturating_mul(2u32.into()))?;
+    }: _(RawOrigin::Signed(caller.clone()), amount::<T>())
+    verify {
+        assert_eq!(<UserPassbook<T>>::get(&caller).free, amount::<T>());
+    }
+
+    event_withdraw {
+        fill_reservoir::<T>()?;
+        let target: AccountOf<T> = account("target", 0, SEED);
+    }: _(RawOrigin::Root, amount::<T>(), target.clone())
+    verify {
+        assert_eq!(T::Currency::free_balance(&target), amount::<T>());
+    }
+
+    create_event {
+        let id = event_id::<T>(b"event");
+    }: _(
+        RawOrigin::Root,
+        id.clone(),
+        10,
+        100u32.into(),
+        amount::<T>(),
+        10u32.into(),
+        UseType::MinerStaking,
+        Perbill::from_percent(10),
+        EventRules::default()
+    )
+    verify {
+        assert!(<Events<T>>::contains_key(&id));
+    }
+
+    // The caller closes an expired loan and passes every eligibility rule but KYC.
+    attend_event {
+        fill_reservoir::<T>()?;
+        let caller = funded_account::<T>("caller", 0);
+
+        let old_id = event_id::<T>(b"old");
+        new_event::<T>(&old_id, UseType::MinerStaking, EventRules::default())?;
+        Pallet::<T>::attend_event(RawOrigin::Signed(caller.clone()).into(), old_id)?;
+        Pallet::<T>::store(RawOrigin::Signed(caller.clone()).into(), amount::<T>())?;
+        frame_system::Pallet::<T>::set_block_number(20u32.into());
+
+        let id = event_id::<T>(b"event");
+        let rules = EventRules {
+            allowlist_only: true,
+            max_per_account: 1,
+            min_account_age: 1u32.into(),
+            require_kyc: false,
+        };
+        new_event::<T>(&id, UseType::MinerStaking, rules)?;
+        let accounts: BoundedVec<AccountOf<T>, T::AllowlistLimit> =
+            sp_std::vec![caller.clone()].try_into().map_err(|_| "allowlist too small")?;
+        Pallet::<T>::set_allowlist(RawOrigin::Root.into(), id.clone(), accounts, true)?;
+    }: _(RawOrigin::Signed(caller.clone()), id.clone())
+    verify {
+        assert_eq!(<Attendance<T>>::get(&id, &caller), 1);
+        assert!(<BorrowList<T>>::contains_key(&caller));
+    }
+
+    // The caller pays back interest and spent funds, closing the loan.
+    repay {
+        fill_reservoir::<T>()?;
+        let caller = funded_account::<T>("caller", 0);
+        let id = event_id::<T>(b"event");
+        new_event::<T>(&id, UseType::TerritoryPurchase, EventRules::default())?;
+        Pallet::<T>::attend_event(RawOrigin::Signed(caller.clone()).into(), id)?;
+        let dest: AccountOf<T> = account("dest", 0, SEED);
+        <Pallet<T> as ReservoirGate<AccountOf<T>, BalanceOf<T>>>::spend(
+            &caller,
+            UseType::TerritoryPurchase,
+            amount::<T>() / 2u32.into(),
+            &dest,
+        )?;
+    }: _(RawOrigin::Signed(caller.clone()), amount::<T>())
+    verify {
+        assert!(!<BorrowList<T>>::contains_key(&caller));
+    }
+
+    set_allowlist {
+        let v in 1 .. T::AllowlistLimit::get();
+        let id = event_id::<T>(b"event");
+        new_event::<T>(&id, UseType::MinerStaking, EventRules::default())?;
+        let accounts: Vec<AccountOf<T>> = (0..v).map(|i| account("allowed", i, SEED)).collect();
+        let accounts: BoundedVec<AccountOf<T>, T::AllowlistLimit> =
+            accounts.try_into().map_err(|_| "allowlist too small")?;
+        let last = accounts[accounts.len() - 1].clone();
+    }: _(RawOrigin::Root, id.clone(), accounts, true)
+    verify {
+        assert!(<EventAllowlist<T>>::contains_key(&id, &last));
+    }
+}
@@ -1,5 +1,5 @@
 use super::*;
-use sp_runtime::traits::Zero;
+use sp_runtime::traits::{Zero, Saturating};
 
 impl<Balance: Zero> Default for ReservoirInfo<Balance> {
     fn default() -> Self {
@@ -18,4 +18,22 @@ impl<Balance: Zero> Default  for UserHold<Balance> {
             staking: Balance::zero(),
         }
     }
+}
+
+impl<T: Config> BorrowInfo<T> {
+    /// Everything the borrower still has to pay back.
+    pub(super) fn owed(&self) -> BalanceOf<T> {
+        self.interest.saturating_add(self.debt)
+    }
+}
+
+impl<BlockNumber: Zero> Default for EventRules<BlockNumber> {
+    fn default() -> Self {
+        EventRules::<BlockNumber> {
+            allowlist_only: false,
+            max_per_account: u32::MAX,
+            min_account_age: BlockNumber::zero(),
+            require_kyc: false,
+        }
+    }
 }
\ No newline at end of file
@@ -1,16 +1,31 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 mod types;
-use types::*;
+pub use types::*;
 
 mod impls;
 
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod migrations;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
 pub use pallet::*;
 
 use codec::{Decode, Encode};
-use sp_runtime::traits::{
-    Zero, CheckedAdd, CheckedSub, AccountIdConversion,
-
+use sp_runtime::{
+    Perbill,
+    traits::{
+        Zero, CheckedAdd, CheckedSub, AccountIdConversion, Saturating,
+    },
 };
 use frame_system::{
     pallet_prelude::{OriginFor, *},
@@ -18,9 +33,10 @@ use frame_system::{
 use frame_support::{
     PalletId,
     pallet_prelude::*,
+    storage::with_storage_layer,
     traits::{
-        Get, Currency, ReservableCurrency,
-        ExistenceRequirement::KeepAlive,
+        Get, Currency, ReservableCurrency, StorageVersion, OnNewAccount, OnKilledAccount,
+        ExistenceRequirement::{AllowDeath, KeepAlive},
     },
 };
 
@@ -28,6 +44,8 @@ type AccountOf<T> = <T as frame_system::Config>::AccountId;
 type BalanceOf<T> =
 	<<T as pallet::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -48,6 +66,21 @@ pub mod pallet {
         /// ongoing event cap
         #[pallet::constant]
         type EventLimit: Get<u32>;
+        /// Maximum number of loans expired in the same block. Later loans spill over to the next block.
+        #[pallet::constant]
+        type LoanLimit: Get<u32>;
+        /// Maximum number of accounts added to an event allowlist at once
+        #[pallet::constant]
+        type AllowlistLimit: Get<u32>;
+        /// Tells which accounts passed KYC.
+        type KycProvider: KycProvider<Self::AccountId>;
+        /// Takes back the miner collateral staked with borrowed funds once the loan expires.
+        type LoanCollateral: LoanCollateral<Self::AccountId, BalanceOf<Self>>;
+        /// Maximum number of allowlist and attendance entries of expired events removed per block
+        #[pallet::constant]
+        type EventCleanupLimit: Get<u32>;
+        /// Weight information for extrinsics in this pallet.
+        type WeightInfo: WeightInfo;
     }
 
     #[pallet::event]
@@ -66,7 +99,26 @@ pub mod pallet {
         /// Attending an event success event.
         AttendEvent { id: BoundedVec<u8, T::IdLength> },
         /// Information reported when polling and processing expiration events.
-        EventExpired { id: BoundedVec<u8, T::IdLength> },     
+        EventExpired { id: BoundedVec<u8, T::IdLength> },
+        /// A borrower paid back interest or slashed principal.
+        Repay { acc: AccountOf<T>, amount: BalanceOf<T> },
+        /// Borrowed stake was slashed and is now owed by the borrower.
+        BorrowedStakeSlashed { acc: AccountOf<T>, amount: BalanceOf<T> },
+        /// Miner collateral staked through the reservoir was taken back from an expired loan.
+        CollateralRecovered { acc: AccountOf<T>, amount: BalanceOf<T> },
+        /// Funds were taken back from a borrower to cover its loan.
+        LoanClawback { acc: AccountOf<T>, amount: BalanceOf<T> },
+        /// A loan reached its deadline without being settled.
+        LoanOverdue { acc: AccountOf<T>, staking: BalanceOf<T>, owed: BalanceOf<T> },
+        /// A loan was settled and its remaining funds returned to the reservoir.
+        LoanClosed { acc: AccountOf<T> },
+        /// The allowlist of an event was updated.
+        AllowlistUpdated { id: BoundedVec<u8, T::IdLength>, count: u32, allowed: bool },
+        /// Borrowed funds were spent for the use they were lent for. `dest` is the reservoir when
+        /// the funds were paid out of it by another pallet.
+        Spend { acc: AccountOf<T>, use_type: UseType, amount: BalanceOf<T>, dest: AccountOf<T> },
+        /// Spent funds came back to the reservoir and can be spent again.
+        Refund { acc: AccountOf<T>, use_type: UseType, amount: BalanceOf<T> },
     }
 
     #[pallet::error]
@@ -89,6 +141,22 @@ pub mod pallet {
         UpperLimit,
         /// The current user already has a borrowing bill.
         Borrowed,
+        /// The loan is still backing miner collateral or has something left to pay.
+        Outstanding,
+        /// The account is not on the allowlist of the event.
+        NotAllowed,
+        /// The account attended the event as many times as allowed.
+        AttendLimit,
+        /// The account was created too recently to attend the event.
+        AccountTooNew,
+        /// The event requires a KYC verified identity.
+        NotVerified,
+        /// The borrowed funds were lent for another use.
+        UseTypeMismatch,
+        /// The borrow period of the loan is over.
+        LoanExpired,
+        /// The event id belongs to an expired event that is still being cleaned up.
+        CleanupPending,
     }
 
     #[pallet::storage]
@@ -110,8 +178,31 @@ pub mod pallet {
     #[pallet::storage]
     #[pallet::getter(fn event_expired_records)]
     pub(super) type EventExpiredRecords<T: Config> = StorageMap<_, Twox64Concat, BlockNumberFor<T>, BoundedVec<BoundedVec<u8, T::IdLength>, T::EventLimit>, ValueQuery>;
-    
+
+    #[pallet::storage]
+    #[pallet::getter(fn loan_deadlines)]
+    pub(super) type LoanDeadlines<T: Config> = StorageMap<_, Twox64Concat, BlockNumberFor<T>, BoundedVec<AccountOf<T>, T::LoanLimit>, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn event_allowlist)]
+    pub(super) type EventAllowlist<T: Config> = StorageDoubleMap<_, Twox64Concat, BoundedVec<u8, T::IdLength>, Twox64Concat, AccountOf<T>, ()>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn attendance)]
+    pub(super) type Attendance<T: Config> = StorageDoubleMap<_, Twox64Concat, BoundedVec<u8, T::IdLength>, Twox64Concat, AccountOf<T>, u32, ValueQuery>;
+
+    /// Expired events whose allowlist and attendance are still being removed.
+    #[pallet::storage]
+    #[pallet::getter(fn stale_events)]
+    pub(super) type StaleEvents<T: Config> = StorageMap<_, Twox64Concat, BoundedVec<u8, T::IdLength>, ()>;
+
+    /// The block accounts were created at. Accounts created before it was tracked are missing.
+    #[pallet::storage]
+    #[pallet::getter(fn account_birth)]
+    pub(super) type AccountBirth<T: Config> = StorageMap<_, Twox64Concat, AccountOf<T>, BlockNumberFor<T>>;
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(PhantomData<T>);
 
     #[pallet::hooks]
@@ -123,9 +214,17 @@ pub mod pallet {
             weight = weight.saturating_add(T::DbWeight::get().reads(1));
             for id in list.iter() {
                 Events::<T>::remove(id);
-                weight = weight.saturating_add(T::DbWeight::get().writes(1));
+                <StaleEvents<T>>::insert(id, ());
+                weight = weight.saturating_add(T::DbWeight::get().writes(2));
                 Self::deposit_event( Event::<T>::EventExpired { id: id.clone() });
             }
+            weight = weight.saturating_add(Self::clean_up_events());
+
+            let borrowers = <LoanDeadlines<T>>::take(&now);
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+            for acc in borrowers.iter() {
+                weight = weight.saturating_add(Self::expire_loan(acc, now));
+            }
 
             weight
         }
@@ -134,7 +233,7 @@ pub mod pallet {
     #[pallet::call]
 	impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
-		#[pallet::weight(Weight::zero())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::filling())]
         pub fn filling(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
@@ -153,7 +252,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(1)]
-		#[pallet::weight(Weight::zero())]
+		#[pallet::weight(<T as pallet::Config>::WeightInfo::store())]
         pub fn store(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
@@ -178,7 +277,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(2)]
-        #[pallet::weight(Weight::zero())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::withdraw())]
         pub fn withdraw(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
             let sender = ensure_signed(origin)?;
 
@@ -203,7 +302,7 @@ pub mod pallet {
         }
 
         #[pallet::call_index(3)]
-        #[pallet::weight(Weight::zero())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::event_withdraw())]
         pub fn event_withdraw(origin: OriginFor<T>, amount: BalanceOf<T>, target: AccountOf<T>) -> DispatchResult {
             ensure_root(origin)?;
 
@@ -223,7 +322,7 @@ pub mod pallet {
         // When calling this method to create an activity, 
         // please ensure that the free_balance of the reservoir is sufficient.
         #[pallet::call_index(4)]
-        #[pallet::weight(Weight::zero())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::create_event())]
         pub fn create_event(
             origin: OriginFor<T>, 
             id: BoundedVec<u8, T::IdLength>,
@@ -232,10 +331,13 @@ pub mod pallet {
             unit_amount: BalanceOf<T>, 
             borrow_period: BlockNumberFor<T>, 
             use_type: UseType,
+            interest_rate: Perbill,
+            rules: EventRules<BlockNumberFor<T>>,
         ) -> DispatchResult {
             ensure_root(origin)?;
 
             ensure!(!<Events<T>>::contains_key(&id), Error::<T>::LengthExceedsLimit);
+            ensure!(!<StaleEvents<T>>::contains_key(&id), Error::<T>::CleanupPending);
 
             let event_info = EventInfo::<T>{
                 quota,
@@ -243,6 +345,8 @@ pub mod pallet {
                 unit_amount,
                 borrow_period,
                 use_type,
+                interest_rate,
+                rules,
             };
 
             <Events<T>>::insert(&id, event_info);
@@ -261,24 +365,16 @@ pub mod pallet {
         }
 
         #[pallet::call_index(5)]
-        #[pallet::weight(Weight::zero())]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::attend_event())]
         pub fn attend_event(origin: OriginFor<T>, id: BoundedVec<u8, T::IdLength>) -> DispatchResult {
             let sender = ensure_signed(origin)?;
             
             ensure!(Events::<T>::contains_key(&id), Error::<T>::IdNonExistent);
-            if let Ok(borrow_info) = <BorrowList<T>>::try_get(&sender) {
+            if let Ok(mut borrow_info) = <BorrowList<T>>::try_get(&sender) {
                 let now = <frame_system::Pallet<T>>::block_number();
-                if now >= borrow_info.deadline && borrow_info.staking == BalanceOf::<T>::zero() {
-                    Reservoir::<T>::try_mutate(|reservoir_info| -> DispatchResult {
-                        reservoir_info.borrow_balance = reservoir_info.borrow_balance.checked_sub(&borrow_info.free).ok_or(Error::<T>::Overflow)?;
-                        reservoir_info.free_balance = reservoir_info.free_balance.checked_add(&borrow_info.free).ok_or(Error::<T>::Overflow)?;
-
-                        Ok(())
-                    })?;
-                    <BorrowList<T>>::remove(&sender);
-                } else {
-                    return Err(Error::<T>::Borrowed)?;
-                }
+                // A loan can only be replaced once it is over and fully paid back.
+                ensure!(now >= borrow_info.deadline, Error::<T>::Borrowed);
+                ensure!(Self::settle_loan(&sender, &mut borrow_info, now)?, Error::<T>::Outstanding);
             }
 
             <Events<T>>::try_mutate(&id, |event_opt| -> DispatchResult {
@@ -288,6 +384,9 @@ pub mod pallet {
                     return Err(Error::<T>::UpperLimit)?;
                 }
 
+                Self::check_eligibility(&sender, &id, &event.rules)?;
+                <Attendance<T>>::mutate(&id, &sender, |count| *count = count.saturating_add(1));
+
                 event.quota = event.quota.checked_sub(1).ok_or(Error::<T>::Overflow)?;
 
                 let reservoir = T::PalletId::get().into_account_truncating();
@@ -299,9 +398,15 @@ pub mod pallet {
                     lender: reservoir,
                     staking: BalanceOf::<T>::zero(),
                     deadline: deadline,
+                    interest: event.interest_rate * event.unit_amount,
+                    debt: BalanceOf::<T>::zero(),
+                    overdue: false,
+                    use_type: event.use_type.clone(),
                 };
                 BorrowList::<T>::insert(&sender, borrow_info);
 
+                Self::schedule_expiry(&sender, deadline)?;
+
                 Reservoir::<T>::try_mutate(|reservoir_info| -> DispatchResult {
                     reservoir_info.borrow_balance = reservoir_info.borrow_balance.checked_add(&event.unit_amount).ok_or(Error::<T>::Overflow)?;
                     reservoir_info.free_balance = reservoir_info.free_balance.checked_sub(&event.unit_amount).ok_or(Error::<T>::Overflow)?;
@@ -316,22 +421,363 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Pays back the interest and the slashed principal of the caller's loan.
+        ///
+        /// Interest is paid first. Once nothing is owed and no borrowed funds back
+        /// miner collateral anymore, the loan is closed.
+        #[pallet::call_index(6)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::repay())]
+        pub fn repay(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+            let sender = ensure_signed(origin)?;
+
+            let mut borrow_info = <BorrowList<T>>::try_get(&sender).map_err(|_| Error::<T>::NonExistent)?;
+            let amount = amount.min(borrow_info.owed());
+            if !amount.is_zero() {
+                let reservoir = T::PalletId::get().into_account_truncating();
+                T::Currency::transfer(&sender, &reservoir, amount, KeepAlive)?;
+                Self::apply_repayment(&mut borrow_info, amount, true)?;
+            }
+
+            if borrow_info.staking.is_zero() && borrow_info.owed().is_zero() {
+                Self::close_loan(&sender, &borrow_info)?;
+            } else {
+                <BorrowList<T>>::insert(&sender, borrow_info);
+            }
+
+            Self::deposit_event(Event::<T>::Repay { acc: sender, amount });
+
+            Ok(())
+        }
+
+        /// Adds accounts to, or removes them from, the allowlist of an event.
+        #[pallet::call_index(7)]
+        #[pallet::weight(<T as pallet::Config>::WeightInfo::set_allowlist(accounts.len() as u32))]
+        pub fn set_allowlist(
+            origin: OriginFor<T>,
+            id: BoundedVec<u8, T::IdLength>,
+            accounts: BoundedVec<AccountOf<T>, T::AllowlistLimit>,
+            allowed: bool,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(Events::<T>::contains_key(&id), Error::<T>::IdNonExistent);
+            for acc in accounts.iter() {
+                if allowed {
+                    <EventAllowlist<T>>::insert(&id, acc, ());
+                } else {
+                    <EventAllowlist<T>>::remove(&id, acc);
+                }
+            }
+
+            Self::deposit_event(Event::<T>::AllowlistUpdated { id, count: accounts.len() as u32, allowed });
+
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    fn check_eligibility(acc: &AccountOf<T>, id: &BoundedVec<u8, T::IdLength>, rules: &EventRules<BlockNumberFor<T>>) -> DispatchResult {
+        if rules.allowlist_only {
+            ensure!(<EventAllowlist<T>>::contains_key(id, acc), Error::<T>::NotAllowed);
+        }
+
+        ensure!(<Attendance<T>>::get(id, acc) < rules.max_per_account, Error::<T>::AttendLimit);
+
+        if !rules.min_account_age.is_zero() {
+            let now = <frame_system::Pallet<T>>::block_number();
+            let birth = <AccountBirth<T>>::get(acc).unwrap_or_else(Zero::zero);
+            ensure!(now.saturating_sub(birth) >= rules.min_account_age, Error::<T>::AccountTooNew);
+        }
+
+        if rules.require_kyc {
+            ensure!(T::KycProvider::is_verified(acc), Error::<T>::NotVerified);
+        }
+
+        Ok(())
+    }
+
+    // Removes the allowlists and attendance of expired events, at most `EventCleanupLimit`
+    // entries per block. Unfinished prefixes are picked up again in the next block.
+    fn clean_up_events() -> Weight {
+        let mut weight = Weight::zero();
+        let mut budget = T::EventCleanupLimit::get();
+        while budget > 0 {
+            weight = weight.saturating_add(T::DbWeight::get().reads(1));
+            let Some(id) = <StaleEvents<T>>::iter_keys().next() else {
+                break;
+            };
+
+            let allowlist = EventAllowlist::<T>::clear_prefix(&id, budget, None);
+            budget = budget.saturating_sub(allowlist.backend);
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(allowlist.loops.into(), allowlist.unique.into()));
+            if allowlist.maybe_cursor.is_some() || budget == 0 {
+                break;
+            }
+
+            let attendance = Attendance::<T>::clear_prefix(&id, budget, None);
+            budget = budget.saturating_sub(attendance.backend);
+            weight = weight.saturating_add(T::DbWeight::get().reads_writes(attendance.loops.into(), attendance.unique.into()));
+            if attendance.maybe_cursor.is_some() {
+                break;
+            }
+
+            <StaleEvents<T>>::remove(&id);
+            weight = weight.saturating_add(T::DbWeight::get().writes(1));
+        }
+
+        weight
+    }
+
+    // Called when a loan reaches its deadline: the borrowed stake and what is owed are taken back
+    // from the miner collateral, the rest of what is owed from the borrower's passbook, and the
+    // loan is closed if nothing is left, or flagged overdue otherwise.
+    fn expire_loan(acc: &AccountOf<T>, now: BlockNumberFor<T>) -> Weight {
+        let mut weight = T::DbWeight::get().reads(1);
+
+        let Ok(mut borrow_info) = <BorrowList<T>>::try_get(acc) else {
+            return weight;
+        };
+        // The borrower closed this loan early and took a new one.
+        if now < borrow_info.deadline {
+            return weight;
+        }
+
+        weight = weight.saturating_add(T::DbWeight::get().reads_writes(5, 5));
+        let unsettled = borrow_info.clone();
+        match with_storage_layer(|| {
+            Self::recover_collateral(acc, &mut borrow_info)?;
+            Self::settle_loan(acc, &mut borrow_info, now)
+        }) {
+            Ok(true) => (),
+            Ok(false) => {
+                borrow_info.overdue = true;
+                Self::deposit_event(Event::<T>::LoanOverdue {
+                    acc: acc.clone(),
+                    staking: borrow_info.staking,
+                    owed: borrow_info.owed(),
+                });
+                <BorrowList<T>>::insert(acc, borrow_info);
+            },
+            Err(_) => {
+                let mut borrow_info = unsettled;
+                borrow_info.overdue = true;
+                <BorrowList<T>>::insert(acc, borrow_info);
+            },
+        }
+
+        weight
+    }
+
+    // Queues the loan of `acc` for expiry at `deadline`, or at the first later block with room left.
+    fn schedule_expiry(acc: &AccountOf<T>, deadline: BlockNumberFor<T>) -> DispatchResult {
+        let mut at = deadline;
+        while LoanDeadlines::<T>::try_mutate(&at, |list| list.try_push(acc.clone())).is_err() {
+            at = at.checked_add(&1u32.into()).ok_or(Error::<T>::Overflow)?;
+        }
+
+        Ok(())
+    }
+
+    // Withdraws the borrowed stake of an expired loan from the miner collateral it backs, along
+    // with as much of the borrower's own stake as it owes. The recovered borrowed stake returns
+    // to the unused funds of the loan, the borrower's stake pays interest first, then the debt.
+    // Collateral the miner no longer holds stays on the loan, which is then flagged overdue.
+    fn recover_collateral(acc: &AccountOf<T>, borrow_info: &mut BorrowInfo<T>) -> DispatchResult {
+        let own_stake = <UserPassbook<T>>::get(acc).staking;
+        let wanted = borrow_info.staking.saturating_add(borrow_info.owed().min(own_stake));
+        if wanted.is_zero() {
+            return Ok(());
+        }
+
+        let recovered = T::LoanCollateral::withdraw_collateral(acc, wanted).min(wanted);
+        if recovered.is_zero() {
+            return Ok(());
+        }
+        let reservoir = T::PalletId::get().into_account_truncating();
+        T::Currency::unreserve(&reservoir, recovered);
+
+        let stake = recovered.min(borrow_info.staking);
+        borrow_info.staking = borrow_info.staking.checked_sub(&stake).ok_or(Error::<T>::Overflow)?;
+        borrow_info.free = borrow_info.free.checked_add(&stake).ok_or(Error::<T>::Overflow)?;
+
+        let repaid = recovered.checked_sub(&stake).ok_or(Error::<T>::Overflow)?;
+        if !repaid.is_zero() {
+            <UserPassbook<T>>::try_mutate(acc, |user_hold| -> DispatchResult {
+                user_hold.staking = user_hold.staking.checked_sub(&repaid).ok_or(Error::<T>::BugInvalid)?;
+
+                Ok(())
+            })?;
+            Reservoir::<T>::try_mutate(|reservoir_info| -> DispatchResult {
+                reservoir_info.store_balance = reservoir_info.store_balance.checked_sub(&repaid).ok_or(Error::<T>::Overflow)?;
+
+                Ok(())
+            })?;
+            Self::apply_repayment(borrow_info, repaid, true)?;
+        }
+
+        Self::deposit_event(Event::<T>::CollateralRecovered { acc: acc.clone(), amount: recovered });
+
+        Ok(())
+    }
+
+    // Moves `amount` of the unused funds of the loan of `acc` to what it owes, for them to be
+    // spent for `use_type`.
+    fn charge_loan(acc: &AccountOf<T>, use_type: &UseType, amount: BalanceOf<T>) -> DispatchResult {
+        // Funds borrowed for miner staking stay in the reservoir and go through `staking`.
+        ensure!(*use_type != UseType::MinerStaking, Error::<T>::UseTypeMismatch);
+
+        let mut borrow_info = <BorrowList<T>>::try_get(acc).map_err(|_| Error::<T>::NonExistent)?;
+        ensure!(borrow_info.use_type == *use_type, Error::<T>::UseTypeMismatch);
+        let now = <frame_system::Pallet<T>>::block_number();
+        ensure!(now < borrow_info.deadline, Error::<T>::LoanExpired);
+
+        borrow_info.free = borrow_info.free.checked_sub(&amount).ok_or(Error::<T>::Insufficient)?;
+        borrow_info.debt = borrow_info.debt.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+        <BorrowList<T>>::insert(acc, borrow_info);
+
+        Ok(())
+    }
+
+    // The borrowed stake no longer backs the loan, the borrower owes it back.
+    fn stake_to_debt(borrow_info: &mut BorrowInfo<T>, amount: BalanceOf<T>) -> DispatchResult {
+        borrow_info.staking = borrow_info.staking.checked_sub(&amount).ok_or(Error::<T>::BugInvalid)?;
+        borrow_info.debt = borrow_info.debt.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+
+        Ok(())
+    }
+
+    // Covers what the borrower owes with the funds it stored in the reservoir, then closes the
+    // loan if it is over and fully paid back. Interest is only taken once the loan is due.
+    // Returns whether the loan was closed, otherwise the caller stores `borrow_info`.
+    fn settle_loan(acc: &AccountOf<T>, borrow_info: &mut BorrowInfo<T>, now: BlockNumberFor<T>) -> Result<bool, DispatchError> {
+        let is_due = now >= borrow_info.deadline;
+        let due = if is_due { borrow_info.owed() } else { borrow_info.debt };
+        if !due.is_zero() {
+            let recovered = <UserPassbook<T>>::try_mutate(acc, |user_hold| -> Result<BalanceOf<T>, DispatchError> {
+                let recovered = user_hold.free.min(due);
+                user_hold.free = user_hold.free.checked_sub(&recovered).ok_or(Error::<T>::Overflow)?;
+
+                Ok(recovered)
+            })?;
+
+            if !recovered.is_zero() {
+                Reservoir::<T>::try_mutate(|reservoir_info| -> DispatchResult {
+                    reservoir_info.store_balance = reservoir_info.store_balance.checked_sub(&recovered).ok_or(Error::<T>::Overflow)?;
+
+                    Ok(())
+                })?;
+                Self::apply_repayment(borrow_info, recovered, is_due)?;
+                Self::deposit_event(Event::<T>::LoanClawback { acc: acc.clone(), amount: recovered });
+            }
+        }
+
+        if is_due && borrow_info.staking.is_zero() && borrow_info.owed().is_zero() {
+            Self::close_loan(acc, borrow_info)?;
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    // Pays interest first, unless `with_interest` is false, then the slashed principal. `amount`
+    // must not exceed what is owed. Interest is income of the reservoir, repaid principal goes back
+    // to the lendable funds.
+    fn apply_repayment(borrow_info: &mut BorrowInfo<T>, amount: BalanceOf<T>, with_interest: bool) -> DispatchResult {
+        let interest = if with_interest { amount.min(borrow_info.interest) } else { BalanceOf::<T>::zero() };
+        let principal = amount.checked_sub(&interest).ok_or(Error::<T>::Overflow)?;
+        borrow_info.interest = borrow_info.interest.checked_sub(&interest).ok_or(Error::<T>::Overflow)?;
+        borrow_info.debt = borrow_info.debt.checked_sub(&principal).ok_or(Error::<T>::Insufficient)?;
+
+        Reservoir::<T>::try_mutate(|reservoir_info| -> DispatchResult {
+            reservoir_info.borrow_balance = reservoir_info.borrow_balance.checked_sub(&principal).ok_or(Error::<T>::Overflow)?;
+            reservoir_info.free_balance = reservoir_info.free_balance.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+
+            Ok(())
+        })
+    }
+
+    // Returns the unused borrowed funds to the reservoir and forgets the loan.
+    fn close_loan(acc: &AccountOf<T>, borrow_info: &BorrowInfo<T>) -> DispatchResult {
+        Reservoir::<T>::try_mutate(|reservoir_info| -> DispatchResult {
+            reservoir_info.borrow_balance = reservoir_info.borrow_balance.checked_sub(&borrow_info.free).ok_or(Error::<T>::Overflow)?;
+            reservoir_info.free_balance = reservoir_info.free_balance.checked_add(&borrow_info.free).ok_or(Error::<T>::Overflow)?;
+
+            Ok(())
+        })?;
+        <BorrowList<T>>::remove(acc);
+
+        Self::deposit_event(Event::<T>::LoanClosed { acc: acc.clone() });
+
+        Ok(())
+    }
+}
+
+/// Tells whether an account passed KYC.
+pub trait KycProvider<AccountId> {
+    fn is_verified(acc: &AccountId) -> bool;
+}
+
+impl<AccountId> KycProvider<AccountId> for () {
+    fn is_verified(_acc: &AccountId) -> bool {
+        false
+    }
+}
+
+impl<T: Config> OnNewAccount<AccountOf<T>> for Pallet<T> {
+    fn on_new_account(who: &AccountOf<T>) {
+        <AccountBirth<T>>::insert(who, <frame_system::Pallet<T>>::block_number());
+    }
+}
+
+impl<T: Config> OnKilledAccount<AccountOf<T>> for Pallet<T> {
+    fn on_killed_account(who: &AccountOf<T>) {
+        <AccountBirth<T>>::remove(who);
+    }
+}
+
+/// Gives back miner collateral staked through the reservoir.
+pub trait LoanCollateral<AccountId, Balance> {
+    /// Withdraws up to `amount` of the collateral `acc` staked through the reservoir and returns
+    /// how much was withdrawn. The funds stay reserved on the reservoir account.
+    fn withdraw_collateral(acc: &AccountId, amount: Balance) -> Balance;
+}
+
+impl<AccountId, Balance: Zero> LoanCollateral<AccountId, Balance> for () {
+    fn withdraw_collateral(_acc: &AccountId, _amount: Balance) -> Balance {
+        Zero::zero()
     }
 }
 
 pub trait ReservoirGate<AccountId, Balance> {
+    // `check_qualification`, `staking`, `redeem` and `punish` only use funds borrowed for miner staking.
     fn check_qualification(acc: &AccountId, amount: Balance) -> DispatchResult;
     fn staking(acc: &AccountId, amount: Balance, flag: bool) -> DispatchResult;
     fn redeem(acc: &AccountId, amount: Balance, flag: bool) -> DispatchResult;
     fn punish(acc: &AccountId, amount: Balance, flag:bool) -> DispatchResult;
     fn get_reservoir_acc() -> AccountId;
+    /// Borrowed funds `acc` can still spend for `use_type`.
+    fn spendable(acc: &AccountId, use_type: UseType) -> Balance;
+    /// Pays `amount` of the funds `acc` borrowed for `use_type` to `dest`. Spent funds are owed back.
+    fn spend(acc: &AccountId, use_type: UseType, amount: Balance, dest: &AccountId) -> DispatchResult;
+    /// Like `spend`, for the caller to pay `amount` out of the reservoir account itself.
+    fn charge(acc: &AccountId, use_type: UseType, amount: Balance) -> DispatchResult;
+    /// Takes up to `amount` of the funds `acc` spent for `use_type` back from `from`, where they
+    /// can be spent again. Returns how much was taken back.
+    fn refund(acc: &AccountId, use_type: UseType, amount: Balance, from: &AccountId) -> Result<Balance, DispatchError>;
+    /// Whether the loan of `acc` reached its deadline.
+    fn is_due(acc: &AccountId) -> bool;
 }
 
 impl<T: Config> ReservoirGate<AccountOf<T>, BalanceOf<T>> for Pallet<T> {
     fn check_qualification(acc: &AccountOf<T>, amount: BalanceOf<T>) -> DispatchResult {
         let mut total_free: BalanceOf<T> = BalanceOf::<T>::zero();
         if let Ok(borrow_info) = <BorrowList<T>>::try_get(acc) {
-            total_free = total_free.checked_add(&borrow_info.free).ok_or(Error::<T>::Overflow)?;
+            let now = <frame_system::Pallet<T>>::block_number();
+            if now < borrow_info.deadline && borrow_info.use_type == UseType::MinerStaking {
+                total_free = total_free.checked_add(&borrow_info.free).ok_or(Error::<T>::Overflow)?;
+            }
         }
 
         let user_passbook = <UserPassbook<T>>::get(acc);
@@ -346,7 +792,7 @@ impl<T: Config> ReservoirGate<AccountOf<T>, BalanceOf<T>> for Pallet<T> {
         let mut need_staking = amount;
         if let Ok(mut borrow_info) = <BorrowList<T>>::try_get(acc) {
             let now = <frame_system::Pallet<T>>::block_number();
-            if now < borrow_info.deadline {
+            if now < borrow_info.deadline && borrow_info.use_type == UseType::MinerStaking {
                 if borrow_info.free >= need_staking {
                     borrow_info.free = borrow_info.free.checked_sub(&need_staking).ok_or(Error::<T>::Overflow)?;
                     borrow_info.staking = borrow_info.staking.checked_add(&need_staking).ok_or(Error::<T>::Overflow)?;
@@ -358,8 +804,6 @@ impl<T: Config> ReservoirGate<AccountOf<T>, BalanceOf<T>> for Pallet<T> {
                 }
     
                 <BorrowList<T>>::insert(acc, borrow_info);
-            } else {
-                BorrowList::<T>::remove(acc);
             }
         }
 
@@ -403,13 +847,22 @@ impl<T: Config> ReservoirGate<AccountOf<T>, BalanceOf<T>> for Pallet<T> {
                 if borrow_info.staking >= need_amount {
                     borrow_info.staking = borrow_info.staking.checked_sub(&need_amount).ok_or(Error::<T>::Overflow)?;
                     borrow_info.free = borrow_info.free.checked_add(&need_amount).ok_or(Error::<T>::Overflow)?;
-                    <BorrowList<T>>::insert(acc, borrow_info);
+                    let now = <frame_system::Pallet<T>>::block_number();
+                    if !Self::settle_loan(acc, &mut borrow_info, now)? {
+                        <BorrowList<T>>::insert(acc, borrow_info);
+                    }
                 } else {
                     return Err(Error::<T>::BugInvalid)?;
                 }
             } else {
                 return Err(Error::<T>::BugInvalid)?;
             }
+        } else if let Ok(mut borrow_info) = <BorrowList<T>>::try_get(acc) {
+            // The borrower's own stake released into its passbook pays an overdue loan back.
+            let now = <frame_system::Pallet<T>>::block_number();
+            if !Self::settle_loan(acc, &mut borrow_info, now)? {
+                <BorrowList<T>>::insert(acc, borrow_info);
+            }
         }
 
         let reservoir = T::PalletId::get().into_account_truncating();
@@ -439,8 +892,14 @@ impl<T: Config> ReservoirGate<AccountOf<T>, BalanceOf<T>> for Pallet<T> {
         if need_amount != 0u32.into() {
             if let Ok(mut borrow_info) = <BorrowList<T>>::try_get(acc) {
                 if borrow_info.staking >= need_amount {
-                    borrow_info.staking = borrow_info.staking.checked_sub(&need_amount).ok_or(Error::<T>::Overflow)?;
-                    <BorrowList<T>>::insert(acc, borrow_info);
+                    // The slashed borrowed stake left the reservoir, the borrower owes it back.
+                    Self::stake_to_debt(&mut borrow_info, need_amount)?;
+                    Self::deposit_event(Event::<T>::BorrowedStakeSlashed { acc: acc.clone(), amount: need_amount });
+
+                    let now = <frame_system::Pallet<T>>::block_number();
+                    if !Self::settle_loan(acc, &mut borrow_info, now)? {
+                        <BorrowList<T>>::insert(acc, borrow_info);
+                    }
                 } else {
                     return Err(Error::<T>::BugInvalid)?;
                 }
@@ -461,4 +920,60 @@ impl<T: Config> ReservoirGate<AccountOf<T>, BalanceOf<T>> for Pallet<T> {
     fn get_reservoir_acc() -> AccountOf<T> {
         T::PalletId::get().into_account_truncating()
     }
+
+    fn spendable(acc: &AccountOf<T>, use_type: UseType) -> BalanceOf<T> {
+        let now = <frame_system::Pallet<T>>::block_number();
+        match <BorrowList<T>>::try_get(acc) {
+            Ok(borrow_info) if borrow_info.use_type == use_type && now < borrow_info.deadline => borrow_info.free,
+            _ => BalanceOf::<T>::zero(),
+        }
+    }
+
+    fn spend(acc: &AccountOf<T>, use_type: UseType, amount: BalanceOf<T>, dest: &AccountOf<T>) -> DispatchResult {
+        Self::charge_loan(acc, &use_type, amount)?;
+
+        let reservoir = T::PalletId::get().into_account_truncating();
+        T::Currency::transfer(&reservoir, dest, amount, KeepAlive)?;
+
+        Self::deposit_event(Event::<T>::Spend { acc: acc.clone(), use_type, amount, dest: dest.clone() });
+
+        Ok(())
+    }
+
+    fn charge(acc: &AccountOf<T>, use_type: UseType, amount: BalanceOf<T>) -> DispatchResult {
+        Self::charge_loan(acc, &use_type, amount)?;
+
+        let reservoir = T::PalletId::get().into_account_truncating();
+        Self::deposit_event(Event::<T>::Spend { acc: acc.clone(), use_type, amount, dest: reservoir });
+
+        Ok(())
+    }
+
+    fn refund(acc: &AccountOf<T>, use_type: UseType, amount: BalanceOf<T>, from: &AccountOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        let mut borrow_info = <BorrowList<T>>::try_get(acc).map_err(|_| Error::<T>::NonExistent)?;
+        ensure!(borrow_info.use_type == use_type, Error::<T>::UseTypeMismatch);
+
+        let amount = amount.min(borrow_info.debt);
+        if amount.is_zero() {
+            return Ok(amount);
+        }
+        let reservoir = T::PalletId::get().into_account_truncating();
+        T::Currency::transfer(from, &reservoir, amount, AllowDeath)?;
+
+        borrow_info.debt = borrow_info.debt.checked_sub(&amount).ok_or(Error::<T>::Overflow)?;
+        borrow_info.free = borrow_info.free.checked_add(&amount).ok_or(Error::<T>::Overflow)?;
+        let now = <frame_system::Pallet<T>>::block_number();
+        if now < borrow_info.deadline || !Self::settle_loan(acc, &mut borrow_info, now)? {
+            <BorrowList<T>>::insert(acc, borrow_info);
+        }
+
+        Self::deposit_event(Event::<T>::Refund { acc: acc.clone(), use_type, amount });
+
+        Ok(amount)
+    }
+
+    fn is_due(acc: &AccountOf<T>) -> bool {
+        let now = <frame_system::Pallet<T>>::block_number();
+        <BorrowList<T>>::try_get(acc).map_or(false, |borrow_info| now >= borrow_info.deadline)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,76 @@
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+
+/// Adds interest, overdue tracking and use types to loans, and interest rates and eligibility
+/// rules to events.
+pub mod v1 {
+    use super::*;
+
+    #[derive(Decode)]
+    pub struct OldBorrowInfo<T: Config> {
+        free: BalanceOf<T>,
+        lender: AccountOf<T>,
+        staking: BalanceOf<T>,
+        deadline: BlockNumberFor<T>,
+    }
+
+    #[derive(Decode)]
+    pub struct OldEventInfo<T: Config> {
+        quota: u32,
+        deadline: BlockNumberFor<T>,
+        unit_amount: BalanceOf<T>,
+        borrow_period: BlockNumberFor<T>,
+        use_type: UseType,
+    }
+
+    pub struct VersionUncheckedMigrateV0ToV1<T>(core::marker::PhantomData<T>);
+    impl<T: Config> UncheckedOnRuntimeUpgrade for VersionUncheckedMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            // Existing loans are interest free miner staking loans, falling due at their old deadline.
+            BorrowList::<T>::translate::<OldBorrowInfo<T>, _>(|acc, old| {
+                translated.saturating_inc();
+                let deadline = old.deadline.max(now.saturating_add(1u32.into()));
+                let _ = Pallet::<T>::schedule_expiry(&acc, deadline);
+                Some(BorrowInfo::<T> {
+                    free: old.free,
+                    lender: old.lender,
+                    staking: old.staking,
+                    deadline: old.deadline,
+                    interest: BalanceOf::<T>::zero(),
+                    debt: BalanceOf::<T>::zero(),
+                    overdue: false,
+                    use_type: UseType::MinerStaking,
+                })
+            });
+
+            Events::<T>::translate::<OldEventInfo<T>, _>(|_, old| {
+                translated.saturating_inc();
+                Some(EventInfo::<T> {
+                    quota: old.quota,
+                    deadline: old.deadline,
+                    unit_amount: old.unit_amount,
+                    borrow_period: old.borrow_period,
+                    use_type: old.use_type,
+                    interest_rate: Perbill::zero(),
+                    rules: Default::default(),
+                })
+            });
+
+            T::DbWeight::get().reads_writes(translated, translated.saturating_mul(2))
+        }
+    }
+
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        VersionUncheckedMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
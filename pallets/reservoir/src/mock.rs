@@ -0,0 +1,138 @@
+//! Test utilities
+
+use super::*;
+use crate as pallet_reservoir;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU128, ConstU32, Hooks},
+};
+use sp_runtime::BuildStorage;
+
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Reservoir: pallet_reservoir,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+}
+
+pub(crate) const ALICE: AccountId = 1;
+pub(crate) const BOB: AccountId = 2;
+pub(crate) const CHARLIE: AccountId = 3;
+pub(crate) const DAVE: AccountId = 4;
+
+pub(crate) const FILLING: Balance = 10_000;
+pub(crate) const UNIT_AMOUNT: Balance = 1_000;
+pub(crate) const BORROW_PERIOD: BlockNumber = 50;
+pub(crate) const EVENT_PERIOD: BlockNumber = 100;
+pub(crate) const LOAN_LIMIT: u32 = 2;
+pub(crate) const CLEANUP_LIMIT: u32 = 3;
+
+parameter_types! {
+	pub const ReservoirPalletId: PalletId = PalletId(*b"rsorptid");
+	/// The collateral BOB staked through the reservoir as a miner.
+	pub static MinerCollateral: Balance = 0;
+}
+
+pub struct MockCollateral;
+impl LoanCollateral<AccountId, Balance> for MockCollateral {
+	fn withdraw_collateral(acc: &AccountId, amount: Balance) -> Balance {
+		if *acc != BOB {
+			return 0;
+		}
+		let withdrawn = MinerCollateral::get().min(amount);
+		MinerCollateral::mutate(|collateral| *collateral -= withdrawn);
+		withdrawn
+	}
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type PalletId = ReservoirPalletId;
+	type IdLength = ConstU32<32>;
+	type EventLimit = ConstU32<10>;
+	type LoanLimit = ConstU32<LOAN_LIMIT>;
+	type AllowlistLimit = ConstU32<10>;
+	type KycProvider = ();
+	type LoanCollateral = MockCollateral;
+	type EventCleanupLimit = ConstU32<CLEANUP_LIMIT>;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1_000_000), (BOB, 1_000_000), (CHARLIE, 1_000_000), (DAVE, 1_000_000)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		MinerCollateral::set(0);
+	});
+	ext
+}
+
+pub fn event_id(name: &[u8]) -> BoundedVec<u8, ConstU32<32>> {
+	name.to_vec().try_into().unwrap()
+}
+
+/// Fill the reservoir and open an event for `EVENT_PERIOD` blocks, lending `UNIT_AMOUNT` for
+/// `BORROW_PERIOD` blocks.
+pub fn open_event(name: &[u8], use_type: UseType, interest_rate: Perbill) {
+	open_event_with_rules(name, use_type, interest_rate, Default::default());
+}
+
+/// Like `open_event`, only attendable under `rules`.
+pub fn open_event_with_rules(
+	name: &[u8],
+	use_type: UseType,
+	interest_rate: Perbill,
+	rules: EventRules<BlockNumber>,
+) {
+	Reservoir::filling(RuntimeOrigin::signed(ALICE), FILLING).unwrap();
+	Reservoir::create_event(
+		RuntimeOrigin::root(),
+		event_id(name),
+		5,
+		EVENT_PERIOD,
+		UNIT_AMOUNT,
+		BORROW_PERIOD,
+		use_type,
+		interest_rate,
+		rules,
+	)
+	.unwrap();
+}
+
+/// Run the pallet hooks of every block up to and including `n`.
+pub fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Reservoir::on_initialize(next);
+	}
+}
@@ -0,0 +1,417 @@
+use super::*;
+use crate::mock::{
+	event_id, new_test_ext, open_event, open_event_with_rules, run_to_block, Balances, MinerCollateral, Reservoir,
+	RuntimeEvent, RuntimeOrigin, System, Test, BOB, BORROW_PERIOD, CHARLIE, DAVE, EVENT_PERIOD, FILLING, LOAN_LIMIT,
+	UNIT_AMOUNT,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::unhashed,
+	traits::OnRuntimeUpgrade,
+};
+
+fn reservoir_info(free_balance: u128, borrow_balance: u128, store_balance: u128) -> ReservoirInfo<u128> {
+	ReservoirInfo { free_balance, borrow_balance, store_balance }
+}
+
+fn has_event(event: Event<Test>) -> bool {
+	System::events().iter().any(|record| record.event == RuntimeEvent::Reservoir(event.clone()))
+}
+
+#[test]
+fn repay_pays_the_interest_and_closes_the_loan() {
+	new_test_ext().execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::from_percent(10));
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+		assert_eq!(Reservoir::borrow_list(BOB).unwrap().interest, 100);
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING - UNIT_AMOUNT, UNIT_AMOUNT, 0));
+
+		assert_ok!(Reservoir::repay(RuntimeOrigin::signed(BOB), 40));
+		assert_eq!(Reservoir::borrow_list(BOB).unwrap().interest, 60);
+		assert_eq!(Balances::free_balance(BOB), 1_000_000 - 40);
+
+		// Paying more than owed only takes what is owed.
+		assert_ok!(Reservoir::repay(RuntimeOrigin::signed(BOB), 1_000));
+		assert_eq!(Balances::free_balance(BOB), 1_000_000 - 100);
+		assert!(Reservoir::borrow_list(BOB).is_none());
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING + 100, 0, 0));
+		assert!(has_event(Event::LoanClosed { acc: BOB }));
+	});
+}
+
+#[test]
+fn repay_needs_a_loan() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(Reservoir::repay(RuntimeOrigin::signed(BOB), 10), Error::<Test>::NonExistent);
+	});
+}
+
+#[test]
+fn repay_keeps_loans_backing_miner_collateral() {
+	new_test_ext().execute_with(|| {
+		open_event(b"miner", UseType::MinerStaking, Perbill::zero());
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"miner")));
+		assert_ok!(<Reservoir as ReservoirGate<_, _>>::staking(&BOB, 600, true));
+
+		assert_ok!(Reservoir::repay(RuntimeOrigin::signed(BOB), 10));
+		let loan = Reservoir::borrow_list(BOB).unwrap();
+		assert_eq!((loan.free, loan.staking), (400, 600));
+		assert_eq!(Balances::free_balance(BOB), 1_000_000);
+	});
+}
+
+#[test]
+fn settle_loan_only_takes_the_debt_before_the_deadline() {
+	new_test_ext().execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::from_percent(10));
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+		assert_ok!(<Reservoir as ReservoirGate<_, _>>::spend(&BOB, UseType::GasSubsidy, 300, &DAVE));
+		assert_ok!(Reservoir::store(RuntimeOrigin::signed(BOB), 500));
+
+		let mut loan = Reservoir::borrow_list(BOB).unwrap();
+		assert_eq!(Reservoir::settle_loan(&BOB, &mut loan, 2), Ok(false));
+		assert_eq!((loan.debt, loan.interest), (0, 100));
+		assert_eq!(Reservoir::user_passbook(BOB).free, 200);
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING - UNIT_AMOUNT + 300, UNIT_AMOUNT - 300, 200));
+	});
+}
+
+#[test]
+fn expired_loans_are_settled_from_the_passbook() {
+	new_test_ext().execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::from_percent(10));
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+		assert_ok!(Reservoir::store(RuntimeOrigin::signed(BOB), 500));
+		let deadline = 1 + BORROW_PERIOD;
+
+		run_to_block(deadline - 1);
+		assert!(Reservoir::borrow_list(BOB).is_some());
+
+		run_to_block(deadline);
+		assert!(Reservoir::borrow_list(BOB).is_none());
+		assert!(Reservoir::loan_deadlines(deadline).is_empty());
+		assert_eq!(Reservoir::user_passbook(BOB).free, 400);
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING + 100, 0, 400));
+	});
+}
+
+#[test]
+fn expired_loans_without_funds_become_overdue() {
+	new_test_ext().execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::from_percent(10));
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+
+		run_to_block(1 + BORROW_PERIOD);
+		let loan = Reservoir::borrow_list(BOB).unwrap();
+		assert!(loan.overdue);
+		assert!(has_event(Event::LoanOverdue { acc: BOB, staking: 0, owed: 100 }));
+
+		// An overdue loan is closed once paid back.
+		assert_ok!(Reservoir::repay(RuntimeOrigin::signed(BOB), 100));
+		assert!(Reservoir::borrow_list(BOB).is_none());
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING + 100, 0, 0));
+	});
+}
+
+/// BOB borrows `UNIT_AMOUNT` for miner staking and stakes it with `own` funds of its passbook.
+fn stake_as_miner(own: u128) {
+	open_event(b"miner", UseType::MinerStaking, Perbill::from_percent(10));
+	assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"miner")));
+	assert_ok!(Reservoir::store(RuntimeOrigin::signed(BOB), own));
+	assert_ok!(<Reservoir as ReservoirGate<_, _>>::staking(&BOB, UNIT_AMOUNT + own, true));
+	MinerCollateral::set(UNIT_AMOUNT + own);
+}
+
+#[test]
+fn expired_loans_are_recovered_from_the_collateral() {
+	new_test_ext().execute_with(|| {
+		stake_as_miner(300);
+		let reservoir_acc = <Reservoir as ReservoirGate<_, _>>::get_reservoir_acc();
+		assert_eq!(Balances::reserved_balance(reservoir_acc), UNIT_AMOUNT + 300);
+
+		// The borrowed stake and the interest leave the collateral, the rest stays staked.
+		run_to_block(1 + BORROW_PERIOD);
+		assert!(Reservoir::borrow_list(BOB).is_none());
+		assert!(has_event(Event::CollateralRecovered { acc: BOB, amount: UNIT_AMOUNT + 100 }));
+		assert_eq!(MinerCollateral::get(), 200);
+		assert_eq!(Reservoir::user_passbook(BOB).staking, 200);
+		assert_eq!(Balances::reserved_balance(reservoir_acc), 200);
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING + 100, 0, 200));
+		assert_eq!(Balances::free_balance(reservoir_acc), FILLING + 100);
+	});
+}
+
+#[test]
+fn expired_loans_keep_the_collateral_the_miner_no_longer_holds() {
+	new_test_ext().execute_with(|| {
+		stake_as_miner(0);
+		// The miner no longer holds part of the collateral.
+		MinerCollateral::set(600);
+
+		run_to_block(1 + BORROW_PERIOD);
+		let loan = Reservoir::borrow_list(BOB).unwrap();
+		assert_eq!((loan.free, loan.staking, loan.interest, loan.overdue), (600, 400, 100, true));
+		assert!(has_event(Event::LoanOverdue { acc: BOB, staking: 400, owed: 100 }));
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING - UNIT_AMOUNT, UNIT_AMOUNT, 0));
+
+		// Releasing the rest of the collateral and paying the interest closes the loan.
+		assert_ok!(<Reservoir as ReservoirGate<_, _>>::redeem(&BOB, 400, true));
+		assert_ok!(Reservoir::repay(RuntimeOrigin::signed(BOB), 100));
+		assert!(Reservoir::borrow_list(BOB).is_none());
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING + 100, 0, 0));
+		let reservoir_acc = <Reservoir as ReservoirGate<_, _>>::get_reservoir_acc();
+		assert_eq!(Balances::reserved_balance(reservoir_acc), 0);
+		assert_eq!(Balances::free_balance(reservoir_acc), FILLING + 100);
+	});
+}
+
+#[test]
+fn close_loan_returns_the_unused_funds() {
+	new_test_ext().execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::zero());
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+		assert_ok!(<Reservoir as ReservoirGate<_, _>>::spend(&BOB, UseType::GasSubsidy, 300, &DAVE));
+		assert_eq!(Balances::free_balance(DAVE), 1_000_000 + 300);
+
+		// Paying back the spent funds closes the loan.
+		assert_ok!(Reservoir::repay(RuntimeOrigin::signed(BOB), 300));
+		assert!(Reservoir::borrow_list(BOB).is_none());
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING, 0, 0));
+
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(CHARLIE), event_id(b"gas")));
+		let loan = Reservoir::borrow_list(CHARLIE).unwrap();
+		assert_ok!(Reservoir::close_loan(&CHARLIE, &loan));
+		assert!(Reservoir::borrow_list(CHARLIE).is_none());
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING, 0, 0));
+		assert!(has_event(Event::LoanClosed { acc: CHARLIE }));
+	});
+}
+
+#[test]
+fn charge_keeps_the_funds_in_the_reservoir() {
+	new_test_ext().execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::zero());
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+		let reservoir_acc = <Reservoir as ReservoirGate<_, _>>::get_reservoir_acc();
+		let reservoir_balance = Balances::free_balance(reservoir_acc);
+
+		assert_ok!(<Reservoir as ReservoirGate<_, _>>::charge(&BOB, UseType::GasSubsidy, 300));
+		let loan = Reservoir::borrow_list(BOB).unwrap();
+		assert_eq!((loan.free, loan.debt), (UNIT_AMOUNT - 300, 300));
+		assert_eq!(Balances::free_balance(reservoir_acc), reservoir_balance);
+		assert_eq!(Balances::free_balance(BOB), 1_000_000);
+
+		assert_noop!(
+			<Reservoir as ReservoirGate<_, _>>::charge(&BOB, UseType::MinerStaking, 1),
+			Error::<Test>::UseTypeMismatch
+		);
+	});
+}
+
+#[test]
+fn refund_returns_spent_funds_to_the_loan() {
+	new_test_ext().execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::zero());
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+		assert_ok!(<Reservoir as ReservoirGate<_, _>>::spend(&BOB, UseType::GasSubsidy, 300, &DAVE));
+
+		// Only what was spent goes back.
+		assert_eq!(<Reservoir as ReservoirGate<_, _>>::refund(&BOB, UseType::GasSubsidy, 500, &DAVE), Ok(300));
+		let loan = Reservoir::borrow_list(BOB).unwrap();
+		assert_eq!((loan.free, loan.debt), (UNIT_AMOUNT, 0));
+		assert_eq!(Balances::free_balance(DAVE), 1_000_000);
+		assert!(has_event(Event::Refund { acc: BOB, use_type: UseType::GasSubsidy, amount: 300 }));
+
+		// A refund after the deadline settles the loan.
+		assert_ok!(<Reservoir as ReservoirGate<_, _>>::spend(&BOB, UseType::GasSubsidy, 300, &DAVE));
+		System::set_block_number(1 + BORROW_PERIOD);
+		assert!(<Reservoir as ReservoirGate<_, _>>::is_due(&BOB));
+		assert_eq!(<Reservoir as ReservoirGate<_, _>>::refund(&BOB, UseType::GasSubsidy, 300, &DAVE), Ok(300));
+		assert!(Reservoir::borrow_list(BOB).is_none());
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING, 0, 0));
+	});
+}
+
+#[test]
+fn loans_spill_over_to_the_next_block() {
+	new_test_ext().execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::zero());
+		for acc in [BOB, CHARLIE, DAVE] {
+			assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(acc), event_id(b"gas")));
+		}
+		let deadline = 1 + BORROW_PERIOD;
+		assert_eq!(Reservoir::loan_deadlines(deadline).len() as u32, LOAN_LIMIT);
+		assert_eq!(Reservoir::loan_deadlines(deadline + 1).to_vec(), vec![DAVE]);
+
+		run_to_block(deadline);
+		assert!(Reservoir::borrow_list(BOB).is_none());
+		assert!(Reservoir::borrow_list(CHARLIE).is_none());
+		assert!(Reservoir::borrow_list(DAVE).is_some());
+
+		run_to_block(deadline + 1);
+		assert!(Reservoir::borrow_list(DAVE).is_none());
+		assert_eq!(Reservoir::reservoir(), reservoir_info(FILLING, 0, 0));
+	});
+}
+
+#[test]
+fn migration_to_v1_keeps_loans_and_events() {
+	new_test_ext().execute_with(|| {
+		let lender = <Reservoir as ReservoirGate<_, _>>::get_reservoir_acc();
+		unhashed::put(&BorrowList::<Test>::hashed_key_for(BOB), &(400u128, lender, 600u128, 30u64));
+		// Loans that fell due during the upgrade expire in the next block.
+		unhashed::put(&BorrowList::<Test>::hashed_key_for(CHARLIE), &(1_000u128, lender, 0u128, 0u64));
+		unhashed::put(&Events::<Test>::hashed_key_for(event_id(b"old")), &(3u32, 100u64, 1_000u128, 50u64, UseType::MinerStaking));
+		StorageVersion::new(0).put::<Reservoir>();
+
+		migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(StorageVersion::get::<Reservoir>(), 1);
+		let loan = Reservoir::borrow_list(BOB).unwrap();
+		assert_eq!((loan.free, loan.staking, loan.deadline), (400, 600, 30));
+		assert_eq!((loan.interest, loan.debt, loan.overdue), (0, 0, false));
+		assert_eq!(loan.use_type, UseType::MinerStaking);
+		assert_eq!(Reservoir::loan_deadlines(30).to_vec(), vec![BOB]);
+		assert_eq!(Reservoir::loan_deadlines(2).to_vec(), vec![CHARLIE]);
+
+		let event = Reservoir::events(event_id(b"old")).unwrap();
+		assert_eq!((event.quota, event.unit_amount, event.borrow_period), (3, 1_000, 50));
+		assert_eq!(event.interest_rate, Perbill::zero());
+		assert_eq!(event.rules, EventRules::default());
+	});
+}
+
+#[test]
+fn allowlist_only_events_reject_other_accounts() {
+	new_test_ext().execute_with(|| {
+		let rules = EventRules { allowlist_only: true, ..Default::default() };
+		open_event_with_rules(b"gas", UseType::GasSubsidy, Perbill::zero(), rules);
+		assert_ok!(Reservoir::set_allowlist(RuntimeOrigin::root(), event_id(b"gas"), vec![BOB].try_into().unwrap(), true));
+
+		assert_noop!(Reservoir::attend_event(RuntimeOrigin::signed(CHARLIE), event_id(b"gas")), Error::<Test>::NotAllowed);
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+
+		assert_ok!(Reservoir::set_allowlist(RuntimeOrigin::root(), event_id(b"gas"), vec![BOB].try_into().unwrap(), false));
+		assert!(!EventAllowlist::<Test>::contains_key(event_id(b"gas"), BOB));
+	});
+}
+
+#[test]
+fn events_limit_attendance_per_account() {
+	new_test_ext().execute_with(|| {
+		let rules = EventRules { max_per_account: 1, ..Default::default() };
+		open_event_with_rules(b"gas", UseType::GasSubsidy, Perbill::zero(), rules);
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+
+		// The first loan is over and closed, the limit still holds.
+		run_to_block(1 + BORROW_PERIOD);
+		assert!(Reservoir::borrow_list(BOB).is_none());
+		assert_noop!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")), Error::<Test>::AttendLimit);
+	});
+}
+
+#[test]
+fn events_reject_new_accounts() {
+	new_test_ext().execute_with(|| {
+		let rules = EventRules { min_account_age: 10, ..Default::default() };
+		open_event_with_rules(b"gas", UseType::GasSubsidy, Perbill::zero(), rules);
+		AccountBirth::<Test>::insert(BOB, 1);
+
+		assert_noop!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")), Error::<Test>::AccountTooNew);
+		run_to_block(11);
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+		// Accounts created before births were tracked count as old enough.
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(CHARLIE), event_id(b"gas")));
+	});
+}
+
+#[test]
+fn kyc_events_need_a_verified_identity() {
+	new_test_ext().execute_with(|| {
+		let rules = EventRules { require_kyc: true, ..Default::default() };
+		open_event_with_rules(b"gas", UseType::GasSubsidy, Perbill::zero(), rules);
+
+		assert_noop!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")), Error::<Test>::NotVerified);
+	});
+}
+
+#[test]
+fn borrowed_funds_are_only_spent_for_their_use() {
+	new_test_ext().execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::zero());
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+		assert_eq!(<Reservoir as ReservoirGate<_, _>>::spendable(&BOB, UseType::GasSubsidy), UNIT_AMOUNT);
+		assert_eq!(<Reservoir as ReservoirGate<_, _>>::spendable(&BOB, UseType::TerritoryPurchase), 0);
+
+		assert_noop!(
+			<Reservoir as ReservoirGate<_, _>>::spend(&BOB, UseType::TerritoryPurchase, 100, &DAVE),
+			Error::<Test>::UseTypeMismatch
+		);
+		assert_noop!(
+			<Reservoir as ReservoirGate<_, _>>::spend(&BOB, UseType::MinerStaking, 100, &DAVE),
+			Error::<Test>::UseTypeMismatch
+		);
+		assert_noop!(
+			<Reservoir as ReservoirGate<_, _>>::spend(&BOB, UseType::GasSubsidy, UNIT_AMOUNT + 1, &DAVE),
+			Error::<Test>::Insufficient
+		);
+
+		assert_ok!(<Reservoir as ReservoirGate<_, _>>::spend(&BOB, UseType::GasSubsidy, 300, &DAVE));
+		let loan = Reservoir::borrow_list(BOB).unwrap();
+		assert_eq!((loan.free, loan.debt), (UNIT_AMOUNT - 300, 300));
+		assert_eq!(Balances::free_balance(DAVE), 1_000_000 + 300);
+		assert!(has_event(Event::Spend { acc: BOB, use_type: UseType::GasSubsidy, amount: 300, dest: DAVE }));
+
+		run_to_block(1 + BORROW_PERIOD);
+		assert_eq!(<Reservoir as ReservoirGate<_, _>>::spendable(&BOB, UseType::GasSubsidy), 0);
+		assert_noop!(
+			<Reservoir as ReservoirGate<_, _>>::spend(&BOB, UseType::GasSubsidy, 100, &DAVE),
+			Error::<Test>::LoanExpired
+		);
+	});
+}
+
+#[test]
+fn expired_events_are_cleaned_up_over_several_blocks() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		open_event(b"gas", UseType::GasSubsidy, Perbill::zero());
+		let accounts: Vec<u64> = (10..14).collect();
+		assert_ok!(Reservoir::set_allowlist(RuntimeOrigin::root(), event_id(b"gas"), accounts.try_into().unwrap(), true));
+		assert_ok!(Reservoir::attend_event(RuntimeOrigin::signed(BOB), event_id(b"gas")));
+	});
+	// Only entries of earlier blocks count towards the cleanup limit.
+	ext.commit_all().unwrap();
+
+	let expiry = 1 + EVENT_PERIOD;
+	ext.execute_with(|| {
+		run_to_block(expiry);
+		assert!(Reservoir::events(event_id(b"gas")).is_none());
+		assert!(has_event(Event::EventExpired { id: event_id(b"gas") }));
+		// Three of the four allowlist entries are gone.
+		assert_eq!(EventAllowlist::<Test>::iter_prefix(event_id(b"gas")).count(), 1);
+		assert!(Reservoir::stale_events(event_id(b"gas")).is_some());
+		assert_noop!(
+			Reservoir::create_event(
+				RuntimeOrigin::root(),
+				event_id(b"gas"),
+				1,
+				EVENT_PERIOD,
+				UNIT_AMOUNT,
+				BORROW_PERIOD,
+				UseType::GasSubsidy,
+				Perbill::zero(),
+				Default::default(),
+			),
+			Error::<Test>::CleanupPending
+		);
+	});
+	ext.commit_all().unwrap();
+
+	ext.execute_with(|| {
+		run_to_block(expiry + 1);
+		assert_eq!(EventAllowlist::<Test>::iter_prefix(event_id(b"gas")).count(), 0);
+		assert_eq!(Attendance::<Test>::iter_prefix(event_id(b"gas")).count(), 0);
+		assert!(Reservoir::stale_events(event_id(b"gas")).is_none());
+	});
+}
@@ -11,10 +11,20 @@ pub struct ReservoirInfo<Balance> {
 #[scale_info(skip_type_params(T))]
 #[codec(mel_bound())]
 pub struct BorrowInfo<T: Config> {
+    /// Borrowed funds not staked yet.
     pub(super) free: BalanceOf<T>,
     pub(super) lender: AccountOf<T>,
+    /// Borrowed funds staked as miner collateral.
     pub(super) staking: BalanceOf<T>,
     pub(super) deadline: BlockNumberFor<T>,
+    /// Interest still owed on the loan.
+    pub(super) interest: BalanceOf<T>,
+    /// Borrowed funds lost to slashing and still owed.
+    pub(super) debt: BalanceOf<T>,
+    /// Whether the loan outlived its deadline without being settled.
+    pub(super) overdue: bool,
+    /// What the borrowed funds may be used for.
+    pub(super) use_type: UseType,
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -32,9 +42,33 @@ pub struct EventInfo<T: Config> {
     pub(super) unit_amount: BalanceOf<T>,
     pub(super) borrow_period: BlockNumberFor<T>,
     pub(super) use_type: UseType,
+    /// Interest charged on `unit_amount` for the whole borrow period.
+    pub(super) interest_rate: Perbill,
+    /// Who may attend the event.
+    pub(super) rules: EventRules<BlockNumberFor<T>>,
+}
+
+/// Eligibility rules of an event.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct EventRules<BlockNumber> {
+    /// Only accounts on the event allowlist may attend.
+    pub allowlist_only: bool,
+    /// How many times a single account may attend the event.
+    pub max_per_account: u32,
+    /// How many blocks an account must have existed for before attending.
+    pub min_account_age: BlockNumber,
+    /// Only accounts with a positive identity judgement may attend.
+    pub require_kyc: bool,
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 pub enum UseType {
+    /// Collateral of a storage miner.
     MinerStaking,
+    /// Payment of a storage territory.
+    TerritoryPurchase,
+    /// Stake of a TEE worker.
+    TeeWorkerStake,
+    /// Transaction fees.
+    GasSubsidy,
 }
\ No newline at end of file
@@ -0,0 +1,157 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for pallet_reservoir
+//!
+//! Not benchmarked yet: the weights below are estimated from the storage accesses of each call.
+//! Regenerate it with `scripts/run/benchmark.sh reservoir` on reference hardware.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_reservoir.
+pub trait WeightInfo {
+	fn filling() -> Weight;
+	fn store() -> Weight;
+	fn withdraw() -> Weight;
+	fn event_withdraw() -> Weight;
+	fn create_event() -> Weight;
+	fn attend_event() -> Weight;
+	fn repay() -> Weight;
+	fn set_allowlist(v: u32, ) -> Weight;
+}
+
+/// Weights for pallet_reservoir using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: System Account (r:2 w:2)
+	// Storage: Reservoir Reservoir (r:1 w:1)
+	fn filling() -> Weight {
+		Weight::from_parts(41_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: Reservoir UserPassbook (r:1 w:1)
+	// Storage: Reservoir Reservoir (r:1 w:1)
+	fn store() -> Weight {
+		Weight::from_parts(47_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Reservoir UserPassbook (r:1 w:1)
+	// Storage: Reservoir Reservoir (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn withdraw() -> Weight {
+		Weight::from_parts(48_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: System Account (r:2 w:2)
+	// Storage: Reservoir Reservoir (r:1 w:1)
+	fn event_withdraw() -> Weight {
+		Weight::from_parts(42_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(3 as u64))
+	}
+	// Storage: Reservoir Events (r:1 w:1)
+	// Storage: Reservoir StaleEvents (r:1 w:0)
+	// Storage: Reservoir EventExpiredRecords (r:1 w:1)
+	fn create_event() -> Weight {
+		Weight::from_parts(24_000_000, 4118)
+			.saturating_add(T::DbWeight::get().reads(3 as u64))
+			.saturating_add(T::DbWeight::get().writes(2 as u64))
+	}
+	// Storage: Reservoir Events (r:1 w:1)
+	// Storage: Reservoir BorrowList (r:1 w:1)
+	// Storage: Reservoir UserPassbook (r:1 w:1)
+	// Storage: Reservoir EventAllowlist (r:1 w:0)
+	// Storage: Reservoir Attendance (r:1 w:1)
+	// Storage: Reservoir AccountBirth (r:1 w:0)
+	// Storage: Reservoir Reservoir (r:1 w:1)
+	// Storage: Reservoir LoanDeadlines (r:1 w:1)
+	fn attend_event() -> Weight {
+		Weight::from_parts(68_000_000, 11254)
+			.saturating_add(T::DbWeight::get().reads(8 as u64))
+			.saturating_add(T::DbWeight::get().writes(6 as u64))
+	}
+	// Storage: Reservoir BorrowList (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: Reservoir Reservoir (r:1 w:1)
+	fn repay() -> Weight {
+		Weight::from_parts(55_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(4 as u64))
+			.saturating_add(T::DbWeight::get().writes(4 as u64))
+	}
+	// Storage: Reservoir Events (r:1 w:0)
+	// Storage: Reservoir EventAllowlist (r:0 w:v)
+	fn set_allowlist(v: u32, ) -> Weight {
+		Weight::from_parts(16_000_000, 4118)
+			.saturating_add(Weight::from_parts(4_100_000, 0).saturating_mul(v as u64))
+			.saturating_add(T::DbWeight::get().reads(1 as u64))
+			.saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(v as u64)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn filling() -> Weight {
+		Weight::from_parts(41_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn store() -> Weight {
+		Weight::from_parts(47_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn withdraw() -> Weight {
+		Weight::from_parts(48_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn event_withdraw() -> Weight {
+		Weight::from_parts(42_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(3 as u64))
+	}
+	fn create_event() -> Weight {
+		Weight::from_parts(24_000_000, 4118)
+			.saturating_add(RocksDbWeight::get().reads(3 as u64))
+			.saturating_add(RocksDbWeight::get().writes(2 as u64))
+	}
+	fn attend_event() -> Weight {
+		Weight::from_parts(68_000_000, 11254)
+			.saturating_add(RocksDbWeight::get().reads(8 as u64))
+			.saturating_add(RocksDbWeight::get().writes(6 as u64))
+	}
+	fn repay() -> Weight {
+		Weight::from_parts(55_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(4 as u64))
+			.saturating_add(RocksDbWeight::get().writes(4 as u64))
+	}
+	fn set_allowlist(v: u32, ) -> Weight {
+		Weight::from_parts(16_000_000, 4118)
+			.saturating_add(Weight::from_parts(4_100_000, 0).saturating_mul(v as u64))
+			.saturating_add(RocksDbWeight::get().reads(1 as u64))
+			.saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(v as u64)))
+	}
+}
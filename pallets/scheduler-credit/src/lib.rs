@@ -20,7 +20,7 @@ use sp_runtime::{
 	Percent,
 	RuntimeDebug, Perbill,
 };
-use ces_rrsc_vrf_solver::ValidatorCredits;
+use ces_rrsc_vrf_solver::{CandidateScore, ElectionObserver, ValidatorCredits};
 
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
@@ -51,6 +51,8 @@ pub const SERVICE_VERIFY_BASE_SIZE: u128 = 1 * G_BYTE;
 
 pub const SERVICE_VERIFY_BASE_POINT: u64 = 12;
 
+pub const PUNISHMENT_MULTIPLIER: u32 = 10;
+
 pub const FULL_CREDIT_SCORE: u32 = 1000;
 const LOG_TARGET: &str = "scheduler-credit";
 /// The weight of credit value when figure credit score.
@@ -63,6 +65,106 @@ const PERIOD_WEIGHT: [Percent; 5] = [
 	Percent::from_percent(5),
 ];
 
+/// The work a scheduler earns points for.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum CreditAction {
+	Tag,
+	Cert,
+	Replace,
+	IdleVerify,
+	ServiceVerify,
+}
+
+/// The parameters of the scheduler credit model.
+pub trait CreditModel {
+	/// The points earned for `action`, per started chunk of the returned size in bytes.
+	///
+	/// Returns `(base_size, base_point)`.
+	fn action_rate(action: CreditAction) -> (u128, u64);
+
+	/// The weight of the credit values of past periods in the credit score, most recent first.
+	///
+	/// Its length is the number of periods kept in history.
+	fn period_weights() -> Vec<Percent>;
+
+	/// Each punishment in a period costs `(multiplier * punishment_count)^2` credit value.
+	fn punishment_multiplier() -> u32;
+}
+
+/// The credit model used since genesis.
+pub struct DefaultCreditModel;
+
+impl CreditModel for DefaultCreditModel {
+	fn action_rate(action: CreditAction) -> (u128, u64) {
+		match action {
+			CreditAction::Tag => (TAG_BASE_SIZE, TAG_BASE_POINT),
+			CreditAction::Cert => (CERT_BASE_SIZE, CERT_BASE_POINT),
+			CreditAction::Replace => (REPLACE_BASE_SIZE, REPLACE_BASE_POINT),
+			CreditAction::IdleVerify => (IDLE_VERIFY_BASE_SIZE, IDLE_VERIFY_BASE_POINT),
+			CreditAction::ServiceVerify => (SERVICE_VERIFY_BASE_SIZE, SERVICE_VERIFY_BASE_POINT),
+		}
+	}
+
+	fn period_weights() -> Vec<Percent> {
+		PERIOD_WEIGHT.to_vec()
+	}
+
+	fn punishment_multiplier() -> u32 {
+		PUNISHMENT_MULTIPLIER
+	}
+}
+
+/// Points a scheduler earned in a period, by action.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct ActionPoints {
+	pub tag: u64,
+	pub cert: u64,
+	pub replace: u64,
+	pub idle_verify: u64,
+	pub service_verify: u64,
+}
+
+impl ActionPoints {
+	pub fn add(&mut self, action: CreditAction, point: u64) {
+		let entry = match action {
+			CreditAction::Tag => &mut self.tag,
+			CreditAction::Cert => &mut self.cert,
+			CreditAction::Replace => &mut self.replace,
+			CreditAction::IdleVerify => &mut self.idle_verify,
+			CreditAction::ServiceVerify => &mut self.service_verify,
+		};
+		*entry = entry.saturating_add(point);
+	}
+}
+
+/// How the credit value of a scheduler in a period came about.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct PeriodCreditBreakdown {
+	/// Points earned through each action.
+	pub action_points: ActionPoints,
+	/// All points of the scheduler, including the ones recorded without an action.
+	pub scheduler_points: u64,
+	/// All points of all schedulers.
+	pub network_points: u64,
+	/// The scheduler share of the network points, scaled to `FULL_CREDIT_SCORE`.
+	pub share_value: CreditScore,
+	pub punishment_count: u32,
+	/// The credit value lost to punishments.
+	pub punishment_deduction: CreditScore,
+	/// `share_value` minus `punishment_deduction`.
+	pub credit_value: CreditScore,
+}
+
+/// How the credit score of a scheduler came about.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, TypeInfo, RuntimeDebug)]
+pub struct CreditScoreBreakdown {
+	/// The weighted sum of the period credit values.
+	pub score: CreditScore,
+	/// `(period, weight, breakdown)` from the last finished period backwards. Periods the
+	/// scheduler did no work in are missing.
+	pub periods: Vec<(u32, Percent, PeriodCreditBreakdown)>,
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug)]
 pub struct SchedulerCounterEntry {
 	pub proceed_block_size: u64,
@@ -80,17 +182,32 @@ impl SchedulerCounterEntry {
 		Ok(())
 	}
 
-	pub fn figure_credit_value(&self, total_block_size: u64) -> CreditScore {
-		if total_block_size != 0 {
-			let a = Perbill::from_rational(self.proceed_block_size, total_block_size) * FULL_CREDIT_SCORE;
-			return a.saturating_sub(self.punishment_part())
+	pub fn figure_credit_value<T: Config>(&self, total_block_size: u64) -> CreditScore {
+		self.figure_credit_breakdown::<T::CreditModel>(total_block_size).credit_value
+	}
+
+	/// The credit value of the entry under the credit model `M`, with its parts.
+	pub fn figure_credit_breakdown<M: CreditModel>(&self, total_block_size: u64) -> PeriodCreditBreakdown {
+		let share_value = if total_block_size != 0 {
+			Perbill::from_rational(self.proceed_block_size, total_block_size) * FULL_CREDIT_SCORE
+		} else {
+			0
+		};
+		let punishment_deduction = if total_block_size != 0 { self.punishment_part::<M>() } else { 0 };
+		PeriodCreditBreakdown {
+			action_points: Default::default(),
+			scheduler_points: self.proceed_block_size,
+			network_points: total_block_size,
+			share_value,
+			punishment_count: self.punishment_count,
+			punishment_deduction,
+			credit_value: share_value.saturating_sub(punishment_deduction),
 		}
-		return 0
 	}
 
-	fn punishment_part(&self) -> u32 {
+	fn punishment_part<M: CreditModel>(&self) -> u32 {
 		if self.punishment_count != 0 {
-			return (10 * self.punishment_count).pow(2)
+			return M::punishment_multiplier().saturating_mul(self.punishment_count).saturating_pow(2)
 		}
 		return 0
 	}
@@ -116,6 +233,17 @@ pub mod pallet {
 		type PeriodDuration: Get<BlockNumberFor<Self>>;
 
 		type StashAccountFinder: SchedulerStashAccountFinder<Self::AccountId>;
+
+		/// Points per action, period weights and punishment cost.
+		type CreditModel: CreditModel;
+
+		/// The maximum number of candidates kept in the diagnostics of an election.
+		#[pallet::constant]
+		type MaxDiagnosedCandidates: Get<u32>;
+
+		/// The number of elections whose diagnostics are kept.
+		#[pallet::constant]
+		type ElectionDiagnosticsDepth: Get<u32>;
 	}
 
 	#[pallet::error]
@@ -131,10 +259,35 @@ pub mod pallet {
 	pub(super) type CurrentCounters<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::AccountId, SchedulerCounterEntry, ValueQuery>;
 
+	/// Points earned in the current period, by action.
+	#[pallet::storage]
+	pub(super) type CurrentActionPoints<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ActionPoints, ValueQuery>;
+
 	#[pallet::storage]
 	pub(super) type HistoryCreditValues<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
 
+	/// The parts of each value in [`HistoryCreditValues`].
+	#[pallet::storage]
+	pub(super) type HistoryCreditBreakdowns<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u32, Blake2_128Concat, T::AccountId, PeriodCreditBreakdown>;
+
+	/// How the best scored candidates of the validator election enacted in an epoch were scored,
+	/// ineligible candidates last.
+	#[pallet::storage]
+	pub(super) type ElectionDiagnostics<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u64,
+		BoundedVec<CandidateScore<T::AccountId>, T::MaxDiagnosedCandidates>,
+		ValueQuery,
+	>;
+
+	/// The epoch of the last election found in [`ElectionDiagnostics`].
+	#[pallet::storage]
+	pub(super) type LastDiagnosedEpoch<T: Config> = StorageValue<_, u64>;
+
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
@@ -158,6 +311,24 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Record the points for `space` bytes of `action`, rounded up to whole base sizes.
+	pub fn record_action(scheduler_id: &T::AccountId, action: CreditAction, space: u128) -> DispatchResult {
+		let (base_size, base_point) = T::CreditModel::action_rate(action);
+		let mut base_count: u64 = space
+			.checked_div(base_size).ok_or(Error::<T>::PointOverflow)?
+			.try_into().map_err(|_| Error::<T>::Overflow)?;
+		if space % base_size != 0 {
+			base_count = base_count.checked_add(1).ok_or(Error::<T>::PointOverflow)?;
+		}
+
+		let point: u64 = base_count.checked_mul(base_point).ok_or(Error::<T>::PointOverflow)?;
+
+		Self::record_proceed_block_size(scheduler_id, point)?;
+		<CurrentActionPoints<T>>::mutate(scheduler_id, |points| points.add(action, point));
+
+		Ok(())
+	}
+
 	pub fn record_punishment(scheduler_id: &T::AccountId) -> DispatchResult {
 		<CurrentCounters<T>>::mutate(scheduler_id, |scb| -> DispatchResult {
 			scb.increase_punishment_count::<T>()?;
@@ -175,7 +346,9 @@ impl<T: Config> Pallet<T> {
 		}
 
 		for (ctrl_account_id, counter_entry) in <CurrentCounters<T>>::iter() {
-			let credit_value = counter_entry.figure_credit_value(total_size);
+			let mut breakdown = counter_entry.figure_credit_breakdown::<T::CreditModel>(total_size);
+			breakdown.action_points = <CurrentActionPoints<T>>::get(&ctrl_account_id);
+			let credit_value = breakdown.credit_value;
 			debug!(
 				target: LOG_TARGET,
 				"scheduler control account: {:?}, credit value: {}",
@@ -183,7 +356,8 @@ impl<T: Config> Pallet<T> {
 				credit_value
 			);
 			HistoryCreditValues::<T>::insert(&period, &ctrl_account_id, credit_value);
-			weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+			HistoryCreditBreakdowns::<T>::insert(&period, &ctrl_account_id, breakdown);
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 2));
 		}
 
 		// Clear CurrentCounters
@@ -195,16 +369,31 @@ impl<T: Config> Pallet<T> {
 		};
 		weight = weight.saturating_add(T::DbWeight::get().reads(cc_keys_removed.into()));
 
+		#[allow(deprecated)]
+		let cap_outcome = CurrentActionPoints::<T>::remove_all(None);
+		let cap_keys_removed = match cap_outcome {
+			KillStorageResult::AllRemoved(count) => count,
+			KillStorageResult::SomeRemaining(count) => count,
+		};
+		weight = weight.saturating_add(T::DbWeight::get().reads(cap_keys_removed.into()));
+
 		// Remove `period - history_depth` credit values in history.
-		let history_depth = PERIOD_WEIGHT.len() as u32;
+		let history_depth = T::CreditModel::period_weights().len() as u32;
 		if period >= history_depth {
+			let expired = period.saturating_sub(history_depth);
 			#[allow(deprecated)]
-			let hcv_outcome = HistoryCreditValues::<T>::remove_prefix(&period.saturating_sub(history_depth), None);
+			let hcv_outcome = HistoryCreditValues::<T>::remove_prefix(&expired, None);
 			let hcv_keys_removed= match hcv_outcome {
 				KillStorageResult::AllRemoved(count) => count,
 				KillStorageResult::SomeRemaining(count) => count,
 			};
-			weight = weight.saturating_add(T::DbWeight::get().reads(hcv_keys_removed.into()));
+			#[allow(deprecated)]
+			let hcb_outcome = HistoryCreditBreakdowns::<T>::remove_prefix(&expired, None);
+			let hcb_keys_removed = match hcb_outcome {
+				KillStorageResult::AllRemoved(count) => count,
+				KillStorageResult::SomeRemaining(count) => count,
+			};
+			weight = weight.saturating_add(T::DbWeight::get().reads((hcv_keys_removed + hcb_keys_removed).into()));
 		}
 		weight
 	}
@@ -225,14 +414,7 @@ impl<T: Config> Pallet<T> {
 				if let Some(stash_account_id) =
 					T::StashAccountFinder::find_stash_account_id(&ctrl_account_id)
 				{
-					let mut credit_score = 0_u32;
-					for (index, weight) in PERIOD_WEIGHT.into_iter().enumerate() {
-						if last_period >= index as u32 {
-							let credit_value = HistoryCreditValues::<T>::try_get(&last_period.saturating_sub(index as u32), &ctrl_account_id)
-								.unwrap_or(0);
-							credit_score += weight * credit_value;
-						}
-					}
+					let credit_score = Self::figure_credit_score(last_period, &ctrl_account_id);
 					debug!(
 						target: LOG_TARGET,
 						"scheduler stash account: {:?}, credit value: {}",
@@ -250,82 +432,88 @@ impl<T: Config> Pallet<T> {
 		});
 		result
 	}
-}
 
-impl<T: Config> SchedulerCreditCounter<T::AccountId> for Pallet<T> {
-	fn increase_point_for_tag(scheduler_id: &T::AccountId, space: u128) -> DispatchResult {
-		let mut base_count: u64 =  space
-			.checked_div(TAG_BASE_SIZE).ok_or(Error::<T>::PointOverflow)?
-			.try_into().map_err(|_| Error::<T>::Overflow)?;
-		if space % TAG_BASE_SIZE != 0 {
-			base_count = base_count.checked_add(1).ok_or(Error::<T>::PointOverflow)?;
+	/// Weighted sum of the credit values of `ctrl_account_id` from `last_period` backwards.
+	pub fn figure_credit_score(last_period: u32, ctrl_account_id: &T::AccountId) -> CreditScore {
+		let mut credit_score = 0_u32;
+		for (index, weight) in T::CreditModel::period_weights().into_iter().enumerate() {
+			if last_period >= index as u32 {
+				let credit_value = HistoryCreditValues::<T>::try_get(&last_period.saturating_sub(index as u32), ctrl_account_id)
+					.unwrap_or(0);
+				credit_score += weight * credit_value;
+			}
 		}
+		credit_score
+	}
 
-		let point: u64 = base_count.checked_mul(TAG_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
+	/// The credit score of a single scheduler as of the last finished period.
+	pub fn current_credit_score(ctrl_account_id: &T::AccountId) -> CreditScore {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let period_duration = T::PeriodDuration::get();
+		let period: u32 = (now / period_duration).saturated_into();
 
-		Self::record_proceed_block_size(scheduler_id, point)?;
+		if period == 0 {
+			return 0;
+		}
 
-		Ok(())
+		Self::figure_credit_score(period.saturating_sub(1), ctrl_account_id)
 	}
 
-	fn increase_point_for_cert(scheduler_id: &T::AccountId, space: u128) -> DispatchResult {
-		let mut base_count: u64 =  space
-			.checked_div(CERT_BASE_SIZE).ok_or(Error::<T>::PointOverflow)?
-			.try_into().map_err(|_| Error::<T>::Overflow)?;
-		if space % CERT_BASE_SIZE != 0 {
-			base_count = base_count.checked_add(1).ok_or(Error::<T>::PointOverflow)?;
-		}
-
-		let point: u64 = base_count.checked_mul(CERT_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
+	/// The credit score of a single scheduler as of the last finished period, with the
+	/// breakdown of every period it is made of.
+	pub fn credit_score_breakdown(ctrl_account_id: &T::AccountId) -> CreditScoreBreakdown {
+		let now = <frame_system::Pallet<T>>::block_number();
+		let period_duration = T::PeriodDuration::get();
+		let period: u32 = (now / period_duration).saturated_into();
 
-		Self::record_proceed_block_size(scheduler_id, point)?;
+		if period == 0 {
+			return Default::default();
+		}
 
-		Ok(())
+		let last_period = period.saturating_sub(1);
+		let periods = T::CreditModel::period_weights()
+			.into_iter()
+			.enumerate()
+			.filter(|(index, _)| last_period >= *index as u32)
+			.filter_map(|(index, weight)| {
+				let period = last_period.saturating_sub(index as u32);
+				HistoryCreditBreakdowns::<T>::get(&period, ctrl_account_id).map(|breakdown| (period, weight, breakdown))
+			})
+			.collect();
+
+		CreditScoreBreakdown { score: Self::figure_credit_score(last_period, ctrl_account_id), periods }
 	}
 
-	fn increase_point_for_idle_verify(scheduler_id: &T::AccountId, space: u128) -> DispatchResult {
-		let mut base_count: u64 =  space
-			.checked_div(IDLE_VERIFY_BASE_SIZE).ok_or(Error::<T>::PointOverflow)?
-			.try_into().map_err(|_| Error::<T>::Overflow)?;
-		if space % IDLE_VERIFY_BASE_SIZE != 0 {
-			base_count = base_count.checked_add(1).ok_or(Error::<T>::PointOverflow)?;
+	/// How the candidates of the election of `epoch_index` were scored, the last election by
+	/// default.
+	pub fn election_diagnostics(epoch_index: Option<u64>) -> Option<(u64, Vec<CandidateScore<T::AccountId>>)> {
+		let epoch_index = epoch_index.or_else(LastDiagnosedEpoch::<T>::get)?;
+		if !ElectionDiagnostics::<T>::contains_key(epoch_index) {
+			return None;
 		}
+		Some((epoch_index, ElectionDiagnostics::<T>::get(epoch_index).into_inner()))
+	}
+}
 
-		let point: u64 = base_count.checked_mul(IDLE_VERIFY_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
+impl<T: Config> SchedulerCreditCounter<T::AccountId> for Pallet<T> {
+	fn increase_point_for_tag(scheduler_id: &T::AccountId, space: u128) -> DispatchResult {
+		Pallet::<T>::record_action(scheduler_id, CreditAction::Tag, space)
+	}
 
-		Self::record_proceed_block_size(scheduler_id, point)?;
+	fn increase_point_for_cert(scheduler_id: &T::AccountId, space: u128) -> DispatchResult {
+		Pallet::<T>::record_action(scheduler_id, CreditAction::Cert, space)
+	}
 
-		Ok(())
+	fn increase_point_for_idle_verify(scheduler_id: &T::AccountId, space: u128) -> DispatchResult {
+		Pallet::<T>::record_action(scheduler_id, CreditAction::IdleVerify, space)
 	}
 
 	fn increase_point_for_service_verify(scheduler_id: &T::AccountId, space: u128) -> DispatchResult {
-		let mut base_count: u64 =  space
-			.checked_div(SERVICE_VERIFY_BASE_SIZE).ok_or(Error::<T>::PointOverflow)?
-			.try_into().map_err(|_| Error::<T>::Overflow)?;
-		if space % SERVICE_VERIFY_BASE_SIZE != 0 {
-			base_count = base_count.checked_add(1).ok_or(Error::<T>::PointOverflow)?;
-		}
-
-		let point: u64 = base_count.checked_mul(SERVICE_VERIFY_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
-
-		Self::record_proceed_block_size(scheduler_id, point)?;
-
-		Ok(())
+		Pallet::<T>::record_action(scheduler_id, CreditAction::ServiceVerify, space)
 	}
 
 	fn increase_point_for_replace(scheduler_id: &T::AccountId, space: u128) -> DispatchResult {
-		let mut base_count: u64 =  space
-			.checked_div(REPLACE_BASE_SIZE).ok_or(Error::<T>::PointOverflow)?
-			.try_into().map_err(|_| Error::<T>::Overflow)?;
-		if space % REPLACE_BASE_SIZE != 0 {
-			base_count = base_count.checked_add(1).ok_or(Error::<T>::PointOverflow)?;
-		}
-
-		let point: u64 = base_count.checked_mul(REPLACE_BASE_POINT).ok_or(Error::<T>::PointOverflow)?;
-
-		Self::record_proceed_block_size(scheduler_id, point)?;
-
-		Ok(())
+		Pallet::<T>::record_action(scheduler_id, CreditAction::Replace, space)
 	}
 
 	fn record_proceed_block_size(scheduler_id: &T::AccountId, block_size: u64) -> DispatchResult {
@@ -337,6 +525,10 @@ impl<T: Config> SchedulerCreditCounter<T::AccountId> for Pallet<T> {
 		Pallet::<T>::record_punishment(scheduler_id)?;
 		Ok(())
 	}
+
+	fn credit_score(scheduler_id: &T::AccountId) -> u32 {
+		Pallet::<T>::current_credit_score(scheduler_id)
+	}
 }
 
 impl<T: Config> ValidatorCredits<T::AccountId> for Pallet<T> {
@@ -350,9 +542,22 @@ impl<T: Config> ValidatorCredits<T::AccountId> for Pallet<T> {
 	}
 }
 
+impl<T: Config> ElectionObserver<T::AccountId> for Pallet<T> {
+	fn on_scored(epoch_index: u64, scores: Vec<CandidateScore<T::AccountId>>) {
+		let scores = BoundedVec::truncate_from(scores);
+		ElectionDiagnostics::<T>::insert(epoch_index, scores);
+		LastDiagnosedEpoch::<T>::put(epoch_index);
+
+		let depth = T::ElectionDiagnosticsDepth::get() as u64;
+		if let Some(expired) = epoch_index.checked_sub(depth) {
+			ElectionDiagnostics::<T>::remove(expired);
+		}
+	}
+}
+
 #[cfg(test)]
 mod test {
-	use crate::SchedulerCounterEntry;
+	use crate::{DefaultCreditModel, SchedulerCounterEntry};
 	use crate::mock::Test;
 	#[test]
 	fn scheduler_counter_works() {
@@ -361,16 +566,16 @@ mod test {
 		assert_eq!(100, sce.proceed_block_size);
 		let _ = sce.increase_block_size::<Test>(100);
 		assert_eq!(200, sce.proceed_block_size);
-		assert_eq!(0, sce.punishment_part());
-		assert_eq!(100, sce.figure_credit_value(2000));
+		assert_eq!(0, sce.punishment_part::<DefaultCreditModel>());
+		assert_eq!(100, sce.figure_credit_value::<Test>(2000));
 
 		let _ = sce.increase_punishment_count::<Test>();
 		assert_eq!(1, sce.punishment_count);
 
-		assert_eq!(100, sce.figure_credit_value(1000));
+		assert_eq!(100, sce.figure_credit_value::<Test>(1000));
 		let _ = sce.increase_punishment_count::<Test>();
 
 		assert_eq!(2, sce.punishment_count);
-		assert_eq!(0, sce.figure_credit_value(1000));
+		assert_eq!(0, sce.figure_credit_value::<Test>(1000));
 	}
 }
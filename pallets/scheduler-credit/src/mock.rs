@@ -61,6 +61,9 @@ impl Config for Test {
 	type StashAccountFinder = MockStashAccountFinder<Self::AccountId>;
 
 	type PeriodDuration = PeriodDuration;
+	type CreditModel = DefaultCreditModel;
+	type MaxDiagnosedCandidates = ConstU32<3>;
+	type ElectionDiagnosticsDepth = ConstU32<2>;
 }
 
 frame_support::construct_runtime!(
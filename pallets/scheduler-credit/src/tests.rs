@@ -2,6 +2,7 @@ use super::*;
 use crate::mock::*;
 use cp_scheduler_credit::SchedulerCreditCounter;
 use cessp_consensus_rrsc::traits::ValidatorCredits;
+use frame_support::assert_ok;
 
 #[test]
 fn figure_credit_scores_works() {
@@ -33,5 +34,74 @@ fn figure_credit_scores_works() {
 		assert_eq!(&250, vc_map.get(&1).unwrap());
 		assert_eq!(&62, vc_map.get(&2).unwrap());
 		assert_eq!(&187, vc_map.get(&3).unwrap());
+
+		// single scheduler credit score matches the map
+		assert_eq!(250, <Pallet<Test> as SchedulerCreditCounter<AccountId>>::credit_score(&1));
+		assert_eq!(0, <Pallet<Test> as SchedulerCreditCounter<AccountId>>::credit_score(&4));
+	});
+}
+
+#[test]
+fn credit_breakdown_works() {
+	ExtBuilder::default().build_and_execute(|| {
+		// Points are counted per started base size.
+		assert_ok!(<Pallet<Test> as SchedulerCreditCounter<AccountId>>::increase_point_for_tag(&1, TAG_BASE_SIZE));
+		assert_ok!(<Pallet<Test> as SchedulerCreditCounter<AccountId>>::increase_point_for_service_verify(
+			&1,
+			SERVICE_VERIFY_BASE_SIZE + 1
+		));
+		assert_ok!(<Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_proceed_block_size(&2, 204));
+		assert_ok!(<Pallet<Test> as SchedulerCreditCounter<AccountId>>::record_punishment(&1));
+		assert_eq!(
+			CurrentActionPoints::<Test>::get(1),
+			ActionPoints { tag: TAG_BASE_POINT, service_verify: 2 * SERVICE_VERIFY_BASE_POINT, ..Default::default() }
+		);
+
+		System::set_block_number(PeriodDuration::get());
+		Pallet::<Test>::on_initialize(System::block_number());
+		assert_eq!(CurrentActionPoints::<Test>::contains_key(&1), false);
+
+		let breakdown = HistoryCreditBreakdowns::<Test>::get(&0, &1).unwrap();
+		assert_eq!(breakdown.scheduler_points, 204);
+		assert_eq!(breakdown.network_points, 408);
+		assert_eq!(breakdown.share_value, 500);
+		assert_eq!(breakdown.punishment_count, 1);
+		assert_eq!(breakdown.punishment_deduction, 100);
+		assert_eq!(breakdown.credit_value, 400);
+		assert_eq!(HistoryCreditValues::<Test>::get(&0, &1), 400);
+
+		let score = Pallet::<Test>::credit_score_breakdown(&1);
+		assert_eq!(score.score, 200);
+		assert_eq!(score.periods, vec![(0, Percent::from_percent(50), breakdown)]);
+		assert_eq!(Pallet::<Test>::credit_score_breakdown(&3), CreditScoreBreakdown::default());
+	});
+}
+
+#[test]
+fn election_diagnostics_are_bounded() {
+	ExtBuilder::default().build_and_execute(|| {
+		let score = |who: AccountId, final_score: u32| CandidateScore {
+			who,
+			backed_stake: 100,
+			credit_score: 0,
+			stake_score: 100,
+			random_number: 0,
+			random_score: 0,
+			final_score,
+			eligible: true,
+			elected: who == 1,
+		};
+		assert_eq!(Pallet::<Test>::election_diagnostics(None), None);
+
+		let scores = vec![score(1, 50), score(2, 40), score(3, 30), score(4, 20)];
+		<Pallet<Test> as ElectionObserver<AccountId>>::on_scored(1, scores.clone());
+		assert_eq!(Pallet::<Test>::election_diagnostics(None), Some((1, scores[..3].to_vec())));
+
+		<Pallet<Test> as ElectionObserver<AccountId>>::on_scored(2, vec![score(2, 10)]);
+		<Pallet<Test> as ElectionObserver<AccountId>>::on_scored(3, vec![score(3, 10)]);
+		assert_eq!(Pallet::<Test>::election_diagnostics(None), Some((3, vec![score(3, 10)])));
+		assert_eq!(Pallet::<Test>::election_diagnostics(Some(2)), Some((2, vec![score(2, 10)])));
+		// Older than the depth.
+		assert_eq!(Pallet::<Test>::election_diagnostics(Some(1)), None);
 	});
 }
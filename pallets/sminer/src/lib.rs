@@ -40,13 +40,15 @@ use frame_support::{
 };
 use frame_system::pallet_prelude::BlockNumberFor;
 use pallet_cess_treasury::{RewardPool, TreasuryHandle};
-use pallet_reservoir::ReservoirGate;
+use pallet_reservoir::{LoanCollateral, ReservoirGate};
 use pallet_storage_handler::StorageHandle;
 use pallet_tee_worker::TeeWorkerHandler;
 use scale_info::TypeInfo;
 use sp_core::ConstU32;
 use sp_runtime::{
-	traits::{AccountIdConversion, CheckedAdd, CheckedMul, CheckedSub, Dispatchable, SaturatedConversion, Zero},
+	traits::{
+		AccountIdConversion, CheckedAdd, CheckedMul, CheckedSub, Dispatchable, SaturatedConversion, Saturating, Zero,
+	},
 	Perbill, RuntimeDebug,
 };
 use sp_staking::StakingInterface;
@@ -1147,6 +1149,7 @@ pub trait MinerControl<AccountId, BlockNumber> {
 	fn get_power(acc: &AccountId) -> Result<(u128, u128), DispatchError>;
 	fn miner_is_exist(acc: AccountId) -> bool;
 	fn get_miner_state(acc: &AccountId) -> Result<Vec<u8>, DispatchError>;
+	fn get_staking_account(acc: &AccountId) -> Result<AccountId, DispatchError>;
 	fn get_all_miner() -> Result<Vec<AccountId>, DispatchError>;
 	// Associated functions related to uploading files.
 	fn insert_service_bloom(acc: &AccountId, hash_list: Vec<Box<[u8; 256]>>) -> DispatchResult;
@@ -1245,6 +1248,11 @@ impl<T: Config> MinerControl<<T as frame_system::Config>::AccountId, BlockNumber
 		Ok(miner.state.to_vec())
 	}
 
+	fn get_staking_account(acc: &AccountOf<T>) -> Result<AccountOf<T>, DispatchError> {
+		let miner = <MinerItems<T>>::try_get(acc).map_err(|_| Error::<T>::NotMiner)?;
+		Ok(miner.staking_account)
+	}
+
 	fn get_all_miner() -> Result<Vec<AccountOf<T>>, DispatchError> {
 		Ok(AllMiner::<T>::get().to_vec())
 	}
@@ -1417,4 +1425,33 @@ impl<T: Config> MinerControl<<T as frame_system::Config>::AccountId, BlockNumber
 			Ok(())
 		})
 	}
-}
\ No newline at end of file
+}
+
+impl<T: Config> LoanCollateral<AccountOf<T>, BalanceOf<T>> for Pallet<T> {
+	fn withdraw_collateral(acc: &AccountOf<T>, amount: BalanceOf<T>) -> BalanceOf<T> {
+		<MinerItems<T>>::mutate(acc, |miner_info_opt| {
+			let Some(miner_info) = miner_info_opt.as_mut() else {
+				return BalanceOf::<T>::zero();
+			};
+			if miner_info.staking_account != T::ReservoirGate::get_reservoir_acc() {
+				return BalanceOf::<T>::zero();
+			}
+
+			let withdrawn = miner_info.collaterals.min(amount);
+			miner_info.collaterals = miner_info.collaterals.saturating_sub(withdrawn);
+			// Like after a punishment, a miner left without enough collateral is frozen.
+			if miner_info.state.to_vec() == STATE_POSITIVE.as_bytes().to_vec() {
+				let power = Self::calculate_power(miner_info.idle_space, miner_info.service_space);
+				let limit: Option<BalanceOf<T>> =
+					Self::calculate_limit_by_space(power).ok().and_then(|limit| limit.try_into().ok());
+				if limit.map_or(false, |limit| miner_info.collaterals < limit) {
+					if let Ok(state) = Self::str_to_bound(STATE_FROZEN) {
+						miner_info.state = state;
+					}
+				}
+			}
+
+			withdrawn
+		})
+	}
+}
@@ -30,7 +30,7 @@ pub struct Reward<T: pallet::Config> {
 	//Total reward for miners
     pub(super) total_reward: BalanceOf<T>,
 	//Rewards issued at present
-	pub(super) reward_issued: BalanceOf<T>,
+	pub reward_issued: BalanceOf<T>,
 	//Reward order list, up to 180 reward orders can be accumulated
 	pub(super) order_list: BoundedVec<RewardOrder<BalanceOf<T>, BlockNumberFor<T>>, ConstU32<{RELEASE_NUMBER as u32}>>,
 }
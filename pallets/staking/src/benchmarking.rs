@@ -961,6 +961,27 @@ benchmarks! {
 		assert_eq!(Staking::<T>::inspect_bond_state(&stash), Ok(LedgerIntegrityState::Ok));
 	}
 
+	set_emission_schedule {
+		let phase = crate::emission::EmissionPhase {
+			start_era: 0,
+			validator_emission: BalanceOf::<T>::max_value(),
+			sminer_emission: BalanceOf::<T>::max_value(),
+			yearly_decay: Perbill::one(),
+			decay_years: 0,
+		};
+		let phases = (0..crate::emission::MAX_EMISSION_PHASES)
+			.map(|i| crate::emission::EmissionPhase { start_era: i, ..phase.clone() })
+			.collect::<Vec<_>>();
+		let schedule = crate::emission::EmissionSchedule {
+			phases: BoundedVec::truncate_from(phases),
+			validator_floor: Zero::zero(),
+			sminer_floor: Zero::zero(),
+		};
+	}: _(RawOrigin::Root, Some(schedule.clone()))
+	verify {
+		assert_eq!(EmissionScheduleOverride::<T>::get(), Some(schedule));
+	}
+
 	impl_benchmark_test_suite!(
 		Staking,
 		crate::mock::ExtBuilder::default().has_stakers(true),
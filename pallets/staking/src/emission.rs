@@ -0,0 +1,191 @@
+//! The emission schedule decides how many tokens are minted every era, and how they are split
+//! between validators and the storage miner reward pool.
+//!
+//! The schedule is a list of phases. Each phase starts at an era with a yearly emission that
+//! decays every year for a bounded number of years, and stays flat afterwards. A floor keeps the
+//! yearly emission from decaying below a minimum.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::AtLeast32BitUnsigned, Perbill, RuntimeDebug};
+use sp_staking::EraIndex;
+
+/// The maximum number of phases in a schedule.
+pub const MAX_EMISSION_PHASES: u32 = 16;
+
+/// The maximum number of years a phase may decay for.
+pub const MAX_DECAY_YEARS: u32 = 100;
+
+/// The maximum number of eras a projection covers.
+pub const MAX_PROJECTED_ERAS: u32 = 4096;
+
+/// A segment of the emission schedule.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EmissionPhase<Balance> {
+	/// The first era of the phase.
+	pub start_era: EraIndex,
+	/// Tokens minted for validators in the first year of the phase.
+	pub validator_emission: Balance,
+	/// Tokens minted for the storage miner reward pool in the first year of the phase.
+	pub sminer_emission: Balance,
+	/// The share of the yearly emission kept from one year to the next.
+	pub yearly_decay: Perbill,
+	/// The number of years the emission decays for. No decrease thereafter.
+	pub decay_years: u32,
+}
+
+/// The emission curve of the network.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct EmissionSchedule<Balance> {
+	/// Phases ordered by `start_era`, the first one starting at era 0.
+	pub phases: BoundedVec<EmissionPhase<Balance>, ConstU32<MAX_EMISSION_PHASES>>,
+	/// The lowest yearly emission for validators.
+	pub validator_floor: Balance,
+	/// The lowest yearly emission for the storage miner reward pool.
+	pub sminer_floor: Balance,
+}
+
+impl<Balance: AtLeast32BitUnsigned + Copy> EmissionSchedule<Balance> {
+	/// A single decaying phase, as configured by the legacy `Config` constants.
+	pub fn single_phase(
+		validator_emission: Balance,
+		sminer_emission: Balance,
+		yearly_decay: Perbill,
+		decay_years: u32,
+	) -> Self {
+		let phase = EmissionPhase { start_era: 0, validator_emission, sminer_emission, yearly_decay, decay_years };
+		Self {
+			phases: BoundedVec::truncate_from(alloc::vec![phase]),
+			validator_floor: Balance::zero(),
+			sminer_floor: Balance::zero(),
+		}
+	}
+
+	/// Whether the schedule covers every era with well ordered, bounded phases.
+	pub fn is_valid(&self) -> bool {
+		let Some(first) = self.phases.first() else {
+			return false;
+		};
+		first.start_era == 0 &&
+			self.phases.windows(2).all(|w| w[0].start_era < w[1].start_era) &&
+			self.phases.iter().all(|p| p.decay_years <= MAX_DECAY_YEARS)
+	}
+
+	/// The phase in effect at `era`.
+	pub fn phase_at(&self, era: EraIndex) -> Option<&EmissionPhase<Balance>> {
+		self.phases.iter().rev().find(|p| p.start_era <= era)
+	}
+
+	/// The yearly emission for validators and the storage miner reward pool at `era`.
+	pub fn yearly_emission(&self, era: EraIndex, eras_per_year: u64) -> (Balance, Balance) {
+		let Some(phase) = self.phase_at(era) else {
+			return (Balance::zero(), Balance::zero());
+		};
+		let years = (era.saturating_sub(phase.start_era) as u64)
+			.checked_div(eras_per_year)
+			.unwrap_or_default()
+			.min(phase.decay_years as u64);
+
+		let mut validator = phase.validator_emission;
+		let mut sminer = phase.sminer_emission;
+		for _ in 0..years {
+			validator = phase.yearly_decay * validator;
+			sminer = phase.yearly_decay * sminer;
+		}
+		(validator.max(self.validator_floor), sminer.max(self.sminer_floor))
+	}
+
+	/// The payout of validators and of the storage miner reward pool for `era`.
+	pub fn era_payout(&self, era: EraIndex, eras_per_year: u64) -> (Balance, Balance)
+	where
+		Balance: From<u64>,
+	{
+		if eras_per_year == 0 {
+			return (Balance::zero(), Balance::zero());
+		}
+		let (validator, sminer) = self.yearly_emission(era, eras_per_year);
+		let eras_per_year = Balance::from(eras_per_year);
+		(validator / eras_per_year, sminer / eras_per_year)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	const ERAS_PER_YEAR: u64 = 100;
+
+	fn legacy() -> EmissionSchedule<u128> {
+		EmissionSchedule::single_phase(238_500_000_000, 477_000_000_000, Perbill::from_perthousand(841), 30)
+	}
+
+	#[test]
+	fn single_phase_matches_legacy_curve() {
+		let schedule = legacy();
+		assert_eq!(schedule.era_payout(0, ERAS_PER_YEAR), (2_385_000_000, 4_770_000_000));
+		assert_eq!(schedule.era_payout(99, ERAS_PER_YEAR), (2_385_000_000, 4_770_000_000));
+
+		let ratio = Perbill::from_perthousand(841);
+		assert_eq!(
+			schedule.era_payout(100, ERAS_PER_YEAR),
+			((ratio * 238_500_000_000u128) / 100, (ratio * 477_000_000_000u128) / 100)
+		);
+		// No decrease after the decay years.
+		assert_eq!(schedule.era_payout(3_000, ERAS_PER_YEAR), schedule.era_payout(10_000, ERAS_PER_YEAR));
+	}
+
+	#[test]
+	fn floor_applies() {
+		let mut schedule = legacy();
+		schedule.validator_floor = 200_000_000_000;
+		schedule.sminer_floor = 1;
+		assert_eq!(schedule.yearly_emission(0, ERAS_PER_YEAR), (238_500_000_000, 477_000_000_000));
+		let (validator, sminer) = schedule.yearly_emission(100, ERAS_PER_YEAR);
+		assert_eq!(validator, 200_000_000_000);
+		assert_eq!(sminer, Perbill::from_perthousand(841) * 477_000_000_000u128);
+	}
+
+	#[test]
+	fn later_phase_takes_over() {
+		let mut schedule = legacy();
+		schedule
+			.phases
+			.try_push(EmissionPhase {
+				start_era: 500,
+				validator_emission: 1_000,
+				sminer_emission: 3_000,
+				yearly_decay: Perbill::one(),
+				decay_years: 0,
+			})
+			.unwrap();
+		assert!(schedule.is_valid());
+		assert_eq!(schedule.phase_at(499).unwrap().start_era, 0);
+		assert_eq!(schedule.era_payout(500, ERAS_PER_YEAR), (10, 30));
+		assert_eq!(schedule.era_payout(5_000, ERAS_PER_YEAR), (10, 30));
+	}
+
+	#[test]
+	fn invalid_schedules_are_rejected() {
+		let mut empty = legacy();
+		empty.phases.clear();
+		assert!(!empty.is_valid());
+
+		let mut late_start = legacy();
+		late_start.phases[0].start_era = 1;
+		assert!(!late_start.is_valid());
+
+		let mut unordered = legacy();
+		unordered.phases.try_push(unordered.phases[0].clone()).unwrap();
+		assert!(!unordered.is_valid());
+
+		let mut endless = legacy();
+		endless.phases[0].decay_years = MAX_DECAY_YEARS + 1;
+		assert!(!endless.is_valid());
+	}
+
+	#[test]
+	fn zero_eras_per_year_pays_nothing() {
+		assert_eq!(legacy().era_payout(10, 0), (0, 0));
+	}
+}
@@ -297,6 +297,7 @@ pub(crate) mod mock;
 
 pub mod asset;
 pub mod election_size_tracker;
+pub mod emission;
 pub mod inflation;
 pub mod ledger;
 pub mod migrations;
@@ -917,6 +918,21 @@ impl<Balance: Default> EraPayout<Balance> for () {
 	}
 }
 
+/// Handler for deciding how much of the scheduled storage miner payout is paid out in an era.
+pub trait SminerPayout<Balance> {
+	/// The part of the sminer payout scheduled for `era_index` paid into the storage miner
+	/// reward pool.
+	///
+	/// The rest of `scheduled` is routed to the reward reserve instead.
+	fn sminer_payout(era_index: EraIndex, scheduled: Balance) -> Balance;
+}
+
+impl<Balance> SminerPayout<Balance> for () {
+	fn sminer_payout(_era_index: EraIndex, scheduled: Balance) -> Balance {
+		scheduled
+	}
+}
+
 /// Adaptor to turn a `PiecewiseLinear` curve definition into an `EraPayout` impl, used for
 /// backwards compatibility.
 pub struct ConvertCurve<T>(core::marker::PhantomData<T>);
@@ -291,7 +291,30 @@ impl crate::pallet::pallet::Config for Test {
 	const FIRST_YEAR_SMINER_REWARDS: Balance = 477_000_000 * 1000;
 	const REWARD_DECREASE_RATIO: Perbill = Perbill::from_perthousand(841);
 	const REWARD_DECREASE_YEARS: u64 = 30;
-	type SminerRewardPool = ();
+	type SminerRewardPool = DepositInto<SMINER_REWARD_POOL>;
+	type SminerPayout = MockSminerPayout;
+	type SminerReserve = DepositInto<SMINER_RESERVE>;
+}
+
+pub(crate) const SMINER_REWARD_POOL: AccountId = 1_000;
+pub(crate) const SMINER_RESERVE: AccountId = 1_001;
+
+parameter_types! {
+	pub static SminerPayoutShare: Perbill = Perbill::one();
+}
+
+pub struct MockSminerPayout;
+impl SminerPayout<Balance> for MockSminerPayout {
+	fn sminer_payout(_era_index: EraIndex, scheduled: Balance) -> Balance {
+		SminerPayoutShare::get() * scheduled
+	}
+}
+
+pub struct DepositInto<const WHO: AccountId>;
+impl<const WHO: AccountId> OnUnbalanced<NegativeImbalanceOf<Test>> for DepositInto<WHO> {
+	fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+		Balances::resolve_creating(&WHO, amount);
+	}
 }
 
 pub struct WeightedNominationsQuota<const MAX: u32>;
@@ -36,7 +36,7 @@ use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use pallet_session::historical;
 use sp_runtime::{
 	traits::{
-		Bounded, CheckedAdd, CheckedSub, Convert, One, Saturating,
+		Bounded, CheckedAdd, CheckedSub, Convert, One, SaturatedConversion, Saturating,
 		StaticLookup, Zero,
 	},
 	ArithmeticError, Perbill,
@@ -50,8 +50,12 @@ use sp_staking::{
 };
 
 use crate::{
-	asset, election_size_tracker::StaticTracker, log, slashing, weights::WeightInfo, ActiveEraInfo,
-	BalanceOf, EraInfo, Exposure, ExposureOf, Forcing, IndividualExposure,
+	asset,
+	election_size_tracker::StaticTracker,
+	emission::{EmissionSchedule, MAX_PROJECTED_ERAS},
+	log, slashing,
+	weights::WeightInfo,
+	ActiveEraInfo, BalanceOf, EraInfo, Exposure, ExposureOf, Forcing, IndividualExposure,
 	LedgerIntegrityState, MaxNominationsOf, MaxWinnersOf, Nominations, NominationsQuota,
 	PositiveImbalanceOf, RewardDestination, SessionInterface, StakingLedger, ValidatorPrefs,
 };
@@ -573,7 +577,8 @@ impl<T: Config> Pallet<T> {
 	fn end_era(active_era: ActiveEraInfo, _session_index: SessionIndex) {
 		// Note: active_era_start can be None if end era is called during genesis config.
 		if let Some(_active_era_start) = active_era.start {
-			let (validator_payout, sminer_payout) = Self::rewards_in_era(active_era.index);
+			let (validator_payout, scheduled_sminer_payout) = Self::rewards_in_era(active_era.index);
+			let sminer_payout = T::SminerPayout::sminer_payout(active_era.index, scheduled_sminer_payout);
 
 			Self::deposit_event(Event::<T>::EraPaid {
 				era_index: active_era.index,
@@ -583,35 +588,58 @@ impl<T: Config> Pallet<T> {
 
 			// Set ending era reward.
 			<ErasValidatorReward<T>>::insert(&active_era.index, validator_payout);
-			T::SminerRewardPool::on_unbalanced(asset::issue::<T>(sminer_payout));
+			// Mint the scheduled payout once and split it, so the reserve never adds to what the
+			// schedule issues.
+			let (paid, reserve) = asset::issue::<T>(scheduled_sminer_payout).split(sminer_payout);
+			T::SminerRewardPool::on_unbalanced(paid);
+			let reserved = reserve.peek();
+			if !reserved.is_zero() {
+				T::SminerReserve::on_unbalanced(reserve);
+				Self::deposit_event(Event::<T>::SminerPayoutReserved {
+					era_index: active_era.index,
+					reserved,
+				});
+			}
 
 			// Clear disabled validators.
 			<DisabledValidators<T>>::kill();
 		}
 	}
 
+	/// The emission schedule in effect, the governance override or the `Config` default.
+	pub fn emission_schedule() -> EmissionSchedule<BalanceOf<T>> {
+		EmissionScheduleOverride::<T>::get().unwrap_or_else(|| {
+			EmissionSchedule::single_phase(
+				T::FIRST_YEAR_VALIDATOR_REWARDS,
+				T::FIRST_YEAR_SMINER_REWARDS,
+				T::REWARD_DECREASE_RATIO,
+				T::REWARD_DECREASE_YEARS.saturated_into(),
+			)
+		})
+	}
+
 	/// Compute rewards for validator and sminer for era.
 	pub fn rewards_in_era(active_era_index: EraIndex) -> (BalanceOf<T>, BalanceOf<T>) {
-		let mut year_num = active_era_index as u64 / T::ERAS_PER_YEAR;
-		let mut validator_rewards_this_year =
-			TryInto::<u128>::try_into(T::FIRST_YEAR_VALIDATOR_REWARDS).ok().unwrap();
-		let mut sminer_rewards_this_year =
-			TryInto::<u128>::try_into(T::FIRST_YEAR_SMINER_REWARDS).ok().unwrap();
-		
-		if year_num > T::REWARD_DECREASE_YEARS {
-			year_num = T::REWARD_DECREASE_YEARS;
-		}
-		for _ in 0..year_num {
-			validator_rewards_this_year = T::REWARD_DECREASE_RATIO * validator_rewards_this_year;
-			sminer_rewards_this_year = T::REWARD_DECREASE_RATIO * sminer_rewards_this_year;
-		}
+		Self::emission_schedule().era_payout(active_era_index, T::ERAS_PER_YEAR)
+	}
 
-		let validator_rewards_this_era = validator_rewards_this_year / T::ERAS_PER_YEAR as u128;
-		let sminer_rewards_this_era = sminer_rewards_this_year / T::ERAS_PER_YEAR as u128;
-		(
-			validator_rewards_this_era.try_into().ok().unwrap(),
-			sminer_rewards_this_era.try_into().ok().unwrap(),
-		)
+	/// The validator and sminer payouts of `count` eras from `from_era`, under the current
+	/// schedule.
+	///
+	/// Defaults to the active era. Capped at [`MAX_PROJECTED_ERAS`] eras.
+	pub fn projected_era_payouts(
+		from_era: Option<EraIndex>,
+		count: u32,
+	) -> Vec<(EraIndex, BalanceOf<T>, BalanceOf<T>)> {
+		let schedule = Self::emission_schedule();
+		let from_era = from_era.or_else(|| ActiveEra::<T>::get().map(|e| e.index)).unwrap_or_default();
+		(0..count.min(MAX_PROJECTED_ERAS))
+			.filter_map(|offset| from_era.checked_add(offset))
+			.map(|era| {
+				let (validator_payout, sminer_payout) = schedule.era_payout(era, T::ERAS_PER_YEAR);
+				(era, validator_payout, sminer_payout)
+			})
+			.collect()
 	}
 
 	/// Plan a new era.
@@ -48,11 +48,11 @@ mod impls;
 pub use impls::*;
 
 use crate::{
-	asset, slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf,
-	DisablingStrategy, EraPayout, EraRewardPoints, Exposure, ExposurePage, Forcing,
+	asset, emission::EmissionSchedule, slashing, weights::WeightInfo, AccountIdLookupOf,
+	ActiveEraInfo, BalanceOf, DisablingStrategy, EraPayout, EraRewardPoints, Exposure, ExposurePage, Forcing,
 	LedgerIntegrityState, MaxNominationsOf, NegativeImbalanceOf, Nominations, NominationsQuota,
-	PositiveImbalanceOf, RewardDestination, SessionInterface, StakingLedger, UnappliedSlash,
-	UnlockChunk, ValidatorPrefs,
+	PositiveImbalanceOf, RewardDestination, SessionInterface, SminerPayout, StakingLedger,
+	UnappliedSlash, UnlockChunk, ValidatorPrefs,
 };
 
 // The speculative number of spans are used as an input of the weight annotation of
@@ -112,6 +112,14 @@ pub mod pallet {
 		#[pallet::no_default]
 		type SminerRewardPool: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+		/// Decides how much of the scheduled sminer payout reaches the `SminerRewardPool`.
+		#[pallet::no_default]
+		type SminerPayout: SminerPayout<BalanceOf<Self>>;
+
+		/// Tokens minted for the part of the sminer payout that is not paid out.
+		#[pallet::no_default]
+		type SminerReserve: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
 		/// The staking balance.
 		#[pallet::no_default]
 		type Currency: LockableCurrency<
@@ -641,6 +649,12 @@ pub mod pallet {
 	#[pallet::getter(fn eras_validator_reward)]
 	pub type ErasValidatorReward<T: Config> = StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>>;
 
+	/// The emission schedule set by governance.
+	///
+	/// When not set, the schedule is derived from the reward constants of [`Config`].
+	#[pallet::storage]
+	pub type EmissionScheduleOverride<T: Config> = StorageValue<_, EmissionSchedule<BalanceOf<T>>>;
+
 	/// Rewards for the last [`Config::HistoryDepth`] eras.
 	/// If reward hasn't been set or has been removed then 0 reward is returned.
 	#[pallet::storage]
@@ -892,6 +906,10 @@ pub mod pallet {
 		ForceEra { mode: Forcing },
 		/// Report of a controller batch deprecation.
 		ControllerBatchDeprecated { failures: u32 },
+		/// The emission schedule was changed, `None` meaning back to the `Config` default.
+		EmissionScheduleSet { schedule: Option<EmissionSchedule<BalanceOf<T>>> },
+		/// Part of the era sminer payout was routed to the reward reserve.
+		SminerPayoutReserved { era_index: EraIndex, reserved: BalanceOf<T> },
 	}
 
 	#[pallet::error]
@@ -963,6 +981,8 @@ pub mod pallet {
 		NotEnoughFunds,
 		/// Operation not allowed for virtual stakers.
 		VirtualStakerNotAllowed,
+		/// The emission schedule does not start at era 0, is unordered or decays for too long.
+		InvalidEmissionSchedule,
 	}
 
 	#[pallet::hooks]
@@ -2175,6 +2195,31 @@ pub mod pallet {
 			);
 			Ok(())
 		}
+
+		/// Replace the emission schedule, or fall back to the `Config` default with `None`.
+		///
+		/// Takes effect from the payout of the active era.
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::set_emission_schedule())]
+		pub fn set_emission_schedule(
+			origin: OriginFor<T>,
+			schedule: Option<EmissionSchedule<BalanceOf<T>>>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			match &schedule {
+				Some(schedule) => {
+					ensure!(schedule.is_valid(), Error::<T>::InvalidEmissionSchedule);
+					EmissionScheduleOverride::<T>::put(schedule);
+				},
+				None => EmissionScheduleOverride::<T>::kill(),
+			}
+
+			Self::deposit_event(Event::<T>::EmissionScheduleSet { schedule });
+			Ok(())
+		}
 	}
 }
 
@@ -8322,3 +8322,106 @@ mod byzantine_threshold_disabling_strategy {
 		});
 	}
 }
+
+mod sminer_payout {
+	use super::*;
+
+	#[test]
+	fn end_era_splits_the_scheduled_sminer_payout() {
+		SminerPayoutShare::set(Perbill::from_percent(30));
+		ExtBuilder::default().build_and_execute(|| {
+			let issuance = Balances::total_issuance();
+			let (_, scheduled) = Staking::rewards_in_era(0);
+			let paid = Perbill::from_percent(30) * scheduled;
+			assert!(paid > 0 && paid < scheduled);
+
+			start_active_era(1);
+
+			// Validator rewards are minted when claimed, so the era only issued the sminer payout.
+			assert_eq!(Balances::total_issuance(), issuance + scheduled);
+			assert_eq!(Balances::free_balance(SMINER_REWARD_POOL), paid);
+			assert_eq!(Balances::free_balance(SMINER_RESERVE), scheduled - paid);
+			assert!(staking_events()
+				.contains(&Event::SminerPayoutReserved { era_index: 0, reserved: scheduled - paid }));
+		});
+	}
+
+	#[test]
+	fn end_era_reserves_nothing_when_the_payout_is_paid_in_full() {
+		ExtBuilder::default().build_and_execute(|| {
+			let issuance = Balances::total_issuance();
+			let (_, scheduled) = Staking::rewards_in_era(0);
+
+			start_active_era(1);
+
+			assert_eq!(Balances::total_issuance(), issuance + scheduled);
+			assert_eq!(Balances::free_balance(SMINER_REWARD_POOL), scheduled);
+			assert_eq!(Balances::free_balance(SMINER_RESERVE), 0);
+			assert!(!staking_events().iter().any(|e| matches!(e, Event::SminerPayoutReserved { .. })));
+		});
+	}
+}
+
+mod emission_schedule {
+	use super::*;
+	use crate::emission::{EmissionPhase, EmissionSchedule, MAX_DECAY_YEARS};
+
+	fn schedule(phases: Vec<(EraIndex, u32)>) -> EmissionSchedule<Balance> {
+		let phases = phases
+			.into_iter()
+			.map(|(start_era, decay_years)| EmissionPhase {
+				start_era,
+				validator_emission: 1_000,
+				sminer_emission: 2_000,
+				yearly_decay: Perbill::from_percent(10),
+				decay_years,
+			})
+			.collect::<Vec<_>>();
+		EmissionSchedule { phases: BoundedVec::truncate_from(phases), validator_floor: 100, sminer_floor: 200 }
+	}
+
+	#[test]
+	fn set_emission_schedule_requires_the_admin_origin() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_noop!(
+				Staking::set_emission_schedule(RuntimeOrigin::signed(2), Some(schedule(vec![(0, 5)]))),
+				BadOrigin
+			);
+			assert_noop!(Staking::set_emission_schedule(RuntimeOrigin::signed(2), None), BadOrigin);
+		});
+	}
+
+	#[test]
+	fn set_emission_schedule_rejects_invalid_schedules() {
+		ExtBuilder::default().build_and_execute(|| {
+			for invalid in [
+				schedule(vec![]),
+				schedule(vec![(1, 5)]),
+				schedule(vec![(0, 5), (10, 5), (10, 5)]),
+				schedule(vec![(0, MAX_DECAY_YEARS + 1)]),
+			] {
+				assert_noop!(
+					Staking::set_emission_schedule(RuntimeOrigin::root(), Some(invalid)),
+					Error::<Test>::InvalidEmissionSchedule
+				);
+			}
+		});
+	}
+
+	#[test]
+	fn set_emission_schedule_stores_and_clears_the_override() {
+		ExtBuilder::default().build_and_execute(|| {
+			let new_schedule = schedule(vec![(0, 5), (100, 10)]);
+			assert_ok!(Staking::set_emission_schedule(RuntimeOrigin::signed(1), Some(new_schedule.clone())));
+			assert_eq!(EmissionScheduleOverride::<Test>::get(), Some(new_schedule.clone()));
+			assert_eq!(
+				*staking_events().last().unwrap(),
+				Event::EmissionScheduleSet { schedule: Some(new_schedule) }
+			);
+
+			assert_ok!(Staking::set_emission_schedule(RuntimeOrigin::root(), None));
+			assert_eq!(EmissionScheduleOverride::<Test>::get(), None);
+			assert_eq!(*staking_events().last().unwrap(), Event::EmissionScheduleSet { schedule: None });
+		});
+	}
+}
@@ -83,6 +83,7 @@ pub trait WeightInfo {
 	fn force_apply_min_commission() -> Weight;
 	fn set_min_commission() -> Weight;
 	fn restore_ledger() -> Weight;
+	fn set_emission_schedule() -> Weight;
 }
 
 /// Weights for `pallet_staking` using the Substrate node and recommended hardware.
@@ -834,6 +835,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(5_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: `Staking::EmissionScheduleOverride` (r:0 w:1)
+	/// Proof: `Staking::EmissionScheduleOverride` (`max_values`: Some(1), `max_size`: Some(1157), added: 1652, mode: `MaxEncodedLen`)
+	/// Not benchmarked yet: estimated from `set_min_commission` plus encoding a full schedule.
+	/// Regenerate with the `set_emission_schedule` benchmark.
+	fn set_emission_schedule() -> Weight {
+		Weight::from_parts(6_480_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -1584,4 +1593,12 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(5_u64))
 			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
+	/// Storage: `Staking::EmissionScheduleOverride` (r:0 w:1)
+	/// Proof: `Staking::EmissionScheduleOverride` (`max_values`: Some(1), `max_size`: Some(1157), added: 1652, mode: `MaxEncodedLen`)
+	/// Not benchmarked yet: estimated from `set_min_commission` plus encoding a full schedule.
+	/// Regenerate with the `set_emission_schedule` benchmark.
+	fn set_emission_schedule() -> Weight {
+		Weight::from_parts(6_480_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -1,7 +1,10 @@
 use super::*;
+use sp_runtime::{helpers_128bit::multiply_by_rational_with_rounding, traits::Saturating, Rounding};
 
 pub trait StorageHandle<AccountId> {
     fn check_territry_owner(acc: &AccountId, name: &TerrName) -> DispatchResult;
+    /// Whether `acc` owns any territory.
+    fn has_territory(acc: &AccountId) -> bool;
     fn check_expired(acc: &AccountId, name: &TerrName) -> bool;
     fn add_territory_used_space(acc: &AccountId, name: &TerrName, size: u128) -> DispatchResult;
     fn sub_territory_used_space(acc: &AccountId, name: &TerrName, size: u128) -> DispatchResult;
@@ -17,6 +20,8 @@ pub trait StorageHandle<AccountId> {
     fn unlock_and_used_user_space(acc: &AccountId, name: &TerrName, needed_space: u128) -> DispatchResult;
     fn get_user_avail_space(acc: &AccountId, name: &TerrName) -> Result<u128, DispatchError>;
     fn frozen_task() -> (Weight, Vec<(AccountId, TerrName)>);
+    /// The price of `size` bytes of the territory for the rest of its lifetime.
+    fn remaining_value(acc: &AccountId, name: &TerrName, size: u128) -> Result<u128, DispatchError>;
 }
 
 impl<T: Config> StorageHandle<T::AccountId> for Pallet<T> {
@@ -26,6 +31,10 @@ impl<T: Config> StorageHandle<T::AccountId> for Pallet<T> {
         Ok(())
     }
 
+    fn has_territory(acc: &T::AccountId) -> bool {
+        <Territory<T>>::iter_prefix_values(acc).next().is_some()
+    }
+
     fn check_expired(acc: &T::AccountId, name: &TerrName) -> bool {
         let territory = <Territory<T>>::try_get(acc, name).map_err(|_| Error::<T>::NotHaveTerritory);
 
@@ -149,6 +158,22 @@ impl<T: Config> StorageHandle<T::AccountId> for Pallet<T> {
         Self::frozen_task()
     }
 
+    fn remaining_value(acc: &T::AccountId, name: &TerrName, size: u128) -> Result<u128, DispatchError> {
+        let territory = <Territory<T>>::try_get(acc, name).map_err(|_e| Error::<T>::NotHaveTerritory)?;
+        let now = <frame_system::Pallet<T>>::block_number();
+        let remaining_blocks: u128 = territory.deadline.saturating_sub(now).saturated_into();
+        let one_day: u128 = <T as pallet::Config>::OneDay::get().saturated_into();
+        let unit_price: u128 = <UnitPrice<T>>::get().ok_or(Error::<T>::BugInvalid)?.saturated_into();
+
+        // The unit price is the price of one gib for 30 days.
+        let gib_value = multiply_by_rational_with_rounding(unit_price, remaining_blocks, one_day.saturating_mul(30), Rounding::Down)
+            .ok_or(Error::<T>::Overflow)?;
+        let value = multiply_by_rational_with_rounding(gib_value, size, G_BYTE, Rounding::Down)
+            .ok_or(Error::<T>::Overflow)?;
+
+        Ok(value)
+    }
+
     fn get_total_idle_space() -> u128 {
         <TotalIdleSpace<T>>::get()
     }
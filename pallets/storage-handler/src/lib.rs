@@ -28,6 +28,7 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use cp_cess_common::*;
 use pallet_cess_treasury::TreasuryHandle;
+use pallet_reservoir::{ReservoirGate, UseType};
 use sp_core::H256;
 
 pub mod weights;
@@ -37,7 +38,7 @@ use weights::WeightInfo;
 pub mod benchmarking;
 
 mod types;
-use types::*;
+pub use types::*;
 
 pub mod impls;
 pub use impls::*;
@@ -109,6 +110,8 @@ pub mod pallet {
 		type FrozenDays: Get<BlockNumberFor<Self>> + Clone + Eq + PartialEq;
 
         type CessTreasuryHandle: TreasuryHandle<AccountOf<Self>, BalanceOf<Self>>;
+        /// Lends funds for territory purchases.
+        type ReservoirGate: ReservoirGate<AccountOf<Self>, BalanceOf<Self>>;
 
         type MyRandomness: Randomness<Option<Self::Hash>, BlockNumberFor<Self>>;
 
@@ -387,12 +390,13 @@ pub mod pallet {
 			Self::add_purchased_space(space)?;
             let price = Self::calculate_price(gib_count, days)?;
             
+			let own_price = Self::draw_territory_loan(&sender, price)?;
 			ensure!(
-				<T as pallet::Config>::Currency::can_slash(&sender, price.clone()),
+				<T as pallet::Config>::Currency::can_slash(&sender, own_price),
 				Error::<T>::InsufficientBalance
 			);
 
-            T::CessTreasuryHandle::send_to_sid(sender.clone(), price)?;
+            T::CessTreasuryHandle::send_to_sid(sender.clone(), own_price)?;
 
 			Self::deposit_event(Event::<T>::MintTerritory {
                 token: token, 
@@ -438,16 +442,17 @@ pub mod pallet {
 			}
 			//Calculate the final price difference to be made up.
             let price = Self::calculate_price(gib_count, remain_day)?;
+			let own_price = Self::draw_territory_loan(&sender, price)?;
 			//Judge whether the balance is sufficient
 			ensure!(
-				<T as pallet::Config>::Currency::can_slash(&sender, price.clone()),
+				<T as pallet::Config>::Currency::can_slash(&sender, own_price),
 				Error::<T>::InsufficientBalance
 			);
 
 			Self::add_purchased_space(space)?;
 			Self::update_territory_space(sender.clone(), territory_name.clone(), space)?;
 
-            T::CessTreasuryHandle::send_to_sid(sender.clone(), price.clone())?;
+            T::CessTreasuryHandle::send_to_sid(sender.clone(), own_price)?;
 
 			Self::deposit_event(Event::<T>::ExpansionTerritory {
 				name: territory_name,
@@ -478,12 +483,13 @@ pub mod pallet {
 			let gib_count = cur_owned_space.total_space.checked_div(G_BYTE).ok_or(Error::<T>::Overflow)?;
             let price = Self::calculate_price(gib_count as u32, days)?;
 
+			let own_price = Self::draw_territory_loan(&sender, price)?;
 			ensure!(
-				<T as pallet::Config>::Currency::can_slash(&sender, price.clone()),
+				<T as pallet::Config>::Currency::can_slash(&sender, own_price),
 				Error::<T>::InsufficientBalance
 			);
 
-			T::CessTreasuryHandle::send_to_sid(sender.clone(), price.clone())?;
+			T::CessTreasuryHandle::send_to_sid(sender.clone(), own_price)?;
 
 			Self::update_territory_days(sender.clone(), territory_name.clone(), days)?;
 			Self::deposit_event(Event::<T>::RenewalTerritory {
@@ -522,12 +528,13 @@ pub mod pallet {
                 .checked_mul(&gib_count.saturated_into())
                 .ok_or(Error::<T>::Overflow)?;
 
+            let own_price = Self::draw_territory_loan(&sender, price)?;
             ensure!(
-                <T as pallet::Config>::Currency::can_slash(&sender, price.clone()),
+                <T as pallet::Config>::Currency::can_slash(&sender, own_price),
                 Error::<T>::InsufficientBalance
             );
 
-            T::CessTreasuryHandle::send_to_sid(sender.clone(), price.clone())?;
+            T::CessTreasuryHandle::send_to_sid(sender.clone(), own_price)?;
 
             Self::add_purchased_space(territory.total_space)?;
             Self::initial_territory(sender.clone(), territory_name.clone(), days)?;
@@ -572,12 +579,13 @@ pub mod pallet {
                 .checked_mul(&gib_count.saturated_into())
                 .ok_or(Error::<T>::Overflow)?;
 
+            let own_price = Self::draw_territory_loan(&signer, price)?;
             ensure!(
-                <T as pallet::Config>::Currency::can_slash(&signer, price.clone()),
+                <T as pallet::Config>::Currency::can_slash(&signer, own_price),
                 Error::<T>::InsufficientBalance
             );
 
-            T::CessTreasuryHandle::send_to_sid(signer.clone(), price.clone())?;
+            T::CessTreasuryHandle::send_to_sid(signer.clone(), own_price)?;
 
             Self::add_purchased_space(territory.total_space)?;
             Self::initial_territory(sender.clone(), territory_name.clone(), days)?;
@@ -694,12 +702,18 @@ pub mod pallet {
             let consignment = <Consignment<T>>::try_get(&token).map_err(|_| Error::<T>::NonExistentConsignment)?;
             let buyer = consignment.buyers.ok_or(Error::<T>::Unexpected)?;
             ensure!(consignment.locked, Error::<T>::ConsignmentUnLocked);
+            let (holder, name) = <TerritoryKey<T>>::try_get(&token).map_err(|_| Error::<T>::Unexpected)?;
+            // The territory purchase loan of the buyer pays the seller directly.
+            let borrowed = T::ReservoirGate::spendable(&buyer, UseType::TerritoryPurchase).min(consignment.price);
+            if borrowed > 0u32.saturated_into() {
+                T::ReservoirGate::spend(&buyer, UseType::TerritoryPurchase, borrowed, &holder)?;
+            }
+            let own_price = consignment.price.checked_sub(&borrowed).ok_or(Error::<T>::Overflow)?;
             ensure!(
-                <T as pallet::Config>::Currency::can_slash(&buyer, consignment.price),
+                <T as pallet::Config>::Currency::can_slash(&buyer, own_price),
                 Error::<T>::InsufficientBalance
             );
 
-            let (holder, name) = <TerritoryKey<T>>::try_get(&token).map_err(|_| Error::<T>::Unexpected)?;
             let mut territory = <Territory<T>>::try_get(&holder, &name).map_err(|_| Error::<T>::Unexpected)?;
             ensure!(territory.state == TerritoryState::OnConsignment, Error::<T>::Unexpected);
 
@@ -710,7 +724,7 @@ pub mod pallet {
             <TerritoryKey<T>>::insert(&token, (buyer.clone(), territory_name));
             <Consignment<T>>::remove(&token);
             <T as pallet::Config>::Currency::unreserve(&buyer, consignment.price);
-            <T as pallet::Config>::Currency::transfer(&buyer, &holder, consignment.price, KeepAlive)?;
+            <T as pallet::Config>::Currency::transfer(&buyer, &holder, own_price, KeepAlive)?;
 
             Self::deposit_event(Event::<T>::ExecConsignment {
                 buyer: buyer,
@@ -998,7 +1012,8 @@ pub mod pallet {
                 },
             };
 
-            T::CessTreasuryHandle::send_to_sid(sender, order.pay)?;
+            let own_price = Self::draw_territory_loan(&sender, order.pay)?;
+            T::CessTreasuryHandle::send_to_sid(sender, own_price)?;
             Self::deposit_event(Event::<T>::PaidOrder { order_hash: order_id });
 
             Ok(())
@@ -1045,6 +1060,19 @@ pub mod pallet {
 }
 
 impl<T: Config> Pallet<T> {
+    // Pays what the territory purchase loan of `acc` can cover of `price` from the reservoir
+    // straight to the space treasury, so the borrowed funds never pass through `acc`. Returns the
+    // rest of `price`, which `acc` pays itself.
+    fn draw_territory_loan(acc: &AccountOf<T>, price: BalanceOf<T>) -> Result<BalanceOf<T>, DispatchError> {
+        let borrowed = T::ReservoirGate::spendable(acc, UseType::TerritoryPurchase).min(price);
+        if borrowed > 0u32.saturated_into() {
+            T::ReservoirGate::charge(acc, UseType::TerritoryPurchase, borrowed)?;
+            T::CessTreasuryHandle::send_to_sid(T::ReservoirGate::get_reservoir_acc(), borrowed)?;
+        }
+
+        Ok(price.checked_sub(&borrowed).ok_or(Error::<T>::Overflow)?)
+    }
+
     fn calculate_price(gib_count: u32, days: u32) -> Result<BalanceOf<T>, DispatchError> {
         let unit_price: u128 = <UnitPrice<T>>::get().unwrap().try_into().map_err(|_| Error::<T>::Overflow)?;
 
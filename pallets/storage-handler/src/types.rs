@@ -24,14 +24,14 @@ pub enum OrderType {
 #[scale_info(skip_type_params(T))]
 #[codec(mel_bound())]
 pub struct TerritoryInfo<T: Config> {
-	pub(super) token: TokenId,
-    pub(super) total_space: u128,
-	pub(super) used_space: u128,
-	pub(super) locked_space: u128,
-	pub(super) remaining_space: u128,
-	pub(super) start: BlockNumberFor<T>,
-	pub(super) deadline: BlockNumberFor<T>,
-	pub(super) state: TerritoryState,
+	pub token: TokenId,
+    pub total_space: u128,
+	pub used_space: u128,
+	pub locked_space: u128,
+	pub remaining_space: u128,
+	pub start: BlockNumberFor<T>,
+	pub deadline: BlockNumberFor<T>,
+	pub state: TerritoryState,
 }
 
 #[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
@@ -1,6 +1,11 @@
 use crate::*;
 
 impl<T: Config> Pallet<T> {
+	/// The stash that bonds the TEE worker stake `acc` borrows from the reservoir.
+	pub fn reservoir_stash(acc: &AccountOf<T>) -> AccountOf<T> {
+		T::TeeWorkerPalletId::get().into_sub_account_truncating(acc)
+	}
+
 	pub fn check_time_unix(signing_time: &u64) -> bool {
 		let expiration = 4 * 60 * 60 * 1000; // 4 hours
 		let now = T::UnixTime::now().as_millis().saturated_into::<u64>();
@@ -11,23 +16,143 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	pub fn clear_mission(now: BlockNumberFor<T>) -> Weight {
-		let mut weight: Weight = Weight::zero();
+	/// Visit a bounded batch of workers and move them through their lifecycle.
+	///
+	/// Workers idle for longer than `AtLeastWorkBlock` turn dormant and stop receiving work. Dormant
+	/// workers that work again are reactivated, the others are removed once `DormantGracePeriod`
+	/// has passed. The sweep resumes from [`LifecycleCursor`] on the next call.
+	pub fn process_worker_lifecycle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+		let db = T::DbWeight::get();
+		let mut weight: Weight = db.reads_writes(1, 1);
+		// Worst case of a single visit: removing the worker.
+		let per_worker = db.reads_writes(4, 9);
 
-		let least = T::AtLeastWorkBlock::get();
+		let by_weight = remaining_weight.saturating_sub(weight).ref_time() / per_worker.ref_time().max(1);
+		let limit = (T::MaxLifecycleChecksPerBlock::get() as u64).min(by_weight) as usize;
+		if limit == 0 {
+			return Weight::zero();
+		}
 
-		for (pubkey, last_block) in LastWork::<T>::iter() {
-			weight = weight.saturating_add(T::DbWeight::get().reads(1));
-			if last_block.saturating_add(least) < now {
-				if let Ok(temp_weight) = Self::execute_exit(pubkey.clone()) {
-					weight.saturating_add(temp_weight);
-					LastWork::<T>::remove(pubkey.clone());
+		let cursor = LifecycleCursor::<T>::get();
+		let mut iter = if cursor.is_empty() {
+			Workers::<T>::iter_keys()
+		} else {
+			Workers::<T>::iter_keys_from(cursor.to_vec())
+		};
+		let visited: Vec<WorkerPublicKey> = iter.by_ref().take(limit).collect();
+		if visited.len() < limit {
+			LifecycleCursor::<T>::kill();
+		} else {
+			match iter.last_raw_key().to_vec().try_into() {
+				Ok(cursor) => LifecycleCursor::<T>::put::<BoundedVec<u8, ConstU32<128>>>(cursor),
+				Err(_) => {
+					log::warn!("tee-worker: worker storage key exceeds the lifecycle cursor bound");
+					LifecycleCursor::<T>::kill();
+				},
+			}
+		}
+
+		for pubkey in visited {
+			weight = weight.saturating_add(Self::update_worker_lifecycle(pubkey, now));
+		}
+
+		weight
+	}
+
+	fn update_worker_lifecycle(pubkey: WorkerPublicKey, now: BlockNumberFor<T>) -> Weight {
+		let db = T::DbWeight::get();
+		// Workers that never reported any work get one period from now to send a heartbeat.
+		let Ok(last_work) = LastWork::<T>::try_get(&pubkey) else {
+			LastWork::<T>::insert(&pubkey, now);
+			return db.reads_writes(1, 1);
+		};
+		let dormant_since = DormantWorkers::<T>::get(&pubkey);
+		let mut weight = db.reads(2);
+
+		match LifecycleAction::decide(
+			now,
+			last_work,
+			dormant_since,
+			T::AtLeastWorkBlock::get(),
+			T::DormantGracePeriod::get(),
+		) {
+			LifecycleAction::Keep => {},
+			LifecycleAction::Dormant => {
+				DormantWorkers::<T>::insert(&pubkey, now);
+				ValidationTypeList::<T>::mutate(|puk_list| puk_list.retain(|g| *g != pubkey));
+				weight = weight.saturating_add(db.reads_writes(1, 2));
+				Self::deposit_event(Event::<T>::WorkerDormant { pubkey, last_work });
+			},
+			LifecycleAction::Reactivate => {
+				weight = weight.saturating_add(Self::reactivate_worker(&pubkey));
+			},
+			LifecycleAction::Remove => match Self::execute_exit(pubkey) {
+				Ok(exit_weight) => {
+					weight = weight.saturating_add(exit_weight);
 					Self::deposit_event(Event::<T>::ClearInvalidTee { pubkey });
-				}
+				},
+				Err(e) => log::warn!("tee-worker: failed to remove dormant worker {:?}: {:?}", pubkey, e),
+			},
+		}
+
+		weight
+	}
+
+	/// Record that a worker did some work, waking it up if it was dormant.
+	pub fn refresh_last_work(pubkey: &WorkerPublicKey, now: BlockNumberFor<T>) -> Weight {
+		LastWork::<T>::insert(pubkey, now);
+		let mut weight = T::DbWeight::get().reads_writes(1, 1);
+		if DormantWorkers::<T>::contains_key(pubkey) {
+			weight = weight.saturating_add(Self::reactivate_worker(pubkey));
+		}
+		weight
+	}
+
+	fn reactivate_worker(pubkey: &WorkerPublicKey) -> Weight {
+		let db = T::DbWeight::get();
+		DormantWorkers::<T>::remove(pubkey);
+		let mut weight = db.writes(1);
+
+		let Ok(worker) = Workers::<T>::try_get(pubkey) else {
+			return weight.saturating_add(db.reads(1));
+		};
+		if worker.role == WorkerRole::Full || worker.role == WorkerRole::Verifier {
+			let pushed = ValidationTypeList::<T>::mutate(|puk_list| {
+				puk_list.contains(pubkey) || puk_list.try_push(*pubkey).is_ok()
+			});
+			if !pushed {
+				log::warn!("tee-worker: validation list is full, {:?} stays out of verification", pubkey);
 			}
 		}
+		weight = weight.saturating_add(db.reads_writes(2, 1));
 
-		return weight;
+		Self::deposit_event(Event::<T>::WorkerReactivated { pubkey: *pubkey });
+		weight
+	}
+
+	/// Record the typed endpoint of a worker from the url it registered with.
+	///
+	/// A url that cannot be parsed leaves the worker without a typed endpoint until it calls
+	/// `update_endpoint`.
+	pub fn register_endpoint(pubkey: &WorkerPublicKey, url: &str, role: &WorkerRole) {
+		match WorkerEndpoint::parse(url, WorkerService::defaults_for(role)) {
+			Ok(endpoint) => WorkerEndpoints::<T>::insert(pubkey, endpoint),
+			Err(e) => {
+				log::warn!("tee-worker: worker {:?} registered an invalid endpoint: {}", pubkey, e);
+				WorkerEndpoints::<T>::remove(pubkey);
+			},
+		}
+	}
+
+	pub fn check_endpoint(endpoint: &WorkerEndpoint, role: &WorkerRole) -> DispatchResult {
+		ensure!(!endpoint.host.is_empty() && endpoint.host.len() <= 253, Error::<T>::InvalidEndpoint);
+		ensure!(endpoint.port != 0, Error::<T>::InvalidEndpoint);
+		ensure!(!endpoint.services.is_empty(), Error::<T>::InvalidEndpoint);
+		for (index, service) in endpoint.services.iter().enumerate() {
+			ensure!(!endpoint.services[..index].contains(service), Error::<T>::InvalidEndpoint);
+			ensure!(service.allowed_for(role), Error::<T>::ServiceNotAllowedForRole);
+		}
+		Ok(())
 	}
 
 	pub fn execute_exit(pbk: WorkerPublicKey) -> Result<Weight, DispatchError> {
@@ -47,6 +172,18 @@ impl<T: Config> Pallet<T> {
 		Endpoints::<T>::remove(&pbk);
 		weight = weight.saturating_add(T::DbWeight::get().writes(1));
 
+		WorkerMeasurement::<T>::remove(&pbk);
+		UnmeasuredWorkers::<T>::remove(&pbk);
+		weight = weight.saturating_add(T::DbWeight::get().writes(2));
+
+		WorkerEndpoints::<T>::remove(&pbk);
+		EndpointSigningTimes::<T>::remove(&pbk);
+		weight = weight.saturating_add(T::DbWeight::get().writes(2));
+
+		LastWork::<T>::remove(&pbk);
+		DormantWorkers::<T>::remove(&pbk);
+		weight = weight.saturating_add(T::DbWeight::get().writes(2));
+
 		ValidationTypeList::<T>::mutate(|puk_list| -> DispatchResult {
 			puk_list.retain(|g| *g != pbk);
 			Ok(())
@@ -56,6 +193,145 @@ impl<T: Config> Pallet<T> {
 		Ok(weight)
 	}
 
+	/// Advance the ceseal rollout in progress, if any.
+	pub fn process_ceseal_rollout(now: BlockNumberFor<T>) -> Weight {
+		let mut weight: Weight = T::DbWeight::get().reads(1);
+
+		let Some(mut rollout) = CurrentCesealRollout::<T>::get() else {
+			return weight;
+		};
+
+		match rollout.stage.clone() {
+			RolloutStage::Scheduled => {
+				if now < rollout.activation_block {
+					return weight;
+				}
+				CesealBinAllowList::<T>::mutate(|allowlist| {
+					if !allowlist.contains(&rollout.new_hash) {
+						allowlist.push(rollout.new_hash);
+						CesealBinAddedAt::<T>::insert(&rollout.new_hash, now);
+					}
+				});
+				let (major, minor, patch) = rollout.minimum_version;
+				MinimumCesealVersion::<T>::put(rollout.minimum_version);
+				rollout.stage = RolloutStage::Activated;
+				CurrentCesealRollout::<T>::put(&rollout);
+				weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 4));
+
+				Self::deposit_event(Event::<T>::CesealRolloutActivated {
+					new_hash: rollout.new_hash,
+					minimum_version: rollout.minimum_version,
+				});
+				Self::deposit_event(Event::<T>::MinimumCesealVersionChangedTo(major, minor, patch));
+			},
+			RolloutStage::Activated => {
+				if now < rollout.deprecation_block {
+					return weight;
+				}
+				if let Some(previous_hash) = rollout.previous_hash {
+					CesealBinAllowList::<T>::mutate(|allowlist| allowlist.retain(|h| *h != previous_hash));
+					CesealBinAddedAt::<T>::remove(&previous_hash);
+					weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+				}
+				rollout.stage = RolloutStage::Expelling(Default::default());
+				CurrentCesealRollout::<T>::put(&rollout);
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+
+				Self::deposit_event(Event::<T>::CesealRolloutDeprecated { previous_hash: rollout.previous_hash });
+			},
+			RolloutStage::Expelling(cursor) => {
+				let limit = T::MaxWorkerExpelPerBlock::get() as usize;
+				let mut iter = if cursor.is_empty() {
+					Workers::<T>::iter_keys()
+				} else {
+					Workers::<T>::iter_keys_from(cursor.to_vec())
+				};
+				let visited: Vec<WorkerPublicKey> = iter.by_ref().take(limit).collect();
+				let last_key = iter.last_raw_key().to_vec();
+				let finished = visited.len() < limit;
+				weight = weight.saturating_add(T::DbWeight::get().reads(visited.len() as u64));
+
+				for pubkey in visited {
+					let measurement = WorkerMeasurement::<T>::get(&pubkey);
+					weight = weight.saturating_add(T::DbWeight::get().reads(1));
+					let outdated = match measurement {
+						Some(measurement) => Some(measurement) == rollout.previous_hash,
+						None => Self::unmeasured_grace_expired(&pubkey, now, &mut weight),
+					};
+					if !outdated {
+						continue;
+					}
+					match Self::execute_exit(pubkey) {
+						Ok(exit_weight) => {
+							weight = weight.saturating_add(exit_weight);
+							RolloutExpelledCount::<T>::mutate(|count| *count = count.saturating_add(1));
+							weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+							Self::deposit_event(Event::<T>::WorkerExpelled { pubkey, measurement });
+						},
+						Err(e) => log::warn!("tee-worker: failed to expel {:?}: {:?}", pubkey, e),
+					}
+				}
+
+				if finished {
+					if let Some(expel_after) = RolloutGraceDeadline::<T>::take() {
+						// Keep the rollout until the workers spared by this sweep can be expelled.
+						rollout.stage = RolloutStage::AwaitingGrace(expel_after);
+						CurrentCesealRollout::<T>::put(&rollout);
+						weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 2));
+						return weight;
+					}
+					let expelled = RolloutExpelledCount::<T>::take();
+					CurrentCesealRollout::<T>::kill();
+					weight = weight.saturating_add(T::DbWeight::get().writes(2));
+					Self::deposit_event(Event::<T>::CesealRolloutCompleted { new_hash: rollout.new_hash, expelled });
+				} else {
+					match last_key.try_into() {
+						Ok(cursor) => rollout.stage = RolloutStage::Expelling(cursor),
+						Err(_) => log::warn!("tee-worker: worker storage key exceeds the rollout cursor bound"),
+					}
+					CurrentCesealRollout::<T>::put(&rollout);
+					weight = weight.saturating_add(T::DbWeight::get().writes(1));
+				}
+			},
+			RolloutStage::AwaitingGrace(expel_after) => {
+				if now < expel_after {
+					return weight;
+				}
+				rollout.stage = RolloutStage::Expelling(Default::default());
+				CurrentCesealRollout::<T>::put(&rollout);
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			},
+		}
+
+		weight
+	}
+
+	// Workers without a recorded measurement registered before measurements were tracked, so
+	// the ceseal they run is unknown. The first sweep to find one starts its grace period, to
+	// give it time to register again; the rollout sweeps again once the period is over and
+	// expels it then.
+	fn unmeasured_grace_expired(pubkey: &WorkerPublicKey, now: BlockNumberFor<T>, weight: &mut Weight) -> bool {
+		*weight = weight.saturating_add(T::DbWeight::get().reads(1));
+		let expel_after = match UnmeasuredWorkers::<T>::get(pubkey) {
+			Some(found_at) => found_at.saturating_add(T::UnmeasuredWorkerGracePeriod::get()),
+			None => {
+				let expel_after = now.saturating_add(T::UnmeasuredWorkerGracePeriod::get());
+				UnmeasuredWorkers::<T>::insert(pubkey, now);
+				*weight = weight.saturating_add(T::DbWeight::get().writes(1));
+				Self::deposit_event(Event::<T>::WorkerMeasurementMissing { pubkey: *pubkey, expel_after });
+				expel_after
+			},
+		};
+		if now >= expel_after {
+			return true;
+		}
+		RolloutGraceDeadline::<T>::mutate(|deadline| {
+			*deadline = Some(deadline.map_or(expel_after, |deadline| deadline.max(expel_after)))
+		});
+		*weight = weight.saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		false
+	}
+
 	pub fn clean_expired_master_key_postation(block_number: BlockNumberFor<T>) -> Weight {
 		let mut weight: Weight = Weight::zero();
 		for (key, value) in MasterKeyPostation::<T>::iter() {
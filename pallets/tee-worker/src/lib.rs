@@ -1,10 +1,12 @@
 //! # Tee Worker Module
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(test)]
+mod mock;
+
 #[cfg(test)]
 mod tests;
 
-mod mock;
 mod types;
 pub use types::*;
 
@@ -12,19 +14,20 @@ pub use types::*;
 pub mod benchmarking;
 
 use alloc::string::{String, ToString};
-use ces_types::{MasterPublicKey, WorkerPublicKey, WorkerRole};
+use ces_types::{MasterPublicKey, WorkerEndpoint, WorkerPublicKey, WorkerRole, WorkerService};
 use codec::{Decode, Encode, MaxEncodedLen};
 use cp_cess_common::*;
 use cp_scheduler_credit::SchedulerCreditCounter;
 use frame_support::{
 	dispatch::DispatchResult,
 	pallet_prelude::*,
-	traits::{Get, Randomness, ReservableCurrency, StorageVersion, UnixTime},
+	traits::{Currency, Get, Randomness, ReservableCurrency, StorageVersion, UnixTime},
 	BoundedVec, PalletId,
 };
 use frame_system::{ensure_signed, pallet_prelude::*};
+use pallet_reservoir::{ReservoirGate, UseType};
 use scale_info::TypeInfo;
-use sp_runtime::{DispatchError, RuntimeDebug, SaturatedConversion, Saturating};
+use sp_runtime::{traits::AccountIdConversion, DispatchError, RuntimeDebug, SaturatedConversion, Saturating};
 use sp_std::{convert::TryInto, prelude::*};
 
 pub use pallet::*;
@@ -42,6 +45,8 @@ use sp_core::hashing;
 use sp_io::hashing;
 
 type AccountOf<T> = <T as frame_system::Config>::AccountId;
+type BalanceOf<T> =
+	<<T as pallet::Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 type SHA256 = [u8; 32];
 
@@ -55,8 +60,8 @@ pub mod pallet {
 
 	use ces_types::{
 		attestation::{self, Error as AttestationError},
-		AttestationProvider, EcdhPublicKey, MasterKeyApplyPayload, MasterKeyDistributePayload, MasterKeyLaunchPayload,
-		WorkerRegistrationInfo,
+		AttestationProvider, EcdhPublicKey, EndpointUpdatePayload, HeartbeatPayload, MasterKeyApplyPayload,
+		MasterKeyDistributePayload, MasterKeyLaunchPayload, WorkerRegistrationInfo,
 	};
 
 	// Re-export
@@ -89,6 +94,26 @@ pub mod pallet {
 		type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
 		type Randomness: Randomness<Self::Hash, BlockNumberFor<Self>>;
+
+		/// The maximum number of workers checked for expulsion per block during a ceseal rollout.
+		#[pallet::constant]
+		type MaxWorkerExpelPerBlock: Get<u32>;
+
+		/// How long a worker registered before measurements were tracked may stay after a
+		/// rollout sweep found it without a measurement.
+		#[pallet::constant]
+		type UnmeasuredWorkerGracePeriod: Get<BlockNumberFor<Self>>;
+
+		/// How long a worker may stay dormant before it is removed.
+		#[pallet::constant]
+		type DormantGracePeriod: Get<BlockNumberFor<Self>>;
+
+		/// The maximum number of workers visited by the lifecycle sweep per block.
+		#[pallet::constant]
+		type MaxLifecycleChecksPerBlock: Get<u32>;
+
+		/// Lends funds for TEE worker stake.
+		type ReservoirGate: ReservoirGate<Self::AccountId, BalanceOf<Self>>;
 	}
 
 	#[pallet::event]
@@ -136,6 +161,73 @@ pub mod pallet {
 		CesealBinAdded(H256),
 
 		CesealBinRemoved(H256),
+
+		CesealRolloutScheduled {
+			new_hash: H256,
+			previous_hash: Option<H256>,
+			activation_block: BlockNumberFor<T>,
+			deprecation_block: BlockNumberFor<T>,
+		},
+
+		CesealRolloutActivated {
+			new_hash: H256,
+			minimum_version: (u32, u32, u32),
+		},
+
+		CesealRolloutDeprecated {
+			previous_hash: Option<H256>,
+		},
+
+		CesealRolloutCompleted {
+			new_hash: H256,
+			expelled: u32,
+		},
+
+		CesealRolloutCancelled {
+			new_hash: H256,
+		},
+
+		WorkerExpelled {
+			pubkey: WorkerPublicKey,
+			measurement: Option<H256>,
+		},
+
+		/// A rollout sweep found a worker without a measurement. The rollout sweeps again at
+		/// `expel_after` and expels it then, unless it has registered again.
+		WorkerMeasurementMissing {
+			pubkey: WorkerPublicKey,
+			expel_after: BlockNumberFor<T>,
+		},
+
+		WorkerEndpointUpdated {
+			pubkey: WorkerPublicKey,
+			endpoint: WorkerEndpoint,
+		},
+
+		WorkerHeartbeat {
+			pubkey: WorkerPublicKey,
+		},
+
+		WorkerDormant {
+			pubkey: WorkerPublicKey,
+			last_work: BlockNumberFor<T>,
+		},
+
+		WorkerReactivated {
+			pubkey: WorkerPublicKey,
+		},
+
+		/// Stake bonded with funds borrowed from the reservoir started unbonding.
+		ReservoirStakeUnbonded {
+			acc: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
+
+		/// Unbonded stake went back to the reservoir.
+		ReservoirStakeReturned {
+			acc: AccountOf<T>,
+			amount: BalanceOf<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -203,6 +295,18 @@ pub mod pallet {
 
 		EmpltyFullWorker,
 		NotFoundDistributor,
+
+		CesealRolloutInProgress,
+		CesealRolloutNotFound,
+		CesealRolloutAlreadyActivated,
+		InvalidCesealRolloutSchedule,
+		CesealVersionNotIncreased,
+
+		InvalidEndpoint,
+		ServiceNotAllowedForRole,
+		StaleEndpointUpdate,
+		/// Only the borrower can unbond its reservoir stake before the loan is due.
+		LoanNotDue,
 	}
 
 	#[pallet::storage]
@@ -244,13 +348,52 @@ pub mod pallet {
 	pub type CesealBinAddedAt<T: Config> = StorageMap<_, Twox64Concat, H256, BlockNumberFor<T>>;
 
 	/// Mapping from worker pubkey to CESS Network identity
-	/// deprecated, use Workers element value.endpoint instead
+	/// deprecated, use WorkerEndpoints instead
 	#[pallet::storage]
 	pub type Endpoints<T: Config> = StorageMap<_, Twox64Concat, WorkerPublicKey, alloc::string::String>;
 
+	/// The endpoint each worker serves and the services reachable through it
+	#[pallet::storage]
+	pub type WorkerEndpoints<T: Config> = StorageMap<_, Twox64Concat, WorkerPublicKey, WorkerEndpoint>;
+
+	/// The signing time of the last endpoint update accepted from each worker
+	#[pallet::storage]
+	pub type EndpointSigningTimes<T: Config> = StorageMap<_, Twox64Concat, WorkerPublicKey, u64>;
+
+	/// The ceseal measurement each worker attested with on its latest registration.
+	#[pallet::storage]
+	pub type WorkerMeasurement<T: Config> = StorageMap<_, Twox64Concat, WorkerPublicKey, H256>;
+
+	/// Workers a rollout sweep found without a measurement, and the block they were found at.
+	#[pallet::storage]
+	pub type UnmeasuredWorkers<T: Config> = StorageMap<_, Twox64Concat, WorkerPublicKey, BlockNumberFor<T>>;
+
+	/// The ceseal rollout in progress, at most one at a time.
+	#[pallet::storage]
+	pub type CurrentCesealRollout<T: Config> = StorageValue<_, CesealRollout<BlockNumberFor<T>>>;
+
+	/// Workers expelled so far by the rollout in progress.
+	#[pallet::storage]
+	pub type RolloutExpelledCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+	/// The block the last grace period of the unmeasured workers kept by the rollout sweep in
+	/// progress ends at.
+	#[pallet::storage]
+	pub type RolloutGraceDeadline<T: Config> = StorageValue<_, BlockNumberFor<T>>;
+
 	#[pallet::storage]
 	pub type LastWork<T: Config> = StorageMap<_, Twox64Concat, WorkerPublicKey, BlockNumberFor<T>, ValueQuery>;
 
+	/// Workers that stopped working, and the block they turned dormant at.
+	///
+	/// Dormant workers are left out of [`ValidationTypeList`] until they work again.
+	#[pallet::storage]
+	pub type DormantWorkers<T: Config> = StorageMap<_, Twox64Concat, WorkerPublicKey, BlockNumberFor<T>>;
+
+	/// Raw storage key of the last worker visited by the lifecycle sweep.
+	#[pallet::storage]
+	pub type LifecycleCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<128>>, ValueQuery>;
+
 	/// Ceseals whoes version less than MinimumCesealVersion would be forced to quit.
 	#[pallet::storage]
 	pub type MinimumCesealVersion<T: Config> = StorageValue<_, (u32, u32, u32), ValueQuery>;
@@ -303,17 +446,18 @@ pub mod pallet {
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
 		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
-			let weight: Weight = Weight::zero();
+			let mut weight: Weight = Weight::zero();
 
-			let least = T::AtLeastWorkBlock::get();
-			if now % least == 0u32.saturated_into() {
-				weight.saturating_add(Self::clear_mission(now));
-			}
+			weight = weight.saturating_add(Self::clean_expired_master_key_postation(now));
 
-			weight.saturating_add(Self::clean_expired_master_key_postation(now));
+			weight = weight.saturating_add(Self::process_ceseal_rollout(now));
 
 			weight
 		}
+
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::process_worker_lifecycle(now, remaining_weight)
+		}
 	}
 
 	#[pallet::call]
@@ -369,7 +513,10 @@ pub mod pallet {
 
 				Workers::<T>::insert(&pubkey, worker_info);
 				WorkerAddedAt::<T>::insert(&pubkey, frame_system::Pallet::<T>::block_number());
+				WorkerMeasurement::<T>::insert(&pubkey, attestation_report.measurement_hash);
+				UnmeasuredWorkers::<T>::remove(&pubkey);
 				Endpoints::<T>::insert(&pubkey, ceseal_info.endpoint.clone()); //will deprecated
+				Self::register_endpoint(&pubkey, &ceseal_info.endpoint, &ceseal_info.role);
 				let now = <frame_system::Pallet<T>>::block_number();
 				<LastWork<T>>::insert(&pubkey, now);
 
@@ -400,6 +547,10 @@ pub mod pallet {
 					Ok(())
 				})?;
 				Endpoints::<T>::insert(&pubkey, ceseal_info.endpoint.clone());
+				Self::register_endpoint(&pubkey, &ceseal_info.endpoint, &ceseal_info.role);
+				WorkerMeasurement::<T>::insert(&pubkey, attestation_report.measurement_hash);
+				UnmeasuredWorkers::<T>::remove(&pubkey);
+				Self::refresh_last_work(&pubkey, <frame_system::Pallet<T>>::block_number());
 				Self::deposit_event(Event::<T>::WorkerUpdated {
 					pubkey,
 					attestation_provider: attestation_report.provider,
@@ -631,43 +782,222 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::call_index(114)]
-		#[pallet::weight({0})]
-		pub fn migration_last_work(origin: OriginFor<T>) -> DispatchResult {
+		/// Schedule a staged upgrade of the ceseal fleet.
+		///
+		/// At `activation_block`, `new_hash` is added to [`CesealBinAllowList`] and
+		/// [`MinimumCesealVersion`] is raised to `minimum_version`. At `deprecation_block`,
+		/// `previous_hash` is removed from the allow list and workers still attested with it
+		/// are expelled, a bounded number per block. `minimum_version` must be higher than the
+		/// current [`MinimumCesealVersion`].
+		///
+		/// Workers registered before measurements were tracked are not expelled by the first
+		/// sweep that finds them, but by a later one once `UnmeasuredWorkerGracePeriod` passed
+		/// without them registering again.
+		///
+		/// Can only be called by `GovernanceOrigin`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(Weight::from_parts(10_000u64, 0) + T::DbWeight::get().reads_writes(1u64, 1u64))]
+		pub fn schedule_ceseal_rollout(
+			origin: OriginFor<T>,
+			new_hash: H256,
+			previous_hash: Option<H256>,
+			activation_block: BlockNumberFor<T>,
+			deprecation_block: BlockNumberFor<T>,
+			minimum_version: (u32, u32, u32),
+		) -> DispatchResult {
 			T::GovernanceOrigin::ensure_origin(origin)?;
-			let now = <frame_system::Pallet<T>>::block_number();
-			for (puk, _) in Workers::<T>::iter() {
-				<LastWork<T>>::insert(&puk, now);
-			}
+			ensure!(!CurrentCesealRollout::<T>::exists(), Error::<T>::CesealRolloutInProgress);
 
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				activation_block > now && deprecation_block > activation_block,
+				Error::<T>::InvalidCesealRolloutSchedule
+			);
+			ensure!(previous_hash != Some(new_hash), Error::<T>::InvalidCesealRolloutSchedule);
+			ensure!(minimum_version > MinimumCesealVersion::<T>::get(), Error::<T>::CesealVersionNotIncreased);
+
+			CurrentCesealRollout::<T>::put(CesealRollout {
+				new_hash,
+				previous_hash,
+				activation_block,
+				deprecation_block,
+				minimum_version,
+				stage: RolloutStage::Scheduled,
+			});
+			RolloutExpelledCount::<T>::kill();
+			RolloutGraceDeadline::<T>::kill();
+
+			Self::deposit_event(Event::<T>::CesealRolloutScheduled {
+				new_hash,
+				previous_hash,
+				activation_block,
+				deprecation_block,
+			});
 			Ok(())
 		}
 
-		// FOR TEST
-		#[pallet::call_index(115)]
-		#[pallet::weight({0})]
-		pub fn patch_clear_invalid_tee(origin: OriginFor<T>) -> DispatchResult {
+		/// Cancel a ceseal rollout that has not been activated yet.
+		///
+		/// Can only be called by `GovernanceOrigin`.
+		#[pallet::call_index(13)]
+		#[pallet::weight(Weight::from_parts(10_000u64, 0) + T::DbWeight::get().reads_writes(1u64, 1u64))]
+		pub fn cancel_ceseal_rollout(origin: OriginFor<T>) -> DispatchResult {
 			T::GovernanceOrigin::ensure_origin(origin)?;
+			let rollout = CurrentCesealRollout::<T>::get().ok_or(Error::<T>::CesealRolloutNotFound)?;
+			ensure!(rollout.stage == RolloutStage::Scheduled, Error::<T>::CesealRolloutAlreadyActivated);
+
+			CurrentCesealRollout::<T>::kill();
+
+			Self::deposit_event(Event::<T>::CesealRolloutCancelled { new_hash: rollout.new_hash });
+			Ok(())
+		}
+
+		/// Update the endpoint of a worker.
+		///
+		/// The payload must be signed by the worker key. The announced services must match
+		/// the worker role, and the payload must be signed later than the last one accepted, so an
+		/// old update can't be replayed over a newer one.
+		#[pallet::call_index(14)]
+		#[pallet::weight(Weight::from_parts(10_000u64, 0) + T::DbWeight::get().reads_writes(2u64, 4u64))]
+		pub fn update_endpoint(
+			origin: OriginFor<T>,
+			payload: EndpointUpdatePayload,
+			signature: Vec<u8>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			// Validate the signature
+			Self::verify_signature(&signature, &payload.encode(), &payload.pubkey)?;
+			// Validate the signing time: 10 minutes expiration
+			Self::verify_signing_time(payload.signing_time, 10 * 60)?;
+
+			let pubkey = payload.pubkey;
+			ensure!(
+				EndpointSigningTimes::<T>::get(&pubkey).map_or(true, |last| payload.signing_time > last),
+				Error::<T>::StaleEndpointUpdate
+			);
+			let endpoint = payload.endpoint;
+			Workers::<T>::try_mutate(&pubkey, |worker_opt| -> DispatchResult {
+				let worker = worker_opt.as_mut().ok_or(Error::<T>::WorkerNotFound)?;
+				Self::check_endpoint(&endpoint, &worker.role)?;
+				worker.endpoint = endpoint.url();
+				Ok(())
+			})?;
+			Endpoints::<T>::insert(&pubkey, endpoint.url());
+			WorkerEndpoints::<T>::insert(&pubkey, endpoint.clone());
+			EndpointSigningTimes::<T>::insert(&pubkey, payload.signing_time);
+
+			Self::deposit_event(Event::<T>::WorkerEndpointUpdated { pubkey, endpoint });
+			Ok(())
+		}
+
+		/// Report a worker alive.
+		///
+		/// The payload must be signed by the worker key. Refreshes [`LastWork`], so workers that
+		/// keep sending heartbeats do not turn dormant, and wakes up dormant ones.
+		#[pallet::call_index(15)]
+		#[pallet::weight(Weight::from_parts(10_000u64, 0) + T::DbWeight::get().reads_writes(1u64, 1u64))]
+		pub fn heartbeat(origin: OriginFor<T>, payload: HeartbeatPayload, signature: Vec<u8>) -> DispatchResult {
+			ensure_signed(origin)?;
+			// Validate the signature
+			Self::verify_signature(&signature, &payload.encode(), &payload.pubkey)?;
+			// Validate the signing time: 10 minutes expiration
+			Self::verify_signing_time(payload.signing_time, 10 * 60)?;
+			ensure!(Workers::<T>::contains_key(&payload.pubkey), Error::<T>::WorkerNotFound);
 
 			let now = <frame_system::Pallet<T>>::block_number();
-			let _ = Self::clear_mission(now);
+			Self::refresh_last_work(&payload.pubkey, now);
 
+			Self::deposit_event(Event::<T>::WorkerHeartbeat { pubkey: payload.pubkey });
 			Ok(())
 		}
-		// FOR TEST
-		#[pallet::call_index(116)]
-		#[pallet::weight({0})]
-		pub fn patch_clear_not_work_tee(origin: OriginFor<T>) -> DispatchResult {
-			T::GovernanceOrigin::ensure_origin(origin)?;
-			for (puk, _) in Workers::<T>::iter() {
-				if !<LastWork<T>>::contains_key(&puk) {
-					Self::execute_exit(puk)?;
-				}
+
+		/// Bond funds borrowed from the reservoir for TEE worker stake.
+		///
+		/// The borrowed funds go to the stash [`Pallet::reservoir_stash`] derives for the caller,
+		/// which bonds them, or adds them to its bond, with the rewards paid to the caller. The
+		/// caller names that stash as the stash account of its workers. Nobody holds its key, so
+		/// the funds only leave it back to the reservoir.
+		#[pallet::call_index(16)]
+		#[pallet::weight(
+			<<T as pallet_cess_staking::Config>::WeightInfo as pallet_cess_staking::WeightInfo>::bond()
+				.saturating_add(T::DbWeight::get().reads_writes(3u64, 3u64))
+		)]
+		pub fn bond_from_reservoir(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let stash = Self::reservoir_stash(&sender);
+			T::ReservoirGate::spend(&sender, UseType::TeeWorkerStake, amount, &stash)?;
+			let stash_origin = frame_system::RawOrigin::Signed(stash.clone()).into();
+			let value = amount.saturated_into::<u128>().saturated_into();
+			if <pallet_cess_staking::Pallet<T>>::bonded(&stash).is_some() {
+				<pallet_cess_staking::Pallet<T>>::bond_extra(stash_origin, value)
+			} else {
+				let payee = pallet_cess_staking::RewardDestination::Account(sender);
+				<pallet_cess_staking::Pallet<T>>::bond(stash_origin, value, payee)
 			}
+		}
+
+		/// Start unbonding the stake bonded from the reservoir for `borrower`.
+		///
+		/// The borrower may unbond at any time, anyone else once its loan is due. The unbonded
+		/// funds are returned with [`Pallet::return_reservoir_stake`].
+		#[pallet::call_index(17)]
+		#[pallet::weight(
+			<<T as pallet_cess_staking::Config>::WeightInfo as pallet_cess_staking::WeightInfo>::unbond()
+				.saturating_add(T::DbWeight::get().reads(2u64))
+		)]
+		pub fn unbond_reservoir_stake(origin: OriginFor<T>, borrower: AccountOf<T>) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			ensure!(sender == borrower || T::ReservoirGate::is_due(&borrower), Error::<T>::LoanNotDue);
+
+			let stash = Self::reservoir_stash(&borrower);
+			let ledger = pallet_cess_staking::Ledger::<T>::get(&stash).ok_or(Error::<T>::NotBond)?;
+			let stash_origin = frame_system::RawOrigin::Signed(stash).into();
+			<pallet_cess_staking::Pallet<T>>::unbond(stash_origin, ledger.active).map_err(|e| e.error)?;
+
+			let amount = ledger.active.saturated_into::<u128>().saturated_into();
+			Self::deposit_event(Event::<T>::ReservoirStakeUnbonded { acc: borrower, amount });
+			Ok(())
+		}
+
+		/// Return the unbonded stake of `borrower` to the reservoir, where its loan can spend
+		/// it again, or is paid back with it once due.
+		///
+		/// `num_slashing_spans` is passed on to `withdraw_unbonded` of the stash.
+		#[pallet::call_index(18)]
+		#[pallet::weight(
+			<<T as pallet_cess_staking::Config>::WeightInfo as pallet_cess_staking::WeightInfo>::withdraw_unbonded_kill(
+				*num_slashing_spans
+			)
+			.saturating_add(T::DbWeight::get().reads_writes(3u64, 3u64))
+		)]
+		pub fn return_reservoir_stake(
+			origin: OriginFor<T>,
+			borrower: AccountOf<T>,
+			num_slashing_spans: u32,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
 
+			let stash = Self::reservoir_stash(&borrower);
+			if <pallet_cess_staking::Pallet<T>>::bonded(&stash).is_some() {
+				let stash_origin = frame_system::RawOrigin::Signed(stash.clone()).into();
+				<pallet_cess_staking::Pallet<T>>::withdraw_unbonded(stash_origin, num_slashing_spans)
+					.map_err(|e| e.error)?;
+			}
+			// Whatever is still bonded or unlocking stays locked on the stash.
+			let locked: BalanceOf<T> = pallet_cess_staking::Ledger::<T>::get(&stash)
+				.map_or(0u128, |ledger| ledger.total.saturated_into::<u128>())
+				.saturated_into();
+			let unbonded = <T as pallet::Config>::Currency::free_balance(&stash).saturating_sub(locked);
+			let amount = T::ReservoirGate::refund(&borrower, UseType::TeeWorkerStake, unbonded, &stash)?;
+
+			Self::deposit_event(Event::<T>::ReservoirStakeReturned { acc: borrower, amount });
 			Ok(())
 		}
 
+		/// Remove a worker immediately.
+		///
+		/// Idle workers are cleaned up by the lifecycle sweep, this is for emergencies only.
 		#[pallet::call_index(117)]
 		#[pallet::weight({0})]
 		pub fn force_clear_tee(origin: OriginFor<T>, puk: WorkerPublicKey) -> DispatchResult {
@@ -704,6 +1034,8 @@ pub mod pallet {
 			};
 			Workers::<T>::insert(worker_info.pubkey, &worker_info);
 			WorkerAddedAt::<T>::insert(worker_info.pubkey, frame_system::Pallet::<T>::block_number());
+			Self::register_endpoint(&pubkey, &worker_info.endpoint, &worker_info.role);
+			<LastWork<T>>::insert(&pubkey, frame_system::Pallet::<T>::block_number());
 			Self::deposit_event(Event::<T>::WorkerAdded {
 				pubkey,
 				attestation_provider: Some(AttestationProvider::Root),
@@ -948,10 +1280,8 @@ impl<T: Config> TeeWorkerHandler<AccountOf<T>, BlockNumberFor<T>> for Pallet<T>
 	}
 
 	fn update_work_block(now: BlockNumberFor<T>, pbk: &WorkerPublicKey) -> DispatchResult {
-		<LastWork<T>>::try_mutate(pbk, |last_block| {
-			*last_block = now;
+		Pallet::<T>::refresh_last_work(pbk, now);
 
-			Ok(())
-		})
+		Ok(())
 	}
 }
@@ -0,0 +1,246 @@
+//! Test utilities
+
+use super::*;
+use crate as pallet_tee_worker;
+use frame_election_provider_support::NoElection;
+use frame_support::{
+	derive_impl, parameter_types,
+	traits::{ConstU128, ConstU32, ConstU64, Currency, Hooks},
+};
+use ces_types::{EndpointUpdatePayload, HeartbeatPayload};
+use frame_system::EnsureRoot;
+use sp_core::{sr25519, Pair, H256};
+use sp_runtime::{BuildStorage, DispatchResult, Perbill};
+
+pub(crate) type AccountId = u64;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Timestamp: pallet_timestamp,
+		Balances: pallet_balances,
+		Staking: pallet_cess_staking,
+		Reservoir: pallet_reservoir,
+		TeeWorker: pallet_tee_worker,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<Balance>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type Balance = Balance;
+	type ExistentialDeposit = ConstU128<1>;
+	type AccountStore = System;
+}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<5>;
+	type WeightInfo = ();
+}
+
+#[derive_impl(pallet_cess_staking::config_preludes::TestDefaultConfig)]
+impl pallet_cess_staking::Config for Test {
+	type Currency = Balances;
+	type UnixTime = Timestamp;
+	type AdminOrigin = EnsureRoot<AccountId>;
+	type EraPayout = ();
+	type ElectionProvider = NoElection<(AccountId, BlockNumber, Staking, ConstU32<100>)>;
+	type GenesisElectionProvider = Self::ElectionProvider;
+	type VoterList = pallet_cess_staking::UseNominatorsAndValidatorsMap<Self>;
+	type TargetList = pallet_cess_staking::UseValidatorsMap<Self>;
+
+	const ERAS_PER_YEAR: u64 = 8766;
+	const FIRST_YEAR_VALIDATOR_REWARDS: Balance = 238_500_000 * 1000;
+	const FIRST_YEAR_SMINER_REWARDS: Balance = 477_000_000 * 1000;
+	const REWARD_DECREASE_RATIO: Perbill = Perbill::from_perthousand(841);
+	const REWARD_DECREASE_YEARS: u64 = 30;
+	type SminerRewardPool = ();
+	type SminerPayout = ();
+	type SminerReserve = ();
+}
+
+parameter_types! {
+	pub const ReservoirPalletId: PalletId = PalletId(*b"rsorptid");
+}
+
+impl pallet_reservoir::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type PalletId = ReservoirPalletId;
+	type IdLength = ConstU32<32>;
+	type EventLimit = ConstU32<10>;
+	type LoanLimit = ConstU32<10>;
+	type AllowlistLimit = ConstU32<10>;
+	type KycProvider = ();
+	type LoanCollateral = ();
+	type EventCleanupLimit = ConstU32<10>;
+	type WeightInfo = ();
+}
+
+pub struct MockCreditCounter;
+impl SchedulerCreditCounter<AccountId> for MockCreditCounter {
+	fn increase_point_for_tag(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn increase_point_for_cert(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn increase_point_for_idle_verify(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn increase_point_for_service_verify(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn increase_point_for_replace(_: &AccountId, _: u128) -> DispatchResult {
+		Ok(())
+	}
+	fn record_proceed_block_size(_: &AccountId, _: u64) -> DispatchResult {
+		Ok(())
+	}
+	fn record_punishment(_: &AccountId) -> DispatchResult {
+		Ok(())
+	}
+	fn credit_score(_: &AccountId) -> u32 {
+		0
+	}
+}
+
+pub struct MockRandomness;
+impl Randomness<H256, BlockNumber> for MockRandomness {
+	fn random(subject: &[u8]) -> (H256, BlockNumber) {
+		(H256::from(sp_io::hashing::blake2_256(subject)), 0)
+	}
+}
+
+pub(crate) const AT_LEAST_WORK: BlockNumber = 100;
+pub(crate) const DORMANT_GRACE: BlockNumber = 50;
+pub(crate) const UNMEASURED_GRACE: BlockNumber = 20;
+pub(crate) const MAX_EXPEL_PER_BLOCK: u32 = 2;
+
+parameter_types! {
+	pub const TeeWorkerPalletId: PalletId = PalletId(*b"filmpdpt");
+	#[derive(Clone, PartialEq, Eq)]
+	pub const SchedulerMaximum: u32 = 10000;
+	#[derive(Clone, PartialEq, Eq)]
+	pub const MaxWhitelist: u32 = 200;
+	pub const AtLeastWorkBlock: BlockNumber = AT_LEAST_WORK;
+	pub const DormantGracePeriod: BlockNumber = DORMANT_GRACE;
+	pub const UnmeasuredWorkerGracePeriod: BlockNumber = UNMEASURED_GRACE;
+}
+
+impl Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type TeeWorkerPalletId = TeeWorkerPalletId;
+	type SchedulerMaximum = SchedulerMaximum;
+	type WeightInfo = ();
+	type CreditCounter = MockCreditCounter;
+	type MaxWhitelist = MaxWhitelist;
+	type AtLeastWorkBlock = AtLeastWorkBlock;
+	type GovernanceOrigin = EnsureRoot<AccountId>;
+	type Randomness = MockRandomness;
+	type MaxWorkerExpelPerBlock = ConstU32<MAX_EXPEL_PER_BLOCK>;
+	type UnmeasuredWorkerGracePeriod = UnmeasuredWorkerGracePeriod;
+	type DormantGracePeriod = DormantGracePeriod;
+	type MaxLifecycleChecksPerBlock = ConstU32<10>;
+	type ReservoirGate = Reservoir;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn worker_pair(seed: u8) -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[seed; 32])
+}
+
+/// Insert a full worker as `register_worker` would, attested with `measurement`.
+pub fn add_worker(seed: u8, measurement: Option<H256>) -> WorkerPublicKey {
+	let pubkey = worker_pair(seed).public();
+	let worker_info = WorkerInfo::<AccountId> {
+		pubkey,
+		ecdh_pubkey: pubkey,
+		version: 0,
+		last_updated: 1,
+		stash_account: None,
+		attestation_provider: None,
+		confidence_level: 128u8,
+		features: vec![],
+		role: WorkerRole::Full,
+		endpoint: Default::default(),
+	};
+	Workers::<Test>::insert(&pubkey, worker_info);
+	WorkerAddedAt::<Test>::insert(&pubkey, System::block_number());
+	if let Some(measurement) = measurement {
+		WorkerMeasurement::<Test>::insert(&pubkey, measurement);
+	}
+	LastWork::<Test>::insert(&pubkey, System::block_number());
+	ValidationTypeList::<Test>::mutate(|puk_list| puk_list.try_push(pubkey).unwrap());
+	pubkey
+}
+
+/// A heartbeat of the worker made from `seed`, signed now.
+pub fn heartbeat(seed: u8) -> (HeartbeatPayload, Vec<u8>) {
+	let pair = worker_pair(seed);
+	let payload = HeartbeatPayload { pubkey: pair.public(), signing_time: <Timestamp as UnixTime>::now().as_secs() };
+	let signature = pair.sign(&payload.encode());
+	(payload, signature.as_ref().to_vec())
+}
+
+/// An update of the endpoint of the worker made from `seed` to `url`, signed now.
+pub fn endpoint_update(seed: u8, url: &str) -> (EndpointUpdatePayload, Vec<u8>) {
+	let pair = worker_pair(seed);
+	let payload = EndpointUpdatePayload {
+		pubkey: pair.public(),
+		endpoint: WorkerEndpoint::parse(url, vec![WorkerService::Verify]).unwrap(),
+		signing_time: <Timestamp as UnixTime>::now().as_secs(),
+	};
+	let signature = pair.sign(&payload.encode());
+	(payload, signature.as_ref().to_vec())
+}
+
+/// Run the pallet hooks of every block up to and including `n`.
+pub fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		TeeWorker::on_initialize(next);
+		TeeWorker::on_idle(next, Weight::MAX);
+	}
+}
+
+/// Lend `amount` to `acc` from the reservoir for TEE worker stake.
+pub fn borrow_stake(acc: AccountId, amount: Balance) {
+	let lender = 100;
+	let _ = Balances::deposit_creating(&lender, amount * 3);
+	Reservoir::filling(RuntimeOrigin::signed(lender), amount * 2).unwrap();
+	let id: BoundedVec<u8, ConstU32<32>> = b"tee".to_vec().try_into().unwrap();
+	Reservoir::create_event(
+		RuntimeOrigin::root(),
+		id.clone(),
+		1,
+		100,
+		amount,
+		100,
+		pallet_reservoir::UseType::TeeWorkerStake,
+		Perbill::zero(),
+		Default::default(),
+	)
+	.unwrap();
+	Reservoir::attend_event(RuntimeOrigin::signed(acc), id).unwrap();
+}
@@ -1,3 +1,357 @@
+use super::*;
+use crate::mock::{
+	add_worker, borrow_stake, endpoint_update, heartbeat, new_test_ext, run_to_block, worker_pair, AccountId, Balances,
+	ReservoirPalletId, RuntimeOrigin, System, TeeWorker, Test, Timestamp, UNMEASURED_GRACE,
+};
+use frame_support::{assert_noop, assert_ok, traits::Hooks};
+use sp_core::{Pair, H256};
+
+const AT_LEAST_WORK: u32 = 100;
+const GRACE: u32 = 50;
+
+fn decide(now: u32, last_work: u32, dormant_since: Option<u32>) -> LifecycleAction {
+	LifecycleAction::decide(now, last_work, dormant_since, AT_LEAST_WORK, GRACE)
+}
+
+#[test]
+fn active_worker_is_kept_within_work_period() {
+	assert_eq!(decide(150, 50, None), LifecycleAction::Keep);
+	assert_eq!(decide(150, 100, None), LifecycleAction::Keep);
+}
+
+#[test]
+fn idle_worker_becomes_dormant() {
+	assert_eq!(decide(151, 50, None), LifecycleAction::Dormant);
+	assert_eq!(decide(1_000, 0, None), LifecycleAction::Dormant);
+}
+
+#[test]
+fn dormant_worker_is_kept_during_grace_period() {
+	assert_eq!(decide(160, 50, Some(151)), LifecycleAction::Keep);
+	assert_eq!(decide(200, 50, Some(151)), LifecycleAction::Keep);
+}
+
+#[test]
+fn dormant_worker_is_removed_after_grace_period() {
+	assert_eq!(decide(201, 50, Some(151)), LifecycleAction::Remove);
+	assert_eq!(decide(u32::MAX, 50, Some(u32::MAX - 1)), LifecycleAction::Remove);
+}
+
+#[test]
+fn dormant_worker_that_worked_again_is_reactivated() {
+	assert_eq!(decide(160, 151, Some(151)), LifecycleAction::Reactivate);
+	// Work beats removal even when the sweep comes late.
+	assert_eq!(decide(500, 180, Some(151)), LifecycleAction::Reactivate);
+}
+
+#[test]
+fn sweep_turns_idle_worker_dormant_and_removes_it_after_grace_period() {
+	new_test_ext().execute_with(|| {
+		let pubkey = add_worker(1, None);
+
+		run_to_block(1 + AT_LEAST_WORK as u64);
+		assert!(!DormantWorkers::<Test>::contains_key(&pubkey));
+
+		run_to_block(2 + AT_LEAST_WORK as u64);
+		let dormant_since = DormantWorkers::<Test>::get(&pubkey).unwrap();
+		assert!(Workers::<Test>::contains_key(&pubkey));
+		assert!(!ValidationTypeList::<Test>::get().contains(&pubkey));
+		System::assert_has_event(Event::<Test>::WorkerDormant { pubkey, last_work: 1 }.into());
+
+		run_to_block(dormant_since + GRACE as u64 - 1);
+		assert!(Workers::<Test>::contains_key(&pubkey));
+
+		run_to_block(dormant_since + GRACE as u64);
+		assert!(!Workers::<Test>::contains_key(&pubkey));
+		assert!(!DormantWorkers::<Test>::contains_key(&pubkey));
+		assert!(!LastWork::<Test>::contains_key(&pubkey));
+		System::assert_has_event(Event::<Test>::ClearInvalidTee { pubkey }.into());
+	});
+}
+
+#[test]
+fn heartbeat_keeps_worker_active() {
+	new_test_ext().execute_with(|| {
+		let pubkey = add_worker(1, None);
+		run_to_block(90);
+
+		let (payload, signature) = heartbeat(1);
+		assert_ok!(TeeWorker::heartbeat(RuntimeOrigin::signed(1), payload, signature));
+		assert_eq!(LastWork::<Test>::get(&pubkey), 90);
+
+		run_to_block(90 + AT_LEAST_WORK as u64);
+		assert!(!DormantWorkers::<Test>::contains_key(&pubkey));
+		assert!(ValidationTypeList::<Test>::get().contains(&pubkey));
+	});
+}
+
+#[test]
+fn heartbeat_reactivates_dormant_worker() {
+	new_test_ext().execute_with(|| {
+		let pubkey = add_worker(1, None);
+		run_to_block(2 + AT_LEAST_WORK as u64);
+		assert!(DormantWorkers::<Test>::contains_key(&pubkey));
+
+		let (payload, signature) = heartbeat(1);
+		assert_ok!(TeeWorker::heartbeat(RuntimeOrigin::signed(1), payload, signature));
+		assert!(!DormantWorkers::<Test>::contains_key(&pubkey));
+		assert!(ValidationTypeList::<Test>::get().contains(&pubkey));
+		System::assert_has_event(Event::<Test>::WorkerReactivated { pubkey }.into());
+
+		// The sweep no longer counts the time it spent dormant.
+		run_to_block(2 + 2 * AT_LEAST_WORK as u64);
+		assert!(Workers::<Test>::contains_key(&pubkey));
+		assert!(!DormantWorkers::<Test>::contains_key(&pubkey));
+	});
+}
+
+#[test]
+fn heartbeat_of_unknown_worker_is_rejected() {
+	new_test_ext().execute_with(|| {
+		let (payload, signature) = heartbeat(1);
+		assert_noop!(
+			TeeWorker::heartbeat(RuntimeOrigin::signed(1), payload, signature),
+			Error::<Test>::WorkerNotFound
+		);
+	});
+}
+
+#[test]
+fn sweep_without_weight_left_does_nothing() {
+	new_test_ext().execute_with(|| {
+		let pubkey = add_worker(1, None);
+		System::set_block_number(10 * AT_LEAST_WORK as u64);
+		assert_eq!(TeeWorker::on_idle(System::block_number(), Weight::zero()), Weight::zero());
+		assert!(!DormantWorkers::<Test>::contains_key(&pubkey));
+	});
+}
+
+#[test]
+fn update_endpoint_requires_signed_origin() {
+	new_test_ext().execute_with(|| {
+		add_worker(1, None);
+		let (payload, signature) = endpoint_update(1, "https://[::1]/");
+		assert_noop!(TeeWorker::update_endpoint(RuntimeOrigin::none(), payload, signature), DispatchError::BadOrigin);
+	});
+}
+
+#[test]
+fn update_endpoint_rejects_signature_of_another_key() {
+	new_test_ext().execute_with(|| {
+		add_worker(1, None);
+		let (payload, _) = endpoint_update(1, "https://[::1]/");
+		let signature = worker_pair(2).sign(&payload.encode()).as_ref().to_vec();
+		assert_noop!(
+			TeeWorker::update_endpoint(RuntimeOrigin::signed(1), payload, signature),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn update_endpoint_rejects_replayed_payload() {
+	new_test_ext().execute_with(|| {
+		add_worker(1, None);
+		Timestamp::set_timestamp(100_000);
+		let (old, old_signature) = endpoint_update(1, "http://10.0.0.1:8000");
+		Timestamp::set_timestamp(160_000);
+		let (new, new_signature) = endpoint_update(1, "http://10.0.0.2:8000");
+
+		assert_ok!(TeeWorker::update_endpoint(RuntimeOrigin::signed(1), new.clone(), new_signature.clone()));
+		assert_noop!(
+			TeeWorker::update_endpoint(RuntimeOrigin::signed(1), new, new_signature),
+			Error::<Test>::StaleEndpointUpdate
+		);
+		// Still within its signing window, but older than the update already accepted.
+		assert_noop!(
+			TeeWorker::update_endpoint(RuntimeOrigin::signed(1), old, old_signature),
+			Error::<Test>::StaleEndpointUpdate
+		);
+	});
+}
+
+#[test]
+fn update_endpoint_records_endpoint() {
+	new_test_ext().execute_with(|| {
+		let pubkey = add_worker(1, None);
+		Timestamp::set_timestamp(100_000);
+		let (payload, signature) = endpoint_update(1, "https://[::1]/");
+		let endpoint = payload.endpoint.clone();
+
+		assert_ok!(TeeWorker::update_endpoint(RuntimeOrigin::signed(1), payload, signature));
+		assert_eq!(WorkerEndpoints::<Test>::get(&pubkey), Some(endpoint.clone()));
+		assert_eq!(Endpoints::<Test>::get(&pubkey), Some(String::from("https://[::1]:443")));
+		assert_eq!(Workers::<Test>::get(&pubkey).unwrap().endpoint, "https://[::1]:443");
+		assert_eq!(EndpointSigningTimes::<Test>::get(&pubkey), Some(100));
+		System::assert_last_event(Event::<Test>::WorkerEndpointUpdated { pubkey, endpoint }.into());
+	});
+}
+
+const OLD_CESEAL: H256 = H256::repeat_byte(1);
+const NEW_CESEAL: H256 = H256::repeat_byte(2);
+
+fn schedule_rollout(activation_block: u64, deprecation_block: u64, minimum_version: (u32, u32, u32)) {
+	assert_ok!(TeeWorker::schedule_ceseal_rollout(
+		RuntimeOrigin::root(),
+		NEW_CESEAL,
+		Some(OLD_CESEAL),
+		activation_block,
+		deprecation_block,
+		minimum_version,
+	));
+}
+
+#[test]
+fn rollout_must_raise_minimum_version() {
+	new_test_ext().execute_with(|| {
+		MinimumCesealVersion::<Test>::put((1, 2, 0));
+		for version in [(1, 2, 0), (1, 1, 9), (0, 9, 9)] {
+			assert_noop!(
+				TeeWorker::schedule_ceseal_rollout(RuntimeOrigin::root(), NEW_CESEAL, Some(OLD_CESEAL), 5, 10, version),
+				Error::<Test>::CesealVersionNotIncreased
+			);
+		}
+		schedule_rollout(5, 10, (1, 2, 1));
+	});
+}
+
+#[test]
+fn rollout_expels_workers_on_previous_ceseal_only() {
+	new_test_ext().execute_with(|| {
+		let old = add_worker(1, Some(OLD_CESEAL));
+		let new = add_worker(2, Some(NEW_CESEAL));
+		let other = add_worker(3, Some(H256::repeat_byte(3)));
+		schedule_rollout(5, 10, (1, 0, 0));
+
+		run_to_block(5);
+		assert_eq!(MinimumCesealVersion::<Test>::get(), (1, 0, 0));
+		assert!(CesealBinAllowList::<Test>::get().contains(&NEW_CESEAL));
+
+		// Both ceseals may run until the deprecation block.
+		run_to_block(9);
+		assert!(Workers::<Test>::contains_key(&old));
+
+		run_to_block(20);
+		assert!(CurrentCesealRollout::<Test>::get().is_none());
+		assert!(!Workers::<Test>::contains_key(&old));
+		assert!(!ValidationTypeList::<Test>::get().contains(&old));
+		assert!(Workers::<Test>::contains_key(&new));
+		assert!(Workers::<Test>::contains_key(&other));
+		System::assert_has_event(Event::<Test>::WorkerExpelled { pubkey: old, measurement: Some(OLD_CESEAL) }.into());
+		System::assert_has_event(Event::<Test>::CesealRolloutCompleted { new_hash: NEW_CESEAL, expelled: 1 }.into());
+	});
+}
+
+#[test]
+fn unmeasured_worker_is_kept_through_its_grace_period() {
+	new_test_ext().execute_with(|| {
+		let legacy = add_worker(1, None);
+		schedule_rollout(5, 10, (1, 0, 0));
+
+		run_to_block(20);
+		assert!(Workers::<Test>::contains_key(&legacy));
+		let found_at = UnmeasuredWorkers::<Test>::get(&legacy).unwrap();
+		let expel_after = found_at + UNMEASURED_GRACE;
+		System::assert_has_event(Event::<Test>::WorkerMeasurementMissing { pubkey: legacy, expel_after }.into());
+		// The rollout waits for the grace period instead of completing.
+		assert_eq!(CurrentCesealRollout::<Test>::get().unwrap().stage, RolloutStage::AwaitingGrace(expel_after));
+
+		// The worker registers again on the new ceseal within its grace period.
+		WorkerMeasurement::<Test>::insert(&legacy, NEW_CESEAL);
+		UnmeasuredWorkers::<Test>::remove(&legacy);
+
+		run_to_block(expel_after + 5);
+		assert!(CurrentCesealRollout::<Test>::get().is_none());
+		assert!(Workers::<Test>::contains_key(&legacy));
+		System::assert_has_event(Event::<Test>::CesealRolloutCompleted { new_hash: NEW_CESEAL, expelled: 0 }.into());
+	});
+}
+
+#[test]
+fn unmeasured_worker_is_expelled_after_its_grace_period() {
+	new_test_ext().execute_with(|| {
+		let legacy = add_worker(1, None);
+		schedule_rollout(5, 10, (1, 0, 0));
+		run_to_block(20);
+		let expel_after = UnmeasuredWorkers::<Test>::get(&legacy).unwrap() + UNMEASURED_GRACE;
+
+		run_to_block(expel_after - 1);
+		assert!(Workers::<Test>::contains_key(&legacy));
+
+		// The same rollout sweeps again once the grace period is over.
+		run_to_block(expel_after + 5);
+		assert!(CurrentCesealRollout::<Test>::get().is_none());
+		assert!(!Workers::<Test>::contains_key(&legacy));
+		assert!(!UnmeasuredWorkers::<Test>::contains_key(&legacy));
+		System::assert_has_event(Event::<Test>::WorkerExpelled { pubkey: legacy, measurement: None }.into());
+		System::assert_has_event(Event::<Test>::CesealRolloutCompleted { new_hash: NEW_CESEAL, expelled: 1 }.into());
+	});
+}
+
+#[test]
+fn reservoir_loans_bond_tee_worker_stake() {
+	new_test_ext().execute_with(|| {
+		let borrower = 10;
+		let stash = TeeWorker::reservoir_stash(&borrower);
+		borrow_stake(borrower, 1_000);
+
+		assert_ok!(TeeWorker::bond_from_reservoir(RuntimeOrigin::signed(borrower), 600));
+		assert_eq!(pallet_cess_staking::Ledger::<Test>::get(stash).unwrap().active, 600);
+		assert_ok!(TeeWorker::bond_from_reservoir(RuntimeOrigin::signed(borrower), 400));
+		assert_eq!(pallet_cess_staking::Ledger::<Test>::get(stash).unwrap().active, 1_000);
+		// The borrowed funds never reach the borrower.
+		assert_eq!(Balances::free_balance(borrower), 0);
+		assert!(pallet_cess_staking::Ledger::<Test>::get(borrower).is_none());
+
+		assert_noop!(
+			TeeWorker::bond_from_reservoir(RuntimeOrigin::signed(borrower), 1),
+			pallet_reservoir::Error::<Test>::Insufficient
+		);
+	});
+}
+
+#[test]
+fn reservoir_stake_returns_to_the_reservoir() {
+	new_test_ext().execute_with(|| {
+		let borrower = 10;
+		let stash = TeeWorker::reservoir_stash(&borrower);
+		borrow_stake(borrower, 1_000);
+		let reservoir: AccountId = ReservoirPalletId::get().into_account_truncating();
+		let reservoir_balance = Balances::free_balance(reservoir);
+		assert_ok!(TeeWorker::bond_from_reservoir(RuntimeOrigin::signed(borrower), 1_000));
+		assert_eq!(Balances::free_balance(reservoir), reservoir_balance - 1_000);
+
+		assert_noop!(TeeWorker::unbond_reservoir_stake(RuntimeOrigin::signed(11), borrower), Error::<Test>::LoanNotDue);
+		assert_ok!(TeeWorker::unbond_reservoir_stake(RuntimeOrigin::signed(borrower), borrower));
+		System::assert_last_event(Event::<Test>::ReservoirStakeUnbonded { acc: borrower, amount: 1_000 }.into());
+
+		// Nothing is returned while the stake is still unlocking.
+		assert_ok!(TeeWorker::return_reservoir_stake(RuntimeOrigin::signed(11), borrower, 0));
+		assert_eq!(Balances::free_balance(reservoir), reservoir_balance - 1_000);
+
+		pallet_cess_staking::CurrentEra::<Test>::put(3);
+		assert_ok!(TeeWorker::return_reservoir_stake(RuntimeOrigin::signed(11), borrower, 0));
+		System::assert_last_event(Event::<Test>::ReservoirStakeReturned { acc: borrower, amount: 1_000 }.into());
+		assert_eq!(Balances::free_balance(reservoir), reservoir_balance);
+		assert_eq!(Balances::free_balance(stash), 0);
+
+		// The returned funds are lent again.
+		assert_ok!(TeeWorker::bond_from_reservoir(RuntimeOrigin::signed(borrower), 1_000));
+	});
+}
+
+#[test]
+fn anyone_unbonds_reservoir_stake_of_a_due_loan() {
+	new_test_ext().execute_with(|| {
+		let borrower = 10;
+		borrow_stake(borrower, 1_000);
+		assert_ok!(TeeWorker::bond_from_reservoir(RuntimeOrigin::signed(borrower), 1_000));
+
+		System::set_block_number(101);
+		assert_ok!(TeeWorker::unbond_reservoir_stake(RuntimeOrigin::signed(11), borrower));
+		System::assert_last_event(Event::<Test>::ReservoirStakeUnbonded { acc: borrower, amount: 1_000 }.into());
+	});
+}
 
 // use super::*;
 // use libp2p::core::{
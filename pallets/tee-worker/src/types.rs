@@ -23,4 +23,64 @@ pub enum TeeType {
     Full,
     Verifier,
     Marker,
-}
\ No newline at end of file
+}
+/// A staged upgrade of the ceseal fleet from one measurement to another.
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct CesealRollout<BlockNumber> {
+    /// The measurement of the new ceseal binary.
+    pub new_hash: sp_core::H256,
+    /// The measurement being replaced, if any.
+    pub previous_hash: Option<sp_core::H256>,
+    /// From this block on, `new_hash` is allowed to register.
+    pub activation_block: BlockNumber,
+    /// From this block on, `previous_hash` is no longer allowed and its workers are expelled.
+    pub deprecation_block: BlockNumber,
+    /// The `MinimumCesealVersion` that takes effect on activation.
+    pub minimum_version: (u32, u32, u32),
+    pub stage: RolloutStage<BlockNumber>,
+}
+
+#[derive(PartialEq, Eq, Encode, Decode, Clone, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub enum RolloutStage<BlockNumber> {
+    /// Waiting for the activation block.
+    Scheduled,
+    /// Both measurements are allowed, waiting for the deprecation block.
+    Activated,
+    /// The previous measurement is removed, workers still on it are being expelled.
+    /// Holds the raw storage key of the last visited worker.
+    Expelling(BoundedVec<u8, ConstU32<128>>),
+    /// The sweep kept unmeasured workers within their grace period. Holds the block the last
+    /// of those periods ends at, when the workers are swept again.
+    AwaitingGrace(BlockNumber),
+}
+
+/// What the lifecycle sweep does with a worker on a visit.
+#[derive(PartialEq, Eq, Clone, Copy, RuntimeDebug)]
+pub enum LifecycleAction {
+    /// Nothing to do.
+    Keep,
+    /// The worker has not worked for `AtLeastWorkBlock`, stop assigning it work.
+    Dormant,
+    /// The dormant worker has worked again.
+    Reactivate,
+    /// The worker stayed dormant for the whole grace period.
+    Remove,
+}
+
+impl LifecycleAction {
+    pub fn decide<BlockNumber: sp_runtime::traits::AtLeast32BitUnsigned + Copy>(
+        now: BlockNumber,
+        last_work: BlockNumber,
+        dormant_since: Option<BlockNumber>,
+        at_least_work: BlockNumber,
+        grace_period: BlockNumber,
+    ) -> Self {
+        match dormant_since {
+            Some(since) if last_work >= since => LifecycleAction::Reactivate,
+            Some(since) if since.saturating_add(grace_period) <= now => LifecycleAction::Remove,
+            Some(_) => LifecycleAction::Keep,
+            None if last_work.saturating_add(at_least_work) < now => LifecycleAction::Dormant,
+            None => LifecycleAction::Keep,
+        }
+    }
+}
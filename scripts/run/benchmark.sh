@@ -0,0 +1,47 @@
+#!/bin/bash
+
+# Regenerates the weights of the given pallets, or of the pallets whose weights are not measured
+# yet, with the benchmarks of the node. Run it on reference hardware from the repository root after
+# building the node with `cargo build --release --features runtime-benchmarks`.
+
+bin=${BIN:-"./target/release/cess-node"}
+chain_spec=${CHAIN:-dev}
+steps=${STEPS:-50}
+repeat=${REPEAT:-20}
+
+pallets=("$@")
+if [ ${#pallets[@]} -eq 0 ]; then
+    pallets=(
+        audit
+        cacher
+        cess-treasury
+        evm-account-mapping
+        file-bank
+        oss
+        reservoir
+        staking
+    )
+fi
+
+for pallet in "${pallets[@]}"; do
+    case $pallet in
+    staking)
+        name=pallet_cess_staking
+        ;;
+    *)
+        name=pallet_${pallet//-/_}
+        ;;
+    esac
+
+    echo "benchmarking $name"
+    $bin benchmark pallet \
+        --chain $chain_spec \
+        --wasm-execution=compiled \
+        --pallet $name \
+        --extrinsic '*' \
+        --steps $steps \
+        --repeat $repeat \
+        --heap-pages=4096 \
+        --template=./.maintain/frame-weight-template.hbs \
+        --output=./pallets/$pallet/src/weights.rs || exit 1
+done
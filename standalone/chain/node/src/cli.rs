@@ -83,4 +83,7 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	FrontierDb(fc_cli::FrontierDbCmd),
+
+	/// Replay a validator election offline from exported state.
+	SimulateElection(crate::simulate_election::SimulateElectionCmd),
 }
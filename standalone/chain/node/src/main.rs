@@ -9,6 +9,7 @@ mod command;
 mod eth;
 mod rpc;
 mod service;
+mod simulate_election;
 
 fn main() -> polkadot_sdk::sc_cli::Result<()> {
 	command::run()
@@ -0,0 +1,122 @@
+//! RPC to audit how the validator elections scored their candidates.
+
+use polkadot_sdk::*;
+use ces_rrsc_vrf_solver::{CandidateScore, ExtendedBalance};
+use cess_node_runtime::scheduler_credit_api::SchedulerCreditApi;
+use codec::Codec;
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{ErrorObject, ErrorObjectOwned},
+};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Block as BlockT;
+use std::sync::Arc;
+
+/// How a candidate was scored in an election.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoredCandidate<AccountId> {
+	/// The candidate.
+	pub who: AccountId,
+	/// The stake backing the candidate.
+	pub backed_stake: ExtendedBalance,
+	/// The credit of the candidate, out of the full credit.
+	pub credit_score: u32,
+	/// The backed stake relative to the best backed candidate, out of 100.
+	pub stake_score: u32,
+	/// The random draw of the candidate.
+	pub random_number: u32,
+	/// The random draw reduced to the full credit.
+	pub random_score: u32,
+	/// The score the candidates are ranked by.
+	pub final_score: u32,
+	/// Whether the candidate was backed by enough stake to be elected.
+	pub eligible: bool,
+	/// Whether the candidate was elected.
+	pub elected: bool,
+}
+
+impl<AccountId> From<CandidateScore<AccountId>> for ScoredCandidate<AccountId> {
+	fn from(s: CandidateScore<AccountId>) -> Self {
+		Self {
+			who: s.who,
+			backed_stake: s.backed_stake,
+			credit_score: s.credit_score,
+			stake_score: s.stake_score,
+			random_number: s.random_number,
+			random_score: s.random_score,
+			final_score: s.final_score,
+			eligible: s.eligible,
+			elected: s.elected,
+		}
+	}
+}
+
+/// The diagnostics of the validator election of an epoch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElectionDiagnostics<AccountId> {
+	/// The epoch the election ran in.
+	pub epoch_index: u64,
+	/// The scored candidates, best scored first.
+	pub candidates: Vec<ScoredCandidate<AccountId>>,
+}
+
+/// Election diagnostics RPC methods.
+#[rpc(client, server)]
+pub trait ElectionApi<BlockHash, AccountId> {
+	/// Returns how the candidates of the election of `epoch_index` were scored, the last
+	/// recorded election if no epoch is given.
+	#[method(name = "election_diagnostics")]
+	fn election_diagnostics(
+		&self,
+		epoch_index: Option<u64>,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ElectionDiagnostics<AccountId>>>;
+}
+
+/// Provides the election diagnostics recorded on chain.
+pub struct Election<C, Block> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<Block>,
+}
+
+impl<C, Block> Election<C, Block> {
+	/// Create a new instance of the election diagnostics RPC.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> ElectionApiServer<<Block as BlockT>::Hash, AccountId> for Election<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: SchedulerCreditApi<Block, AccountId>,
+	AccountId: Codec + Clone + Serialize + Send + Sync + 'static,
+{
+	fn election_diagnostics(
+		&self,
+		epoch_index: Option<u64>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<ElectionDiagnostics<AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let diagnostics = self
+			.client
+			.runtime_api()
+			.election_diagnostics(at, epoch_index)
+			.map_err(runtime_error_into_rpc_error)?;
+
+		Ok(diagnostics.map(|(epoch_index, scores)| ElectionDiagnostics {
+			epoch_index,
+			candidates: scores.into_iter().map(Into::into).collect(),
+		}))
+	}
+}
+
+fn runtime_error_into_rpc_error(err: impl std::fmt::Debug) -> ErrorObjectOwned {
+	ErrorObject::owned(1, "Runtime error", Some(format!("{:?}", err)))
+}
@@ -28,6 +28,7 @@ use sp_keystore::KeystorePtr;
 use sp_runtime::traits::Block as BlockT;
 use std::sync::Arc;
 
+mod election;
 mod eth;
 pub use self::eth::{create_eth, EthDeps};
 
@@ -118,6 +119,7 @@ where
 	C::Api: fp_rpc::ConvertTransactionRuntimeApi<Block>,
 	C::Api: fp_rpc::EthereumRuntimeRPCApi<Block>,
 	C::Api: BabeApi<Block>,
+	C::Api: cess_node_runtime::scheduler_credit_api::SchedulerCreditApi<Block, AccountId>,
 	C: BlockchainEvents<Block> + 'static,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
 	C: AuxStore + UsageProvider<Block> + StorageProvider<Block, B>,
@@ -137,6 +139,7 @@ where
 	use sc_consensus_beefy_rpc::{Beefy, BeefyApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 	use substrate_state_trie_migration_rpc::{StateMigration, StateMigrationApiServer};
+	use self::election::{Election, ElectionApiServer};
 
 	let mut io = RpcModule::new(());
 	let BabeDeps { keystore, babe_worker_handle } = babe;
@@ -169,6 +172,8 @@ where
 
 	io.merge(StateMigration::new(client.clone(), backend.clone()).into_rpc())?;	
 
+	io.merge(ElectionApiServer::<_, AccountId>::into_rpc(Election::<C, Block>::new(client.clone())))?;
+
 	io.merge(
 		Beefy::<Block, AuthorityId>::new(
 			beefy.beefy_finality_proof_stream,
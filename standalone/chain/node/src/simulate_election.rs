@@ -0,0 +1,116 @@
+//! Replay of the validator election on exported state, without a running chain.
+
+use ces_rrsc_vrf_solver::{simulate, ElectionInput, ExtendedBalance, VoteWeight};
+use polkadot_sdk::*;
+use serde::{Deserialize, Serialize};
+use sp_runtime::{PerThing, Perbill};
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// The state an election runs on, as exported from a chain.
+///
+/// Accounts are kept as opaque strings, so any account format can be replayed.
+#[derive(Debug, Deserialize)]
+pub struct ElectionSnapshot {
+	/// The number of validators to elect.
+	pub to_elect: usize,
+	/// The validator candidates.
+	pub targets: Vec<String>,
+	/// The nominators, as `(who, vote_weight, targets)`.
+	pub voters: Vec<(String, VoteWeight, Vec<String>)>,
+	/// The credit of every candidate in the election epoch.
+	#[serde(default)]
+	pub credits: BTreeMap<String, u32>,
+	/// The full credit score.
+	pub full_credit: u32,
+	/// The lowest vote weight a candidate needs to be elected.
+	pub min_electable_weight: VoteWeight,
+	/// The hex encoded 32 bytes seed of the randomness.
+	pub seed: Option<String>,
+}
+
+/// An elected validator.
+#[derive(Debug, Serialize)]
+pub struct SimulatedWinner {
+	/// The validator.
+	pub who: String,
+	/// The stake backing the validator.
+	pub backed_stake: ExtendedBalance,
+}
+
+/// The targets a nominator backs, with the share of its vote in parts per billion.
+#[derive(Debug, Serialize)]
+pub struct SimulatedAssignment {
+	/// The nominator.
+	pub who: String,
+	/// The backed targets and their share.
+	pub distribution: Vec<(String, u32)>,
+}
+
+/// The outcome of a simulated election.
+#[derive(Debug, Serialize)]
+pub struct SimulatedElection {
+	/// The hex encoded seed the election ran with.
+	pub seed: String,
+	/// The elected validators, best scored first.
+	pub winners: Vec<SimulatedWinner>,
+	/// The nominations backing the elected validators.
+	pub assignments: Vec<SimulatedAssignment>,
+}
+
+/// The `simulate-election` command.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct SimulateElectionCmd {
+	/// Path to the JSON election snapshot.
+	#[arg(long)]
+	pub input: PathBuf,
+
+	/// Hex encoded 32 bytes seed, overriding the one of the snapshot.
+	#[arg(long)]
+	pub seed: Option<String>,
+}
+
+impl SimulateElectionCmd {
+	/// Run the election on the snapshot and print the result as JSON.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let file = std::fs::File::open(&self.input)?;
+		let snapshot: ElectionSnapshot =
+			serde_json::from_reader(file).map_err(|e| sc_cli::Error::Input(format!("Invalid snapshot: {e}")))?;
+
+		let seed = match self.seed.as_ref().or(snapshot.seed.as_ref()) {
+			Some(seed) => array_bytes::hex2array::<_, 32>(seed)
+				.map_err(|e| sc_cli::Error::Input(format!("Invalid seed: {e:?}")))?,
+			None => [0u8; 32],
+		};
+
+		let input = ElectionInput {
+			to_elect: snapshot.to_elect,
+			targets: snapshot.targets,
+			voters: snapshot.voters,
+			credits: snapshot.credits,
+			full_credit: snapshot.full_credit,
+			min_electable_weight: snapshot.min_electable_weight,
+		};
+		let result = simulate::<String, Perbill>(input, seed);
+
+		let output = SimulatedElection {
+			seed: array_bytes::bytes2hex("0x", seed),
+			winners: result
+				.winners
+				.into_iter()
+				.map(|(who, backed_stake)| SimulatedWinner { who, backed_stake })
+				.collect(),
+			assignments: result
+				.assignments
+				.into_iter()
+				.map(|a| SimulatedAssignment {
+					who: a.who,
+					distribution: a.distribution.into_iter().map(|(t, p)| (t, p.deconstruct())).collect(),
+				})
+				.collect(),
+		};
+		let json = serde_json::to_string_pretty(&output).map_err(|e| sc_cli::Error::Application(Box::new(e)))?;
+		println!("{json}");
+
+		Ok(())
+	}
+}
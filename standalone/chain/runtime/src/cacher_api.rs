@@ -0,0 +1,20 @@
+//! Runtime API definition for discovering cachers.
+
+use polkadot_sdk::*;
+use alloc::vec::Vec;
+use codec::Codec;
+use cp_cess_common::Hash;
+use pallet_cacher::CacherListing;
+
+sp_api::decl_runtime_apis! {
+	pub trait CacherApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns up to `limit` cachers whose deposit covers the required deposit. Cachers paid
+		/// for serving more of `fragments` come first, then the cheaper ones, then those with the
+		/// better reputation.
+		fn cachers(fragments: Vec<Hash>, limit: u32) -> Vec<CacherListing<AccountId, Balance>>;
+	}
+}
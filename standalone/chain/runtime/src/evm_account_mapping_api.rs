@@ -0,0 +1,23 @@
+//! Runtime API definition for the EVM account bindings.
+
+use polkadot_sdk::*;
+use codec::Codec;
+use pallet_evm_account_mapping::BindingInfo;
+use sp_core::H160;
+
+sp_api::decl_runtime_apis! {
+	pub trait EvmAccountMappingApi<AccountId, BlockNumber>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the account `evm_address` resolves to in the EVM, whether it is bound or not.
+		fn evm_account(evm_address: H160) -> AccountId;
+
+		/// Returns the account the meta calls of `who` act as.
+		fn canonical_account(who: AccountId) -> AccountId;
+
+		/// Returns the EVM key bound to `who`, if any.
+		fn binding(who: AccountId) -> Option<BindingInfo<AccountId, BlockNumber>>;
+	}
+}
@@ -0,0 +1,32 @@
+//! Runtime API definition for the file bank object namespaces.
+
+use polkadot_sdk::*;
+use alloc::vec::Vec;
+use codec::Codec;
+use cp_cess_common::TerrName;
+use pallet_file_bank::{ListedObject, ObjectInfo};
+
+sp_api::decl_runtime_apis! {
+	pub trait FileBankApi<AccountId, Balance, BlockNumber>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the file `key` names in the namespace of the owner's territory.
+		fn object(owner: AccountId, territory_name: TerrName, key: Vec<u8>) -> Option<ObjectInfo<AccountId, Balance, BlockNumber>>;
+
+		/// Returns up to `limit` objects of the owner's territory whose key starts with `prefix`,
+		/// following `start_after`, in key order. With a `delimiter`, the keys containing it after
+		/// the prefix are listed once, as their common prefix. The last key or common prefix
+		/// returned is the `start_after` of the next page.
+		fn list_objects(
+			owner: AccountId,
+			territory_name: TerrName,
+			prefix: Vec<u8>,
+			delimiter: Option<Vec<u8>>,
+			start_after: Option<Vec<u8>>,
+			limit: u32,
+		) -> Vec<ListedObject<AccountId, Balance, BlockNumber>>;
+	}
+}
@@ -6,7 +6,7 @@ use core::marker::PhantomData;
 use fp_evm::weight_per_gas;
 use frame_support::{parameter_types, traits::FindAuthor};
 use pallet_ethereum::PostLogContent;
-use pallet_evm::{EnsureAddressTruncated, HashedAddressMapping};
+use pallet_evm::{AddressMapping, EnsureAddressTruncated, HashedAddressMapping};
 use precompiles::FrontierPrecompiles;
 use sp_core::{H160, U256};
 use sp_runtime::{
@@ -31,6 +31,23 @@ impl<F: FindAuthor<u32>> FindAuthor<H160> for FindAuthorTruncated<F> {
 	}
 }
 
+/// The account the EVM maps an address to when it isn't bound to one.
+pub struct HashedEvmAddress;
+impl sp_runtime::traits::Convert<H160, AccountId> for HashedEvmAddress {
+	fn convert(address: H160) -> AccountId {
+		HashedAddressMapping::<BlakeTwo256>::into_account_id(address)
+	}
+}
+
+/// Resolves the addresses bound through `pallet-evm-account-mapping` to their bound account, and
+/// the others as `HashedAddressMapping`.
+pub struct BoundAddressMapping;
+impl AddressMapping<AccountId> for BoundAddressMapping {
+	fn into_account_id(address: H160) -> AccountId {
+		EvmAccountMapping::evm_account(address)
+	}
+}
+
 const BLOCK_GAS_LIMIT: u64 = 75_000_000;
 const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
 /// The maximum storage growth per block in bytes.
@@ -52,7 +69,7 @@ impl pallet_evm::Config for Runtime {
 	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
 	type CallOrigin = EnsureAddressTruncated;
 	type WithdrawOrigin = EnsureAddressTruncated;
-	type AddressMapping = HashedAddressMapping<BlakeTwo256>;
+	type AddressMapping = BoundAddressMapping;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type PrecompilesType = FrontierPrecompiles<Self>;
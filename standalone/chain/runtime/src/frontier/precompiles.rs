@@ -1,3 +1,10 @@
+mod sminer;
+mod staking;
+mod storage;
+#[cfg(test)]
+mod testing;
+mod utils;
+
 use core::marker::PhantomData;
 use pallet_evm::{IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult, PrecompileSet};
 use polkadot_sdk::sp_core::H160;
@@ -5,6 +12,16 @@ use polkadot_sdk::sp_core::H160;
 use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+pub use sminer::MinerPrecompile;
+pub use staking::StakingPrecompile;
+pub use storage::StoragePrecompile;
+
+/// Address of the CESS storage precompile, see `CessStorage.sol`.
+pub const STORAGE_PRECOMPILE_ADDRESS: u64 = 2048;
+/// Address of the CESS staking precompile, see `CessStaking.sol`.
+pub const STAKING_PRECOMPILE_ADDRESS: u64 = 2049;
+/// Address of the CESS storage miner precompile, see `CessMiner.sol`.
+pub const MINER_PRECOMPILE_ADDRESS: u64 = 2050;
 
 pub struct FrontierPrecompiles<R>(PhantomData<R>);
 
@@ -15,13 +32,27 @@ where
 	pub fn new() -> Self {
 		Self(Default::default())
 	}
-	pub fn used_addresses() -> [H160; 7] {
-		[hash(1), hash(2), hash(3), hash(4), hash(5), hash(1024), hash(1025)]
+	pub fn used_addresses() -> [H160; 10] {
+		[
+			hash(1),
+			hash(2),
+			hash(3),
+			hash(4),
+			hash(5),
+			hash(1024),
+			hash(1025),
+			hash(STORAGE_PRECOMPILE_ADDRESS),
+			hash(STAKING_PRECOMPILE_ADDRESS),
+			hash(MINER_PRECOMPILE_ADDRESS),
+		]
 	}
 }
 impl<R> PrecompileSet for FrontierPrecompiles<R>
 where
 	R: pallet_evm::Config,
+	StoragePrecompile<R>: Precompile,
+	StakingPrecompile<R>: Precompile,
+	MinerPrecompile<R>: Precompile,
 {
 	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
 		match handle.code_address() {
@@ -34,6 +65,10 @@ where
 			// Non-Frontier specific nor Ethereum precompiles :
 			a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
 			a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
+			// CESS specific precompiles :
+			a if a == hash(STORAGE_PRECOMPILE_ADDRESS) => Some(StoragePrecompile::<R>::execute(handle)),
+			a if a == hash(STAKING_PRECOMPILE_ADDRESS) => Some(StakingPrecompile::<R>::execute(handle)),
+			a if a == hash(MINER_PRECOMPILE_ADDRESS) => Some(MinerPrecompile::<R>::execute(handle)),
 			_ => None,
 		}
 	}
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The CessMiner contract's address.
+address constant CESS_MINER_ADDRESS = 0x0000000000000000000000000000000000000802;
+
+/// @dev The CessMiner contract's instance.
+CessMiner constant CESS_MINER_CONTRACT = CessMiner(CESS_MINER_ADDRESS);
+
+/// @title CESS storage miner interface
+/// @notice Registers and funds storage miners with the calling account as the miner.
+/// Substrate accounts are addressed by their 32 byte account id.
+/// @custom:address 0x0000000000000000000000000000000000000802
+interface CessMiner {
+    /// @dev Raised when the miner pallet rejects a call, `reason` names the pallet error.
+    error MinerError(string reason);
+
+    /// @dev Register the caller as a miner declaring `tibCount` TiB, staking `stakingVal`.
+    /// @custom:selector 67b5af2c
+    function regnstk(bytes32 beneficiary, bytes memory endpoint, uint256 stakingVal, uint32 tibCount) external;
+
+    /// @dev Add `collaterals` from the caller to the stake of `miner`.
+    /// @custom:selector beea88a1
+    function increaseCollateral(bytes32 miner, uint256 collaterals) external;
+
+    /// @dev Change the account receiving the caller's rewards.
+    /// @custom:selector faa64ddc
+    function updateBeneficiary(bytes32 beneficiary) external;
+
+    /// @dev Pay the caller's released rewards to its beneficiary.
+    /// @custom:selector ff3d9e4f
+    function receiveReward() external;
+
+    /// @dev Emitted when `miner` registered.
+    event Registered(address indexed miner);
+
+    /// @dev Emitted when `sender` increased a miner's stake, `balance` being its new collaterals.
+    event IncreaseCollateral(address indexed sender, uint256 balance);
+
+    /// @dev Emitted when `miner` changed its beneficiary.
+    event UpdateBeneficiary(address indexed miner, bytes32 beneficiary);
+
+    /// @dev Emitted when `reward` was paid to `beneficiary`.
+    event Receive(bytes32 indexed beneficiary, uint256 reward);
+}
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The CessStaking contract's address.
+address constant CESS_STAKING_ADDRESS = 0x0000000000000000000000000000000000000801;
+
+/// @dev The CessStaking contract's instance.
+CessStaking constant CESS_STAKING_CONTRACT = CessStaking(CESS_STAKING_ADDRESS);
+
+/// @title CESS staking interface
+/// @notice Bonds and nominates with the calling account as stash and controller.
+/// Validators are addressed by their 32 byte substrate account id.
+/// @custom:address 0x0000000000000000000000000000000000000801
+interface CessStaking {
+    /// @dev Raised when the staking pallet rejects a call, `reason` names the pallet error.
+    error StakingError(string reason);
+
+    /// @dev Bond `value` from the caller. `payee` is 0 to restake rewards and 1 to pay them to the stash.
+    /// @custom:selector d5c90b29
+    function bond(uint256 value, uint8 payee) external;
+
+    /// @dev Nominate `targets` with the caller's bonded funds.
+    /// @custom:selector f5330e96
+    function nominate(bytes32[] memory targets) external;
+
+    /// @dev Schedule `value` of the caller's active stake to be unlocked.
+    /// @custom:selector 27de9e32
+    function unbond(uint256 value) external;
+
+    /// @dev Pay out the rewards of `validatorStash` and its nominators for `era`.
+    /// @custom:selector 6bc56089
+    function payoutStakers(bytes32 validatorStash, uint32 era) external;
+
+    /// @dev Emitted when `stash` bonded `amount`.
+    event Bonded(address indexed stash, uint256 amount);
+
+    /// @dev Emitted when `stash` unbonded `amount`.
+    event Unbonded(address indexed stash, uint256 amount);
+
+    /// @dev Emitted when the payout of `validatorStash` for `era` starts.
+    event PayoutStarted(uint32 indexed era, bytes32 indexed validatorStash);
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: GPL-3.0-only
+pragma solidity >=0.8.3;
+
+/// @dev The CessStorage contract's address.
+address constant CESS_STORAGE_ADDRESS = 0x0000000000000000000000000000000000000800;
+
+/// @dev The CessStorage contract's instance.
+CessStorage constant CESS_STORAGE_CONTRACT = CessStorage(CESS_STORAGE_ADDRESS);
+
+/// @title CESS storage interface
+/// @notice Buys territories and declares files on behalf of the calling account.
+/// Hashes are the 64 byte hex encoded hashes used by the CESS chain.
+/// @custom:address 0x0000000000000000000000000000000000000800
+interface CessStorage {
+    /// @dev A segment of a declared file and the hashes of its fragments.
+    struct Segment {
+        bytes hash;
+        bytes[] fragments;
+    }
+
+    /// @dev Buy a new territory of `gibCount` GiB, valid for `days` days.
+    /// @custom:selector 853b61b1
+    function mintTerritory(uint32 gibCount, string memory territoryName, uint32 days) external;
+
+    /// @dev Add `gibCount` GiB to an active territory until its current deadline.
+    /// @custom:selector a8207480
+    function expandingTerritory(string memory territoryName, uint32 gibCount) external;
+
+    /// @dev Extend an active or frozen territory by `days` days.
+    /// @custom:selector fd52306f
+    function renewalTerritory(string memory territoryName, uint32 days) external;
+
+    /// @dev Declare a file to be stored in the caller's territory.
+    /// @custom:selector 561a9de5
+    function uploadDeclaration(
+        bytes memory fileHash,
+        Segment[] memory segments,
+        string memory fileName,
+        string memory territoryName,
+        uint128 fileSize
+    ) external;
+
+    /// @dev Delete a file of `owner`. The caller must be the owner or an authorized operator.
+    /// @custom:selector dce6016d
+    function deleteFile(address owner, bytes memory fileHash) external;
+
+    /// @dev Read a territory of `owner`.
+    /// `state` is 0 for active, 1 frozen, 2 expired and 3 on consignment.
+    /// @custom:selector 00889dee
+    function territoryInfo(address owner, string memory territoryName)
+        external
+        view
+        returns (
+            bool exists,
+            uint128 totalSpace,
+            uint128 usedSpace,
+            uint128 lockedSpace,
+            uint128 remainingSpace,
+            uint32 start,
+            uint32 deadline,
+            uint8 state
+        );
+
+    /// @dev Read a file. `state` is 0 for active, 1 calculate, 2 missing and 3 recovery.
+    /// @custom:selector 33ea7b06
+    function fileState(bytes memory fileHash) external view returns (bool exists, uint8 state, uint128 fileSize);
+}
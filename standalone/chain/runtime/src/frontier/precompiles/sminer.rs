@@ -0,0 +1,173 @@
+//! Precompile letting EVM accounts register and fund storage miners through `pallet-sminer`.
+//!
+//! The EVM caller is mapped to its substrate account through the runtime's `AddressMapping`.
+//! Failing calls revert with `MinerError(string)` naming the pallet error, and successful ones
+//! emit logs mirroring the pallet events. The Solidity interface is `CessMiner.sol`.
+
+use polkadot_sdk::*;
+use super::utils::{balance_of, try_dispatch};
+use alloc::vec::Vec;
+use codec::MaxEncodedLen;
+use core::marker::PhantomData;
+use frame_support::{
+	dispatch::{GetDispatchInfo, PostDispatchInfo},
+	traits::Currency,
+};
+use pallet_evm::AddressMapping;
+use pallet_sminer::{MinerInfo, Reward};
+use precompile_utils::{prelude::*, solidity};
+use sp_core::{ConstU32, H256, U256};
+use sp_runtime::traits::{Dispatchable, Zero};
+
+#[cfg(test)]
+mod tests;
+
+type BalanceOf<Runtime> = <<Runtime as pallet_sminer::Config>::Currency as Currency<
+	<Runtime as frame_system::Config>::AccountId,
+>>::Balance;
+
+/// Solidity `bytes` holding a miner endpoint.
+pub type EndPointBytes = BoundedBytes<ConstU32<100>>;
+
+/// Selector of `MinerError(string)`.
+pub const MINER_ERROR_SELECTOR: u32 = 0x8dc6fdb2;
+
+/// Solidity selector of the Registered log.
+pub const SELECTOR_LOG_REGISTERED: [u8; 32] = keccak256!("Registered(address)");
+/// Solidity selector of the IncreaseCollateral log.
+pub const SELECTOR_LOG_INCREASE_COLLATERAL: [u8; 32] = keccak256!("IncreaseCollateral(address,uint256)");
+/// Solidity selector of the UpdateBeneficiary log.
+pub const SELECTOR_LOG_UPDATE_BENEFICIARY: [u8; 32] = keccak256!("UpdateBeneficiary(address,bytes32)");
+/// Solidity selector of the Receive log.
+pub const SELECTOR_LOG_RECEIVE: [u8; 32] = keccak256!("Receive(bytes32,uint256)");
+
+pub struct MinerPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> MinerPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config + pallet_sminer::Config,
+	<Runtime as frame_system::Config>::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_sminer::Call<Runtime>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<Runtime::AccountId>>,
+	Runtime::AddressMapping: AddressMapping<Runtime::AccountId>,
+	Runtime::AccountId: From<[u8; 32]> + Into<[u8; 32]>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+{
+	#[precompile::public("regnstk(bytes32,bytes,uint256,uint32)")]
+	fn regnstk(
+		handle: &mut impl PrecompileHandle,
+		beneficiary: H256,
+		endpoint: EndPointBytes,
+		staking_val: U256,
+		tib_count: u32,
+	) -> EvmResult {
+		let miner = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let endpoint: Vec<u8> = endpoint.into();
+		let staking_val = balance_of::<BalanceOf<Runtime>>(staking_val, "stakingVal")?;
+		handle.record_log_costs_manual(2, 0)?;
+
+		let call = pallet_sminer::Call::<Runtime>::regnstk {
+			beneficiary: beneficiary.to_fixed_bytes().into(),
+			endpoint: endpoint.try_into().map_err(|_| RevertReason::custom("endpoint too long").in_field("endpoint"))?,
+			staking_val,
+			tib_count,
+		};
+		try_dispatch::<Runtime, _>(handle, miner, call, MinerInfo::<Runtime>::max_encoded_len() as u64, MINER_ERROR_SELECTOR)?;
+
+		log2(handle.context().address, SELECTOR_LOG_REGISTERED, handle.context().caller, Vec::new())
+			.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("increaseCollateral(bytes32,uint256)")]
+	fn increase_collateral(handle: &mut impl PrecompileHandle, miner: H256, collaterals: U256) -> EvmResult {
+		let sender = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let miner: Runtime::AccountId = miner.to_fixed_bytes().into();
+		let collaterals = balance_of::<BalanceOf<Runtime>>(collaterals, "collaterals")?;
+		handle.record_db_read::<Runtime>(MinerInfo::<Runtime>::max_encoded_len())?;
+		handle.record_log_costs_manual(2, 32)?;
+
+		let call = pallet_sminer::Call::<Runtime>::increase_collateral { miner: miner.clone(), collaterals };
+		try_dispatch::<Runtime, _>(handle, sender, call, 0, MINER_ERROR_SELECTOR)?;
+
+		// Like the pallet event, the log carries the miner's collaterals after the increase.
+		let balance: U256 = pallet_sminer::Pallet::<Runtime>::miner_items(&miner)
+			.map(|info| info.collaterals)
+			.unwrap_or_else(Zero::zero)
+			.into();
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_INCREASE_COLLATERAL,
+			handle.context().caller,
+			solidity::encode_event_data(balance),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("updateBeneficiary(bytes32)")]
+	fn update_beneficiary(handle: &mut impl PrecompileHandle, beneficiary: H256) -> EvmResult {
+		let miner = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		handle.record_log_costs_manual(2, 32)?;
+
+		let call = pallet_sminer::Call::<Runtime>::update_beneficiary { beneficiary: beneficiary.to_fixed_bytes().into() };
+		try_dispatch::<Runtime, _>(handle, miner, call, 0, MINER_ERROR_SELECTOR)?;
+
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_UPDATE_BENEFICIARY,
+			handle.context().caller,
+			solidity::encode_event_data(beneficiary),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("receiveReward()")]
+	fn receive_reward(handle: &mut impl PrecompileHandle) -> EvmResult {
+		let miner = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		handle.record_db_read::<Runtime>(MinerInfo::<Runtime>::max_encoded_len())?;
+		handle.record_db_read::<Runtime>(Reward::<Runtime>::max_encoded_len())?;
+		handle.record_db_read::<Runtime>(Reward::<Runtime>::max_encoded_len())?;
+		handle.record_log_costs_manual(2, 32)?;
+
+		let info = pallet_sminer::Pallet::<Runtime>::miner_items(&miner);
+		let before = Self::reward_issued(&miner);
+		let call = pallet_sminer::Call::<Runtime>::receive_reward {};
+		try_dispatch::<Runtime, _>(handle, miner.clone(), call, 0, MINER_ERROR_SELECTOR)?;
+
+		// The pallet only pays out, and emits its event, for registered miners.
+		if let Some(info) = info {
+			let reward = Self::reward_issued(&miner).saturating_sub(before);
+			let beneficiary: [u8; 32] = info.beneficiary.into();
+			log2(
+				handle.context().address,
+				SELECTOR_LOG_RECEIVE,
+				H256::from(beneficiary),
+				solidity::encode_event_data(reward),
+			)
+			.record(handle)?;
+		}
+
+		Ok(())
+	}
+}
+
+impl<Runtime> MinerPrecompile<Runtime>
+where
+	Runtime: pallet_sminer::Config,
+	BalanceOf<Runtime>: Into<U256>,
+{
+	fn reward_issued(miner: &Runtime::AccountId) -> U256 {
+		pallet_sminer::Pallet::<Runtime>::reward_map(miner)
+			.map(|reward| reward.reward_issued)
+			.unwrap_or_else(Zero::zero)
+			.into()
+	}
+}
@@ -0,0 +1,46 @@
+use super::*;
+use crate::{
+	frontier::precompiles::{testing::*, FrontierPrecompiles, MINER_PRECOMPILE_ADDRESS},
+	Runtime,
+};
+use precompile_utils::testing::*;
+use sp_core::H160;
+
+type PCall = MinerPrecompileCall<Runtime>;
+
+fn precompile() -> H160 {
+	H160::from_low_u64_be(MINER_PRECOMPILE_ADDRESS)
+}
+
+fn precompiles() -> FrontierPrecompiles<Runtime> {
+	FrontierPrecompiles::new()
+}
+
+fn miner_error(reason: &str) -> Vec<u8> {
+	solidity::encode_with_selector(MINER_ERROR_SELECTOR, UnboundedString::from(reason))
+}
+
+#[test]
+fn selectors_match_solidity_interface() {
+	check_precompile_implements_solidity_interfaces(&["src/frontier/precompiles/CessMiner.sol"], PCall::supports_selector);
+	assert_eq!(MINER_ERROR_SELECTOR.to_be_bytes(), keccak256!("MinerError(string)")[..4]);
+}
+
+#[test]
+fn pallet_errors_revert_with_miner_error() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(ALICE, precompile(), PCall::update_beneficiary { beneficiary: H256::repeat_byte(1) })
+			.execute_reverts(|output| output == miner_error("NotMiner"));
+	});
+}
+
+#[test]
+fn receive_reward_of_unregistered_account_has_no_log() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(ALICE, precompile(), PCall::receive_reward {})
+			.expect_no_logs()
+			.execute_returns(());
+	});
+}
@@ -0,0 +1,149 @@
+//! Precompile letting EVM accounts bond, nominate and claim rewards through `pallet-cess-staking`.
+//!
+//! The EVM caller is mapped to its substrate stash through the runtime's `AddressMapping`.
+//! Failing calls revert with `StakingError(string)` naming the pallet error, and successful ones
+//! emit logs mirroring the pallet events. The Solidity interface is `CessStaking.sol`.
+
+use polkadot_sdk::*;
+use super::utils::{balance_of, try_dispatch};
+use alloc::vec::Vec;
+use codec::MaxEncodedLen;
+use core::marker::PhantomData;
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+use pallet_cess_staking::{BalanceOf, RewardDestination, StakingLedger};
+use pallet_evm::AddressMapping;
+use precompile_utils::{prelude::*, solidity};
+use sp_core::{H256, U256};
+use sp_runtime::traits::{Dispatchable, StaticLookup, Zero};
+use sp_staking::StakingAccount;
+
+#[cfg(test)]
+mod tests;
+
+/// Selector of `StakingError(string)`.
+pub const STAKING_ERROR_SELECTOR: u32 = 0x8acbb562;
+
+/// Solidity selector of the Bonded log.
+pub const SELECTOR_LOG_BONDED: [u8; 32] = keccak256!("Bonded(address,uint256)");
+/// Solidity selector of the Unbonded log.
+pub const SELECTOR_LOG_UNBONDED: [u8; 32] = keccak256!("Unbonded(address,uint256)");
+/// Solidity selector of the PayoutStarted log.
+pub const SELECTOR_LOG_PAYOUT_STARTED: [u8; 32] = keccak256!("PayoutStarted(uint32,bytes32)");
+
+pub struct StakingPrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> StakingPrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config + pallet_cess_staking::Config,
+	<Runtime as frame_system::Config>::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_cess_staking::Call<Runtime>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<Runtime::AccountId>>,
+	Runtime::AddressMapping: AddressMapping<Runtime::AccountId>,
+	Runtime::AccountId: From<[u8; 32]> + Into<[u8; 32]>,
+	BalanceOf<Runtime>: TryFrom<U256> + Into<U256>,
+{
+	/// `payee` is 0 to restake rewards and 1 to pay them to the stash.
+	#[precompile::public("bond(uint256,uint8)")]
+	fn bond(handle: &mut impl PrecompileHandle, value: U256, payee: u8) -> EvmResult {
+		let stash = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let value = balance_of::<BalanceOf<Runtime>>(value, "value")?;
+		let payee = match payee {
+			0 => RewardDestination::Staked,
+			1 => RewardDestination::Stash,
+			_ => return Err(RevertReason::custom("unknown reward destination").in_field("payee").into()),
+		};
+		handle.record_db_read::<Runtime>(StakingLedger::<Runtime>::max_encoded_len())?;
+		handle.record_log_costs_manual(2, 32)?;
+
+		let call = pallet_cess_staking::Call::<Runtime>::bond { value, payee };
+		try_dispatch::<Runtime, _>(handle, stash.clone(), call, 0, STAKING_ERROR_SELECTOR)?;
+
+		// The pallet bonds at most the free balance of the stash.
+		let amount = Self::active(stash);
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_BONDED,
+			handle.context().caller,
+			solidity::encode_event_data(amount),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("nominate(bytes32[])")]
+	fn nominate(handle: &mut impl PrecompileHandle, targets: Vec<H256>) -> EvmResult {
+		let controller = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let targets = targets
+			.into_iter()
+			.map(|target| <Runtime as frame_system::Config>::Lookup::unlookup(target.to_fixed_bytes().into()))
+			.collect();
+
+		let call = pallet_cess_staking::Call::<Runtime>::nominate { targets };
+		try_dispatch::<Runtime, _>(handle, controller, call, 0, STAKING_ERROR_SELECTOR)
+	}
+
+	#[precompile::public("unbond(uint256)")]
+	fn unbond(handle: &mut impl PrecompileHandle, value: U256) -> EvmResult {
+		let controller = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let value = balance_of::<BalanceOf<Runtime>>(value, "value")?;
+		handle.record_db_read::<Runtime>(StakingLedger::<Runtime>::max_encoded_len())?;
+		handle.record_db_read::<Runtime>(StakingLedger::<Runtime>::max_encoded_len())?;
+		handle.record_log_costs_manual(2, 32)?;
+
+		// The pallet unbonds at most the active stake.
+		let before = Self::active(controller.clone());
+		let call = pallet_cess_staking::Call::<Runtime>::unbond { value };
+		try_dispatch::<Runtime, _>(handle, controller.clone(), call, 0, STAKING_ERROR_SELECTOR)?;
+		let amount = before.saturating_sub(Self::active(controller));
+
+		log2(
+			handle.context().address,
+			SELECTOR_LOG_UNBONDED,
+			handle.context().caller,
+			solidity::encode_event_data(amount),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("payoutStakers(bytes32,uint32)")]
+	fn payout_stakers(handle: &mut impl PrecompileHandle, validator_stash: H256, era: u32) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		handle.record_log_costs_manual(3, 0)?;
+
+		let call = pallet_cess_staking::Call::<Runtime>::payout_stakers {
+			validator_stash: validator_stash.to_fixed_bytes().into(),
+			era,
+		};
+		try_dispatch::<Runtime, _>(handle, origin, call, 0, STAKING_ERROR_SELECTOR)?;
+
+		log3(
+			handle.context().address,
+			SELECTOR_LOG_PAYOUT_STARTED,
+			H256::from_low_u64_be(era.into()),
+			validator_stash,
+			Vec::new(),
+		)
+		.record(handle)?;
+
+		Ok(())
+	}
+}
+
+impl<Runtime> StakingPrecompile<Runtime>
+where
+	Runtime: pallet_cess_staking::Config,
+	BalanceOf<Runtime>: Into<U256>,
+{
+	fn active(stash: Runtime::AccountId) -> U256 {
+		pallet_cess_staking::Pallet::<Runtime>::ledger(StakingAccount::Stash(stash))
+			.map(|ledger| ledger.active)
+			.unwrap_or_else(|_| Zero::zero())
+			.into()
+	}
+}
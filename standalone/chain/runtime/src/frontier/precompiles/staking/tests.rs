@@ -0,0 +1,64 @@
+use super::*;
+use crate::{
+	constants::currency::DOLLARS,
+	frontier::precompiles::{testing::*, FrontierPrecompiles, STAKING_PRECOMPILE_ADDRESS},
+	Runtime,
+};
+use precompile_utils::testing::*;
+use sp_core::H160;
+
+type PCall = StakingPrecompileCall<Runtime>;
+
+fn precompile() -> H160 {
+	H160::from_low_u64_be(STAKING_PRECOMPILE_ADDRESS)
+}
+
+fn precompiles() -> FrontierPrecompiles<Runtime> {
+	FrontierPrecompiles::new()
+}
+
+fn staking_error(reason: &str) -> Vec<u8> {
+	solidity::encode_with_selector(STAKING_ERROR_SELECTOR, UnboundedString::from(reason))
+}
+
+#[test]
+fn selectors_match_solidity_interface() {
+	check_precompile_implements_solidity_interfaces(&["src/frontier/precompiles/CessStaking.sol"], PCall::supports_selector);
+	assert_eq!(STAKING_ERROR_SELECTOR.to_be_bytes(), keccak256!("StakingError(string)")[..4]);
+}
+
+#[test]
+fn bond_emits_bonded_log() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(ALICE, precompile(), PCall::bond { value: U256::from(100 * DOLLARS), payee: 0 })
+			.expect_log(log2(
+				precompile(),
+				SELECTOR_LOG_BONDED,
+				ALICE,
+				solidity::encode_event_data(U256::from(100 * DOLLARS)),
+			))
+			.execute_returns(());
+
+		let ledger = pallet_cess_staking::Pallet::<Runtime>::ledger(StakingAccount::Stash(account(ALICE))).unwrap();
+		assert_eq!(ledger.active, 100 * DOLLARS);
+	});
+}
+
+#[test]
+fn unknown_reward_destination_reverts() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(ALICE, precompile(), PCall::bond { value: U256::from(100 * DOLLARS), payee: 2 })
+			.execute_reverts(|output| output.ends_with(b"unknown reward destination"));
+	});
+}
+
+#[test]
+fn pallet_errors_revert_with_staking_error() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(ALICE, precompile(), PCall::unbond { value: U256::from(DOLLARS) })
+			.execute_reverts(|output| output == staking_error("NotController"));
+	});
+}
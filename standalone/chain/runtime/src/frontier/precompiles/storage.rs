@@ -0,0 +1,207 @@
+//! Precompile exposing CESS territories and file declarations to EVM contracts.
+//!
+//! Calls are dispatched on behalf of the EVM caller, mapped to a substrate account through the
+//! runtime's `AddressMapping`. The Solidity interface lives next to this file in `CessStorage.sol`.
+
+use polkadot_sdk::*;
+use alloc::vec::Vec;
+use codec::MaxEncodedLen;
+use core::marker::PhantomData;
+use cp_cess_common::{Hash, TerrName};
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::AddressMapping;
+use pallet_file_bank::{FileInfo, FileState, SegmentList, UserBrief};
+use pallet_storage_handler::{TerritoryInfo, TerritoryState};
+use precompile_utils::{prelude::*, solidity};
+use sp_core::{ConstU32, H256};
+use sp_runtime::traits::{Dispatchable, UniqueSaturatedInto};
+
+#[cfg(test)]
+mod tests;
+
+/// Solidity `bytes` holding a 64 byte CESS hash.
+pub type HashBytes = BoundedBytes<ConstU32<64>>;
+/// Solidity `string` holding a territory name.
+pub type TerritoryName = BoundedString<ConstU32<63>>;
+
+/// A segment of a declared file and the hashes of its fragments.
+#[derive(Clone, Debug, Eq, PartialEq, solidity::Codec)]
+pub struct Segment {
+	pub hash: HashBytes,
+	pub fragments: Vec<HashBytes>,
+}
+
+pub struct StoragePrecompile<Runtime>(PhantomData<Runtime>);
+
+#[precompile_utils::precompile]
+impl<Runtime> StoragePrecompile<Runtime>
+where
+	Runtime: pallet_evm::Config + pallet_file_bank::Config + pallet_storage_handler::Config,
+	<Runtime as frame_system::Config>::RuntimeCall: Dispatchable<PostInfo = PostDispatchInfo>
+		+ GetDispatchInfo
+		+ From<pallet_file_bank::Call<Runtime>>
+		+ From<pallet_storage_handler::Call<Runtime>>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<Runtime::AccountId>>,
+	Runtime::AddressMapping: AddressMapping<Runtime::AccountId>,
+{
+	#[precompile::public("mintTerritory(uint32,string,uint32)")]
+	fn mint_territory(
+		handle: &mut impl PrecompileHandle,
+		gib_count: u32,
+		territory_name: TerritoryName,
+		days: u32,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let territory_name = territory_name_of(territory_name)?;
+		// A new territory entry and the token pointing back at it.
+		let storage_growth = (TerritoryInfo::<Runtime>::max_encoded_len() + H256::max_encoded_len()) as u64;
+		let call = pallet_storage_handler::Call::<Runtime>::mint_territory { gib_count, territory_name, days };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, storage_growth)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("expandingTerritory(string,uint32)")]
+	fn expanding_territory(handle: &mut impl PrecompileHandle, territory_name: TerritoryName, gib_count: u32) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let territory_name = territory_name_of(territory_name)?;
+		let call = pallet_storage_handler::Call::<Runtime>::expanding_territory { territory_name, gib_count };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("renewalTerritory(string,uint32)")]
+	fn renewal_territory(handle: &mut impl PrecompileHandle, territory_name: TerritoryName, days: u32) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let territory_name = territory_name_of(territory_name)?;
+		let call = pallet_storage_handler::Call::<Runtime>::renewal_territory { territory_name, days };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("uploadDeclaration(bytes,(bytes,bytes[])[],string,string,uint128)")]
+	fn upload_declaration(
+		handle: &mut impl PrecompileHandle,
+		file_hash: HashBytes,
+		segments: Vec<Segment>,
+		file_name: UnboundedString,
+		territory_name: TerritoryName,
+		file_size: u128,
+	) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let file_hash = hash_of(file_hash)?;
+		let deal_info = segments
+			.into_iter()
+			.map(|segment| {
+				let fragment_list = segment
+					.fragments
+					.into_iter()
+					.map(hash_of)
+					.collect::<EvmResult<Vec<Hash>>>()?
+					.try_into()
+					.map_err(|_| revert("too many fragments"))?;
+				Ok(SegmentList::<Runtime> { hash: hash_of(segment.hash)?, fragment_list })
+			})
+			.collect::<EvmResult<Vec<_>>>()?
+			.try_into()
+			.map_err(|_| revert("too many segments"))?;
+		let file_name: Vec<u8> = file_name.into();
+		let user_brief = UserBrief::<Runtime> {
+			user: origin.clone(),
+			file_name: file_name.try_into().map_err(|_| revert("file name too long"))?,
+			territory_name: territory_name_of(territory_name)?,
+		};
+		// The deal waiting for miners to store the file's fragments.
+		let storage_growth = FileInfo::<Runtime>::max_encoded_len() as u64;
+		let call = pallet_file_bank::Call::<Runtime>::upload_declaration { file_hash, deal_info, user_brief, file_size };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, storage_growth)?;
+
+		Ok(())
+	}
+
+	#[precompile::public("deleteFile(address,bytes)")]
+	fn delete_file(handle: &mut impl PrecompileHandle, owner: Address, file_hash: HashBytes) -> EvmResult {
+		let origin = Runtime::AddressMapping::into_account_id(handle.context().caller);
+		let owner = Runtime::AddressMapping::into_account_id(owner.into());
+		let file_hash = hash_of(file_hash)?;
+		let call = pallet_file_bank::Call::<Runtime>::delete_file { owner, file_hash };
+		RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, 0)?;
+
+		Ok(())
+	}
+
+	/// Returns whether `owner` holds the territory, followed by its total, used, locked and
+	/// remaining space, start and deadline blocks and state.
+	#[precompile::public("territoryInfo(address,string)")]
+	#[precompile::view]
+	fn territory_info(
+		handle: &mut impl PrecompileHandle,
+		owner: Address,
+		territory_name: TerritoryName,
+	) -> EvmResult<(bool, u128, u128, u128, u128, u32, u32, u8)> {
+		handle.record_db_read::<Runtime>(TerritoryInfo::<Runtime>::max_encoded_len())?;
+		let owner = Runtime::AddressMapping::into_account_id(owner.into());
+		let territory_name = territory_name_of(territory_name)?;
+
+		Ok(match pallet_storage_handler::Pallet::<Runtime>::territory(&owner, &territory_name) {
+			Some(info) => (
+				true,
+				info.total_space,
+				info.used_space,
+				info.locked_space,
+				info.remaining_space,
+				info.start.unique_saturated_into(),
+				info.deadline.unique_saturated_into(),
+				territory_state_code(&info.state),
+			),
+			None => Default::default(),
+		})
+	}
+
+	/// Returns whether the file exists, followed by its state and size.
+	#[precompile::public("fileState(bytes)")]
+	#[precompile::view]
+	fn file_state(handle: &mut impl PrecompileHandle, file_hash: HashBytes) -> EvmResult<(bool, u8, u128)> {
+		handle.record_db_read::<Runtime>(FileInfo::<Runtime>::max_encoded_len())?;
+		let file_hash = hash_of(file_hash)?;
+
+		Ok(match pallet_file_bank::Pallet::<Runtime>::file(&file_hash) {
+			Some(info) => (true, file_state_code(&info.stat), info.file_size),
+			None => Default::default(),
+		})
+	}
+}
+
+fn hash_of(bytes: HashBytes) -> EvmResult<Hash> {
+	let bytes: Vec<u8> = bytes.into();
+	let hash: [u8; 64] = bytes.as_slice().try_into().map_err(|_| revert("hash must be 64 bytes"))?;
+	Ok(Hash(hash))
+}
+
+fn territory_name_of(name: TerritoryName) -> EvmResult<TerrName> {
+	let name: Vec<u8> = name.into();
+	name.try_into().map_err(|_| revert("territory name too long"))
+}
+
+/// Codes follow the variant order of `TerritoryState`, as mirrored in `CessStorage.sol`.
+fn territory_state_code(state: &TerritoryState) -> u8 {
+	match state {
+		TerritoryState::Active => 0,
+		TerritoryState::Frozen => 1,
+		TerritoryState::Expired => 2,
+		TerritoryState::OnConsignment => 3,
+	}
+}
+
+/// Codes follow the variant order of `FileState`, as mirrored in `CessStorage.sol`.
+fn file_state_code(stat: &FileState) -> u8 {
+	match stat {
+		FileState::Active => 0,
+		FileState::Calculate => 1,
+		FileState::Missing => 2,
+		FileState::Recovery => 3,
+	}
+}
@@ -0,0 +1,111 @@
+use super::*;
+use crate::{
+	frontier::precompiles::{testing::*, FrontierPrecompiles, STORAGE_PRECOMPILE_ADDRESS},
+	Runtime,
+};
+use cp_cess_common::G_BYTE;
+use precompile_utils::testing::*;
+use sp_core::H160;
+
+type PCall = StoragePrecompileCall<Runtime>;
+
+fn precompile() -> H160 {
+	H160::from_low_u64_be(STORAGE_PRECOMPILE_ADDRESS)
+}
+
+fn precompiles() -> FrontierPrecompiles<Runtime> {
+	FrontierPrecompiles::new()
+}
+
+fn territory_name() -> TerritoryName {
+	TerritoryName::from("evm")
+}
+
+fn hash_bytes(byte: u8) -> HashBytes {
+	HashBytes::from(vec![byte; 64])
+}
+
+#[test]
+fn selectors_match_solidity_interface() {
+	check_precompile_implements_solidity_interfaces(
+		&["src/frontier/precompiles/CessStorage.sol"],
+		PCall::supports_selector,
+	);
+}
+
+#[test]
+fn storage_precompile_is_registered() {
+	assert!(FrontierPrecompiles::<Runtime>::used_addresses().contains(&precompile()));
+}
+
+#[test]
+fn mint_territory_is_dispatched_for_the_mapped_caller() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				ALICE,
+				precompile(),
+				PCall::mint_territory { gib_count: 2, territory_name: territory_name(), days: 30 },
+			)
+			.execute_returns(());
+
+		let name: TerrName = b"evm".to_vec().try_into().unwrap();
+		let info = pallet_storage_handler::Pallet::<Runtime>::territory(&account(ALICE), &name)
+			.expect("territory minted for the mapped account");
+		assert_eq!(info.total_space, 2 * G_BYTE);
+
+		precompiles()
+			.prepare_test(BOB, precompile(), PCall::territory_info { owner: Address(ALICE), territory_name: territory_name() })
+			.expect_no_logs()
+			.execute_returns((true, 2 * G_BYTE, 0u128, 0u128, 2 * G_BYTE, info.start, info.deadline, 0u8));
+	});
+}
+
+#[test]
+fn mint_territory_reverts_when_the_call_fails() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(
+				BOB,
+				precompile(),
+				PCall::mint_territory { gib_count: 1, territory_name: territory_name(), days: 30 },
+			)
+			.execute_reverts(|output| core::str::from_utf8(output).unwrap().contains("InsufficientBalance"));
+	});
+}
+
+#[test]
+fn territory_info_of_unknown_territory_is_empty() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(ALICE, precompile(), PCall::territory_info { owner: Address(BOB), territory_name: territory_name() })
+			.execute_returns((false, 0u128, 0u128, 0u128, 0u128, 0u32, 0u32, 0u8));
+	});
+}
+
+#[test]
+fn file_state_of_unknown_file_is_empty() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(ALICE, precompile(), PCall::file_state { file_hash: hash_bytes(b'a') })
+			.execute_returns((false, 0u8, 0u128));
+	});
+}
+
+#[test]
+fn delete_file_reverts_for_unknown_file() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(ALICE, precompile(), PCall::delete_file { owner: Address(ALICE), file_hash: hash_bytes(b'a') })
+			.execute_reverts(|output| core::str::from_utf8(output).unwrap().contains("NonExistent"));
+	});
+}
+
+#[test]
+fn malformed_hashes_are_rejected() {
+	new_test_ext().execute_with(|| {
+		precompiles()
+			.prepare_test(ALICE, precompile(), PCall::file_state { file_hash: HashBytes::from(vec![0u8; 32]) })
+			.execute_reverts(|output| output == b"hash must be 64 bytes");
+	});
+}
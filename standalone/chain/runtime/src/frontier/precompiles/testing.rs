@@ -0,0 +1,29 @@
+//! Test externalities shared by the CESS precompile tests.
+
+use polkadot_sdk::*;
+use crate::{constants::currency::DOLLARS, AccountId, Runtime, System};
+use pallet_evm::AddressMapping;
+use sp_core::H160;
+use sp_runtime::BuildStorage;
+
+pub const ALICE: H160 = H160::repeat_byte(0xAA);
+pub const BOB: H160 = H160::repeat_byte(0xBB);
+
+/// The substrate account the precompiles act for when called by `address`.
+pub fn account(address: H160) -> AccountId {
+	<Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+}
+
+/// Externalities where only `ALICE` is funded.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Runtime> { balances: vec![(account(ALICE), 1_000_000 * DOLLARS)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_storage_handler::GenesisConfig::<Runtime> { price: DOLLARS }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,46 @@
+//! Helpers shared by the CESS precompiles.
+
+use polkadot_sdk::*;
+use frame_support::dispatch::{GetDispatchInfo, PostDispatchInfo};
+use pallet_evm::{ExitRevert, PrecompileFailure};
+use precompile_utils::{prelude::*, solidity};
+use sp_core::U256;
+use sp_runtime::traits::Dispatchable;
+
+/// Dispatches `call` from `origin`.
+///
+/// A failing call reverts with the Solidity custom error `error_selector(string)`, carrying the
+/// name of the pallet `Error` variant (or of the `DispatchError` when not raised by a pallet).
+pub fn try_dispatch<Runtime, Call>(
+	handle: &mut impl PrecompileHandle,
+	origin: Runtime::AccountId,
+	call: Call,
+	storage_growth: u64,
+	error_selector: u32,
+) -> EvmResult
+where
+	Runtime: pallet_evm::Config,
+	<Runtime as frame_system::Config>::RuntimeCall:
+		Dispatchable<PostInfo = PostDispatchInfo> + GetDispatchInfo + From<Call>,
+	<<Runtime as frame_system::Config>::RuntimeCall as Dispatchable>::RuntimeOrigin:
+		From<Option<Runtime::AccountId>>,
+{
+	match RuntimeHelper::<Runtime>::try_dispatch(handle, Some(origin).into(), call, storage_growth) {
+		Ok(_) => Ok(()),
+		Err(TryDispatchError::Substrate(error)) => {
+			let reason: &'static str = error.into();
+			Err(PrecompileFailure::Revert {
+				exit_status: ExitRevert::Reverted,
+				output: solidity::encode_with_selector(error_selector, UnboundedString::from(reason)),
+			})
+		},
+		Err(e) => Err(e.into()),
+	}
+}
+
+/// Converts a Solidity `uint256` into a runtime balance, reverting when it does not fit.
+pub fn balance_of<Balance: TryFrom<U256>>(value: U256, field: &'static str) -> EvmResult<Balance> {
+	value
+		.try_into()
+		.map_err(|_| RevertReason::value_is_too_large("balance type").in_field(field).into())
+}
@@ -22,8 +22,21 @@ use frame_support::traits::{
 	Currency, OnUnbalanced,
 };
 use pallet_asset_tx_payment::HandleCredit;
+use pallet_reservoir::{ReservoirGate, UseType};
+use pallet_storage_handler::StorageHandle;
+#[allow(deprecated)]
+use pallet_transaction_payment::{CurrencyAdapter, OnChargeTransaction};
+use sp_runtime::{
+	traits::{DispatchInfoOf, PostDispatchInfoOf},
+	transaction_validity::TransactionValidityError,
+	Perbill,
+};
+use sp_staking::EraIndex;
 
-use crate::{AccountId, Assets, Authorship, Balances, NegativeImbalance, Runtime};
+use crate::{
+	AccountId, Assets, Authorship, Balance, Balances, NegativeImbalance, Reservoir, Runtime, RuntimeCall,
+	SminerPayoutBase, StorageHandler,
+};
 
 pub struct Author;
 impl OnUnbalanced<NegativeImbalance> for Author {
@@ -61,6 +74,129 @@ impl cp_scheduler_credit::SchedulerStashAccountFinder<AccountId> for SchedulerSt
 	}
 }
 
+/// An account passed KYC once a registrar judged its identity reasonable or known good.
+pub struct IdentityKyc;
+impl pallet_reservoir::KycProvider<AccountId> for IdentityKyc {
+	fn is_verified(acc: &AccountId) -> bool {
+		use pallet_identity::Judgement;
+		pallet_identity::IdentityOf::<Runtime>::get(acc).is_some_and(|registration| {
+			registration
+				.judgements
+				.iter()
+				.any(|(_, judgement)| matches!(judgement, Judgement::KnownGood | Judgement::Reasonable))
+		})
+	}
+}
+
+/// Charges transaction fees like [`CurrencyAdapter`], after paying the fee, but not the tip, out of
+/// the payer's gas subsidy loan from the reservoir as far as the loan covers it. Refunds of
+/// overestimated fees go back to the loan up to the part of the subsidy left unused, the rest to
+/// the payer.
+pub struct SubsidizedFees<OU>(core::marker::PhantomData<OU>);
+
+impl<OU> SubsidizedFees<OU> {
+	fn subsidy(who: &AccountId, fee: Balance, tip: Balance) -> Balance {
+		<Reservoir as ReservoirGate<AccountId, Balance>>::spendable(who, UseType::GasSubsidy).min(fee.saturating_sub(tip))
+	}
+}
+
+#[allow(deprecated)]
+impl<OU: OnUnbalanced<NegativeImbalance>> OnChargeTransaction<Runtime> for SubsidizedFees<OU> {
+	type Balance = Balance;
+	/// What [`CurrencyAdapter`] withdrew, along with the subsidy paid towards it.
+	type LiquidityInfo = (<CurrencyAdapter<Balances, OU> as OnChargeTransaction<Runtime>>::LiquidityInfo, Balance);
+
+	fn withdraw_fee(
+		who: &AccountId,
+		call: &RuntimeCall,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		fee: Balance,
+		tip: Balance,
+	) -> Result<Self::LiquidityInfo, TransactionValidityError> {
+		let mut subsidy = Self::subsidy(who, fee, tip);
+		// Should the loan fail to pay, the payer still owes the whole fee.
+		if subsidy > 0 &&
+			frame_support::storage::with_storage_layer(|| {
+				<Reservoir as ReservoirGate<AccountId, Balance>>::spend(who, UseType::GasSubsidy, subsidy, who)
+			})
+			.is_err()
+		{
+			subsidy = 0;
+		}
+
+		let withdrawn = CurrencyAdapter::<Balances, OU>::withdraw_fee(who, call, dispatch_info, fee, tip)?;
+		Ok((withdrawn, subsidy))
+	}
+
+	fn can_withdraw_fee(
+		who: &AccountId,
+		call: &RuntimeCall,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		fee: Balance,
+		tip: Balance,
+	) -> Result<(), TransactionValidityError> {
+		let subsidy = Self::subsidy(who, fee, tip);
+		CurrencyAdapter::<Balances, OU>::can_withdraw_fee(who, call, dispatch_info, fee.saturating_sub(subsidy), tip)
+	}
+
+	fn correct_and_deposit_fee(
+		who: &AccountId,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		post_info: &PostDispatchInfoOf<RuntimeCall>,
+		corrected_fee: Balance,
+		tip: Balance,
+		already_withdrawn: Self::LiquidityInfo,
+	) -> Result<(), TransactionValidityError> {
+		let (already_withdrawn, subsidy) = already_withdrawn;
+		CurrencyAdapter::<Balances, OU>::correct_and_deposit_fee(
+			who,
+			dispatch_info,
+			post_info,
+			corrected_fee,
+			tip,
+			already_withdrawn,
+		)?;
+
+		let unused = subsidy.saturating_sub(corrected_fee.saturating_sub(tip));
+		if unused > 0 {
+			// The payer keeps the refund should the loan fail to take it back.
+			let _ = frame_support::storage::with_storage_layer(|| {
+				<Reservoir as ReservoirGate<AccountId, Balance>>::refund(who, UseType::GasSubsidy, unused, who)
+			});
+		}
+		Ok(())
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn endow_account(who: &AccountId, amount: Balance) {
+		CurrencyAdapter::<Balances, OU>::endow_account(who, amount)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn minimum_balance() -> Balance {
+		CurrencyAdapter::<Balances, OU>::minimum_balance()
+	}
+}
+
+/// Pays storage miners a base share of their scheduled era payout, plus the rest scaled by the
+/// share of the network space that serves user data. The unpaid part goes to the reward reserve.
+pub struct StorageAwareSminerPayout;
+impl pallet_cess_staking::SminerPayout<Balance> for StorageAwareSminerPayout {
+	fn sminer_payout(_era_index: EraIndex, scheduled: Balance) -> Balance {
+		let service_space = StorageHandler::get_total_service_space();
+		let total_space = service_space.saturating_add(StorageHandler::get_total_idle_space());
+		// Nothing to compare against before any miner declared space.
+		if total_space == 0 {
+			return scheduled;
+		}
+
+		let base = SminerPayoutBase::get();
+		let utilization = Perbill::from_rational(service_space, total_space);
+		let share = base.saturating_add(base.left_from_one() * utilization);
+		share * scheduled
+	}
+}
+
 #[cfg(test)]
 mod multiplier_tests {
 	use polkadot_sdk::*;
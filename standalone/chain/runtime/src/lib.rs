@@ -31,7 +31,7 @@ pub use cess_node_primitives::{AccountId, AccountIndex, Balance, BlockNumber, Ha
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_election_provider_support::{
 	bounds::{ElectionBounds, ElectionBoundsBuilder},
-	onchain, ElectionDataProvider, VoteWeight,
+	onchain, BoundedSupportsOf, ElectionDataProvider, ElectionProvider, ElectionProviderBase, VoteWeight,
 };
 use frame_support::{
 	derive_impl,
@@ -112,7 +112,9 @@ pub use sp_runtime::BuildStorage;
 
 /// Implementations of some helper traits passed into runtime modules as associated types.
 pub mod impls;
-use impls::{Author, CreditToBlockAuthor, SchedulerStashAccountFinder};
+use impls::{
+	Author, CreditToBlockAuthor, IdentityKyc, SchedulerStashAccountFinder, StorageAwareSminerPayout, SubsidizedFees,
+};
 
 /// Constant values used within the runtime.
 pub mod constants;
@@ -125,6 +127,27 @@ mod voter_bags;
 /// Runtime API definition for assets.
 pub mod assets_api;
 
+/// Runtime API definition for staking emission.
+pub mod staking_api;
+
+/// Runtime API definition for scheduler credit.
+pub mod scheduler_credit_api;
+
+/// Runtime API definition for the cess treasury.
+pub mod treasury_api;
+
+/// Runtime API definition for the EVM account bindings.
+pub mod evm_account_mapping_api;
+
+/// Runtime API definition for discovering OSS gateways.
+pub mod oss_api;
+
+/// Runtime API definition for the file bank object namespaces.
+pub mod file_bank_api;
+
+/// Runtime API definition for discovering cachers.
+pub mod cacher_api;
+
 mod frontier;
 pub use frontier::TransactionConverter;
 
@@ -259,6 +282,8 @@ impl pallet_tx_pause::Config for Runtime {
 
 #[derive_impl(frame_system::config_preludes::SolochainDefaultConfig)]
 impl frame_system::Config for Runtime {
+	type OnNewAccount = Reservoir;
+	type OnKilledAccount = Reservoir;
 	type BaseCallFilter = InsideBoth<Everything, TxPause>;
 	type BlockWeights = RuntimeBlockWeights;
 	type BlockLength = RuntimeBlockLength;
@@ -481,7 +506,7 @@ parameter_types! {
 #[allow(deprecated)]
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
-	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
+	type OnChargeTransaction = SubsidizedFees<DealWithFees>;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
 	type WeightToFee = ConstantMultiplier<Balance, ConstU128<{ WEIGHT_FEE }>>;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
@@ -595,7 +620,7 @@ impl pallet_cess_staking::Config for Runtime {
 	type EraPayout = (); // pallet_cess_staking::ConvertCurve<RewardCurve>;
 	type NextNewSession = Session;
 	type MaxExposurePageSize = ConstU32<256>;
-	type ElectionProvider = ElectionProviderMultiPhase;
+	type ElectionProvider = DiagnosedElection;
 	type GenesisElectionProvider = onchain::OnChainExecution<OnChainVrf>;
 	type VoterList = VoterList;
 	type NominationsQuota = pallet_cess_staking::FixedNominationsQuota<MAX_QUOTA_NOMINATIONS>;
@@ -615,6 +640,13 @@ impl pallet_cess_staking::Config for Runtime {
 	const REWARD_DECREASE_RATIO: Perbill = Perbill::from_perthousand(841);
 	const REWARD_DECREASE_YEARS: u64 = 30;
 	type SminerRewardPool = CessTreasury;
+	type SminerPayout = StorageAwareSminerPayout;
+	type SminerReserve = pallet_cess_treasury::ReserveRewardPool<Runtime>;
+}
+
+parameter_types! {
+	/// The share of the scheduled sminer payout paid regardless of storage utilization.
+	pub const SminerPayoutBase: Perbill = Perbill::from_percent(50);
 }
 
 pub const ERAS_PER_YEAR: u64 = {
@@ -697,7 +729,9 @@ impl pallet_election_provider_multi_phase::BenchmarkingConfig for ElectionProvid
 	const MAXIMUM_TARGETS: u32 = 300;
 }
 
-use ces_rrsc_vrf_solver::{VrfSloverConfig as RrscVrfSloverConfig, VrfSolver as RrscVrfSolver};
+use ces_rrsc_vrf_solver::{
+	BabeRandomness as RrscBabeRandomness, VrfSloverConfig as RrscVrfSloverConfig, VrfSolver as RrscVrfSolver,
+};
 /// A candidate whose backed stake is less than `MIN_ELECTABLE_STAKE` will never be elected.
 pub const MIN_ELECTABLE_STAKE: Balance = 3_000_000 * DOLLARS;
 /// A config for VrfSolver
@@ -709,13 +743,48 @@ impl RrscVrfSloverConfig for OnChainVrfSloverConfig {
 	}
 }
 
+/// The validator election of staking, recording in `SchedulerCredit` how the candidates of each
+/// enacted election were scored.
+///
+/// The scores are figured in the block the election is enacted, out of the snapshot it ran on:
+/// the miner of the multi-phase election works off chain, where nothing it records is kept.
+pub struct DiagnosedElection;
+impl ElectionProviderBase for DiagnosedElection {
+	type AccountId = AccountId;
+	type BlockNumber = BlockNumber;
+	type Error = <ElectionProviderMultiPhase as ElectionProviderBase>::Error;
+	type MaxWinners = <ElectionProviderMultiPhase as ElectionProviderBase>::MaxWinners;
+	type DataProvider = <ElectionProviderMultiPhase as ElectionProviderBase>::DataProvider;
+}
+
+impl ElectionProvider for DiagnosedElection {
+	fn ongoing() -> bool {
+		ElectionProviderMultiPhase::ongoing()
+	}
+
+	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		// The snapshot is cleared once the election is over.
+		let snapshot = pallet_election_provider_multi_phase::Snapshot::<Runtime>::get();
+		let supports = ElectionProviderMultiPhase::elect()?;
+		if let Some(snapshot) = snapshot {
+			let (epoch_index, mut scores) = <OnChainVrf as onchain::Config>::Solver::score(
+				snapshot.targets,
+				snapshot.voters.into_iter().map(|(who, weight, votes)| (who, weight, votes.into_inner())).collect(),
+			);
+			scores.iter_mut().for_each(|s| s.elected = supports.iter().any(|(who, _)| *who == s.who));
+			<SchedulerCredit as ces_rrsc_vrf_solver::ElectionObserver<AccountId>>::on_scored(epoch_index, scores);
+		}
+		Ok(supports)
+	}
+}
+
 pub struct OnChainVrf;
 impl onchain::Config for OnChainVrf {
 	type System = Runtime;
 	type Solver = RrscVrfSolver<
 		AccountId,
 		pallet_election_provider_multi_phase::SolutionAccuracyOf<Runtime>,
-		Runtime,
+		RrscBabeRandomness<Runtime>,
 		SchedulerCredit,
 		OnChainVrfSloverConfig,
 	>;
@@ -767,7 +836,13 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 	type DataProvider = Staking;
 	type Fallback = onchain::OnChainExecution<OnChainVrf>;
 	type GovernanceFallback = onchain::OnChainExecution<OnChainVrf>;
-	type Solver = RrscVrfSolver<AccountId, SolutionAccuracyOf<Self>, Runtime, SchedulerCredit, OnChainVrfSloverConfig>;
+	type Solver = RrscVrfSolver<
+		AccountId,
+		SolutionAccuracyOf<Self>,
+		RrscBabeRandomness<Runtime>,
+		SchedulerCredit,
+		OnChainVrfSloverConfig,
+	>;
 	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type MaxWinners = MaxActiveValidators;
 	type ElectionBounds = ElectionBoundsMultiPhase;
@@ -889,6 +964,41 @@ impl pallet_asset_rate::Config for Runtime {
 	type BenchmarkHelper = ();
 }
 
+parameter_types! {
+	// difference of 26 bytes on-chain for the registration and 9 bytes on-chain for the identity
+	// information, already accounted for by the byte deposit
+	pub const BasicDeposit: Balance = deposit(1, 17);
+	pub const ByteDeposit: Balance = deposit(0, 1);
+	pub const UsernameDeposit: Balance = deposit(0, 32);
+	pub const SubAccountDeposit: Balance = 2 * DOLLARS; // 53 bytes on-chain
+	pub const MaxSubAccounts: u32 = 100;
+	pub const MaxAdditionalFields: u32 = 100;
+	pub const MaxRegistrars: u32 = 20;
+}
+
+impl pallet_identity::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BasicDeposit = BasicDeposit;
+	type ByteDeposit = ByteDeposit;
+	type UsernameDeposit = UsernameDeposit;
+	type SubAccountDeposit = SubAccountDeposit;
+	type MaxSubAccounts = MaxSubAccounts;
+	type IdentityInformation = pallet_identity::legacy::IdentityInfo<MaxAdditionalFields>;
+	type MaxRegistrars = MaxRegistrars;
+	type Slashed = Treasury;
+	type ForceOrigin = EnsureRootOrHalfCouncil;
+	type RegistrarOrigin = EnsureRootOrHalfCouncil;
+	type OffchainSignature = Signature;
+	type SigningPublicKey = <Signature as traits::Verify>::Signer;
+	type UsernameAuthorityOrigin = EnsureRoot<Self::AccountId>;
+	type PendingUsernameExpiration = ConstU32<{ 7 * DAYS }>;
+	type UsernameGracePeriod = ConstU32<{ 30 * DAYS }>;
+	type MaxSuffixLength = ConstU32<7>;
+	type MaxUsernameLength = ConstU32<32>;
+	type WeightInfo = pallet_identity::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
 	pub CodeHashLockupDepositPercent: Perbill = Perbill::from_percent(30);
@@ -1425,6 +1535,9 @@ mod runtime {
 	#[runtime::pallet_index(27)]
 	pub type RandomnessCollectiveFlip = pallet_insecure_randomness_collective_flip::Pallet<Runtime>;
 
+	#[runtime::pallet_index(28)]
+	pub type Identity = pallet_identity::Pallet<Runtime>;
+
 	#[runtime::pallet_index(32)]
 	pub type Scheduler = pallet_scheduler::Pallet<Runtime>;
 
@@ -1570,7 +1683,13 @@ pub type Executive = frame_executive::Executive<
 // `OnRuntimeUpgrade`. Note: These are examples and do not need to be run directly
 // after the genesis block.
 type Migrations =
-	(pallet_contracts::Migration<Runtime>, pallet_cess_staking::migrations::v15::MigrateV14ToV15<Runtime>);
+	(
+		pallet_contracts::Migration<Runtime>,
+		pallet_cess_staking::migrations::v15::MigrateV14ToV15<Runtime>,
+		pallet_reservoir::migrations::v1::MigrateV0ToV1<Runtime>,
+		pallet_oss::migrations::v1::MigrateV0ToV1<Runtime>,
+		pallet_cacher::migrations::v1::MigrateV0ToV1<Runtime>,
+	);
 
 type EventRecord =
 	frame_system::EventRecord<<Runtime as frame_system::Config>::RuntimeEvent, <Runtime as frame_system::Config>::Hash>;
@@ -1593,6 +1712,9 @@ parameter_types! {
 	pub const PunishTreasuryId: PalletId = PalletId(*b"punisdpt");
 	pub const SpaceTreasuryId: PalletId = PalletId(*b"spacedpt");
 	pub const ReserveRewardId: PalletId = PalletId(*b"sererdpt");
+	pub const InsuranceFundId: PalletId = PalletId(*b"insurdpt");
+	pub const PunishInsuranceShare: Perbill = Perbill::from_percent(20);
+	pub const RevenueInsuranceShare: Perbill = Perbill::from_percent(5);
 }
 
 impl pallet_cess_treasury::Config for Runtime {
@@ -1603,8 +1725,13 @@ impl pallet_cess_treasury::Config for Runtime {
 	type PunishTreasuryId = PunishTreasuryId;
 	type SpaceTreasuryId = SpaceTreasuryId;
 	type ReserveRewardId = ReserveRewardId;
+	type InsuranceFundId = InsuranceFundId;
+	type PunishInsuranceShare = PunishInsuranceShare;
+	type RevenueInsuranceShare = RevenueInsuranceShare;
 	type BurnDestination = ();
 	type Staking = Staking;
+	type LedgerHistoryDepth = HistoryDepth;
+	type WeightInfo = pallet_cess_treasury::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -1614,6 +1741,9 @@ parameter_types! {
 impl pallet_scheduler_credit::Config for Runtime {
 	type PeriodDuration = PeriodDuration;
 	type StashAccountFinder = SchedulerStashAccountFinder;
+	type CreditModel = pallet_scheduler_credit::DefaultCreditModel;
+	type MaxDiagnosedCandidates = ConstU32<256>;
+	type ElectionDiagnosticsDepth = ConstU32<28>;
 }
 
 parameter_types! {
@@ -1623,6 +1753,10 @@ parameter_types! {
 	#[derive(Clone, Eq, PartialEq)]
 	pub const MaxWhitelist: u32 = 200;
 	pub const AtLeastWorkBlock: BlockNumber = DAYS / 2;
+	pub const MaxWorkerExpelPerBlock: u32 = 50;
+	pub const UnmeasuredWorkerGracePeriod: BlockNumber = 7 * DAYS;
+	pub const DormantGracePeriod: BlockNumber = 3 * DAYS;
+	pub const MaxLifecycleChecksPerBlock: u32 = 100;
 }
 
 impl pallet_tee_worker::Config for Runtime {
@@ -1636,6 +1770,11 @@ impl pallet_tee_worker::Config for Runtime {
 	type AtLeastWorkBlock = AtLeastWorkBlock;
 	type GovernanceOrigin = EnsureRootOrHalfCouncil;
 	type Randomness = RandomnessCollectiveFlip;
+	type MaxWorkerExpelPerBlock = MaxWorkerExpelPerBlock;
+	type UnmeasuredWorkerGracePeriod = UnmeasuredWorkerGracePeriod;
+	type DormantGracePeriod = DormantGracePeriod;
+	type MaxLifecycleChecksPerBlock = MaxLifecycleChecksPerBlock;
+	type ReservoirGate = Reservoir;
 }
 
 pub struct DealWithServiceFee;
@@ -1652,11 +1791,60 @@ parameter_types! {
 	pub EIP712VerifyingContractAddress: pallet_evm_account_mapping::EIP712VerifyingContractAddress = sp_core::H160::from([0u8; 20]);
 }
 
+/// The calls EVM wallets sign as typed EIP-712 data, showing their fields to the user.
+pub struct EIP712TypedCalls;
+impl pallet_evm_account_mapping::TypedCallConversion<RuntimeCall> for EIP712TypedCalls {
+	fn try_convert(call: &RuntimeCall) -> Option<pallet_evm_account_mapping::eip712::TypedCall> {
+		use pallet_evm_account_mapping::eip712::TypedCall;
+
+		match call {
+			// Accounts given by index have no address to show, so they are signed as call data.
+			RuntimeCall::Balances(pallet_balances::Call::transfer_allow_death { dest: sp_runtime::MultiAddress::Id(dest), value }) =>
+				Some(TypedCall::transfer_allow_death(dest.clone(), (*value).into())),
+			RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive { dest: sp_runtime::MultiAddress::Id(dest), value }) =>
+				Some(TypedCall::transfer_keep_alive(dest.clone(), (*value).into())),
+			RuntimeCall::FileBank(pallet_file_bank::Call::upload_declaration { file_hash, deal_info, user_brief, file_size }) =>
+				Some(TypedCall::upload_declaration(
+					user_brief.user.clone(),
+					file_hash.0.to_vec(),
+					user_brief.file_name.to_vec(),
+					user_brief.territory_name.to_vec(),
+					*file_size,
+					deal_info.encode(),
+				)),
+			RuntimeCall::FileBank(pallet_file_bank::Call::delete_file { owner, file_hash }) =>
+				Some(TypedCall::delete_file(owner.clone(), file_hash.0.to_vec())),
+			RuntimeCall::StorageHandler(pallet_storage_handler::Call::mint_territory { gib_count, territory_name, days }) =>
+				Some(TypedCall::mint_territory(territory_name.to_vec(), *gib_count, *days)),
+			RuntimeCall::StorageHandler(pallet_storage_handler::Call::expanding_territory { territory_name, gib_count }) =>
+				Some(TypedCall::expanding_territory(territory_name.to_vec(), *gib_count)),
+			RuntimeCall::StorageHandler(pallet_storage_handler::Call::renewal_territory { territory_name, days }) =>
+				Some(TypedCall::renewal_territory(territory_name.to_vec(), *days)),
+			RuntimeCall::Oss(pallet_oss::Call::authorize { operator }) => Some(TypedCall::authorize(operator.clone())),
+			_ => None,
+		}
+	}
+}
+
+/// The territories and files that keep the accounts of an EVM key from being bound.
+pub struct EvmKeyStorageHoldings;
+impl pallet_evm_account_mapping::StorageHoldings<AccountId> for EvmKeyStorageHoldings {
+	fn holds_storage(who: &AccountId) -> bool {
+		use pallet_storage_handler::StorageHandle;
+
+		StorageHandler::has_territory(who) || !FileBank::user_hold_file_list(who).is_empty()
+	}
+}
+
 impl pallet_evm_account_mapping::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
 	type Currency = Balances;
 	type AddressConverter = pallet_evm_account_mapping::SubstrateAddressConverter;
+	type TypedCallConverter = EIP712TypedCalls;
+	type EvmAddressMapping = frontier::HashedEvmAddress;
+	type StorageHoldings = EvmKeyStorageHoldings;
+	type MinBindingPeriod = ConstU32<{ 7 * DAYS }>;
 	type ServiceFee = ConstU128<10000000000>;
 	type OnUnbalancedForServiceFee = DealWithServiceFee;
 	type CallFilter = frame_support::traits::Everything;
@@ -1664,6 +1852,9 @@ impl pallet_evm_account_mapping::Config for Runtime {
 	type EIP712Version = EIP712Version;
 	type EIP712ChainID = EIP712ChainID;
 	type EIP712VerifyingContractAddress = EIP712VerifyingContractAddress;
+	type MaxBatchCalls = ConstU32<16>;
+	type MaxSponsoredCalls = ConstU32<64>;
+	type MaxCallNameLen = ConstU32<64>;
 	type WeightInfo = pallet_evm_account_mapping::weights::SubstrateWeight<Runtime>;
 }
 
@@ -1690,6 +1881,12 @@ parameter_types! {
 	pub const RestoralOrderLife: u32 = 250;
 	#[derive(Clone, Eq, PartialEq)]
 	pub const MissionCount: u32 = SEGMENT_COUNT * FRAGMENT_COUNT;
+	pub const MissingGracePeriod: BlockNumber = 7 * DAYS;
+	#[derive(Clone, Eq, PartialEq)]
+	pub const ObjectKeyLimit: u32 = 1024;
+	// The entry of the object and its place among the keys of its file.
+	pub const ObjectDepositBase: Balance = deposit(2, 210);
+	pub const ObjectDepositPerByte: Balance = deposit(0, 2);
 }
 
 impl pallet_file_bank::Config for Runtime {
@@ -1713,6 +1910,12 @@ impl pallet_file_bank::Config for Runtime {
 	type NameMinLength = NameMinLength;
 	type RestoralOrderLife = RestoralOrderLife;
 	type MissionCount = MissionCount;
+	type InsuranceHandle = CessTreasury;
+	type MissingGracePeriod = MissingGracePeriod;
+	type ObjectKeyLimit = ObjectKeyLimit;
+	type Currency = Balances;
+	type ObjectDepositBase = ObjectDepositBase;
+	type ObjectDepositPerByte = ObjectDepositPerByte;
 }
 
 parameter_types! {
@@ -1761,6 +1964,10 @@ parameter_types! {
 	pub const LockTime: BlockNumber = HOURS / 60;
 	#[derive(Clone, PartialEq, Eq)]
 	pub const ReassignCeiling: u8 = 1;
+	pub const CrossCheckRate: Perbill = Perbill::from_percent(10);
+	pub const CrossCheckPeriod: BlockNumber = 6 * HOURS;
+	pub const MinCrossCheckSample: u32 = 20;
+	pub const DisagreementThreshold: Perbill = Perbill::from_percent(30);
 }
 
 impl pallet_audit::Config for Runtime {
@@ -1787,6 +1994,10 @@ impl pallet_audit::Config for Runtime {
 	type SigmaMax = SigmaMax;
 	type IdleTotalHashLength = IdleTotalHashLength;
 	type ReassignCeiling = ReassignCeiling;
+	type CrossCheckRate = CrossCheckRate;
+	type CrossCheckPeriod = CrossCheckPeriod;
+	type MinCrossCheckSample = MinCrossCheckSample;
+	type DisagreementThreshold = DisagreementThreshold;
 }
 
 parameter_types! {
@@ -1818,6 +2029,7 @@ impl pallet_storage_handler::Config for Runtime {
 	type StateStringMax = StateStringMax;
 	type FrozenDays = FrozenDays;
 	type CessTreasuryHandle = CessTreasury;
+	type ReservoirGate = Reservoir;
 	type FrozenLimit = FrozenLimit;
 }
 
@@ -1828,28 +2040,56 @@ parameter_types! {
 	pub const AuthorLimit: u32 = 20;
 	#[derive(Clone, Eq, PartialEq)]
 	pub const PayloadExpired: u32 = 100;
+	pub const OssRegistrationDeposit: Balance = 100 * DOLLARS;
 }
 
 impl pallet_oss::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_oss::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type RegistrationDeposit = OssRegistrationDeposit;
 	type P2PLength = P2PLength;
 	type AuthorLimit = AuthorLimit;
 	type PayloadExpired = PayloadExpired;
 	type AddressConverter = pallet_evm_account_mapping::SubstrateAddressConverter;
+	type MaxScopeTerritories = ConstU32<16>;
+}
+
+parameter_types! {
+	pub const CacherDeposit: Balance = 1_000 * DOLLARS;
+	pub const CacherDisputeSlash: Perbill = Perbill::from_percent(10);
+	pub const CacherDisputeBond: Balance = 10 * DOLLARS;
 }
 
 impl pallet_cacher::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type Currency = Balances;
 	type BillsLimit = ConstU32<10>;
+	type UnixTime = Timestamp;
+	type FragmentLookup = FileBank;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as traits::Verify>::Signer;
+	type DisputePeriod = ConstU32<{ DAYS }>;
+	type DisputeBond = CacherDisputeBond;
+	type DisputeResolutionPeriod = ConstU32<{ 14 * DAYS }>;
+	type MaxOpenDisputes = ConstU32<16>;
+	type ServedFragmentRetention = ConstU32<{ 30 * DAYS }>;
+	type CacherDeposit = CacherDeposit;
+	type DisputeSlash = CacherDisputeSlash;
+	type Slash = Treasury;
+	type GovernanceOrigin = EnsureRootOrHalfCouncil;
 	type WeightInfo = pallet_cacher::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
 }
 
 parameter_types! {
 	pub const ReservoirPalletId: PalletId = PalletId(*b"rsorptid");
 	pub const IdLength: u32 = 64;
 	pub const EventLimit: u32 = 32;
+	pub const LoanLimit: u32 = 256;
+	pub const AllowlistLimit: u32 = 256;
+	pub const EventCleanupLimit: u32 = 512;
 }
 
 impl pallet_reservoir::Config for Runtime {
@@ -1858,6 +2098,12 @@ impl pallet_reservoir::Config for Runtime {
 	type PalletId = ReservoirPalletId;
 	type IdLength = IdLength;
 	type EventLimit = EventLimit;
+	type LoanLimit = LoanLimit;
+	type AllowlistLimit = AllowlistLimit;
+	type KycProvider = IdentityKyc;
+	type LoanCollateral = Sminer;
+	type EventCleanupLimit = EventCleanupLimit;
+	type WeightInfo = pallet_reservoir::weights::SubstrateWeight<Runtime>;
 }
 //------------------------- CESS's end -------------------------
 
@@ -1971,6 +2217,9 @@ mod benches {
 		[pallet_storage_handler, StorageHandler]
 		[pallet_oss, Oss]
 		[pallet_cacher, Cacher]
+		[pallet_cess_treasury, CessTreasury]
+		[pallet_reservoir, Reservoir]
+		[pallet_evm_account_mapping, EvmAccountMapping]
 		[pallet_tx_pause, TxPause]
 	);
 }
@@ -2147,6 +2396,85 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl staking_api::StakingEmissionApi<Block, Balance> for Runtime {
+		fn projected_era_payouts(from_era: Option<sp_staking::EraIndex>, count: u32) -> Vec<(sp_staking::EraIndex, Balance, Balance)> {
+			Staking::projected_era_payouts(from_era, count)
+		}
+	}
+
+	impl treasury_api::CessTreasuryApi<Block, Balance> for Runtime {
+		fn income_statement(from_era: u32, to_era: u32) -> pallet_cess_treasury::IncomeStatement<Balance> {
+			CessTreasury::income_statement(from_era, to_era)
+		}
+	}
+
+	impl evm_account_mapping_api::EvmAccountMappingApi<Block, AccountId, BlockNumber> for Runtime {
+		fn evm_account(evm_address: H160) -> AccountId {
+			EvmAccountMapping::evm_account(evm_address)
+		}
+
+		fn canonical_account(who: AccountId) -> AccountId {
+			EvmAccountMapping::canonical_account(&who)
+		}
+
+		fn binding(who: AccountId) -> Option<pallet_evm_account_mapping::BindingInfo<AccountId, BlockNumber>> {
+			pallet_evm_account_mapping::Bindings::<Runtime>::get(&who)
+		}
+	}
+
+	impl oss_api::OssApi<Block, AccountId, Balance> for Runtime {
+		fn gateways(
+			region: Option<pallet_oss::RegionCode>,
+			capability: Option<pallet_oss::OssCapability>,
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Vec<pallet_oss::OssGateway<AccountId, Balance>> {
+			Oss::gateways(region, capability, start_after, limit)
+		}
+	}
+
+	impl file_bank_api::FileBankApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+		fn object(
+			owner: AccountId,
+			territory_name: cp_cess_common::TerrName,
+			key: Vec<u8>,
+		) -> Option<pallet_file_bank::ObjectInfo<AccountId, Balance, BlockNumber>> {
+			FileBank::object(owner, territory_name, key)
+		}
+
+		fn list_objects(
+			owner: AccountId,
+			territory_name: cp_cess_common::TerrName,
+			prefix: Vec<u8>,
+			delimiter: Option<Vec<u8>>,
+			start_after: Option<Vec<u8>>,
+			limit: u32,
+		) -> Vec<pallet_file_bank::ListedObject<AccountId, Balance, BlockNumber>> {
+			FileBank::list_objects(owner, territory_name, prefix, delimiter, start_after, limit)
+		}
+	}
+
+	impl cacher_api::CacherApi<Block, AccountId, Balance> for Runtime {
+		fn cachers(
+			fragments: Vec<cp_cess_common::Hash>,
+			limit: u32,
+		) -> Vec<pallet_cacher::CacherListing<AccountId, Balance>> {
+			Cacher::cachers(fragments, limit)
+		}
+	}
+
+	impl scheduler_credit_api::SchedulerCreditApi<Block, AccountId> for Runtime {
+		fn credit_score_breakdown(scheduler: AccountId) -> pallet_scheduler_credit::CreditScoreBreakdown {
+			SchedulerCredit::credit_score_breakdown(&scheduler)
+		}
+
+		fn election_diagnostics(
+			epoch_index: Option<u64>,
+		) -> Option<(u64, Vec<ces_rrsc_vrf_solver::CandidateScore<AccountId>>)> {
+			SchedulerCredit::election_diagnostics(epoch_index)
+		}
+	}
+
 	impl pallet_contracts::ContractsApi<Block, AccountId, Balance, BlockNumber, Hash, EventRecord> for Runtime
 	{
 		fn call(
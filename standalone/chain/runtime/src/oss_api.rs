@@ -0,0 +1,24 @@
+//! Runtime API definition for discovering OSS gateways.
+
+use polkadot_sdk::*;
+use alloc::vec::Vec;
+use codec::Codec;
+use pallet_oss::{OssCapability, OssGateway, RegionCode};
+
+sp_api::decl_runtime_apis! {
+	pub trait OssApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Returns up to `limit` registered gateways following `start_after`, serving `region`
+		/// and supporting `capability` when given. The last account returned is the
+		/// `start_after` of the next page.
+		fn gateways(
+			region: Option<RegionCode>,
+			capability: Option<OssCapability>,
+			start_after: Option<AccountId>,
+			limit: u32,
+		) -> Vec<OssGateway<AccountId, Balance>>;
+	}
+}
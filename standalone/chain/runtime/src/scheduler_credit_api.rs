@@ -0,0 +1,22 @@
+//! Runtime API definition for scheduler credit.
+extern crate alloc;
+
+use polkadot_sdk::*;
+use alloc::vec::Vec;
+use codec::Codec;
+use ces_rrsc_vrf_solver::CandidateScore;
+use pallet_scheduler_credit::CreditScoreBreakdown;
+
+sp_api::decl_runtime_apis! {
+	pub trait SchedulerCreditApi<AccountId>
+	where
+		AccountId: Codec,
+	{
+		/// Returns the credit score of a scheduler and the per-period breakdown behind it.
+		fn credit_score_breakdown(scheduler: AccountId) -> CreditScoreBreakdown;
+
+		/// Returns how the candidates of the validator election of an epoch were scored, the
+		/// last recorded election if no epoch is given.
+		fn election_diagnostics(epoch_index: Option<u64>) -> Option<(u64, Vec<CandidateScore<AccountId>>)>;
+	}
+}
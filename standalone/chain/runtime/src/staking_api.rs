@@ -0,0 +1,18 @@
+//! Runtime API definition for staking emission.
+extern crate alloc;
+
+use polkadot_sdk::*;
+use codec::Codec;
+use alloc::vec::Vec;
+use sp_staking::EraIndex;
+
+sp_api::decl_runtime_apis! {
+	pub trait StakingEmissionApi<Balance>
+	where
+		Balance: Codec,
+	{
+		/// Returns `(era, validator_payout, sminer_payout)` for `count` eras starting at
+		/// `from_era`, or at the active era when `None`.
+		fn projected_era_payouts(from_era: Option<EraIndex>, count: u32) -> Vec<(EraIndex, Balance, Balance)>;
+	}
+}
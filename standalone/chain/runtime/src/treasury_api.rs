@@ -0,0 +1,16 @@
+//! Runtime API definition for the cess treasury.
+
+use polkadot_sdk::*;
+use codec::Codec;
+use pallet_cess_treasury::IncomeStatement;
+
+sp_api::decl_runtime_apis! {
+	pub trait CessTreasuryApi<Balance>
+	where
+		Balance: Codec,
+	{
+		/// Returns the flows of every treasury account from `from_era` to `to_era`, both
+		/// included.
+		fn income_statement(from_era: u32, to_era: u32) -> IncomeStatement<Balance>;
+	}
+}